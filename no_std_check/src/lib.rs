@@ -0,0 +1,44 @@
+//! Uses the core types of `typed-measurements` from a `#![no_std]` crate, with only `alloc`.
+//!
+//! Building this crate proves that the core compiles without `std`. To also check linking
+//! on a bare-metal target, build it with e.g.
+//! `cargo build -p no-std-check --target thumbv7em-none-eabihf`.
+#![no_std]
+
+extern crate alloc;
+
+use alloc::string::String;
+use serde::{Deserialize, Serialize};
+use typed_measurements::{define_unit, percentage, prelude::*};
+use uom_derive::Uom;
+
+#[derive(Uom, PartialEq, Debug, Clone, Copy, Serialize, Deserialize)]
+#[uom(label = "Wb", name = "weber")]
+pub struct Weber;
+
+define_unit!(PhotonCount, "ph", "photon");
+
+/// Labels a setpoint after choosing a readable prefix.
+pub fn setpoint_label(millivolts: f64) -> String {
+    Voltage::millivolts(millivolts).nice().label()
+}
+
+/// Checks a reading against a symmetric range with a 10% guard band.
+pub fn in_range(reading: Measurement<Volt>) -> Result<bool, MeasurementError> {
+    let range = RangedMeasurement::<Volt>::builder()
+        .symmetric(Measurement::new(10, Prefix::None))
+        .guard(percentage!(0.1))
+        .build()?;
+    Ok(range.is_in_range(reading, None))
+}
+
+/// Parses a label of a custom unit and returns its long form.
+pub fn parse_photons(label: &str) -> Result<String, ParseMeasurementError> {
+    let m: Measurement<PhotonCount> = label.parse()?;
+    Ok(m.long_label())
+}
+
+/// Returns the name of a derived unit.
+pub fn weber_name() -> String {
+    Weber::name()
+}
@@ -0,0 +1,233 @@
+//! Named time windows of a trace and the statistic to measure in each, as analysis
+//! protocols describe them: "the mean in [5, 15] ms, the peak in [15, 40] ms".
+
+use crate::{
+    error::MeasurementError,
+    m1d::M1d,
+    measurement::Measurement,
+    prefix::Prefix,
+    ranged_measurement::RangedMeasurement,
+    sampling::{sample_to_time, time_to_sample},
+    stats::RunningStats,
+    uom::{Hertz, Second, Uom},
+};
+use ndarray::s;
+use serde::{Deserialize, Serialize};
+
+/// The statistic an [`AnalysisWindow`] measures. NaN samples propagate into every one.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WindowOp {
+    /// The mean of the samples.
+    Mean,
+    /// The smallest sample.
+    Min,
+    /// The largest sample.
+    Max,
+    /// The largest sample minus the smallest.
+    PeakToPeak,
+    /// The population standard deviation of the samples.
+    Std,
+}
+
+/// A named time window of a trace with the statistic to measure in it.
+///
+/// Times count from the first sample of the trace. The window covers every sample whose
+/// index [`time_to_sample`] gives for a time within it, both bounds included.
+///
+/// ```
+/// use typed_measurements::prelude::*;
+///
+/// let trace = M1d::<Volt>::new((0..50).map(f64::from).collect::<Vec<_>>(), Prefix::Milli);
+/// let window = AnalysisWindow::new(
+///     "baseline",
+///     RangedMeasurement::from_label("[5,15]ms").unwrap(),
+///     WindowOp::Mean,
+/// );
+/// let mean = window.apply(&trace, Measurement::new(1.0, Prefix::Kilo)).unwrap();
+/// assert_eq!(mean, Measurement::new(10.0, Prefix::Milli));
+/// ```
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct AnalysisWindow {
+    name: String,
+    window: RangedMeasurement<Second>,
+    op: WindowOp,
+}
+
+impl AnalysisWindow {
+    /// Creates a window measuring `op` over the times of `window`.
+    pub fn new(name: impl Into<String>, window: RangedMeasurement<Second>, op: WindowOp) -> Self {
+        Self {
+            name: name.into(),
+            window,
+            op,
+        }
+    }
+
+    /// Returns the name of the window.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the times the window covers.
+    pub fn window(&self) -> RangedMeasurement<Second> {
+        self.window
+    }
+
+    /// Returns the statistic the window measures.
+    pub fn op(&self) -> WindowOp {
+        self.op
+    }
+
+    /// Measures the statistic of the window on `trace`, sampled at `rate`.
+    ///
+    /// # Returns
+    /// The statistic in the prefix of the trace, or
+    /// [`MeasurementError::WindowOutOfTrace`], naming the window, if it starts before the
+    /// trace or ends after it.
+    ///
+    /// # Panics
+    /// Panics if `rate` is not positive and finite.
+    pub fn apply<U: Uom>(
+        &self,
+        trace: &M1d<U>,
+        rate: Measurement<Hertz>,
+    ) -> Result<Measurement<U>, MeasurementError> {
+        let out_of_trace = || MeasurementError::WindowOutOfTrace {
+            window: self.name.clone(),
+            start: self.window.min().convert_to(Prefix::None).value(),
+            end: self.window.max().convert_to(Prefix::None).value(),
+            duration: sample_to_time(trace.len(), rate).value(),
+        };
+        // rejected explicitly: a start just before zero must not round onto sample 0
+        if self.window.min().value() < 0.0 {
+            return Err(out_of_trace());
+        }
+        let start = time_to_sample(self.window.min(), rate).map_err(|_| out_of_trace())?;
+        let end = time_to_sample(self.window.max(), rate).map_err(|_| out_of_trace())?;
+        if end >= trace.len() {
+            return Err(out_of_trace());
+        }
+        let prefix = trace.prefix();
+        let mut stats = RunningStats::new(prefix);
+        stats.extend(
+            trace
+                .view()
+                .slice(s![start..=end])
+                .iter()
+                .map(|&v| Measurement::new(v, prefix)),
+        );
+        let measured = match self.op {
+            WindowOp::Mean => stats.mean(),
+            WindowOp::Min => stats.min(),
+            WindowOp::Max => stats.max(),
+            WindowOp::PeakToPeak => stats.max().zip(stats.min()).map(|(max, min)| max - min),
+            WindowOp::Std => stats.std(0.0),
+        };
+        Ok(measured.expect("a window holds at least one sample"))
+    }
+
+    /// Measures every window of `windows` on `trace`, sampled at `rate`.
+    ///
+    /// # Returns
+    /// The name and statistic of every window, in order, or the error of the first window
+    /// that does not fit the trace.
+    ///
+    /// # Panics
+    /// Panics if `rate` is not positive and finite.
+    pub fn apply_all<U: Uom>(
+        windows: &[AnalysisWindow],
+        trace: &M1d<U>,
+        rate: Measurement<Hertz>,
+    ) -> Result<Vec<(String, Measurement<U>)>, MeasurementError> {
+        windows
+            .iter()
+            .map(|w| Ok((w.name.clone(), w.apply(trace, rate)?)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod analysis_tests {
+    use super::*;
+    use crate::uom::Volt;
+
+    /// One sample per millisecond, equal to its index in mV.
+    fn ramp() -> M1d<Volt> {
+        M1d::new((0..50).map(f64::from).collect::<Vec<_>>(), Prefix::Milli)
+    }
+
+    fn khz() -> Measurement<Hertz> {
+        Measurement::new(1.0, Prefix::Kilo)
+    }
+
+    fn ms(min: f64, max: f64) -> RangedMeasurement<Second> {
+        RangedMeasurement::from_label(&format!("[{min},{max}]ms")).unwrap()
+    }
+
+    #[test]
+    fn measures_known_window_statistics() {
+        let trace = ramp();
+        // samples 5 to 15: 11 consecutive integers
+        let stat = |op| AnalysisWindow::new("w", ms(5.0, 15.0), op).apply(&trace, khz());
+        let mv = |v| Ok(Measurement::new(v, Prefix::Milli));
+        assert_eq!(stat(WindowOp::Mean), mv(10.0));
+        assert_eq!(stat(WindowOp::Min), mv(5.0));
+        assert_eq!(stat(WindowOp::Max), mv(15.0));
+        assert_eq!(stat(WindowOp::PeakToPeak), mv(10.0));
+        let std = stat(WindowOp::Std).unwrap().value();
+        assert!((std - 10f64.sqrt()).abs() < 1e-12, "{std}");
+        // a window in seconds selects the same samples
+        let seconds = RangedMeasurement::from_label("[0.005,0.015]s").unwrap();
+        let w = AnalysisWindow::new("w", seconds, WindowOp::Mean);
+        assert_eq!(w.apply(&trace, khz()), mv(10.0));
+    }
+
+    #[test]
+    fn applies_every_window_and_names_the_failing_one() {
+        let trace = ramp();
+        let windows = [
+            AnalysisWindow::new("baseline", ms(5.0, 15.0), WindowOp::Mean),
+            AnalysisWindow::new("peak", ms(15.0, 40.0), WindowOp::Max),
+        ];
+        let results = AnalysisWindow::apply_all(&windows, &trace, khz()).unwrap();
+        assert_eq!(
+            results,
+            [
+                (
+                    "baseline".to_string(),
+                    Measurement::new(10.0, Prefix::Milli)
+                ),
+                ("peak".to_string(), Measurement::new(40.0, Prefix::Milli)),
+            ]
+        );
+        let late = AnalysisWindow::new("late", ms(40.0, 60.0), WindowOp::Mean);
+        let e = AnalysisWindow::apply_all(&[windows[0].clone(), late], &trace, khz()).unwrap_err();
+        assert_eq!(
+            e.to_string(),
+            "window `late` [0.04, 0.06] s does not fit a trace of 0.05 s"
+        );
+        let early = AnalysisWindow::new("early", ms(-1.0, 5.0), WindowOp::Mean);
+        assert!(matches!(
+            early.apply(&trace, khz()),
+            Err(MeasurementError::WindowOutOfTrace { window, .. }) if window == "early"
+        ));
+        // less than half a sample before the trace
+        let barely_early = AnalysisWindow::new("barely early", ms(-0.2, 5.0), WindowOp::Mean);
+        assert!(matches!(
+            barely_early.apply(&trace, khz()),
+            Err(MeasurementError::WindowOutOfTrace { window, start, .. })
+                if window == "barely early" && start < 0.0
+        ));
+    }
+
+    #[test]
+    fn round_trips_through_serde() {
+        let w = AnalysisWindow::new("peak", ms(15.0, 40.0), WindowOp::PeakToPeak);
+        let json = serde_json::to_string(&w).unwrap();
+        assert_eq!(
+            json,
+            r#"{"name":"peak","window":{"min":15.0,"max":40.0,"prefix":"m"},"op":"PeakToPeak"}"#
+        );
+        assert_eq!(serde_json::from_str::<AnalysisWindow>(&json).unwrap(), w);
+    }
+}
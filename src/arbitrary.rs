@@ -0,0 +1,242 @@
+//! [`proptest`] strategies for the crate's types, enabled by the `proptest` feature.
+//!
+//! Every type implements [`Arbitrary`], so `any::<Measurement<Volt>>()` can be used directly
+//! in a `proptest!` block. Values are finite and bounded by [`ValueParams::magnitude`]; NaN
+//! and infinities are only generated when [`ValueParams::non_finite`] is set.
+
+use crate::{
+    m1d::M1d, m2d::M2d, measurement::Measurement, percentage::Percentage, prefix::Prefix,
+    ranged_measurement::RangedMeasurement, uom::Uom,
+};
+use ndarray::Array2;
+use proptest::{collection::vec, prelude::*, sample::select};
+
+/// Largest number of samples in a generated [`M1d`] or row of a generated [`M2d`].
+const MAX_LEN: usize = 16;
+/// Largest number of rows in a generated [`M2d`].
+const MAX_ROWS: usize = 4;
+
+/// Parameters for the values generated by the [`Arbitrary`] implementations.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ValueParams {
+    /// Largest absolute value generated, in the generated prefix. Must be finite.
+    pub magnitude: f64,
+    /// Whether NaN and ±infinity may be generated. Ranges never contain them.
+    pub non_finite: bool,
+}
+
+impl Default for ValueParams {
+    fn default() -> Self {
+        Self {
+            magnitude: 1e6,
+            non_finite: false,
+        }
+    }
+}
+
+/// Returns a strategy for raw values according to `params`.
+fn value(params: ValueParams) -> BoxedStrategy<f64> {
+    let m = params.magnitude.abs();
+    let finite = (-m..=m).boxed();
+    if params.non_finite {
+        prop_oneof![
+            7 => finite,
+            1 => Just(f64::NAN),
+            1 => Just(f64::INFINITY),
+            1 => Just(f64::NEG_INFINITY),
+        ]
+        .boxed()
+    } else {
+        finite
+    }
+}
+
+impl Arbitrary for Prefix {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        select(Prefix::ALL.to_vec()).boxed()
+    }
+}
+
+impl Arbitrary for Percentage {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (0.0..=1.0).prop_map(Percentage::new_const).boxed()
+    }
+}
+
+impl<U: Uom> Arbitrary for Measurement<U> {
+    type Parameters = ValueParams;
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(params: Self::Parameters) -> Self::Strategy {
+        (value(params), any::<Prefix>())
+            .prop_map(|(v, p)| Measurement::new(v, p))
+            .boxed()
+    }
+}
+
+/// Generates ranges with `min <= max` and a positive step no larger than the span
+/// (or, for an empty span, no larger than the magnitude).
+impl<U: Uom> Arbitrary for RangedMeasurement<U> {
+    type Parameters = ValueParams;
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(params: Self::Parameters) -> Self::Strategy {
+        let m = params.magnitude.abs();
+        (-m..=m, 0.0..=m, 0.001..=1.0, any::<Prefix>())
+            .prop_map(move |(min, span, fraction, prefix)| {
+                let step = if span > 0.0 { span } else { m.max(1.0) } * fraction;
+                RangedMeasurement::new(min, min + span, step, prefix)
+            })
+            .boxed()
+    }
+}
+
+impl<U: Uom> Arbitrary for M1d<U> {
+    type Parameters = ValueParams;
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(params: Self::Parameters) -> Self::Strategy {
+        (vec(value(params), 0..=MAX_LEN), any::<Prefix>())
+            .prop_map(|(values, prefix)| M1d::new(values, prefix))
+            .boxed()
+    }
+}
+
+impl<U: Uom> Arbitrary for M2d<U> {
+    type Parameters = ValueParams;
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(params: Self::Parameters) -> Self::Strategy {
+        (0..=MAX_ROWS, 0..=MAX_LEN)
+            .prop_flat_map(move |(rows, cols)| {
+                (vec(value(params), rows * cols), any::<Prefix>()).prop_map(
+                    move |(values, prefix)| {
+                        let values = Array2::from_shape_vec((rows, cols), values)
+                            .expect("the vector holds rows * cols values");
+                        M2d::new(values, prefix)
+                    },
+                )
+            })
+            .boxed()
+    }
+}
+
+#[cfg(test)]
+mod arbitrary_tests {
+    use super::*;
+    use crate::uom::{Ohm, Volt};
+
+    /// Asserts that `a` and `b` agree to within `1e-9` of `scale`.
+    fn assert_close(a: f64, b: f64, scale: f64) {
+        assert!(
+            (a - b).abs() <= 1e-9 * scale.abs().max(f64::MIN_POSITIVE),
+            "{a} != {b}"
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn add_sub_round_trip(a in any::<Measurement<Volt>>(), b in any::<Measurement<Volt>>()) {
+            let base = |m: Measurement<Volt>| m.convert_to(Prefix::None).value();
+            let back = (a + b) - b;
+            assert_close(base(back), base(a), base(a).abs().max(base(b).abs()));
+        }
+
+        #[test]
+        fn convert_to_is_reversible(m in any::<Measurement<Volt>>(), p in any::<Prefix>()) {
+            let back = m.convert_to(p).convert_to(m.prefix());
+            prop_assert_eq!(back.prefix(), m.prefix());
+            assert_close(back.value(), m.value(), m.value());
+        }
+
+        #[test]
+        fn label_parse_round_trip(v in any::<Measurement<Volt>>(), r in any::<Measurement<Ohm>>()) {
+            let parsed = v.label().parse::<Measurement<Volt>>().unwrap();
+            prop_assert_eq!(parsed.value(), v.value());
+            prop_assert_eq!(parsed.prefix(), v.prefix());
+            let parsed = r.label().parse::<Measurement<Ohm>>().unwrap();
+            prop_assert_eq!(parsed.value(), r.value());
+            prop_assert_eq!(parsed.prefix(), r.prefix());
+        }
+
+        #[test]
+        fn equality_is_symmetric_and_transitive(
+            m in any::<Measurement<Volt>>(),
+            other in any::<Measurement<Volt>>(),
+            p1 in any::<Prefix>(),
+            p2 in any::<Prefix>(),
+        ) {
+            let (a, b) = (m, m.convert_to(p1));
+            let c = b.convert_to(p2);
+            for (x, y) in [(a, b), (b, c), (a, c), (a, other)] {
+                prop_assert_eq!(x == y, y == x);
+                prop_assert_eq!(x == y, x.partial_cmp(&y) == Some(core::cmp::Ordering::Equal));
+            }
+            if a == b && b == c {
+                prop_assert_eq!(a, c);
+            }
+        }
+
+        #[test]
+        fn array_and_range_equality_is_symmetric_and_transitive(
+            a in any::<M1d<Volt>>(),
+            m in any::<M2d<Volt>>(),
+            r in any::<RangedMeasurement<Volt>>(),
+            p1 in any::<Prefix>(),
+            p2 in any::<Prefix>(),
+        ) {
+            let (b, n, s) = (a.clone().convert_to(p1), m.clone().convert_to(p1), r.convert_to(p1));
+            prop_assert_eq!(a == b, b == a);
+            prop_assert_eq!(m == n, n == m);
+            prop_assert_eq!(r == s, s == r);
+            let (c, o, t) = (b.clone().convert_to(p2), n.clone().convert_to(p2), s.convert_to(p2));
+            if a == b && b == c {
+                prop_assert_eq!(a, c);
+            }
+            if m == n && n == o {
+                prop_assert_eq!(m, o);
+            }
+            if r == s && s == t {
+                prop_assert_eq!(r, t);
+            }
+        }
+
+        #[test]
+        fn values_are_finite_and_bounded(
+            m in any_with::<Measurement<Volt>>(ValueParams { magnitude: 5.0, non_finite: false }),
+            a in any::<M1d<Volt>>(),
+            b in any::<M2d<Volt>>(),
+        ) {
+            prop_assert!(m.value().abs() <= 5.0);
+            prop_assert!(a.view().iter().all(|v| v.is_finite()));
+            prop_assert!(b.values().iter().all(|v| v.is_finite()));
+        }
+
+        #[test]
+        fn ranges_are_ordered(r in any::<RangedMeasurement<Volt>>(), p in any::<Percentage>()) {
+            let step = r.step().unwrap().value();
+            prop_assert!(r.min().value() <= r.max().value());
+            prop_assert!(step > 0.0 && step.is_finite());
+            prop_assert!((0.0..=1.0).contains(&p.get_value()));
+        }
+    }
+
+    #[test]
+    fn non_finite_when_asked() {
+        use proptest::{strategy::ValueTree, test_runner::TestRunner};
+        let mut runner = TestRunner::deterministic();
+        let params = ValueParams {
+            non_finite: true,
+            ..ValueParams::default()
+        };
+        let strategy = vec(any_with::<Measurement<Volt>>(params), 200);
+        let values = strategy.new_tree(&mut runner).unwrap().current();
+        assert!(values.iter().any(|m| !m.value().is_finite()));
+    }
+}
@@ -0,0 +1,365 @@
+//! Apache Arrow export for [`M1d`] and [`M2d`], enabled by the `arrow` feature.
+//!
+//! Every column is a non-nullable `Float64` field whose metadata carries the unit under
+//! `unit` and the prefix label under `prefix`, so a [`RecordBatch`] can be read back
+//! without losing either. With the `parquet` feature the same batches can be written to
+//! and read from Parquet files, which keep the field metadata in their embedded schema.
+
+use crate::{
+    m1d::M1d,
+    m2d::M2d,
+    prefix::{Prefix, finest},
+    uom::Uom,
+};
+use arrow::{
+    array::{Array, ArrayRef, AsArray, Float64Array},
+    datatypes::{DataType, Field, Float64Type, Schema},
+    record_batch::{RecordBatch, RecordBatchOptions},
+};
+use ndarray::Array1;
+use std::{collections::HashMap, fmt, sync::Arc};
+
+/// Field metadata key holding the unit of a column.
+pub const UNIT_KEY: &str = "unit";
+/// Field metadata key holding the prefix label of a column.
+pub const PREFIX_KEY: &str = "prefix";
+
+/// Errors raised while converting to or from Arrow record batches and Parquet files.
+#[derive(Debug)]
+pub enum ArrowError {
+    /// An error from the Arrow library.
+    Arrow(arrow::error::ArrowError),
+    /// A batch whose columns or metadata do not describe a measurement array.
+    Format(String),
+    /// A column whose unit does not match the expected one.
+    UnitMismatch { expected: String, found: String },
+    /// An error from the Parquet library.
+    #[cfg(feature = "parquet")]
+    Parquet(parquet::errors::ParquetError),
+}
+
+impl fmt::Display for ArrowError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Arrow(e) => write!(f, "{e}"),
+            Self::Format(m) => write!(f, "invalid record batch: {m}"),
+            Self::UnitMismatch { expected, found } => {
+                write!(f, "unit mismatch: expected `{expected}`, found `{found}`")
+            }
+            #[cfg(feature = "parquet")]
+            Self::Parquet(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl std::error::Error for ArrowError {}
+
+impl From<arrow::error::ArrowError> for ArrowError {
+    fn from(e: arrow::error::ArrowError) -> Self {
+        Self::Arrow(e)
+    }
+}
+
+#[cfg(feature = "parquet")]
+impl From<parquet::errors::ParquetError> for ArrowError {
+    fn from(e: parquet::errors::ParquetError) -> Self {
+        Self::Parquet(e)
+    }
+}
+
+/// Builds a non-nullable `Float64` field tagged with the unit of `U` and `prefix`.
+fn field<U: Uom>(name: &str, prefix: Prefix) -> Field {
+    Field::new(name, DataType::Float64, false).with_metadata(HashMap::from([
+        (UNIT_KEY.to_string(), U::uom()),
+        (PREFIX_KEY.to_string(), prefix.get_label().to_string()),
+    ]))
+}
+
+/// Reads the prefix out of the metadata of `field`, validating the unit.
+fn parse_field<U: Uom>(field: &Field) -> Result<Prefix, ArrowError> {
+    let metadata = field.metadata();
+    let get = |key: &str| {
+        metadata.get(key).ok_or_else(|| {
+            ArrowError::Format(format!("column `{}` has no `{key}` metadata", field.name()))
+        })
+    };
+    let unit = get(UNIT_KEY)?;
+    if unit != U::uom_str() {
+        return Err(ArrowError::UnitMismatch {
+            expected: U::uom(),
+            found: unit.clone(),
+        });
+    }
+    let label = get(PREFIX_KEY)?;
+    Prefix::from_label(label).ok_or_else(|| {
+        ArrowError::Format(format!(
+            "column `{}` has unknown prefix `{label}`",
+            field.name()
+        ))
+    })
+}
+
+/// Copies the values out of a non-null `Float64` column.
+fn column_values(name: &str, column: &dyn Array) -> Result<Vec<f64>, ArrowError> {
+    let array = column.as_primitive_opt::<Float64Type>().ok_or_else(|| {
+        ArrowError::Format(format!(
+            "column `{name}` is {}, expected Float64",
+            column.data_type()
+        ))
+    })?;
+    if array.null_count() > 0 {
+        return Err(ArrowError::Format(format!(
+            "column `{name}` contains nulls"
+        )));
+    }
+    Ok(array.values().to_vec())
+}
+
+/// Reads every column of `batch`, returning the column prefixes and values.
+fn read_columns<U: Uom>(batch: &RecordBatch) -> Result<(Vec<Prefix>, Vec<Vec<f64>>), ArrowError> {
+    batch
+        .schema()
+        .fields()
+        .iter()
+        .zip(batch.columns())
+        .map(|(field, column)| {
+            Ok((
+                parse_field::<U>(field)?,
+                column_values(field.name(), column)?,
+            ))
+        })
+        .collect::<Result<Vec<_>, _>>()
+        .map(|columns| columns.into_iter().unzip())
+}
+
+impl<U: Uom> M1d<U> {
+    /// Copies the values into an Arrow array, in the array's current prefix.
+    ///
+    /// The array carries no unit; use [`M1d::to_arrow`] to keep the unit and prefix.
+    pub fn to_arrow_array(&self) -> Float64Array {
+        Float64Array::from_iter_values(self.view().iter().copied())
+    }
+
+    /// Builds an array from an Arrow `Float64` array whose values are in `prefix`.
+    ///
+    /// # Returns
+    /// A new [`M1d`], or an [`ArrowError`] if `array` is not a `Float64` array or contains nulls.
+    pub fn from_arrow_array(array: &dyn Array, prefix: Prefix) -> Result<Self, ArrowError> {
+        Ok(M1d::new(column_values("value", array)?, prefix))
+    }
+
+    /// Converts the array into a single-column record batch with a `value` field
+    /// tagged with the unit and prefix.
+    pub fn to_arrow(&self) -> RecordBatch {
+        let schema = Schema::new(vec![field::<U>("value", self.prefix())]);
+        RecordBatch::try_new(Arc::new(schema), vec![Arc::new(self.to_arrow_array())])
+            .expect("a single Float64 column always matches its schema")
+    }
+
+    /// Reads an array written by [`M1d::to_arrow`], taking the prefix from the field metadata.
+    ///
+    /// # Returns
+    /// A new [`M1d`], or an [`ArrowError`] if the batch does not hold exactly one column or
+    /// its unit differs from `U`.
+    pub fn from_arrow(batch: &RecordBatch) -> Result<Self, ArrowError> {
+        let (prefixes, mut columns) = read_columns::<U>(batch)?;
+        match (prefixes.as_slice(), columns.pop()) {
+            ([prefix], Some(values)) => Ok(M1d::new(values, *prefix)),
+            _ => Err(ArrowError::Format(format!(
+                "expected 1 column, found {}",
+                prefixes.len()
+            ))),
+        }
+    }
+}
+
+impl<U: Uom> M2d<U> {
+    /// Converts the array into a record batch with one `Float64` column per row (channel),
+    /// named `ch<i>` and tagged with the unit and prefix.
+    pub fn to_arrow(&self) -> RecordBatch {
+        let fields = (0..self.nrows())
+            .map(|i| field::<U>(&format!("ch{i}"), self.prefix()))
+            .collect::<Vec<_>>();
+        let columns = self
+            .rows()
+            .map(|row| Arc::new(row.to_arrow_array()) as ArrayRef)
+            .collect();
+        let options = RecordBatchOptions::new().with_row_count(Some(self.ncols()));
+        RecordBatch::try_new_with_options(Arc::new(Schema::new(fields)), columns, &options)
+            .expect("rows of equal length always match their schema")
+    }
+
+    /// Reads an array written by [`M2d::to_arrow`], one row per column.
+    ///
+    /// Columns with differing prefixes are converted to the finest of them.
+    ///
+    /// # Returns
+    /// A new [`M2d`], or an [`ArrowError`] if a column is not `Float64`, contains nulls or
+    /// has a unit other than `U`.
+    pub fn from_arrow(batch: &RecordBatch) -> Result<Self, ArrowError> {
+        let (prefixes, columns) = read_columns::<U>(batch)?;
+        let prefix = finest(prefixes.iter().copied()).unwrap_or(Prefix::None);
+        let rows = prefixes
+            .into_iter()
+            .zip(columns)
+            .map(|(p, c)| M1d::new(Array1::from(c), p))
+            .collect::<Vec<_>>();
+        M2d::from_rows(&rows, prefix).map_err(|e| ArrowError::Format(e.to_string()))
+    }
+}
+
+#[cfg(feature = "parquet")]
+mod parquet_io {
+    use super::*;
+    use arrow::{compute::concat_batches, record_batch::RecordBatchReader};
+    use parquet::{
+        arrow::{ArrowWriter, arrow_reader::ParquetRecordBatchReaderBuilder},
+        file::reader::ChunkReader,
+    };
+    use std::io::Write;
+
+    /// Writes `batch` as a Parquet file, embedding its schema and field metadata.
+    fn write_batch<W: Write + Send>(w: W, batch: &RecordBatch) -> Result<(), ArrowError> {
+        let mut writer = ArrowWriter::try_new(w, batch.schema(), None)?;
+        writer.write(batch)?;
+        writer.close()?;
+        Ok(())
+    }
+
+    /// Reads every row group of a Parquet file into a single record batch.
+    fn read_batch<R: ChunkReader + 'static>(r: R) -> Result<RecordBatch, ArrowError> {
+        let reader = ParquetRecordBatchReaderBuilder::try_new(r)?.build()?;
+        let schema = reader.schema();
+        let batches = reader.collect::<Result<Vec<_>, _>>()?;
+        Ok(concat_batches(&schema, &batches)?)
+    }
+
+    impl<U: Uom> M1d<U> {
+        /// Writes the array as a single-column Parquet file, see [`M1d::to_arrow`].
+        pub fn write_parquet<W: Write + Send>(&self, w: W) -> Result<(), ArrowError> {
+            write_batch(w, &self.to_arrow())
+        }
+
+        /// Reads an array written by [`M1d::write_parquet`].
+        ///
+        /// # Returns
+        /// A new [`M1d`], or an [`ArrowError`] if the file cannot be read or its metadata
+        /// does not describe an array of `U`.
+        pub fn read_parquet<R: ChunkReader + 'static>(r: R) -> Result<Self, ArrowError> {
+            M1d::from_arrow(&read_batch(r)?)
+        }
+    }
+
+    impl<U: Uom> M2d<U> {
+        /// Writes the array as a Parquet file with one column per row, see [`M2d::to_arrow`].
+        pub fn write_parquet<W: Write + Send>(&self, w: W) -> Result<(), ArrowError> {
+            write_batch(w, &self.to_arrow())
+        }
+
+        /// Reads an array written by [`M2d::write_parquet`].
+        ///
+        /// # Returns
+        /// A new [`M2d`], or an [`ArrowError`] if the file cannot be read or its metadata
+        /// does not describe an array of `U`.
+        pub fn read_parquet<R: ChunkReader + 'static>(r: R) -> Result<Self, ArrowError> {
+            M2d::from_arrow(&read_batch(r)?)
+        }
+    }
+}
+
+#[cfg(test)]
+mod arrow_io_tests {
+    use super::*;
+    use crate::uom::{Ampere, Volt};
+    use ndarray::Array2;
+
+    #[test]
+    fn m1d_round_trip() {
+        let m = M1d::<Volt>::new(vec![1.5, -3.0, 0.25], Prefix::Micro);
+        let batch = m.to_arrow();
+        let field = batch.schema().field(0).clone();
+        assert_eq!(field.name(), "value");
+        assert_eq!(field.metadata()[UNIT_KEY], "V");
+        assert_eq!(field.metadata()[PREFIX_KEY], "u");
+        assert_eq!(M1d::<Volt>::from_arrow(&batch).unwrap(), m);
+
+        let array = m.to_arrow_array();
+        assert_eq!(array.values().as_ref(), &[1.5, -3.0, 0.25]);
+        assert_eq!(
+            M1d::<Volt>::from_arrow_array(&array, Prefix::Micro).unwrap(),
+            m
+        );
+    }
+
+    #[test]
+    fn m2d_round_trip() {
+        let m = M2d::<Volt>::new(
+            Array2::from_shape_vec((2, 3), vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]).unwrap(),
+            Prefix::Milli,
+        );
+        let batch = m.to_arrow();
+        assert_eq!(batch.num_columns(), 2);
+        assert_eq!(batch.num_rows(), 3);
+        assert_eq!(batch.schema().field(1).name(), "ch1");
+        assert_eq!(M2d::<Volt>::from_arrow(&batch).unwrap(), m);
+    }
+
+    #[test]
+    fn unit_mismatch_is_rejected() {
+        let batch = M2d::<Volt>::new(Array2::zeros((2, 2)), Prefix::None).to_arrow();
+        match M2d::<Ampere>::from_arrow(&batch) {
+            Err(ArrowError::UnitMismatch { expected, found }) => {
+                assert_eq!(expected, "A");
+                assert_eq!(found, "V");
+            }
+            other => panic!("expected a unit mismatch, got {other:?}"),
+        }
+        assert!(
+            M1d::<Ampere>::from_arrow(&M1d::<Volt>::new(vec![1.0], Prefix::None).to_arrow())
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn missing_metadata_and_nulls_are_rejected() {
+        let schema = Schema::new(vec![Field::new("value", DataType::Float64, true)]);
+        let column = Arc::new(Float64Array::from(vec![Some(1.0), None])) as ArrayRef;
+        let batch = RecordBatch::try_new(Arc::new(schema), vec![column.clone()]).unwrap();
+        assert!(matches!(
+            M1d::<Volt>::from_arrow(&batch),
+            Err(ArrowError::Format(_))
+        ));
+        assert!(matches!(
+            M1d::<Volt>::from_arrow_array(&column, Prefix::None),
+            Err(ArrowError::Format(_))
+        ));
+    }
+
+    #[cfg(feature = "parquet")]
+    #[test]
+    fn parquet_round_trip() {
+        let dir = std::env::temp_dir().join(format!("measurements_parquet_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("m2d.parquet");
+        let m = M2d::<Volt>::new(
+            Array2::from_shape_vec((2, 3), vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]).unwrap(),
+            Prefix::Nano,
+        );
+        m.write_parquet(std::fs::File::create(&path).unwrap())
+            .unwrap();
+        let back = M2d::<Volt>::read_parquet(std::fs::File::open(&path).unwrap()).unwrap();
+        assert_eq!(back, m);
+        assert!(matches!(
+            M2d::<Ampere>::read_parquet(std::fs::File::open(&path).unwrap()),
+            Err(ArrowError::UnitMismatch { .. })
+        ));
+
+        let path = dir.join("m1d.parquet");
+        let m = M1d::<Ampere>::new(vec![0.5, 1.5], Prefix::Pico);
+        m.write_parquet(std::fs::File::create(&path).unwrap())
+            .unwrap();
+        let back = M1d::<Ampere>::read_parquet(std::fs::File::open(&path).unwrap()).unwrap();
+        assert_eq!(back, m);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,167 @@
+//! Named channels of different units, as stored in recording files.
+
+use crate::{dyn_measurement::DynM1d, error::MeasurementError, m1d::M1d, uom::Uom};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+/// A map from channel name to a [`DynM1d`], whose units may differ between channels.
+///
+/// Serializes as a plain map from name to array, e.g.
+/// `{"vm": {"values": .., "prefix": .., "unit": "V"}}`. Typed arrays go in with
+/// [`ChannelSet::insert`] and come out with [`ChannelSet::get_typed`], which checks the stored
+/// unit.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ChannelSet {
+    channels: HashMap<String, DynM1d>,
+}
+
+impl ChannelSet {
+    /// Creates an empty set.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a typed array under `name`, recording its unit.
+    ///
+    /// # Returns
+    /// The channel previously stored under `name`, if any.
+    pub fn insert<U: Uom>(&mut self, name: impl Into<String>, m: M1d<U>) -> Option<DynM1d> {
+        self.insert_dyn(name, m.into())
+    }
+
+    /// Adds an array whose unit is only known at runtime under `name`.
+    ///
+    /// # Returns
+    /// The channel previously stored under `name`, if any.
+    pub fn insert_dyn(&mut self, name: impl Into<String>, m: DynM1d) -> Option<DynM1d> {
+        self.channels.insert(name.into(), m)
+    }
+
+    /// Returns the channel stored under `name`, or `None` if there is none.
+    pub fn get(&self, name: &str) -> Option<&DynM1d> {
+        self.channels.get(name)
+    }
+
+    /// Returns the channel stored under `name` as an [`M1d<U>`].
+    ///
+    /// The channel is stored untyped, so the typed array is a copy rather than a reference.
+    ///
+    /// # Returns
+    /// The typed array; [`MeasurementError::MissingChannel`] if there is no channel named
+    /// `name`, or [`MeasurementError::UnitMismatch`] if its unit is not `U`.
+    pub fn get_typed<U: Uom>(&self, name: &str) -> Result<M1d<U>, MeasurementError> {
+        let channel = self
+            .get(name)
+            .ok_or_else(|| MeasurementError::MissingChannel(name.to_string()))?;
+        Ok(channel.try_into_typed()?)
+    }
+
+    /// Removes and returns the channel stored under `name`, if any.
+    pub fn remove(&mut self, name: &str) -> Option<DynM1d> {
+        self.channels.remove(name)
+    }
+
+    /// Returns the number of channels.
+    pub fn len(&self) -> usize {
+        self.channels.len()
+    }
+
+    /// Returns `true` if the set holds no channel.
+    pub fn is_empty(&self) -> bool {
+        self.channels.is_empty()
+    }
+
+    /// Returns an iterator over the names and channels, in arbitrary order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &DynM1d)> {
+        self.channels.iter().map(|(name, m)| (name.as_str(), m))
+    }
+
+    /// Groups the channels by unit label.
+    ///
+    /// # Returns
+    /// A map from unit label to the names and channels of that unit, sorted by name.
+    pub fn group_by_unit(&self) -> BTreeMap<&str, Vec<(&str, &DynM1d)>> {
+        let mut groups = BTreeMap::<&str, Vec<(&str, &DynM1d)>>::new();
+        for (name, m) in self.iter() {
+            groups.entry(m.unit()).or_default().push((name, m));
+        }
+        groups
+            .values_mut()
+            .for_each(|group| group.sort_unstable_by_key(|(name, _)| *name));
+        groups
+    }
+}
+
+#[cfg(test)]
+mod channel_set_tests {
+    use super::*;
+    use crate::{
+        prefix::Prefix,
+        uom::{Ampere, Volt},
+    };
+
+    fn recording() -> ChannelSet {
+        let mut set = ChannelSet::new();
+        set.insert("vm", M1d::<Volt>::new(vec![-70.0, -65.5], Prefix::Milli));
+        set.insert("im", M1d::<Ampere>::new(vec![1.0, 2.0], Prefix::Pico));
+        set.insert("command", M1d::<Volt>::new(vec![0.0, 10.0], Prefix::Milli));
+        set
+    }
+
+    #[test]
+    fn round_trips_mixed_units() {
+        let set = recording();
+        let json = serde_json::to_string(&set).unwrap();
+        let back: ChannelSet = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, set);
+        assert_eq!(back.len(), 3);
+        assert_eq!(
+            back.get_typed::<Volt>("vm").unwrap(),
+            M1d::new(vec![-70.0, -65.5], Prefix::Milli)
+        );
+        assert_eq!(
+            back.get_typed::<Ampere>("im").unwrap(),
+            M1d::new(vec![1.0, 2.0], Prefix::Pico)
+        );
+    }
+
+    #[test]
+    fn checks_names_and_units() {
+        let mut set = recording();
+        assert_eq!(
+            set.get_typed::<Ampere>("vm"),
+            Err(MeasurementError::UnitMismatch {
+                expected: "A".to_string(),
+                found: "V".to_string(),
+            })
+        );
+        assert_eq!(
+            set.get_typed::<Volt>("temperature")
+                .unwrap_err()
+                .to_string(),
+            "no channel named `temperature`"
+        );
+        let previous = set.insert("im", M1d::<Volt>::new(vec![1.0], Prefix::None));
+        assert_eq!(previous.unwrap().unit(), "A");
+        assert!(set.get_typed::<Volt>("im").is_ok());
+        assert!(set.remove("im").is_some());
+        assert_eq!(set.len(), 2);
+    }
+
+    #[test]
+    fn groups_by_unit() {
+        let set = recording();
+        let groups = set.group_by_unit();
+        let names = |unit| {
+            groups[unit]
+                .iter()
+                .map(|(name, _)| *name)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(groups.keys().copied().collect::<Vec<_>>(), ["A", "V"]);
+        assert_eq!(names("A"), ["im"]);
+        assert_eq!(names("V"), ["command", "vm"]);
+        assert!(ChannelSet::new().group_by_unit().is_empty());
+    }
+}
@@ -0,0 +1,196 @@
+//! Acquisitions with a channel count fixed at compile time.
+
+use crate::{
+    error::MeasurementError,
+    m1d::M1d,
+    m2d::M2d,
+    prefix::{Prefix, finest},
+    uom::Uom,
+};
+use ndarray::{ErrorKind, ShapeError};
+use std::ops::Deref;
+
+/// An [`M2d`] with exactly `N` channels, one per row, for devices with a fixed channel count.
+///
+/// The count is part of the type, so a 4-channel acquisition cannot be passed where an
+/// 8-channel one is expected, and [`MChannels::channel_const`] checks its index at compile
+/// time. It dereferences to the wrapped [`M2d`], so every statistic is available as is.
+///
+/// ```
+/// use typed_measurements::prelude::*;
+///
+/// let m = M2d::<Volt>::zeros((4, 100), Prefix::Milli);
+/// let quad = MChannels::<4, Volt>::try_from(m).unwrap();
+/// assert_eq!(quad.channel_const::<3>().len(), 100);
+/// assert_eq!(quad.mean(), Some(Measurement::new(0.0, Prefix::None)));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct MChannels<const N: usize, U: Uom> {
+    data: M2d<U>,
+}
+
+impl<const N: usize, U: Uom> MChannels<N, U> {
+    /// Stacks `channels`, converting them to the finest of their prefixes.
+    ///
+    /// # Returns
+    /// The acquisition, or [`MeasurementError::ShapeMismatch`] if the channels differ in
+    /// length.
+    pub fn from_channels(channels: [M1d<U>; N]) -> Result<Self, MeasurementError> {
+        let prefix = finest(channels.iter().map(M1d::prefix)).unwrap_or(Prefix::None);
+        Ok(Self {
+            data: M2d::from_rows(&channels, prefix)?,
+        })
+    }
+
+    /// Returns channel `i`, copied into an [`M1d`]: the channels are rows of one array and
+    /// are not stored as separate [`M1d`]s to borrow.
+    ///
+    /// # Panics
+    /// Panics if `i` is not below `N`.
+    pub fn channel(&self, i: usize) -> M1d<U> {
+        assert!(i < N, "channel {i} out of bounds for {N} channels");
+        self.data.row(i).expect("the array has N rows")
+    }
+
+    /// Returns channel `I`, like [`MChannels::channel`] with the bound checked at compile
+    /// time.
+    pub fn channel_const<const I: usize>(&self) -> M1d<U> {
+        const { assert!(I < N, "channel index out of bounds") };
+        self.channel(I)
+    }
+
+    /// Returns every channel, each copied into an [`M1d`].
+    pub fn channels(&self) -> [M1d<U>; N] {
+        std::array::from_fn(|i| self.channel(i))
+    }
+
+    /// Applies `f` to every channel, keeping the channel count.
+    ///
+    /// # Returns
+    /// The mapped acquisition, or [`MeasurementError::ShapeMismatch`] if `f` returns
+    /// channels of different lengths.
+    pub fn map_channels<V: Uom>(
+        &self,
+        f: impl FnMut(M1d<U>) -> M1d<V>,
+    ) -> Result<MChannels<N, V>, MeasurementError> {
+        MChannels::from_channels(self.channels().map(f))
+    }
+
+    /// Combines every channel with the matching channel of `other`.
+    ///
+    /// # Returns
+    /// The combined acquisition, or [`MeasurementError::ShapeMismatch`] if `f` returns
+    /// channels of different lengths.
+    pub fn zip_channels<B: Uom, V: Uom>(
+        &self,
+        other: &MChannels<N, B>,
+        mut f: impl FnMut(M1d<U>, M1d<B>) -> M1d<V>,
+    ) -> Result<MChannels<N, V>, MeasurementError> {
+        let mut others = other.channels().into_iter();
+        let zipped = self
+            .channels()
+            .map(|c| f(c, others.next().expect("both have N channels")));
+        MChannels::from_channels(zipped)
+    }
+
+    /// Returns the array without its compile-time channel count.
+    pub fn into_inner(self) -> M2d<U> {
+        self.data
+    }
+}
+
+impl<const N: usize, U: Uom> Deref for MChannels<N, U> {
+    type Target = M2d<U>;
+
+    fn deref(&self) -> &M2d<U> {
+        &self.data
+    }
+}
+
+impl<const N: usize, U: Uom> From<MChannels<N, U>> for M2d<U> {
+    fn from(m: MChannels<N, U>) -> Self {
+        m.data
+    }
+}
+
+impl<const N: usize, U: Uom> TryFrom<M2d<U>> for MChannels<N, U> {
+    type Error = MeasurementError;
+
+    /// Checks that `m` has exactly `N` rows.
+    fn try_from(m: M2d<U>) -> Result<Self, Self::Error> {
+        if m.nrows() != N {
+            return Err(ShapeError::from_kind(ErrorKind::IncompatibleShape).into());
+        }
+        Ok(Self { data: m })
+    }
+}
+
+#[cfg(test)]
+mod channels_tests {
+    use super::*;
+    use crate::{
+        measurement::Measurement,
+        uom::{Ampere, Ohm, Volt},
+    };
+    use ndarray::{Array2, array};
+
+    fn quad() -> MChannels<4, Volt> {
+        MChannels::from_channels([
+            M1d::new(vec![1.0, 2.0], Prefix::Milli),
+            M1d::new(vec![3.0, 4.0], Prefix::Milli),
+            M1d::new(vec![500.0, 600.0], Prefix::Micro),
+            M1d::new(vec![0.0, 0.0], Prefix::None),
+        ])
+        .unwrap()
+    }
+
+    #[test]
+    fn converts_from_and_to_m2d() {
+        let m = M2d::<Volt>::new(Array2::zeros((4, 3)), Prefix::Milli);
+        let quad = MChannels::<4, Volt>::try_from(m.clone()).unwrap();
+        assert_eq!(M2d::from(quad), m);
+        let five = M2d::<Volt>::new(Array2::zeros((5, 3)), Prefix::Milli);
+        assert!(matches!(
+            MChannels::<4, Volt>::try_from(five),
+            Err(MeasurementError::ShapeMismatch(_))
+        ));
+        let ragged = MChannels::<2, Volt>::from_channels([
+            M1d::new(vec![1.0], Prefix::None),
+            M1d::new(vec![1.0, 2.0], Prefix::None),
+        ]);
+        assert!(ragged.is_err());
+    }
+
+    #[test]
+    fn accesses_channels() {
+        let quad = quad();
+        assert_eq!(quad.prefix(), Prefix::Micro);
+        assert_eq!(quad.channel(1), M1d::new(vec![3.0, 4.0], Prefix::Milli));
+        assert_eq!(quad.channel_const::<2>().values(), array![500.0, 600.0]);
+        assert_eq!(quad.channels().len(), 4);
+        // statistics come from the wrapped array
+        assert_eq!(quad.shape(), (4, 2));
+        assert_eq!(quad.max(), Some(Measurement::new(4.0, Prefix::Milli)));
+    }
+
+    #[test]
+    #[should_panic(expected = "channel 4 out of bounds for 4 channels")]
+    fn rejects_out_of_bounds_channels() {
+        quad().channel(4);
+    }
+
+    #[test]
+    fn maps_and_zips_channels() {
+        let quad = quad();
+        let doubled = quad
+            .map_channels(|c| M1d::<Volt>::new(c.values() * 2.0, c.prefix()))
+            .unwrap();
+        assert_eq!(doubled.channel(0), M1d::new(vec![2.0, 4.0], Prefix::Milli));
+        let load = MChannels::<4, Ohm>::from_channels(std::array::from_fn(|_| {
+            M1d::new(vec![1.0, 2.0], Prefix::Kilo)
+        }))
+        .unwrap();
+        let currents: MChannels<4, Ampere> = quad.zip_channels(&load, |v, r| &v / &r).unwrap();
+        assert_eq!(currents.channel(1), M1d::new(vec![3.0, 2.0], Prefix::Micro));
+    }
+}
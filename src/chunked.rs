@@ -0,0 +1,378 @@
+//! Reading recordings larger than memory as a sequence of [`M1d`] chunks.
+//!
+//! An [`M1dChunkReader`] reads a single-column CSV file (with the `csv` feature) or a raw
+//! stream of little-endian `f64`, and yields chunks of a fixed length.
+//! [`M1dChunkReader::fold_chunks`] feeds them into [`RunningStats`], so whole-file
+//! statistics need memory for one chunk only.
+
+#[cfg(feature = "csv")]
+use crate::{
+    csv_io::{CsvError, parse_header},
+    measurement::{ParseMeasurementError, ParseOptions, parse_number},
+};
+use crate::{m1d::M1d, prefix::Prefix, stats::RunningStats, uom::Uom};
+use std::{
+    fmt,
+    io::{self, Read},
+    marker::PhantomData,
+};
+
+/// Errors raised while reading chunks.
+///
+/// Errors in a single record ([`ChunkError::Parse`], and [`ChunkError::Csv`] for a
+/// malformed row) skip that record: the reader can be polled again to resume after it.
+#[derive(Debug)]
+pub enum ChunkError {
+    /// An error from the underlying reader.
+    Io(io::Error),
+    /// A malformed header or row.
+    #[cfg(feature = "csv")]
+    Csv(CsvError),
+    /// A cell that could not be parsed as a number, at `line` starting at byte offset `byte`.
+    Parse { line: u64, byte: u64, value: String },
+    /// A raw stream whose length is not a whole number of `f64`, ending with a partial value
+    /// at byte offset `byte`.
+    Truncated { byte: u64 },
+}
+
+impl fmt::Display for ChunkError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "{e}"),
+            #[cfg(feature = "csv")]
+            Self::Csv(e) => write!(f, "{e}"),
+            Self::Parse { line, byte, value } => {
+                write!(f, "line {line} (byte {byte}): cannot parse `{value}`")
+            }
+            Self::Truncated { byte } => {
+                write!(f, "byte {byte}: the stream ends within a value")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ChunkError {}
+
+impl From<io::Error> for ChunkError {
+    fn from(e: io::Error) -> Self {
+        Self::Io(e)
+    }
+}
+
+#[cfg(feature = "csv")]
+impl From<CsvError> for ChunkError {
+    fn from(e: CsvError) -> Self {
+        Self::Csv(e)
+    }
+}
+
+/// Where the values come from.
+enum Source<R: Read> {
+    #[cfg(feature = "csv")]
+    Csv {
+        reader: csv::Reader<R>,
+        record: csv::StringRecord,
+        options: ParseOptions,
+    },
+    Raw {
+        reader: R,
+        /// Bytes consumed so far.
+        byte: u64,
+    },
+}
+
+/// Reads a recording as [`M1d`] chunks of a fixed length, without loading it whole.
+///
+/// Every chunk holds `chunk_len` values except the last, which holds the rest. Values read
+/// before an error are kept for the next chunk, so skipping a bad record loses only that
+/// record.
+///
+/// ```
+/// use typed_measurements::{chunked::M1dChunkReader, prelude::*};
+///
+/// let bytes = [1.0f64, 2.0, 3.0]
+///     .iter()
+///     .flat_map(|v| v.to_le_bytes())
+///     .collect::<Vec<_>>();
+/// let reader = M1dChunkReader::<Volt, _>::from_raw_f64(bytes.as_slice(), Prefix::Milli, 2);
+/// let stats = reader.fold_chunks().unwrap();
+/// assert_eq!(stats.mean(), Some(Measurement::new(2.0, Prefix::Milli)));
+/// ```
+pub struct M1dChunkReader<U: Uom, R: Read> {
+    source: Source<R>,
+    prefix: Prefix,
+    chunk_len: usize,
+    pending: Vec<f64>,
+    done: bool,
+    uom: PhantomData<U>,
+}
+
+impl<U: Uom, R: Read> M1dChunkReader<U, R> {
+    fn with_source(source: Source<R>, prefix: Prefix, chunk_len: usize) -> Self {
+        assert!(chunk_len > 0, "chunk length must be positive");
+        Self {
+            source,
+            prefix,
+            chunk_len,
+            pending: Vec::with_capacity(chunk_len),
+            done: false,
+            uom: PhantomData,
+        }
+    }
+
+    /// Reads a single column written by [`M1d::to_csv`], taking the prefix from the header.
+    ///
+    /// # Returns
+    /// The reader, or [`ChunkError::Csv`] if the header is malformed, has more than one
+    /// column or a unit other than `U`.
+    ///
+    /// # Panics
+    /// Panics if `chunk_len` is zero.
+    #[cfg(feature = "csv")]
+    pub fn from_csv(r: R, chunk_len: usize) -> Result<Self, ChunkError> {
+        Self::from_csv_with(r, chunk_len, &ParseOptions::default())
+    }
+
+    /// Reads a single column like [`M1dChunkReader::from_csv`], parsing cells with
+    /// `options` as [`M1d::from_csv_with`] does.
+    ///
+    /// # Panics
+    /// Panics if `chunk_len` is zero.
+    #[cfg(feature = "csv")]
+    pub fn from_csv_with(
+        r: R,
+        chunk_len: usize,
+        options: &ParseOptions,
+    ) -> Result<Self, ChunkError> {
+        let mut reader = csv::ReaderBuilder::new()
+            .delimiter(options.list_separator() as u8)
+            .from_reader(r);
+        let headers = reader.headers().map_err(CsvError::from)?;
+        let prefix = match headers.iter().collect::<Vec<_>>()[..] {
+            [header] => parse_header::<U>(header)?,
+            _ => {
+                let found = format!("expected 1 column, found {}", headers.len());
+                return Err(CsvError::Header(found).into());
+            }
+        };
+        let source = Source::Csv {
+            reader,
+            record: csv::StringRecord::new(),
+            options: *options,
+        };
+        Ok(Self::with_source(source, prefix, chunk_len))
+    }
+
+    /// Reads a raw stream of little-endian `f64` values in `prefix`, such as the data of a
+    /// binary recording without its header.
+    ///
+    /// # Panics
+    /// Panics if `chunk_len` is zero.
+    pub fn from_raw_f64(r: R, prefix: Prefix, chunk_len: usize) -> Self {
+        let source = Source::Raw { reader: r, byte: 0 };
+        Self::with_source(source, prefix, chunk_len)
+    }
+
+    /// Returns the prefix of the chunks.
+    pub fn prefix(&self) -> Prefix {
+        self.prefix
+    }
+
+    /// Returns the number of values per chunk.
+    pub fn chunk_len(&self) -> usize {
+        self.chunk_len
+    }
+
+    /// Reads every remaining chunk into [`RunningStats`] accumulated in the prefix of the
+    /// chunks, holding one chunk in memory at a time.
+    ///
+    /// # Returns
+    /// The statistics, or the first error met.
+    pub fn fold_chunks(self) -> Result<RunningStats<U>, ChunkError> {
+        let mut stats = RunningStats::new(self.prefix);
+        for chunk in self {
+            stats.push_array(&chunk?);
+        }
+        Ok(stats)
+    }
+
+    /// Returns the pending values as a chunk, or `None` if there are none.
+    fn take_chunk(&mut self) -> Option<M1d<U>> {
+        let values = std::mem::replace(&mut self.pending, Vec::with_capacity(self.chunk_len));
+        (!values.is_empty()).then(|| M1d::new(values, self.prefix))
+    }
+
+    /// Reads values until the chunk is full or the input ends.
+    fn fill(&mut self) -> Result<(), ChunkError> {
+        match &mut self.source {
+            #[cfg(feature = "csv")]
+            Source::Csv {
+                reader,
+                record,
+                options,
+            } => {
+                while self.pending.len() < self.chunk_len {
+                    match reader.read_record(record) {
+                        Ok(true) => {}
+                        Ok(false) => {
+                            self.done = true;
+                            break;
+                        }
+                        Err(e) => {
+                            // a malformed row can be skipped, a failing reader cannot
+                            self.done = e.is_io_error();
+                            return Err(CsvError::from(e).into());
+                        }
+                    }
+                    let cell = record.get(0).unwrap_or_default();
+                    match parse_number(cell, options) {
+                        Ok(v) => self.pending.push(v),
+                        Err(e) => {
+                            let (line, byte) =
+                                record.position().map_or((0, 0), |p| (p.line(), p.byte()));
+                            let value = cell.to_string();
+                            return Err(match e {
+                                ParseMeasurementError::Ambiguous(_) => {
+                                    CsvError::Ambiguous { line, value }.into()
+                                }
+                                _ => ChunkError::Parse { line, byte, value },
+                            });
+                        }
+                    }
+                }
+            }
+            Source::Raw { reader, byte } => {
+                let mut buf = [0u8; 8];
+                while self.pending.len() < self.chunk_len {
+                    let read = read_full(reader, &mut buf).inspect_err(|_| self.done = true)?;
+                    match read {
+                        8 => self.pending.push(f64::from_le_bytes(buf)),
+                        0 => {
+                            self.done = true;
+                            break;
+                        }
+                        _ => {
+                            self.done = true;
+                            return Err(ChunkError::Truncated { byte: *byte });
+                        }
+                    }
+                    *byte += 8;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Reads into `buf` until it is full or the input ends, returning the number of bytes read.
+fn read_full(r: &mut impl Read, buf: &mut [u8]) -> io::Result<usize> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        match r.read(&mut buf[filled..]) {
+            Ok(0) => break,
+            Ok(n) => filled += n,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(filled)
+}
+
+impl<U: Uom, R: Read> Iterator for M1dChunkReader<U, R> {
+    type Item = Result<M1d<U>, ChunkError>;
+
+    /// Returns the next chunk, or an error for a record that could not be read; polling
+    /// again resumes after a skippable error.
+    fn next(&mut self) -> Option<Self::Item> {
+        if !self.done
+            && let Err(e) = self.fill()
+        {
+            return Some(Err(e));
+        }
+        self.take_chunk().map(Ok)
+    }
+}
+
+#[cfg(test)]
+mod chunked_tests {
+    use super::*;
+    use crate::uom::Volt;
+
+    fn samples() -> Vec<f64> {
+        (0..1000)
+            .map(|i| (i as f64 * 0.37).sin() * 5.0 + 1.0)
+            .collect()
+    }
+
+    #[cfg(feature = "csv")]
+    #[test]
+    fn streamed_stats_match_the_whole_array() {
+        let whole = M1d::<Volt>::new(samples(), Prefix::Milli);
+        let mut csv = Vec::new();
+        whole.to_csv(&mut csv).unwrap();
+        let reader = M1dChunkReader::<Volt, _>::from_csv(csv.as_slice(), 64).unwrap();
+        assert_eq!(reader.prefix(), Prefix::Milli);
+        let lens = reader.map(|c| c.unwrap().len()).collect::<Vec<_>>();
+        assert_eq!(lens.len(), 16);
+        assert!(lens[..15].iter().all(|&n| n == 64));
+        assert_eq!(lens[15], 1000 - 15 * 64);
+        let reader = M1dChunkReader::<Volt, _>::from_csv(csv.as_slice(), 64).unwrap();
+        let stats = reader.fold_chunks().unwrap();
+        assert_eq!(stats.count(), 1000);
+        assert_eq!(stats.mean(), whole.mean());
+        let mut reference = RunningStats::new(Prefix::Milli);
+        reference.push_array(&whole);
+        assert_eq!(stats.min(), reference.min());
+        assert_eq!(stats.max(), reference.max());
+        assert_eq!(stats.variance(1.0), reference.variance(1.0));
+    }
+
+    #[cfg(feature = "csv")]
+    #[test]
+    fn resumes_after_a_bad_record() {
+        let csv = "value[uV]\n1\n2\nabc\n3\n4\n5\n";
+        let mut reader = M1dChunkReader::<Volt, _>::from_csv(csv.as_bytes(), 2).unwrap();
+        assert_eq!(reader.next().unwrap().unwrap().to_vec(), [1.0, 2.0]);
+        let err = reader.next().unwrap().unwrap_err();
+        assert!(
+            matches!(
+                err,
+                ChunkError::Parse {
+                    line: 4,
+                    byte: 14,
+                    ..
+                }
+            ),
+            "{err}"
+        );
+        assert_eq!(err.to_string(), "line 4 (byte 14): cannot parse `abc`");
+        assert_eq!(reader.next().unwrap().unwrap().to_vec(), [3.0, 4.0]);
+        assert_eq!(reader.next().unwrap().unwrap().to_vec(), [5.0]);
+        assert!(reader.next().is_none());
+        let wide = "a[V],b[V]\n1,2\n";
+        assert!(matches!(
+            M1dChunkReader::<Volt, _>::from_csv(wide.as_bytes(), 2),
+            Err(ChunkError::Csv(CsvError::Header(_)))
+        ));
+    }
+
+    #[test]
+    fn reads_raw_f64_streams() {
+        let values = samples();
+        let bytes = values
+            .iter()
+            .flat_map(|v| v.to_le_bytes())
+            .collect::<Vec<_>>();
+        let reader = M1dChunkReader::<Volt, _>::from_raw_f64(bytes.as_slice(), Prefix::Micro, 100);
+        let chunks = reader.collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(chunks.len(), 10);
+        assert_eq!(chunks[0].prefix(), Prefix::Micro);
+        let joined = chunks.iter().flat_map(M1d::to_vec).collect::<Vec<_>>();
+        assert_eq!(joined, values);
+        let mut reader = M1dChunkReader::<Volt, _>::from_raw_f64(&bytes[..20], Prefix::None, 4);
+        let err = reader.next().unwrap().unwrap_err();
+        assert!(matches!(err, ChunkError::Truncated { byte: 16 }), "{err}");
+        assert_eq!(reader.next().unwrap().unwrap().len(), 2);
+        assert!(reader.next().is_none());
+    }
+}
@@ -0,0 +1,161 @@
+//! Value parsers for command-line arguments such as `--amplitude 50mV`, enabled by the `cli`
+//! feature.
+//!
+//! ```
+//! use clap::{Arg, Command};
+//! use typed_measurements::{cli::measurement_parser, prelude::*};
+//!
+//! let matches = Command::new("stim")
+//!     .arg(Arg::new("amplitude").long("amplitude").value_parser(measurement_parser::<Volt>()))
+//!     .try_get_matches_from(["stim", "--amplitude", "50mV"])?;
+//! let amplitude = matches.get_one::<Measurement<Volt>>("amplitude").unwrap();
+//! assert_eq!(*amplitude, Measurement::new(50.0, Prefix::Milli));
+//! # Ok::<(), clap::Error>(())
+//! ```
+
+use crate::{measurement::Measurement, ranged_measurement::RangedMeasurement, uom::Uom};
+use clap::{Arg, Command, builder::TypedValueParser, error::ErrorKind};
+use std::ffi::OsStr;
+
+/// A clap value parser for [`Measurement<U>`] labels, optionally restricted to a range.
+///
+/// Created by [`measurement_parser`] and [`ranged_parser`].
+#[derive(Clone, Debug)]
+pub struct MeasurementParser<U: Uom> {
+    range: Option<RangedMeasurement<U>>,
+}
+
+/// Returns a parser accepting any label in unit `U`, such as `"50mV"` or `"-2e3 uV"`.
+pub fn measurement_parser<U: Uom>() -> MeasurementParser<U> {
+    MeasurementParser { range: None }
+}
+
+/// Returns a parser accepting labels in unit `U` whose value lies within `range`, bounds
+/// included. The step of the range is not enforced.
+pub fn ranged_parser<U: Uom>(range: RangedMeasurement<U>) -> MeasurementParser<U> {
+    MeasurementParser { range: Some(range) }
+}
+
+impl<U: Uom> TypedValueParser for MeasurementParser<U> {
+    type Value = Measurement<U>;
+
+    fn parse_ref(
+        &self,
+        cmd: &Command,
+        arg: Option<&Arg>,
+        value: &OsStr,
+    ) -> Result<Self::Value, clap::Error> {
+        let invalid = |reason: String| {
+            let arg = arg.map_or_else(|| "...".to_string(), Arg::to_string);
+            let message = format!(
+                "invalid value '{}' for '{arg}': {reason}\n",
+                value.to_string_lossy()
+            );
+            clap::Error::raw(ErrorKind::ValueValidation, message).with_cmd(cmd)
+        };
+        let text = value
+            .to_str()
+            .ok_or_else(|| invalid("not valid UTF-8".to_string()))?;
+        let m = text.parse::<Measurement<U>>().map_err(|e| {
+            invalid(format!(
+                "{e} (expected a measurement in {}, such as `1.5m{}`)",
+                U::name_str(),
+                U::uom_str()
+            ))
+        })?;
+        match &self.range {
+            Some(range) if !(m >= range.min() && m <= range.max()) => Err(invalid(format!(
+                "must be between {} and {}",
+                range.min(),
+                range.max()
+            ))),
+            _ => Ok(m),
+        }
+    }
+}
+
+#[cfg(test)]
+mod cli_tests {
+    use super::*;
+    use crate::{
+        prefix::Prefix,
+        uom::{Second, Volt},
+    };
+
+    fn command() -> Command {
+        Command::new("stim")
+            .arg(
+                Arg::new("amplitude")
+                    .long("amplitude")
+                    .value_parser(measurement_parser::<Volt>()),
+            )
+            .arg(
+                Arg::new("duration")
+                    .long("duration")
+                    .value_parser(ranged_parser(RangedMeasurement::<Second>::new(
+                        0.0,
+                        1.0,
+                        0.1,
+                        Prefix::None,
+                    ))),
+            )
+    }
+
+    fn error(args: &[&str]) -> String {
+        let e = command()
+            .try_get_matches_from(args)
+            .expect_err("arguments should be rejected");
+        assert_eq!(e.kind(), ErrorKind::ValueValidation);
+        e.to_string()
+    }
+
+    #[test]
+    fn parses_valid_arguments() {
+        let matches = command()
+            .try_get_matches_from(["stim", "--amplitude", "50mV", "--duration", "200ms"])
+            .unwrap();
+        assert_eq!(
+            matches.get_one::<Measurement<Volt>>("amplitude"),
+            Some(&Measurement::new(50.0, Prefix::Milli))
+        );
+        assert_eq!(
+            matches.get_one::<Measurement<Second>>("duration"),
+            Some(&Measurement::new(200.0, Prefix::Milli))
+        );
+        // bounds are included
+        let matches = command()
+            .try_get_matches_from(["stim", "--duration", "1s"])
+            .unwrap();
+        assert_eq!(
+            matches.get_one::<Measurement<Second>>("duration"),
+            Some(&Measurement::new(1.0, Prefix::None))
+        );
+    }
+
+    #[test]
+    fn reports_the_expected_unit() {
+        let message = error(&["stim", "--amplitude", "50mA"]);
+        assert!(
+            message.contains("invalid value '50mA' for '--amplitude <amplitude>'"),
+            "{message}"
+        );
+        assert!(message.contains("expected `V`, found `mA`"), "{message}");
+        assert!(message.contains("such as `1.5mV`"), "{message}");
+        let message = error(&["stim", "--amplitude", "fifty"]);
+        assert!(
+            message.contains("does not start with a number"),
+            "{message}"
+        );
+    }
+
+    #[test]
+    fn reports_the_allowed_bounds() {
+        let message = error(&["stim", "--duration", "1500ms"]);
+        assert!(
+            message.contains("invalid value '1500ms' for '--duration <duration>'"),
+            "{message}"
+        );
+        assert!(message.contains("must be between 0s and 1s"), "{message}");
+        error(&["stim", "--duration=-1us"]);
+    }
+}
@@ -0,0 +1,263 @@
+//! Versioned deserialization of stored measurements.
+//!
+//! The serde layout of the crate's types may change between releases. The deserializers here
+//! pin every published layout, independently of the current `Deserialize` implementations, so
+//! recordings written by older releases stay readable.
+//!
+//! Version 1 is the layout written up to and including 0.7: a `value` or `values` field next
+//! to a `prefix` holding the variant name of [`Prefix`] (e.g. `"Milli"`), with arrays in
+//! ndarray's serde format (`{"v": 1, "dim": [..], "data": [..]}`). The unit is not stored.
+
+#[cfg(feature = "ndarray")]
+use crate::{m1d::M1d, m2d::M2d};
+use crate::{measurement::Measurement, prefix::Prefix, uom::Uom};
+#[cfg(feature = "ndarray")]
+use alloc::{format, vec::Vec};
+#[cfg(feature = "ndarray")]
+use ndarray::{Array1, Array2};
+#[cfg(feature = "ndarray")]
+use serde::de::Error;
+use serde::{Deserialize, Deserializer};
+#[cfg(feature = "json")]
+use std::fmt;
+
+/// The newest layout version understood by this release.
+pub const LATEST_VERSION: u64 = 1;
+
+/// [`Prefix`] as written by version 1: its variant name.
+#[derive(Deserialize)]
+enum PrefixV1 {
+    Tera,
+    Giga,
+    Mega,
+    Kilo,
+    None,
+    Milli,
+    Micro,
+    Nano,
+    Pico,
+    Femto,
+}
+
+impl From<PrefixV1> for Prefix {
+    fn from(p: PrefixV1) -> Self {
+        match p {
+            PrefixV1::Tera => Prefix::Tera,
+            PrefixV1::Giga => Prefix::Giga,
+            PrefixV1::Mega => Prefix::Mega,
+            PrefixV1::Kilo => Prefix::Kilo,
+            PrefixV1::None => Prefix::None,
+            PrefixV1::Milli => Prefix::Milli,
+            PrefixV1::Micro => Prefix::Micro,
+            PrefixV1::Nano => Prefix::Nano,
+            PrefixV1::Pico => Prefix::Pico,
+            PrefixV1::Femto => Prefix::Femto,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct MeasurementV1 {
+    value: f64,
+    prefix: PrefixV1,
+}
+
+/// An array in ndarray's serde format, in row-major order.
+#[cfg(feature = "ndarray")]
+#[derive(Deserialize)]
+struct ArrayV1 {
+    v: u8,
+    dim: Vec<usize>,
+    data: Vec<f64>,
+}
+
+#[cfg(feature = "ndarray")]
+impl ArrayV1 {
+    /// Checks the array format version and the number of dimensions.
+    fn check<E: Error>(&self, ndim: usize) -> Result<(), E> {
+        if self.v != 1 {
+            return Err(E::custom(format!("unknown array version {}", self.v)));
+        }
+        if self.dim.len() != ndim {
+            return Err(E::custom(format!(
+                "expected {ndim} dimensions, found {}",
+                self.dim.len()
+            )));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "ndarray")]
+#[derive(Deserialize)]
+struct ArrayMeasurementV1 {
+    values: ArrayV1,
+    prefix: PrefixV1,
+}
+
+impl<U: Uom> Measurement<U> {
+    /// Deserializes a measurement stored in the version 1 layout, `{"value": .., "prefix": ..}`.
+    pub fn deserialize_v1<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let m = MeasurementV1::deserialize(deserializer)?;
+        Ok(Measurement::new(m.value, m.prefix.into()))
+    }
+}
+
+#[cfg(feature = "ndarray")]
+impl<U: Uom> M1d<U> {
+    /// Deserializes an array stored in the version 1 layout, `{"values": .., "prefix": ..}`.
+    pub fn deserialize_v1<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let m = ArrayMeasurementV1::deserialize(deserializer)?;
+        m.values.check(1)?;
+        if m.values.dim[0] != m.values.data.len() {
+            return Err(D::Error::custom("array data does not match its shape"));
+        }
+        Ok(M1d::new(Array1::from(m.values.data), m.prefix.into()))
+    }
+}
+
+#[cfg(feature = "ndarray")]
+impl<U: Uom> M2d<U> {
+    /// Deserializes an array stored in the version 1 layout, `{"values": .., "prefix": ..}`.
+    pub fn deserialize_v1<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let m = ArrayMeasurementV1::deserialize(deserializer)?;
+        m.values.check(2)?;
+        let shape = (m.values.dim[0], m.values.dim[1]);
+        let values = Array2::from_shape_vec(shape, m.values.data).map_err(D::Error::custom)?;
+        Ok(M2d::new(values, m.prefix.into()))
+    }
+}
+
+/// A stored value of any of the shapes recognized by [`from_json_any_version`].
+#[cfg(feature = "json")]
+#[derive(Clone, Debug, PartialEq)]
+pub enum Recording<U: Uom> {
+    Measurement(Measurement<U>),
+    M1d(M1d<U>),
+    M2d(M2d<U>),
+}
+
+/// Errors raised by [`from_json_any_version`].
+#[cfg(feature = "json")]
+#[derive(Debug)]
+pub enum CompatError {
+    /// Invalid JSON, or JSON that does not match the layout of its version.
+    Json(serde_json::Error),
+    /// A `version` newer than [`LATEST_VERSION`], or not a non-negative integer.
+    UnsupportedVersion(serde_json::Value),
+    /// JSON that is not a measurement, a 1D array or a 2D array.
+    UnknownShape,
+}
+
+#[cfg(feature = "json")]
+impl fmt::Display for CompatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Json(e) => write!(f, "{e}"),
+            Self::UnsupportedVersion(v) => write!(f, "unsupported layout version {v}"),
+            Self::UnknownShape => write!(f, "not a stored measurement or array"),
+        }
+    }
+}
+
+#[cfg(feature = "json")]
+impl std::error::Error for CompatError {}
+
+#[cfg(feature = "json")]
+impl From<serde_json::Error> for CompatError {
+    fn from(e: serde_json::Error) -> Self {
+        Self::Json(e)
+    }
+}
+
+/// Reads a measurement, [`M1d`] or [`M2d`] stored as JSON by any release.
+///
+/// The layout version is taken from a top-level `"version"` field, and is 1 when that field is
+/// absent, as in everything written so far. The shape is then sniffed from the fields: `value`
+/// for a measurement, and `values` with one or two dimensions for an array.
+///
+/// # Returns
+/// The stored value, [`CompatError::UnsupportedVersion`] for an unknown version,
+/// [`CompatError::UnknownShape`] if the shape cannot be recognized, or
+/// [`CompatError::Json`] if the JSON does not match the recognized layout.
+#[cfg(feature = "json")]
+pub fn from_json_any_version<U: Uom>(json: &str) -> Result<Recording<U>, CompatError> {
+    use serde_json::Value;
+
+    let value: Value = serde_json::from_str(json)?;
+    let object = value.as_object().ok_or(CompatError::UnknownShape)?;
+    match object.get("version") {
+        None => {}
+        Some(v) if v.as_u64() == Some(1) => {}
+        Some(v) => return Err(CompatError::UnsupportedVersion(v.clone())),
+    }
+    if object.contains_key("value") {
+        return Ok(Recording::Measurement(Measurement::deserialize_v1(value)?));
+    }
+    let ndim = object
+        .get("values")
+        .and_then(|values| values.get("dim"))
+        .and_then(Value::as_array)
+        .map(Vec::len);
+    match ndim {
+        Some(1) => Ok(Recording::M1d(M1d::deserialize_v1(value)?)),
+        Some(2) => Ok(Recording::M2d(M2d::deserialize_v1(value)?)),
+        _ => Err(CompatError::UnknownShape),
+    }
+}
+
+#[cfg(test)]
+mod compat_tests {
+    use super::*;
+    use crate::uom::Volt;
+
+    #[test]
+    fn measurement_v1() {
+        let mut de = serde_json::Deserializer::from_str(r#"{"value":1.5,"prefix":"Milli"}"#);
+        let m = Measurement::<Volt>::deserialize_v1(&mut de).unwrap();
+        assert_eq!(m.value(), 1.5);
+        assert_eq!(m.prefix(), Prefix::Milli);
+        let mut de = serde_json::Deserializer::from_str(r#"{"value":1.5,"prefix":"mV"}"#);
+        assert!(Measurement::<Volt>::deserialize_v1(&mut de).is_err());
+    }
+
+    #[cfg(feature = "ndarray")]
+    #[test]
+    fn arrays_v1_check_shape() {
+        let json = r#"{"values":{"v":1,"dim":[2,2],"data":[1.0,2.0,3.0]},"prefix":"None"}"#;
+        let mut de = serde_json::Deserializer::from_str(json);
+        assert!(M2d::<Volt>::deserialize_v1(&mut de).is_err());
+        let json = r#"{"values":{"v":1,"dim":[3],"data":[1.0,2.0,3.0]},"prefix":"None"}"#;
+        let mut de = serde_json::Deserializer::from_str(json);
+        assert!(M2d::<Volt>::deserialize_v1(&mut de).is_err());
+        let mut de = serde_json::Deserializer::from_str(json);
+        let m = M1d::<Volt>::deserialize_v1(&mut de).unwrap();
+        assert_eq!(m, M1d::new(vec![1.0, 2.0, 3.0], Prefix::None));
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn version_dispatch() {
+        let m = from_json_any_version::<Volt>(r#"{"version":1,"value":2.0,"prefix":"Kilo"}"#);
+        assert_eq!(
+            m.unwrap(),
+            Recording::Measurement(Measurement::new(2.0, Prefix::Kilo))
+        );
+        assert!(matches!(
+            from_json_any_version::<Volt>(r#"{"version":2,"value":2.0,"prefix":"k"}"#),
+            Err(CompatError::UnsupportedVersion(_))
+        ));
+        assert!(matches!(
+            from_json_any_version::<Volt>(r#"{"min":0.0,"max":1.0}"#),
+            Err(CompatError::UnknownShape)
+        ));
+        assert!(matches!(
+            from_json_any_version::<Volt>("[1.0]"),
+            Err(CompatError::UnknownShape)
+        ));
+        assert!(matches!(
+            from_json_any_version::<Volt>(r#"{"value":"1","prefix":"None"}"#),
+            Err(CompatError::Json(_))
+        ));
+    }
+}
@@ -0,0 +1,122 @@
+//! Normalized cross-correlation of one-dimensional arrays, for aligning delayed traces.
+
+use crate::{m1d::M1d, math::compensated_sum, uom::Uom};
+use ndarray::{Array1, ArrayView1};
+
+impl<U: Uom> M1d<U> {
+    /// Returns the normalized cross-correlation with `other` for every lag in
+    /// `-max_lag..=max_lag`.
+    ///
+    /// The coefficient at lag `k` pairs sample `i` of this trace with sample `i + k` of
+    /// `other`, so a positive lag means `other` is delayed. Both traces are centred on their own
+    /// mean and the sum of products is divided by the product of their norms, which keeps every
+    /// coefficient in `[-1, 1]` and makes the result independent of the prefixes. The traces
+    /// may differ in length; lags without overlapping samples have a coefficient of zero.
+    ///
+    /// # Arguments
+    /// * `other` - The trace correlated against this one.
+    /// * `max_lag` - The largest lag, in samples, in either direction.
+    ///
+    /// # Returns
+    /// The `2 * max_lag + 1` coefficients, from lag `-max_lag` to `max_lag`. Every coefficient
+    /// is NaN if either trace is constant, empty or contains NaN.
+    pub fn cross_correlate(&self, other: &M1d<U>, max_lag: usize) -> Array1<f64> {
+        let x = centred(self.view());
+        let y = centred(other.view());
+        let norm = (compensated_sum(x.iter().map(|v| v * v))
+            * compensated_sum(y.iter().map(|v| v * v)))
+        .sqrt();
+        let max_lag = max_lag as isize;
+        Array1::from_iter((-max_lag..=max_lag).map(|lag| {
+            let (skip_x, skip_y) = (lag.min(0).unsigned_abs(), lag.max(0).unsigned_abs());
+            let products = x.iter().skip(skip_x).zip(y.iter().skip(skip_y));
+            compensated_sum(products.map(|(a, b)| a * b)) / norm
+        }))
+    }
+
+    /// Returns the lag at which `other` correlates best with this trace.
+    ///
+    /// # Arguments
+    /// * `other` - The trace correlated against this one.
+    /// * `max_lag` - The largest lag, in samples, in either direction.
+    ///
+    /// # Returns
+    /// The lag with the highest coefficient of [`M1d::cross_correlate`], positive when `other`
+    /// is delayed, and that coefficient. Ties go to the most negative lag, and `(0, NaN)` is
+    /// returned when every coefficient is NaN.
+    pub fn best_lag(&self, other: &M1d<U>, max_lag: usize) -> (isize, f64) {
+        self.cross_correlate(other, max_lag)
+            .iter()
+            .enumerate()
+            .filter(|(_, r)| !r.is_nan())
+            .fold((0, f64::NAN), |best, (k, &r)| {
+                if best.1.is_nan() || r > best.1 {
+                    (k as isize - max_lag as isize, r)
+                } else {
+                    best
+                }
+            })
+    }
+}
+
+/// Returns `values` minus their mean.
+fn centred(values: ArrayView1<f64>) -> Array1<f64> {
+    let mean = compensated_sum(values.iter().copied()) / values.len() as f64;
+    values.mapv(|v| v - mean)
+}
+
+#[cfg(test)]
+mod correlation_tests {
+    use super::*;
+    use crate::{prefix::Prefix, uom::Volt};
+    use ndarray::s;
+
+    /// Returns `len` samples of an aperiodic test signal.
+    fn signal(len: usize) -> Array1<f64> {
+        Array1::from_shape_fn(len, |i| {
+            let t = i as f64;
+            (0.37 * t).sin() + 0.5 * (0.11 * t * t).cos()
+        })
+    }
+
+    #[test]
+    fn recovers_a_known_shift() {
+        let source = signal(207);
+        let stimulus = M1d::<Volt>::new(source.slice(s![7..]).to_owned(), Prefix::Milli);
+        let response = M1d::<Volt>::new(source.slice(s![..200]).to_owned(), Prefix::Milli);
+        // response[i + 7] == stimulus[i]: the response is delayed by 7 samples
+        let (lag, r) = stimulus.best_lag(&response, 20);
+        assert_eq!(lag, 7);
+        assert!(r > 0.9 && r <= 1.0, "{r}");
+        let (lag, _) = response.best_lag(&stimulus, 20);
+        assert_eq!(lag, -7);
+
+        let coefficients = stimulus.cross_correlate(&response, 20);
+        assert_eq!(coefficients.len(), 41);
+        assert_eq!(coefficients[27], r);
+        assert!(coefficients.iter().all(|r| r.abs() <= 1.0));
+    }
+
+    #[test]
+    fn independent_of_prefixes() {
+        let a = M1d::<Volt>::new(signal(50), Prefix::Milli);
+        let values = a.view().mapv(|v| v * 1000.0);
+        let b = M1d::<Volt>::new(values, Prefix::Micro);
+        let same = a.cross_correlate(&a, 5);
+        let mixed = a.cross_correlate(&b, 5);
+        assert!(same.iter().zip(&mixed).all(|(s, m)| (s - m).abs() < 1e-12));
+        assert!((same[5] - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn degenerate_traces() {
+        let constant = M1d::<Volt>::new(vec![1.0; 10], Prefix::None);
+        let a = M1d::<Volt>::new(signal(10), Prefix::None);
+        assert!(a.cross_correlate(&constant, 2).iter().all(|r| r.is_nan()));
+        let (lag, r) = a.best_lag(&constant, 2);
+        assert_eq!(lag, 0);
+        assert!(r.is_nan());
+        // lags beyond the overlap contribute nothing
+        assert_eq!(a.cross_correlate(&a, 12)[0], 0.0);
+    }
+}
@@ -0,0 +1,314 @@
+//! CSV import and export for [`M1d`] and [`M2d`], enabled by the `csv` feature.
+//!
+//! Every column carries its prefix and unit in the header (e.g. `value[mV]`),
+//! so files can be read back without side-channel metadata.
+//!
+//! Files written with a decimal comma, as is common in Europe, are read with
+//! [`M1d::from_csv_with`] and [`M2d::from_csv_with`], which expect `;` between cells.
+
+use crate::{
+    m1d::M1d,
+    m2d::M2d,
+    measurement::{ParseMeasurementError, ParseOptions, parse_number},
+    prefix::{Prefix, finest},
+    uom::Uom,
+};
+use ndarray::Array1;
+use std::{
+    fmt,
+    io::{Read, Write},
+};
+
+/// Errors raised while reading or writing CSV files.
+#[derive(Debug)]
+pub enum CsvError {
+    /// An error from the underlying CSV reader or writer.
+    Csv(csv::Error),
+    /// A header that is not of the form `name[prefix unit]`.
+    Header(String),
+    /// A header whose unit does not match the expected one.
+    UnitMismatch { expected: String, found: String },
+    /// A cell that could not be parsed as a number.
+    Parse { line: u64, value: String },
+    /// A cell whose separator may mark decimals or group thousands, see [`ParseOptions`].
+    Ambiguous { line: u64, value: String },
+}
+
+impl fmt::Display for CsvError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Csv(e) => write!(f, "{e}"),
+            Self::Header(h) => write!(f, "malformed header `{h}`, expected `name[unit]`"),
+            Self::UnitMismatch { expected, found } => {
+                write!(f, "unit mismatch: expected `{expected}`, found `{found}`")
+            }
+            Self::Parse { line, value } => write!(f, "line {line}: cannot parse `{value}`"),
+            Self::Ambiguous { line, value } => write!(
+                f,
+                "line {line}: `{value}` is ambiguous, its separator may mark decimals or group thousands"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for CsvError {}
+
+impl From<csv::Error> for CsvError {
+    fn from(e: csv::Error) -> Self {
+        Self::Csv(e)
+    }
+}
+
+impl From<std::io::Error> for CsvError {
+    fn from(e: std::io::Error) -> Self {
+        Self::Csv(e.into())
+    }
+}
+
+/// Formats a column header such as `value[mV]`.
+fn header<U: Uom>(name: &str, prefix: Prefix) -> String {
+    format!("{name}[{}{}]", prefix.get_label(), U::uom_str())
+}
+
+/// Parses the prefix out of a column header such as `value[mV]`, validating the unit.
+pub(crate) fn parse_header<U: Uom>(header: &str) -> Result<Prefix, CsvError> {
+    let label = header
+        .trim()
+        .strip_suffix(']')
+        .and_then(|h| h.split_once('['))
+        .map(|(_, label)| label)
+        .ok_or_else(|| CsvError::Header(header.to_string()))?;
+    let mismatch = || CsvError::UnitMismatch {
+        expected: U::uom(),
+        found: label.to_string(),
+    };
+    let prefix = label.strip_suffix(U::uom_str()).ok_or_else(mismatch)?;
+    Prefix::from_label(prefix).ok_or_else(mismatch)
+}
+
+/// Reads every record of `r`, returning the column prefixes and the parsed columns.
+fn read_columns<U: Uom, R: Read>(
+    r: R,
+    options: &ParseOptions,
+) -> Result<(Vec<Prefix>, Vec<Vec<f64>>), CsvError> {
+    let mut reader = csv::ReaderBuilder::new()
+        .delimiter(options.list_separator() as u8)
+        .from_reader(r);
+    let prefixes = reader
+        .headers()?
+        .iter()
+        .map(parse_header::<U>)
+        .collect::<Result<Vec<_>, _>>()?;
+    let mut columns = vec![Vec::new(); prefixes.len()];
+    for record in reader.records() {
+        let record = record?;
+        let line = record.position().map_or(0, |p| p.line());
+        for (column, cell) in columns.iter_mut().zip(record.iter()) {
+            let value = parse_number(cell, options).map_err(|e| {
+                let value = cell.to_string();
+                match e {
+                    ParseMeasurementError::Ambiguous(_) => CsvError::Ambiguous { line, value },
+                    _ => CsvError::Parse { line, value },
+                }
+            })?;
+            column.push(value);
+        }
+    }
+    Ok((prefixes, columns))
+}
+
+impl<U: Uom> M1d<U> {
+    /// Writes the array as a single CSV column with a `value[<prefix><unit>]` header.
+    pub fn to_csv<W: Write>(&self, w: W) -> Result<(), CsvError> {
+        let mut writer = csv::Writer::from_writer(w);
+        writer.write_record([header::<U>("value", self.prefix())])?;
+        for v in self.view() {
+            writer.write_record([v.to_string()])?;
+        }
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Reads an array written by [`M1d::to_csv`], taking the prefix from the header.
+    ///
+    /// # Returns
+    /// A new [`M1d`], or a [`CsvError`] if the header unit differs from `U` or a row is malformed.
+    pub fn from_csv<R: Read>(r: R) -> Result<Self, CsvError> {
+        Self::from_csv_with(r, &ParseOptions::default())
+    }
+
+    /// Reads a single-column array like [`M1d::from_csv`], parsing cells with `options`.
+    ///
+    /// # Returns
+    /// A new [`M1d`], or a [`CsvError`] if the header unit differs from `U`, a row is
+    /// malformed or a cell is ambiguous.
+    pub fn from_csv_with<R: Read>(r: R, options: &ParseOptions) -> Result<Self, CsvError> {
+        let (prefixes, mut columns) = read_columns::<U, R>(r, options)?;
+        match (prefixes.as_slice(), columns.pop()) {
+            ([prefix], Some(values)) => Ok(M1d::new(values, *prefix)),
+            _ => Err(CsvError::Header(format!(
+                "expected 1 column, found {}",
+                prefixes.len()
+            ))),
+        }
+    }
+}
+
+impl<U: Uom> M2d<U> {
+    /// Writes the array with one CSV column per row (channel), headed `ch<i>[<prefix><unit>]`.
+    pub fn to_csv<W: Write>(&self, w: W) -> Result<(), CsvError> {
+        let mut writer = csv::Writer::from_writer(w);
+        writer.write_record(
+            (0..self.nrows()).map(|i| header::<U>(&format!("ch{i}"), self.prefix())),
+        )?;
+        for j in 0..self.ncols() {
+            writer.write_record((0..self.nrows()).map(|i| self[(i, j)].to_string()))?;
+        }
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Reads an array written by [`M2d::to_csv`], one row per CSV column.
+    ///
+    /// Columns with differing prefixes are converted to the finest of them.
+    ///
+    /// # Returns
+    /// A new [`M2d`], or a [`CsvError`] if a header unit differs from `U` or a row is malformed.
+    pub fn from_csv<R: Read>(r: R) -> Result<Self, CsvError> {
+        Self::from_csv_with(r, &ParseOptions::default())
+    }
+
+    /// Reads an array like [`M2d::from_csv`], parsing cells with `options`. When a comma may
+    /// appear in a number, cells are separated by `;`.
+    ///
+    /// # Returns
+    /// A new [`M2d`], or a [`CsvError`] if a header unit differs from `U`, a row is
+    /// malformed or a cell is ambiguous.
+    pub fn from_csv_with<R: Read>(r: R, options: &ParseOptions) -> Result<Self, CsvError> {
+        let (prefixes, columns) = read_columns::<U, R>(r, options)?;
+        let prefix = finest(prefixes.iter().copied()).unwrap_or(Prefix::None);
+        let rows = prefixes
+            .into_iter()
+            .zip(columns)
+            .map(|(p, c)| M1d::new(Array1::from(c), p))
+            .collect::<Vec<_>>();
+        M2d::from_rows(&rows, prefix).map_err(|e| CsvError::Header(e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod csv_io_tests {
+    use super::*;
+    use crate::uom::{Ampere, Ohm, Volt};
+    use ndarray::Array2;
+
+    #[test]
+    fn m1d_round_trip() {
+        let m = M1d::<Volt>::new(vec![1.5, f64::NAN, -3.0], Prefix::Milli);
+        let mut buf = Vec::new();
+        m.to_csv(&mut buf).unwrap();
+        assert_eq!(
+            String::from_utf8(buf.clone()).unwrap(),
+            "value[mV]\n1.5\nNaN\n-3\n"
+        );
+        let back = M1d::<Volt>::from_csv(buf.as_slice()).unwrap();
+        assert_eq!(back.prefix(), Prefix::Milli);
+        let values = back.values();
+        assert_eq!(values[0], 1.5);
+        assert!(values[1].is_nan());
+        assert_eq!(values[2], -3.0);
+    }
+
+    #[test]
+    fn multibyte_unit_header() {
+        let m = M1d::<Ohm>::new(vec![1.5, 2.0], Prefix::Giga);
+        let mut buf = Vec::new();
+        m.to_csv(&mut buf).unwrap();
+        assert_eq!(
+            String::from_utf8(buf.clone()).unwrap(),
+            "value[GΩ]\n1.5\n2\n"
+        );
+        assert_eq!(M1d::<Ohm>::from_csv(buf.as_slice()).unwrap(), m);
+        let micro = M1d::<Ohm>::from_csv("value[µΩ]\n3\n".as_bytes()).unwrap();
+        assert_eq!(micro.prefix(), Prefix::Micro);
+    }
+
+    #[test]
+    fn m2d_round_trip() {
+        let m = M2d::<Volt>::new(
+            Array2::from_shape_vec((2, 3), vec![1.0, 2.0, f64::NAN, 4.0, 5.0, 6.0]).unwrap(),
+            Prefix::Micro,
+        );
+        let mut buf = Vec::new();
+        m.to_csv(&mut buf).unwrap();
+        assert_eq!(
+            String::from_utf8(buf.clone()).unwrap(),
+            "ch0[uV],ch1[uV]\n1,4\n2,5\nNaN,6\n"
+        );
+        let back = M2d::<Volt>::from_csv(buf.as_slice()).unwrap();
+        assert_eq!(back.shape(), (2, 3));
+        assert_eq!(back.prefix(), Prefix::Micro);
+        assert!(back[(0, 2)].is_nan());
+        assert_eq!(back.row(1), m.row(1));
+    }
+
+    #[test]
+    fn m2d_mixed_prefixes() {
+        let csv = "ch0[mV],ch1[uV]\n1,500\n2,250\n";
+        let m = M2d::<Volt>::from_csv(csv.as_bytes()).unwrap();
+        assert_eq!(m.prefix(), Prefix::Micro);
+        assert_eq!(
+            m.row(0),
+            Some(M1d::new(vec![1000.0, 2000.0], Prefix::Micro))
+        );
+        assert_eq!(m.row(1), Some(M1d::new(vec![500.0, 250.0], Prefix::Micro)));
+    }
+
+    #[test]
+    fn unit_mismatch() {
+        let csv = "value[mA]\n1\n";
+        assert!(matches!(
+            M1d::<Volt>::from_csv(csv.as_bytes()),
+            Err(CsvError::UnitMismatch { .. })
+        ));
+        assert!(M1d::<Ampere>::from_csv(csv.as_bytes()).is_ok());
+        assert!(matches!(
+            M1d::<Volt>::from_csv("value\n1\n".as_bytes()),
+            Err(CsvError::Header(_))
+        ));
+    }
+
+    #[test]
+    fn decimal_comma_files() {
+        let options = ParseOptions {
+            decimal_comma: true,
+            allow_thousands_sep: true,
+        };
+        let csv = "ch0[mV];ch1[mV]\n1,5;-2,25\n1.234,5;NaN\n";
+        let m = M2d::<Volt>::from_csv_with(csv.as_bytes(), &options).unwrap();
+        assert_eq!(m.row(0), Some(M1d::new(vec![1.5, 1234.5], Prefix::Milli)));
+        assert_eq!(m[(1, 0)], -2.25);
+        assert!(m[(1, 1)].is_nan());
+        let csv = "value[V]\n0,5\n1,234\n";
+        let err = M1d::<Volt>::from_csv_with(csv.as_bytes(), &options).unwrap_err();
+        assert!(matches!(err, CsvError::Ambiguous { line: 3, .. }), "{err}");
+        assert!(err.to_string().contains("`1,234` is ambiguous"), "{err}");
+        let options = ParseOptions {
+            decimal_comma: true,
+            allow_thousands_sep: false,
+        };
+        let m = M1d::<Volt>::from_csv_with(csv.as_bytes(), &options).unwrap();
+        assert_eq!(m.values(), Array1::from(vec![0.5, 1.234]));
+    }
+
+    #[test]
+    fn malformed_row_reports_line() {
+        let csv = "value[mV]\n1\n2\nabc\n";
+        let err = M1d::<Volt>::from_csv(csv.as_bytes()).unwrap_err();
+        assert!(matches!(err, CsvError::Parse { line: 4, .. }));
+        assert_eq!(err.to_string(), "line 4: cannot parse `abc`");
+        let csv = "ch0[mV],ch1[mV]\n1,2\n3\n";
+        let err = M2d::<Volt>::from_csv(csv.as_bytes()).unwrap_err();
+        assert!(err.to_string().contains("line: 3"), "{err}");
+    }
+}
@@ -0,0 +1,198 @@
+//! Logarithmic ratios in decibels for power and amplitude quantities.
+//!
+//! Power quantities (W) use `10·log10`, amplitude (root-power) quantities such as V and A
+//! use `20·log10`, so +20 dB is ×100 in power and ×10 in amplitude.
+
+#[cfg(feature = "ndarray")]
+use crate::m1d::M1d;
+use crate::{
+    measurement::Measurement,
+    prefix::Prefix,
+    uom::{Ampere, Uom, Volt, Watt},
+};
+#[cfg(feature = "ndarray")]
+use ndarray::Array1;
+use std::fmt;
+
+/// Whether a quantity is a power or an amplitude, which sets the decibel multiplier.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Gain {
+    /// Power quantities: `10·log10` of the ratio.
+    Power,
+    /// Amplitude (root-power) quantities: `20·log10` of the ratio.
+    Amplitude,
+}
+
+impl Gain {
+    /// Returns the multiplier applied to `log10` of the ratio (10 or 20).
+    pub fn multiplier(self) -> f64 {
+        match self {
+            Self::Power => 10.0,
+            Self::Amplitude => 20.0,
+        }
+    }
+}
+
+/// Units whose ratios can be expressed in decibels.
+pub trait DecibelUom: Uom {
+    /// Whether the unit measures a power or an amplitude.
+    const GAIN: Gain;
+}
+
+impl DecibelUom for Watt {
+    const GAIN: Gain = Gain::Power;
+}
+
+impl DecibelUom for Volt {
+    const GAIN: Gain = Gain::Amplitude;
+}
+
+impl DecibelUom for Ampere {
+    const GAIN: Gain = Gain::Amplitude;
+}
+
+/// Errors raised when a decibel ratio is undefined.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DecibelError {
+    /// The reference is zero, negative or NaN.
+    NonPositiveReference(f64),
+    /// A value is zero, negative or NaN.
+    NonPositiveValue(f64),
+}
+
+impl fmt::Display for DecibelError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NonPositiveReference(v) => {
+                write!(f, "decibel reference must be positive, got {v}")
+            }
+            Self::NonPositiveValue(v) => write!(f, "decibel value must be positive, got {v}"),
+        }
+    }
+}
+
+impl core::error::Error for DecibelError {}
+
+/// Returns the reference value without prefix, or an error if it is not positive.
+fn reference_value<U: Uom>(reference: &Measurement<U>) -> Result<f64, DecibelError> {
+    let r = reference.convert_to(Prefix::None).value();
+    if r > 0.0 {
+        Ok(r)
+    } else {
+        Err(DecibelError::NonPositiveReference(reference.value()))
+    }
+}
+
+impl<U: DecibelUom> Measurement<U> {
+    /// Returns the ratio to `reference` in decibels.
+    ///
+    /// # Returns
+    /// The gain in dB, or a [`DecibelError`] if either value is not positive.
+    ///
+    /// # Example
+    /// ```
+    /// use typed_measurements::prelude::*;
+    ///
+    /// let out = Measurement::<Volt>::new(1, Prefix::None);
+    /// let input = Measurement::<Volt>::new(100, Prefix::Milli);
+    /// assert_eq!(out.ratio_db(&input), Ok(20.0));
+    /// ```
+    pub fn ratio_db(&self, reference: &Measurement<U>) -> Result<f64, DecibelError> {
+        let r = reference_value(reference)?;
+        let v = self.convert_to(Prefix::None).value();
+        if v > 0.0 {
+            Ok(U::GAIN.multiplier() * (v / r).log10())
+        } else {
+            Err(DecibelError::NonPositiveValue(self.value()))
+        }
+    }
+
+    /// Returns the measurement `db` decibels above `reference`, in the reference's prefix.
+    ///
+    /// # Returns
+    /// The measurement, or a [`DecibelError`] if the reference is not positive.
+    pub fn from_db(reference: &Measurement<U>, db: f64) -> Result<Self, DecibelError> {
+        reference_value(reference)?;
+        let ratio = 10f64.powf(db / U::GAIN.multiplier());
+        Ok(Measurement::new(
+            reference.value() * ratio,
+            reference.prefix(),
+        ))
+    }
+}
+
+#[cfg(feature = "ndarray")]
+impl<U: DecibelUom> M1d<U> {
+    /// Returns the ratio of every element to `reference` in decibels, e.g. for Bode plots.
+    ///
+    /// # Returns
+    /// The gains in dB, or a [`DecibelError`] for the first value (or reference) that is
+    /// not positive.
+    pub fn to_db(&self, reference: &Measurement<U>) -> Result<Array1<f64>, DecibelError> {
+        reference_value(reference)?;
+        let r = reference.convert_to(self.prefix()).value();
+        let m = U::GAIN.multiplier();
+        self.view()
+            .iter()
+            .map(|&v| {
+                if v > 0.0 {
+                    Ok(m * (v / r).log10())
+                } else {
+                    Err(DecibelError::NonPositiveValue(v))
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod decibel_tests {
+    use super::*;
+
+    #[test]
+    fn plus_twenty_db() {
+        let v_ref = Measurement::<Volt>::new(1, Prefix::Milli);
+        let v = Measurement::<Volt>::from_db(&v_ref, 20.0).unwrap();
+        assert_eq!(v, Measurement::new(10, Prefix::Milli));
+        let p_ref = Measurement::<Watt>::new(1, Prefix::Milli);
+        let p = Measurement::<Watt>::from_db(&p_ref, 20.0).unwrap();
+        assert_eq!(p, Measurement::new(100, Prefix::Milli));
+        assert_eq!(v.ratio_db(&v_ref), Ok(20.0));
+        assert_eq!(p.ratio_db(&p_ref), Ok(20.0));
+    }
+
+    #[test]
+    fn mixed_prefixes() {
+        let p = Measurement::<Watt>::new(1, Prefix::None);
+        let p_ref = Measurement::<Watt>::new(1, Prefix::Milli);
+        assert_eq!(p.ratio_db(&p_ref), Ok(30.0));
+    }
+
+    #[test]
+    fn non_positive() {
+        let zero = Measurement::<Volt>::new(0, Prefix::None);
+        let one = Measurement::<Volt>::new(1, Prefix::None);
+        assert_eq!(
+            one.ratio_db(&zero),
+            Err(DecibelError::NonPositiveReference(0.0))
+        );
+        assert_eq!(
+            Measurement::<Volt>::new(-1, Prefix::None).ratio_db(&one),
+            Err(DecibelError::NonPositiveValue(-1.0))
+        );
+        assert!(Measurement::from_db(&zero, 3.0).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "ndarray")]
+    fn m1d_to_db() {
+        let m = M1d::<Volt>::new(vec![1.0, 10.0, 100.0], Prefix::Milli);
+        let db = m.to_db(&Measurement::new(10, Prefix::Milli)).unwrap();
+        assert_eq!(db, Array1::from(vec![-20.0, 0.0, 20.0]));
+        let with_zero = M1d::<Volt>::new(vec![1.0, 0.0], Prefix::Milli);
+        assert_eq!(
+            with_zero.to_db(&Measurement::new(1, Prefix::None)),
+            Err(DecibelError::NonPositiveValue(0.0))
+        );
+    }
+}
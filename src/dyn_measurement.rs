@@ -0,0 +1,354 @@
+//! Measurements whose unit is only known at runtime, e.g. when read from a file header.
+//!
+//! [`DynMeasurement`] and [`DynM1d`] carry the unit as a label string and convert
+//! losslessly to and from their typed counterparts once the unit is known.
+
+#[cfg(feature = "ndarray")]
+use crate::m1d::M1d;
+use crate::{measurement::Measurement, prefix::Prefix, uom::Uom};
+use alloc::string::{String, ToString};
+use core::fmt;
+#[cfg(feature = "ndarray")]
+use ndarray::Array1;
+#[cfg(feature = "python")]
+use ndarray::ArrayView1;
+use serde::{Deserialize, Serialize};
+
+/// Error returned when a runtime unit label does not match the expected unit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnitMismatch {
+    /// The unit label that was required.
+    pub expected: String,
+    /// The unit label that was found.
+    pub found: String,
+}
+
+impl fmt::Display for UnitMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "unit mismatch: expected `{}`, found `{}`",
+            self.expected, self.found
+        )
+    }
+}
+
+impl core::error::Error for UnitMismatch {}
+
+/// Returns an error unless `found` is the label of `U`.
+fn check_unit<U: Uom>(found: &str) -> Result<(), UnitMismatch> {
+    check_same(U::uom_str(), found)
+}
+
+/// Returns an error unless the two unit labels are equal.
+fn check_same(expected: &str, found: &str) -> Result<(), UnitMismatch> {
+    if expected == found {
+        Ok(())
+    } else {
+        Err(UnitMismatch {
+            expected: expected.to_string(),
+            found: found.to_string(),
+        })
+    }
+}
+
+/// A measurement with a value, SI prefix, and a unit label known only at runtime.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct DynMeasurement {
+    value: f64,
+    prefix: Prefix,
+    unit: String,
+}
+
+impl DynMeasurement {
+    /// Creates a new [`DynMeasurement`].
+    ///
+    /// # Arguments
+    /// * `value` - The numeric value of the measurement.
+    /// * `prefix` - The SI prefix for the unit.
+    /// * `unit` - The unit label, as returned by [`Uom::uom`] (e.g. "V").
+    pub fn new<V: Into<f64>>(value: V, prefix: Prefix, unit: impl Into<String>) -> Self {
+        Self {
+            value: value.into(),
+            prefix,
+            unit: unit.into(),
+        }
+    }
+
+    /// Creates a [`DynMeasurement`] from a typed [`Measurement`], recording its unit label.
+    pub fn from_typed<U: Uom>(m: Measurement<U>) -> Self {
+        Self::new(m.value(), m.prefix(), U::uom())
+    }
+
+    /// Converts into a typed [`Measurement<U>`].
+    ///
+    /// # Returns
+    /// The typed measurement, or a [`UnitMismatch`] if the unit label is not `U`'s.
+    pub fn try_into_typed<U: Uom>(&self) -> Result<Measurement<U>, UnitMismatch> {
+        check_unit::<U>(&self.unit)?;
+        Ok(Measurement::new(self.value, self.prefix))
+    }
+
+    /// Returns the numeric value of the measurement.
+    pub fn value(&self) -> f64 {
+        self.value
+    }
+
+    /// Returns the SI prefix associated with this measurement.
+    pub fn prefix(&self) -> Prefix {
+        self.prefix
+    }
+
+    /// Returns the unit label (e.g. "V").
+    pub fn unit(&self) -> &str {
+        &self.unit
+    }
+
+    /// Returns a string label combining value, prefix, and unit (e.g., "1.5mV").
+    pub fn label(&self) -> String {
+        self.value.to_string() + self.prefix.get_label() + &self.unit
+    }
+
+    /// Converts the measurement to a different SI prefix, scaling the value accordingly.
+    pub fn convert_to(&self, pfx: Prefix) -> Self {
+        Self::new(
+            self.value * self.prefix.get_conversion_factor(pfx),
+            pfx,
+            self.unit.clone(),
+        )
+    }
+
+    /// Adds two measurements, converting to the prefix of `rhs` like [`Measurement`]'s `+`.
+    ///
+    /// # Returns
+    /// The sum, or a [`UnitMismatch`] if the unit labels differ.
+    pub fn try_add(&self, rhs: &DynMeasurement) -> Result<DynMeasurement, UnitMismatch> {
+        check_same(&self.unit, &rhs.unit)?;
+        let s = self.convert_to(rhs.prefix);
+        Ok(Self::new(s.value + rhs.value, rhs.prefix, rhs.unit.clone()))
+    }
+
+    /// Subtracts `rhs`, converting to the prefix of `rhs` like [`Measurement`]'s `-`.
+    ///
+    /// # Returns
+    /// The difference, or a [`UnitMismatch`] if the unit labels differ.
+    pub fn try_sub(&self, rhs: &DynMeasurement) -> Result<DynMeasurement, UnitMismatch> {
+        check_same(&self.unit, &rhs.unit)?;
+        let s = self.convert_to(rhs.prefix);
+        Ok(Self::new(s.value - rhs.value, rhs.prefix, rhs.unit.clone()))
+    }
+
+    /// Compares two measurements, converting prefixes if necessary.
+    ///
+    /// # Returns
+    /// The ordering (`None` if either value is NaN), or a [`UnitMismatch`] if the unit
+    /// labels differ.
+    pub fn try_partial_cmp(
+        &self,
+        rhs: &DynMeasurement,
+    ) -> Result<Option<core::cmp::Ordering>, UnitMismatch> {
+        check_same(&self.unit, &rhs.unit)?;
+        Ok(self.convert_to(rhs.prefix).value.partial_cmp(&rhs.value))
+    }
+}
+
+impl<U: Uom> From<Measurement<U>> for DynMeasurement {
+    fn from(m: Measurement<U>) -> Self {
+        Self::from_typed(m)
+    }
+}
+
+impl PartialEq for DynMeasurement {
+    /// Two measurements are equal if their units match and their values agree after
+    /// converting to the same prefix.
+    fn eq(&self, other: &Self) -> bool {
+        self.unit == other.unit && self.convert_to(other.prefix).value == other.value
+    }
+}
+
+impl<I: Into<f64>> core::ops::Mul<I> for DynMeasurement {
+    /// Multiplies a [`DynMeasurement`] by a scalar.
+    type Output = DynMeasurement;
+    fn mul(self, rhs: I) -> Self::Output {
+        Self::new(self.value * rhs.into(), self.prefix, self.unit)
+    }
+}
+
+impl<I: Into<f64>> core::ops::Div<I> for DynMeasurement {
+    /// Divides a [`DynMeasurement`] by a scalar.
+    type Output = DynMeasurement;
+    fn div(self, rhs: I) -> Self::Output {
+        Self::new(self.value / rhs.into(), self.prefix, self.unit)
+    }
+}
+
+/// A one-dimensional array of measurements with a unit label known only at runtime.
+#[cfg(feature = "ndarray")]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct DynM1d {
+    values: Array1<f64>,
+    prefix: Prefix,
+    unit: String,
+}
+
+#[cfg(feature = "ndarray")]
+impl DynM1d {
+    /// Creates a new [`DynM1d`].
+    ///
+    /// # Arguments
+    /// * `values` - The values as a type convertible into `Array1<f64>`.
+    /// * `prefix` - The SI prefix for the unit.
+    /// * `unit` - The unit label, as returned by [`Uom::uom`] (e.g. "V").
+    pub fn new<T: Into<Array1<f64>>>(values: T, prefix: Prefix, unit: impl Into<String>) -> Self {
+        Self {
+            values: values.into(),
+            prefix,
+            unit: unit.into(),
+        }
+    }
+
+    /// Creates a [`DynM1d`] from a typed [`M1d`], recording its unit label.
+    pub fn from_typed<U: Uom>(m: &M1d<U>) -> Self {
+        Self::new(m.values(), m.prefix(), U::uom())
+    }
+
+    /// Converts into a typed [`M1d<U>`].
+    ///
+    /// # Returns
+    /// The typed array, or a [`UnitMismatch`] if the unit label is not `U`'s.
+    pub fn try_into_typed<U: Uom>(&self) -> Result<M1d<U>, UnitMismatch> {
+        check_unit::<U>(&self.unit)?;
+        Ok(M1d::new(self.values.clone(), self.prefix))
+    }
+
+    /// Returns a clone of the underlying values array.
+    pub fn values(&self) -> Array1<f64> {
+        self.values.clone()
+    }
+
+    /// Returns a read-only view of the underlying values.
+    #[cfg(feature = "python")]
+    pub(crate) fn view(&self) -> ArrayView1<'_, f64> {
+        self.values.view()
+    }
+
+    /// Returns the SI prefix associated with this array.
+    pub fn prefix(&self) -> Prefix {
+        self.prefix
+    }
+
+    /// Returns the unit label (e.g. "V").
+    pub fn unit(&self) -> &str {
+        &self.unit
+    }
+
+    /// Returns the prefix and unit label (e.g. "mV").
+    pub fn label(&self) -> String {
+        self.prefix.get_label().to_string() + &self.unit
+    }
+
+    /// Returns the number of elements.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Returns `true` if the array has no elements.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Returns the element at `i` as a [`DynMeasurement`], or `None` if out of bounds.
+    pub fn get(&self, i: usize) -> Option<DynMeasurement> {
+        let value = *self.values.get(i)?;
+        Some(DynMeasurement::new(value, self.prefix, self.unit.clone()))
+    }
+
+    /// Converts the array to a different SI prefix, scaling the values accordingly.
+    pub fn convert_to(&self, pfx: Prefix) -> Self {
+        let factor = self.prefix.get_conversion_factor(pfx);
+        Self::new(self.values.mapv(|v| v * factor), pfx, self.unit.clone())
+    }
+}
+
+#[cfg(feature = "ndarray")]
+impl<U: Uom> From<M1d<U>> for DynM1d {
+    fn from(m: M1d<U>) -> Self {
+        Self::from_typed(&m)
+    }
+}
+
+#[cfg(feature = "ndarray")]
+impl PartialEq for DynM1d {
+    /// Two arrays are equal if their units match and their values agree after converting
+    /// to the same prefix.
+    fn eq(&self, other: &Self) -> bool {
+        self.unit == other.unit && self.convert_to(other.prefix).values == other.values
+    }
+}
+
+#[cfg(test)]
+mod dyn_measurement_tests {
+    use super::*;
+    use crate::uom::{Ampere, Volt};
+
+    #[test]
+    fn round_trip_measurement() {
+        let m = Measurement::<Volt>::new(1.5, Prefix::Milli);
+        let d = DynMeasurement::from_typed(m);
+        assert_eq!(d.label(), "1.5mV");
+        assert_eq!(d.try_into_typed::<Volt>(), Ok(m));
+        assert_eq!(
+            d.try_into_typed::<Ampere>(),
+            Err(UnitMismatch {
+                expected: "A".to_string(),
+                found: "V".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn arithmetic() {
+        let a = DynMeasurement::new(1, Prefix::None, "V");
+        let b = DynMeasurement::new(500, Prefix::Milli, "V");
+        assert_eq!(
+            a.try_add(&b),
+            Ok(DynMeasurement::new(1500, Prefix::Milli, "V"))
+        );
+        assert_eq!(
+            a.try_sub(&b),
+            Ok(DynMeasurement::new(0.5, Prefix::None, "V"))
+        );
+        assert_eq!(a.try_partial_cmp(&b), Ok(Some(std::cmp::Ordering::Greater)));
+        assert_eq!(a.clone() * 2, DynMeasurement::new(2, Prefix::None, "V"));
+        let amps = DynMeasurement::new(1, Prefix::None, "A");
+        assert!(a.try_add(&amps).is_err());
+        assert_ne!(a, amps);
+    }
+
+    #[test]
+    #[cfg(feature = "ndarray")]
+    fn dyn_m1d_into_typed() {
+        let d = DynM1d::new(vec![1.0, 2.0], Prefix::Milli, "V");
+        assert_eq!(d.label(), "mV");
+        let typed = d.try_into_typed::<Volt>().unwrap();
+        assert_eq!(typed, M1d::new(vec![1.0, 2.0], Prefix::Milli));
+        assert_eq!(DynM1d::from(typed), d);
+        let err = d.try_into_typed::<Ampere>().unwrap_err();
+        assert_eq!(err.to_string(), "unit mismatch: expected `A`, found `V`");
+        assert_eq!(d.get(1), Some(DynMeasurement::new(2, Prefix::Milli, "V")));
+    }
+
+    #[test]
+    #[cfg(feature = "ndarray")]
+    fn serde_round_trip() {
+        let d = DynM1d::new(vec![1.0, 2.0], Prefix::Micro, "A");
+        let json = serde_json::to_string(&d).unwrap();
+        let back: DynM1d = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, d);
+        assert_eq!(back.unit(), "A");
+        let m = DynMeasurement::new(3, Prefix::Kilo, "Ω");
+        let back: DynMeasurement =
+            serde_json::from_str(&serde_json::to_string(&m).unwrap()).unwrap();
+        assert_eq!(back, m);
+    }
+}
@@ -0,0 +1,216 @@
+//! Epochs: fixed windows of samples cut around event indices, such as stimulus onsets.
+//!
+//! The epoch of event `e` holds samples `e - pre..e + post`, so the event sample sits at
+//! index `pre` of every epoch.
+
+use crate::{error::MeasurementError, m1d::M1d, m2d::M2d, uom::Uom};
+use alloc::vec::Vec;
+use ndarray::{Array2, Axis, s};
+
+/// What to do with events whose epoch would extend past either end of the trace.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum EdgeEvents {
+    /// Fail with [`MeasurementError::OutOfRange`] on the first such event.
+    #[default]
+    Error,
+    /// Leave such events out and report them.
+    Skip,
+}
+
+/// Splits `events` into those with a complete epoch in a trace of `len` samples and those
+/// without, or fails on the first event without one under [`EdgeEvents::Error`].
+fn fitting_events(
+    events: &[usize],
+    pre: usize,
+    post: usize,
+    len: usize,
+    edges: EdgeEvents,
+) -> Result<(Vec<usize>, Vec<usize>), MeasurementError> {
+    let (fitting, skipped): (Vec<usize>, Vec<usize>) = events
+        .iter()
+        .partition(|&&e| e >= pre && e.checked_add(post).is_some_and(|end| end <= len));
+    match (edges, skipped.first()) {
+        (EdgeEvents::Error, Some(&e)) => Err(MeasurementError::OutOfRange {
+            value: e as f64,
+            min: pre as f64,
+            max: len as f64 - post as f64,
+        }),
+        _ => Ok((fitting, skipped)),
+    }
+}
+
+impl<U: Uom> M1d<U> {
+    /// Cuts the epoch of every event and stacks them, one row per event, in the order of
+    /// `events`.
+    ///
+    /// # Arguments
+    /// * `events` - The sample index of every event.
+    /// * `pre` - The number of samples before each event.
+    /// * `post` - The number of samples from each event on, the event sample included.
+    ///
+    /// # Returns
+    /// An [`M2d`] of shape `(events.len(), pre + post)` in this array's prefix, or
+    /// [`MeasurementError::OutOfRange`] if an event is less than `pre` samples from the start
+    /// or less than `post` samples from the end.
+    pub fn epochs(
+        &self,
+        events: &[usize],
+        pre: usize,
+        post: usize,
+    ) -> Result<M2d<U>, MeasurementError> {
+        self.epochs_with(events, pre, post, EdgeEvents::Error)
+            .map(|(epochs, _)| epochs)
+    }
+
+    /// Cuts the epoch of every event, handling events too close to the edges as `edges` says.
+    ///
+    /// # Returns
+    /// The epochs of the events that fit, one row each as in [`M1d::epochs`], and the events
+    /// that were skipped; or [`MeasurementError::OutOfRange`] under [`EdgeEvents::Error`].
+    pub fn epochs_with(
+        &self,
+        events: &[usize],
+        pre: usize,
+        post: usize,
+        edges: EdgeEvents,
+    ) -> Result<(M2d<U>, Vec<usize>), MeasurementError> {
+        let values = self.view();
+        let (fitting, skipped) = fitting_events(events, pre, post, values.len(), edges)?;
+        let epochs = Array2::from_shape_fn((fitting.len(), pre + post), |(k, t)| {
+            values[fitting[k] - pre + t]
+        });
+        Ok((M2d::new(epochs, self.prefix()), skipped))
+    }
+}
+
+impl<U: Uom> M2d<U> {
+    /// Averages the epochs of every event, channel by channel.
+    ///
+    /// Each row is a channel sampled along `Axis(1)`. NaN samples propagate into the averages.
+    ///
+    /// # Arguments
+    /// * `events` - The sample index of every event.
+    /// * `pre` - The number of samples before each event.
+    /// * `post` - The number of samples from each event on, the event sample included.
+    ///
+    /// # Returns
+    /// An [`M2d`] of shape `(channels, pre + post)` in this array's prefix;
+    /// [`MeasurementError::OutOfRange`] if an event is too close to either end, or
+    /// [`MeasurementError::EmptyInput`] if `events` is empty.
+    pub fn epoch_average(
+        &self,
+        events: &[usize],
+        pre: usize,
+        post: usize,
+    ) -> Result<M2d<U>, MeasurementError> {
+        self.epoch_average_with(events, pre, post, EdgeEvents::Error)
+            .map(|(average, _)| average)
+    }
+
+    /// Averages the epochs of every event channel by channel, handling events too close to the
+    /// edges as `edges` says.
+    ///
+    /// # Returns
+    /// The average as in [`M2d::epoch_average`] over the events that fit, and the events that
+    /// were skipped; [`MeasurementError::OutOfRange`] under [`EdgeEvents::Error`], or
+    /// [`MeasurementError::EmptyInput`] if no event fits.
+    pub fn epoch_average_with(
+        &self,
+        events: &[usize],
+        pre: usize,
+        post: usize,
+        edges: EdgeEvents,
+    ) -> Result<(M2d<U>, Vec<usize>), MeasurementError> {
+        let values = self.view();
+        let (fitting, skipped) = fitting_events(events, pre, post, values.len_of(Axis(1)), edges)?;
+        if fitting.is_empty() {
+            return Err(MeasurementError::EmptyInput("no event to average"));
+        }
+        let mut average = Array2::zeros((values.nrows(), pre + post));
+        for &e in &fitting {
+            average += &values.slice(s![.., e - pre..e + post]);
+        }
+        average /= fitting.len() as f64;
+        Ok((M2d::new(average, self.prefix()), skipped))
+    }
+}
+
+#[cfg(test)]
+mod epochs_tests {
+    use super::*;
+    use crate::{prefix::Prefix, uom::Volt};
+    use ndarray::{Array1, array};
+
+    /// A trace whose value is its sample index, so every epoch shows where it was cut.
+    fn ramp(len: usize) -> M1d<Volt> {
+        M1d::new(Array1::from_shape_fn(len, |i| i as f64), Prefix::Milli)
+    }
+
+    #[test]
+    fn epochs_are_aligned_on_events() {
+        let epochs = ramp(100).epochs(&[10, 50, 3], 3, 4).unwrap();
+        assert_eq!(epochs.prefix(), Prefix::Milli);
+        assert_eq!(
+            epochs.values(),
+            array![
+                [7.0, 8.0, 9.0, 10.0, 11.0, 12.0, 13.0],
+                [47.0, 48.0, 49.0, 50.0, 51.0, 52.0, 53.0],
+                [0.0, 1.0, 2.0, 3.0, 4.0, 5.0, 6.0],
+            ]
+        );
+        assert_eq!(ramp(10).epochs(&[], 2, 2).unwrap().shape(), (0, 4));
+        // the last complete epoch ends on the last sample
+        assert_eq!(
+            ramp(10).epochs(&[8], 1, 2).unwrap().values(),
+            array![[7.0, 8.0, 9.0]]
+        );
+    }
+
+    #[test]
+    fn edge_events_error_or_skip() {
+        let trace = ramp(20);
+        assert!(matches!(
+            trace.epochs(&[5, 2], 3, 3),
+            Err(MeasurementError::OutOfRange { value: 2.0, .. })
+        ));
+        assert!(trace.epochs(&[18], 3, 3).is_err());
+        let (epochs, skipped) = trace
+            .epochs_with(&[2, 5, 18, usize::MAX], 3, 3, EdgeEvents::Skip)
+            .unwrap();
+        assert_eq!(epochs.values(), array![[2.0, 3.0, 4.0, 5.0, 6.0, 7.0]]);
+        assert_eq!(skipped, vec![2, 18, usize::MAX]);
+    }
+
+    #[test]
+    fn epoch_average_per_channel() {
+        // channel 0 is a ramp, channel 1 is a unit pulse at every event
+        let mut values = Array2::zeros((2, 30));
+        values
+            .row_mut(0)
+            .assign(&Array1::from_shape_fn(30, |i| i as f64));
+        for e in [5, 12, 25] {
+            values[[1, e]] = 1.0;
+        }
+        let m = M2d::<Volt>::new(values, Prefix::Micro);
+        let average = m.epoch_average(&[5, 12, 25], 2, 3).unwrap();
+        assert_eq!(average.prefix(), Prefix::Micro);
+        assert_eq!(
+            average.values(),
+            array![[12.0, 13.0, 14.0, 15.0, 16.0], [0.0, 0.0, 1.0, 0.0, 0.0]]
+        );
+
+        assert!(m.epoch_average(&[5, 28], 2, 3).is_err());
+        let (average, skipped) = m
+            .epoch_average_with(&[5, 28], 2, 3, EdgeEvents::Skip)
+            .unwrap();
+        assert_eq!(
+            average.values(),
+            array![[3.0, 4.0, 5.0, 6.0, 7.0], [0.0, 0.0, 1.0, 0.0, 0.0]]
+        );
+        assert_eq!(skipped, vec![28]);
+        assert!(matches!(
+            m.epoch_average(&[], 2, 3),
+            Err(MeasurementError::EmptyInput(_))
+        ));
+    }
+}
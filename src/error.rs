@@ -0,0 +1,192 @@
+//! A single error type for the crate's fallible operations.
+//!
+//! Module-specific errors such as [`ParseMeasurementError`] or [`UnitMismatch`] convert into
+//! [`MeasurementError`], so callers mixing several APIs can use `?` throughout.
+
+use crate::{dyn_measurement::UnitMismatch, measurement::ParseMeasurementError};
+use alloc::string::String;
+use core::fmt;
+#[cfg(feature = "ndarray")]
+use ndarray::ShapeError;
+
+/// Errors raised by fallible operations on measurements, ranges and arrays.
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum MeasurementError {
+    /// A label could not be parsed as a measurement.
+    ParseError(ParseMeasurementError),
+    /// A unit label did not match the expected unit.
+    UnitMismatch { expected: String, found: String },
+    /// A range violates one of its invariants, e.g. its minimum exceeds its maximum.
+    RangeInvariant {
+        min: f64,
+        max: f64,
+        step: Option<f64>,
+        reason: &'static str,
+    },
+    /// Two arrays have incompatible shapes, or an index is out of bounds.
+    #[cfg(feature = "ndarray")]
+    ShapeMismatch(ShapeError),
+    /// A combined exponent is outside the range of [`Prefix`](crate::prefix::Prefix).
+    PrefixOverflow { exp: i16 },
+    /// A value lies outside the allowed range.
+    OutOfRange { value: f64, min: f64, max: f64 },
+    /// Options given to a builder are missing or contradict each other.
+    InvalidOptions(&'static str),
+    /// An input was empty where at least one element is required.
+    EmptyInput(&'static str),
+    /// No channel is stored under the requested name.
+    MissingChannel(String),
+    /// A sequence that must be strictly increasing, such as timestamps, is not at `index`.
+    NotIncreasing { index: usize },
+    /// An integer measurement would need a fractional `value` to be represented exactly.
+    NotIntegral { value: f64 },
+    /// Integer arithmetic on measurements overflowed `i64`.
+    IntegerOverflow(&'static str),
+    /// A named time window, in seconds, does not fit within a trace of `duration` seconds.
+    WindowOutOfTrace {
+        window: String,
+        start: f64,
+        end: f64,
+        duration: f64,
+    },
+}
+
+impl fmt::Display for MeasurementError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ParseError(e) => write!(f, "{e}"),
+            Self::UnitMismatch { expected, found } => {
+                write!(f, "unit mismatch: expected `{expected}`, found `{found}`")
+            }
+            Self::RangeInvariant {
+                min,
+                max,
+                step,
+                reason,
+            } => {
+                write!(f, "invalid range [{min}, {max}]")?;
+                if let Some(step) = step {
+                    write!(f, " with step {step}")?;
+                }
+                write!(f, ": {reason}")
+            }
+            #[cfg(feature = "ndarray")]
+            Self::ShapeMismatch(e) => write!(f, "{e}"),
+            Self::PrefixOverflow { exp } => {
+                write!(f, "exponent 10^{exp} has no SI prefix")
+            }
+            Self::OutOfRange { value, min, max } => {
+                write!(f, "{value} is outside the range [{min}, {max}]")
+            }
+            Self::InvalidOptions(reason) => write!(f, "invalid options: {reason}"),
+            Self::EmptyInput(reason) => write!(f, "empty input: {reason}"),
+            Self::MissingChannel(name) => write!(f, "no channel named `{name}`"),
+            Self::NotIncreasing { index } => {
+                write!(f, "values are not strictly increasing at index {index}")
+            }
+            Self::NotIntegral { value } => write!(f, "{value} is not an integer"),
+            Self::IntegerOverflow(op) => write!(f, "integer overflow: {op}"),
+            Self::WindowOutOfTrace {
+                window,
+                start,
+                end,
+                duration,
+            } => write!(
+                f,
+                "window `{window}` [{start}, {end}] s does not fit a trace of {duration} s"
+            ),
+        }
+    }
+}
+
+impl core::error::Error for MeasurementError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            Self::ParseError(e) => Some(e),
+            #[cfg(feature = "ndarray")]
+            Self::ShapeMismatch(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<ParseMeasurementError> for MeasurementError {
+    fn from(e: ParseMeasurementError) -> Self {
+        Self::ParseError(e)
+    }
+}
+
+impl From<UnitMismatch> for MeasurementError {
+    fn from(e: UnitMismatch) -> Self {
+        Self::UnitMismatch {
+            expected: e.expected,
+            found: e.found,
+        }
+    }
+}
+
+#[cfg(feature = "ndarray")]
+impl From<ShapeError> for MeasurementError {
+    fn from(e: ShapeError) -> Self {
+        Self::ShapeMismatch(e)
+    }
+}
+
+#[cfg(test)]
+mod error_tests {
+    use super::*;
+
+    #[test]
+    fn display_carries_values() {
+        let e = MeasurementError::UnitMismatch {
+            expected: "V".to_string(),
+            found: "A".to_string(),
+        };
+        assert_eq!(e.to_string(), "unit mismatch: expected `V`, found `A`");
+        let e = MeasurementError::RangeInvariant {
+            min: 10.0,
+            max: -10.0,
+            step: Some(0.5),
+            reason: "min exceeds max",
+        };
+        assert_eq!(
+            e.to_string(),
+            "invalid range [10, -10] with step 0.5: min exceeds max"
+        );
+        let e = MeasurementError::PrefixOverflow { exp: 24 };
+        assert_eq!(e.to_string(), "exponent 10^24 has no SI prefix");
+        let e = MeasurementError::OutOfRange {
+            value: 12.5,
+            min: -10.0,
+            max: 10.0,
+        };
+        assert_eq!(e.to_string(), "12.5 is outside the range [-10, 10]");
+        let e = MeasurementError::NotIntegral { value: 1.5 };
+        assert_eq!(e.to_string(), "1.5 is not an integer");
+    }
+
+    #[test]
+    fn conversions() {
+        let e: MeasurementError = ParseMeasurementError::Prefix("x".to_string()).into();
+        assert_eq!(e.to_string(), "unknown prefix `x`");
+        let e: MeasurementError = UnitMismatch {
+            expected: "V".to_string(),
+            found: "Ω".to_string(),
+        }
+        .into();
+        assert!(e.to_string().contains("`Ω`"));
+    }
+
+    #[test]
+    #[cfg(feature = "ndarray")]
+    fn shape_error_conversion() {
+        use ndarray::ErrorKind;
+        let e: MeasurementError = ShapeError::from_kind(ErrorKind::IncompatibleShape).into();
+        assert_eq!(
+            e,
+            MeasurementError::ShapeMismatch(ShapeError::from_kind(ErrorKind::IncompatibleShape))
+        );
+        assert!(std::error::Error::source(&e).is_some());
+    }
+}
@@ -0,0 +1,231 @@
+//! Power spectral density of voltage traces, enabled by the `fft` feature.
+//!
+//! Spectra are one-sided and scaled as a density: values are in V²/Hz, whatever the prefixes
+//! of the trace and the sampling rate, so summing them times the bin width gives the mean
+//! square of the (windowed) signal. Any length is supported, not only powers of two.
+
+use crate::{
+    m1d::M1d,
+    measurement::Measurement,
+    prefix::Prefix,
+    uom::{Hertz, Volt},
+};
+use ndarray::{Array1, ArrayView1};
+use realfft::RealFftPlanner;
+use std::f64::consts::PI;
+
+/// A window applied to each segment before the transform.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Window {
+    /// No tapering; exact for signals periodic in the segment.
+    #[default]
+    Rectangular,
+    /// The periodic Hann window, reducing leakage from off-bin frequencies.
+    Hann,
+}
+
+impl Window {
+    /// Returns the `len` window coefficients.
+    fn coefficients(self, len: usize) -> Array1<f64> {
+        match self {
+            Self::Rectangular => Array1::ones(len),
+            Self::Hann => Array1::from_shape_fn(len, |i| {
+                0.5 - 0.5 * (2.0 * PI * i as f64 / len as f64).cos()
+            }),
+        }
+    }
+}
+
+impl M1d<Volt> {
+    /// Returns the one-sided power spectral density of the whole trace.
+    ///
+    /// # Arguments
+    /// * `rate` - The sampling rate.
+    /// * `window` - The window applied to the trace before the transform.
+    ///
+    /// # Returns
+    /// The frequency of every bin, from 0 to the Nyquist frequency in steps of `rate / len`
+    /// and in the prefix of `rate`, and the density of every bin in V²/Hz. Both are empty for
+    /// an empty trace.
+    pub fn power_spectrum(
+        &self,
+        rate: Measurement<Hertz>,
+        window: Window,
+    ) -> (M1d<Hertz>, Array1<f64>) {
+        let volts = self.clone().convert_to(Prefix::None);
+        let len = volts.len();
+        let psd = periodogram(volts.view(), &window.coefficients(len), rate);
+        (frequencies(rate, len), psd)
+    }
+
+    /// Returns the one-sided power spectral density estimated with Welch's method.
+    ///
+    /// The trace is split into Hann-windowed segments of `segment_len` samples, consecutive
+    /// segments sharing `overlap` samples; their periodograms are averaged. Trailing samples
+    /// that do not fill a segment are ignored.
+    ///
+    /// # Arguments
+    /// * `rate` - The sampling rate.
+    /// * `segment_len` - The number of samples per segment, which sets the resolution.
+    /// * `overlap` - The number of samples shared by consecutive segments.
+    ///
+    /// # Returns
+    /// The frequency of every bin in the prefix of `rate`, in steps of `rate / segment_len`,
+    /// and the averaged density of every bin in V²/Hz.
+    ///
+    /// # Panics
+    /// Panics if `segment_len` is zero or longer than the trace, or if `overlap` is not
+    /// smaller than `segment_len`.
+    pub fn welch(
+        &self,
+        rate: Measurement<Hertz>,
+        segment_len: usize,
+        overlap: usize,
+    ) -> (M1d<Hertz>, Array1<f64>) {
+        assert!(
+            segment_len > 0 && segment_len <= self.len(),
+            "segment length must be between 1 and the trace length"
+        );
+        assert!(
+            overlap < segment_len,
+            "overlap must be smaller than the segment length"
+        );
+        let volts = self.clone().convert_to(Prefix::None);
+        let window = Window::Hann.coefficients(segment_len);
+        let hop = segment_len - overlap;
+        let count = (volts.len() - segment_len) / hop + 1;
+        let mut psd = Array1::zeros(segment_len / 2 + 1);
+        for k in 0..count {
+            let start = k * hop;
+            let segment = volts
+                .view()
+                .slice_move(ndarray::s![start..start + segment_len]);
+            psd += &periodogram(segment, &window, rate);
+        }
+        (frequencies(rate, segment_len), psd / count as f64)
+    }
+}
+
+/// Returns the frequencies of the `len / 2 + 1` one-sided bins of a `len`-point transform.
+fn frequencies(rate: Measurement<Hertz>, len: usize) -> M1d<Hertz> {
+    let bins = if len == 0 { 0 } else { len / 2 + 1 };
+    let df = rate.value() / len as f64;
+    M1d::new(
+        Array1::from_shape_fn(bins, |k| k as f64 * df),
+        rate.prefix(),
+    )
+}
+
+/// Returns the one-sided density of `samples` (in V) tapered by `window`, in V²/Hz.
+fn periodogram(
+    samples: ArrayView1<f64>,
+    window: &Array1<f64>,
+    rate: Measurement<Hertz>,
+) -> Array1<f64> {
+    let len = samples.len();
+    if len == 0 {
+        return Array1::zeros(0);
+    }
+    let fs = rate.convert_to(Prefix::None).value();
+    let mut planner = RealFftPlanner::<f64>::new();
+    let r2c = planner.plan_fft_forward(len);
+    let mut input = (&samples * window).to_vec();
+    let mut spectrum = r2c.make_output_vec();
+    r2c.process(&mut input, &mut spectrum)
+        .expect("buffers are sized by the plan");
+    let scale = 1.0 / (fs * window.iter().map(|w| w * w).sum::<f64>());
+    let nyquist = len / 2;
+    Array1::from_iter(spectrum.iter().enumerate().map(|(k, c)| {
+        // every bin except DC and, for even lengths, Nyquist also holds the negative frequency
+        let one_sided = if k == 0 || (len.is_multiple_of(2) && k == nyquist) {
+            1.0
+        } else {
+            2.0
+        };
+        c.norm_sqr() * scale * one_sided
+    }))
+}
+
+#[cfg(test)]
+mod fft_tests {
+    use super::*;
+
+    /// Returns `len` samples of `amplitude * sin(2π f t)` at `fs`, in millivolts.
+    fn sine(len: usize, fs: f64, f: f64, amplitude: f64) -> M1d<Volt> {
+        let values = Array1::from_shape_fn(len, |i| {
+            1000.0 * amplitude * (2.0 * PI * f * i as f64 / fs).sin()
+        });
+        M1d::new(values, Prefix::Milli)
+    }
+
+    /// Returns the frequency of the largest bin in Hz and the total power in V² (density times
+    /// bin width).
+    fn peak_and_power(freqs: &M1d<Hertz>, psd: &Array1<f64>) -> (f64, f64) {
+        let freqs = freqs.clone().convert_to(Prefix::None).values();
+        let peak = psd
+            .iter()
+            .enumerate()
+            .max_by(|a, b| a.1.total_cmp(b.1))
+            .map(|(k, _)| freqs[k])
+            .unwrap();
+        (peak, psd.sum() * (freqs[1] - freqs[0]))
+    }
+
+    #[test]
+    fn sine_on_a_bin() {
+        // 1000 samples: not a power of two
+        let trace = sine(1000, 1000.0, 50.0, 2.0);
+        let rate = Measurement::<Hertz>::new(1.0, Prefix::Kilo);
+        let (freqs, psd) = trace.power_spectrum(rate, Window::Rectangular);
+        assert_eq!(freqs.prefix(), Prefix::Kilo);
+        assert_eq!(freqs.len(), 501);
+        assert_eq!(psd.len(), 501);
+        let (peak, power) = peak_and_power(&freqs, &psd);
+        assert!((peak - 50.0).abs() < 1e-9);
+        // a sine of amplitude A has a mean square of A² / 2, all of it in one bin: 2 V² over 1 Hz
+        assert!((power - 2.0).abs() < 1e-9, "{power}");
+        assert!((psd[50] - 2.0).abs() < 1e-9);
+
+        let (_, hann) = trace.power_spectrum(rate, Window::Hann);
+        let (peak, power) = peak_and_power(&freqs, &hann);
+        assert!((peak - 50.0).abs() < 1e-9);
+        assert!((power - 2.0).abs() < 1e-9, "{power}");
+    }
+
+    #[test]
+    fn odd_length_and_empty() {
+        let trace = sine(999, 999.0, 100.0, 1.0);
+        let (freqs, psd) =
+            trace.power_spectrum(Measurement::new(999.0, Prefix::None), Window::Hann);
+        assert_eq!(freqs.len(), 500);
+        let (peak, power) = peak_and_power(&freqs, &psd);
+        assert!((peak - 100.0).abs() < 1e-9);
+        assert!((power - 0.5).abs() < 1e-6, "{power}");
+
+        let empty = M1d::<Volt>::new(Vec::new(), Prefix::None);
+        let (freqs, psd) = empty.power_spectrum(Measurement::new(1.0, Prefix::None), Window::Hann);
+        assert!(freqs.is_empty() && psd.is_empty());
+    }
+
+    #[test]
+    fn welch_averages_segments() {
+        let trace = sine(1050, 1000.0, 50.0, 2.0);
+        let rate = Measurement::<Hertz>::new(1000.0, Prefix::None);
+        let (freqs, psd) = trace.welch(rate, 200, 100);
+        assert_eq!(freqs.len(), 101);
+        assert_eq!(freqs.values()[1], 5.0);
+        let (peak, power) = peak_and_power(&freqs, &psd);
+        assert!((peak - 50.0).abs() < 1e-9);
+        assert!((power - 2.0).abs() < 1e-9, "{power}");
+
+        let (_, single) = trace.welch(rate, 1050, 0);
+        let (_, full) = trace.power_spectrum(rate, Window::Hann);
+        assert!(single.iter().zip(&full).all(|(a, b)| (a - b).abs() < 1e-12));
+    }
+
+    #[test]
+    #[should_panic(expected = "overlap must be smaller than the segment length")]
+    fn welch_rejects_full_overlap() {
+        sine(100, 100.0, 10.0, 1.0).welch(Measurement::new(100.0, Prefix::None), 10, 10);
+    }
+}
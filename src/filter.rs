@@ -0,0 +1,493 @@
+//! Smoothing and detrending filters for one-dimensional arrays.
+
+use crate::{
+    error::MeasurementError,
+    m1d::M1d,
+    math::{SlidingSum, compensated_sum},
+    uom::Uom,
+};
+use ndarray::{Array1, Array2, ArrayView1, Zip, s};
+
+impl<U: Uom> M1d<U> {
+    /// Replaces every sample with the median of the `window` samples centred on it.
+    ///
+    /// Near the edges the window shrinks symmetrically so that it stays centred: the first and
+    /// last samples are kept as they are, the second and second-to-last are the median of three
+    /// samples, and so on. NaN samples are skipped; a window that holds only NaN yields NaN, and
+    /// a window left with an even number of samples yields the mean of the middle two.
+    ///
+    /// # Arguments
+    /// * `window` - The number of samples in a full window.
+    ///
+    /// # Returns
+    /// A new [`M1d`] with the same length and prefix.
+    ///
+    /// # Panics
+    /// Panics if `window` is not odd.
+    pub fn median_filter(&self, window: usize) -> M1d<U> {
+        assert!(
+            !window.is_multiple_of(2),
+            "median filter window must be odd"
+        );
+        let values = self.view();
+        let len = values.len();
+        let half = window / 2;
+        let mut sorted = Vec::with_capacity(window);
+        let filtered = Array1::from_shape_fn(len, |i| {
+            let k = half.min(i).min(len - 1 - i);
+            sorted.clear();
+            sorted.extend(
+                values
+                    .slice(s![i - k..=i + k])
+                    .iter()
+                    .filter(|v| !v.is_nan()),
+            );
+            sorted.sort_unstable_by(f64::total_cmp);
+            let n = sorted.len();
+            match n {
+                0 => f64::NAN,
+                _ if !n.is_multiple_of(2) => sorted[n / 2],
+                _ => (sorted[n / 2 - 1] + sorted[n / 2]) / 2.0,
+            }
+        });
+        M1d::new(filtered, self.prefix())
+    }
+
+    /// Smooths the samples with a Savitzky–Golay filter.
+    ///
+    /// Every sample is replaced by the value at its position of the least-squares polynomial of
+    /// degree `poly_order` through the `window` samples centred on it. The first and last
+    /// `window / 2` samples take their values from the polynomial fitted to the first and last
+    /// full windows, as `scipy.signal.savgol_filter` does with `mode="interp"`. The convolution
+    /// coefficients are computed once per call. NaN propagates: every output whose window (or
+    /// edge fit) contains a NaN sample is NaN.
+    ///
+    /// # Arguments
+    /// * `window` - The number of samples in each fit, odd.
+    /// * `poly_order` - The degree of the fitted polynomial, smaller than `window`.
+    ///
+    /// # Returns
+    /// A new [`M1d`] with the same length and prefix,
+    /// [`MeasurementError::InvalidOptions`] if `window` is even or not larger than
+    /// `poly_order`, or [`MeasurementError::OutOfRange`] if `window` is longer than the array.
+    pub fn savitzky_golay(
+        &self,
+        window: usize,
+        poly_order: usize,
+    ) -> Result<M1d<U>, MeasurementError> {
+        if window.is_multiple_of(2) {
+            return Err(MeasurementError::InvalidOptions(
+                "Savitzky-Golay window must be odd",
+            ));
+        }
+        if poly_order >= window {
+            return Err(MeasurementError::InvalidOptions(
+                "polynomial order must be smaller than the window",
+            ));
+        }
+        let len = self.len();
+        if window > len {
+            return Err(MeasurementError::OutOfRange {
+                value: window as f64,
+                min: 1.0,
+                max: len as f64,
+            });
+        }
+        let coefficients = savgol_coefficients(window, poly_order);
+        let values = self.view();
+        let half = window / 2;
+        let mut filtered = Array1::zeros(len);
+        let centre = coefficients.row(half);
+        Zip::from(filtered.slice_mut(s![half..len - half]))
+            .and(values.windows(window))
+            .for_each(|y, x| *y = centre.dot(&x));
+        filtered.slice_mut(s![..half]).assign(
+            &coefficients
+                .slice(s![..half, ..])
+                .dot(&values.slice(s![..window])),
+        );
+        filtered.slice_mut(s![len - half..]).assign(
+            &coefficients
+                .slice(s![half + 1.., ..])
+                .dot(&values.slice(s![len - window..])),
+        );
+        Ok(M1d::new(filtered, self.prefix()))
+    }
+
+    /// Returns the exponential moving average, seeded with the first sample.
+    ///
+    /// Sample `i` of the result is `alpha * x[i] + (1 - alpha) * y[i - 1]`, with `y[0] = x[0]`.
+    /// A NaN sample makes its output and every later output NaN.
+    ///
+    /// # Arguments
+    /// * `alpha` - The weight of the newest sample; `1.0` returns the samples unchanged.
+    ///
+    /// # Returns
+    /// A new [`M1d`] with the same length and prefix.
+    ///
+    /// # Panics
+    /// Panics if `alpha` is not in `(0, 1]`.
+    pub fn ema(&self, alpha: f64) -> M1d<U> {
+        assert!(
+            alpha > 0.0 && alpha <= 1.0,
+            "smoothing factor must be in (0, 1]"
+        );
+        let mut previous = None;
+        let smoothed = Array1::from_iter(self.view().iter().map(|&x| {
+            let y = previous.map_or(x, |p| alpha * x + (1.0 - alpha) * p);
+            previous = Some(y);
+            y
+        }));
+        M1d::new(smoothed, self.prefix())
+    }
+
+    /// Subtracts the least-squares line through the samples, fitted against the sample index.
+    ///
+    /// The result has a mean and a slope of zero; a pure ramp becomes all zeros. Any NaN sample
+    /// makes the whole result NaN.
+    ///
+    /// # Returns
+    /// A new [`M1d`] with the same length and prefix.
+    pub fn detrend_linear(&self) -> M1d<U> {
+        let values = self.view();
+        let (intercept, slope) = linear_fit(values);
+        let detrended =
+            Array1::from_shape_fn(values.len(), |i| values[i] - (intercept + slope * i as f64));
+        M1d::new(detrended, self.prefix())
+    }
+
+    /// Removes slow drift by subtracting a centred moving average of `window` samples.
+    ///
+    /// Near the edges the average is taken over the samples of the window that exist, so the
+    /// result keeps the full length. A NaN or infinite sample only affects the outputs whose
+    /// window contains it.
+    ///
+    /// # Arguments
+    /// * `window` - The number of samples averaged for the baseline, odd.
+    ///
+    /// # Returns
+    /// A new [`M1d`] with the same length and prefix.
+    ///
+    /// # Panics
+    /// Panics if `window` is not odd.
+    pub fn highpass_baseline(&self, window: usize) -> M1d<U> {
+        assert!(!window.is_multiple_of(2), "baseline window must be odd");
+        let values = self.view();
+        let len = values.len();
+        let half = window / 2;
+        let mut sum = SlidingSum::default();
+        let (mut lo, mut hi) = (0, 0);
+        let filtered = Array1::from_shape_fn(len, |i| {
+            while hi < (i + half + 1).min(len) {
+                sum.push(values[hi]);
+                hi += 1;
+            }
+            while lo < i.saturating_sub(half) {
+                sum.pop(values[lo]);
+                lo += 1;
+            }
+            let window = values.slice(s![lo..hi]);
+            values[i] - sum.total(window.iter().copied()) / (hi - lo) as f64
+        });
+        M1d::new(filtered, self.prefix())
+    }
+}
+
+/// Returns the intercept and slope of the least-squares line through `values` against their
+/// index. The slope is zero for fewer than two samples.
+fn linear_fit(values: ArrayView1<f64>) -> (f64, f64) {
+    let len = values.len();
+    if len == 0 {
+        return (0.0, 0.0);
+    }
+    let n = len as f64;
+    let mean_index = (n - 1.0) / 2.0;
+    let mean = compensated_sum(values.iter().copied()) / n;
+    // Σ (i - ī)² over 0..n is n (n² - 1) / 12
+    let spread = n * (n * n - 1.0) / 12.0;
+    let slope = if len < 2 {
+        0.0
+    } else {
+        compensated_sum(
+            values
+                .iter()
+                .enumerate()
+                .map(|(i, x)| (i as f64 - mean_index) * (x - mean)),
+        ) / spread
+    };
+    (mean - slope * mean_index, slope)
+}
+
+/// Returns the Savitzky–Golay coefficients of a `window`-point fit of degree `poly_order`.
+///
+/// Row `r` holds the weights that, applied to the window, give the fitted polynomial at
+/// position `r` of the window; the middle row is the usual convolution kernel.
+fn savgol_coefficients(window: usize, poly_order: usize) -> Array2<f64> {
+    let half = window / 2;
+    let terms = poly_order + 1;
+    // positions scaled to [-1, 1] keep the normal equations well conditioned
+    let scale = half.max(1) as f64;
+    let vandermonde = Array2::from_shape_fn((window, terms), |(j, k)| {
+        ((j as f64 - half as f64) / scale).powi(k as i32)
+    });
+    // solve (AᵀA) G = Aᵀ by Gauss-Jordan elimination; AᵀA is symmetric positive definite
+    let mut normal = vandermonde.t().dot(&vandermonde);
+    let mut fit = vandermonde.t().to_owned();
+    for c in 0..terms {
+        let pivot = (c..terms)
+            .max_by(|&a, &b| normal[[a, c]].abs().total_cmp(&normal[[b, c]].abs()))
+            .expect("pivot range is not empty");
+        for col in 0..terms {
+            normal.swap([c, col], [pivot, col]);
+        }
+        for col in 0..window {
+            fit.swap([c, col], [pivot, col]);
+        }
+        let d = normal[[c, c]];
+        normal.row_mut(c).mapv_inplace(|v| v / d);
+        fit.row_mut(c).mapv_inplace(|v| v / d);
+        for r in (0..terms).filter(|&r| r != c) {
+            let factor = normal[[r, c]];
+            let (pivot_normal, pivot_fit) = (normal.row(c).to_owned(), fit.row(c).to_owned());
+            normal.row_mut(r).scaled_add(-factor, &pivot_normal);
+            fit.row_mut(r).scaled_add(-factor, &pivot_fit);
+        }
+    }
+    vandermonde.dot(&fit)
+}
+
+#[cfg(test)]
+mod filter_tests {
+    use super::*;
+    use crate::{prefix::Prefix, uom::Volt};
+
+    /// A step from 0 to 1 at sample 10 with small noise and a spike at sample 6, in millivolts.
+    fn noisy_step() -> M1d<Volt> {
+        M1d::new(
+            vec![
+                0.03, -0.02, 0.05, -0.04, 0.01, 0.0, 0.9, 0.02, 0.04, -0.05, 1.02, 0.99, 1.03,
+                0.98, 1.0, 1.05, 0.96, 1.01, 0.97, 1.02, 1.01,
+            ],
+            Prefix::Milli,
+        )
+    }
+
+    fn assert_close(actual: &M1d<Volt>, expected: &[f64]) {
+        assert_eq!(actual.len(), expected.len());
+        for (a, e) in actual.view().iter().zip(expected) {
+            assert!((a - e).abs() < 1e-12, "{a} != {e}");
+        }
+    }
+
+    #[test]
+    fn median_removes_the_spike() {
+        let filtered = noisy_step().median_filter(5);
+        assert_eq!(filtered.prefix(), Prefix::Milli);
+        assert_close(
+            &filtered,
+            &[
+                0.03, 0.03, 0.01, 0.0, 0.01, 0.01, 0.02, 0.02, 0.04, 0.04, 0.99, 0.99, 1.0, 1.0,
+                1.0, 1.0, 1.0, 1.01, 1.01, 1.01, 1.01,
+            ],
+        );
+        assert_eq!(noisy_step().median_filter(1), noisy_step());
+    }
+
+    #[test]
+    fn median_skips_nan() {
+        let a = M1d::<Volt>::new(vec![1.0, f64::NAN, 3.0, 8.0, f64::NAN], Prefix::None);
+        let filtered = a.median_filter(3);
+        assert_eq!(
+            filtered.view().slice(s![..4]),
+            ndarray::array![1.0, 2.0, 5.5, 5.5]
+        );
+        assert!(filtered.view()[4].is_nan());
+    }
+
+    #[test]
+    #[should_panic(expected = "median filter window must be odd")]
+    fn median_rejects_even_window() {
+        noisy_step().median_filter(4);
+    }
+
+    #[test]
+    fn savitzky_golay_matches_exact_least_squares() {
+        // Reference values for scipy.signal.savgol_filter(x, 7, 2) and savgol_filter(x, 5, 3)
+        // with mode="interp": each interior sample from the least-squares polynomial over its
+        // centred window, the edges from the first and last full windows. They were computed
+        // as exact rational fits; tests/fixtures/savgol/generate.py prints scipy's values
+        // (with the numpy and scipy versions) to regenerate or cross-check them.
+        let filtered = noisy_step().savitzky_golay(7, 2).unwrap();
+        assert_eq!(filtered.prefix(), Prefix::Milli);
+        assert_close(
+            &filtered,
+            &[
+                0.12880952380952382,
+                -0.053571428571428575,
+                -0.12571428571428572,
+                -0.08761904761904762,
+                0.12761904761904763,
+                0.24857142857142858,
+                0.32142857142857145,
+                0.17,
+                0.18476190476190477,
+                0.24666666666666667,
+                0.6661904761904762,
+                0.9495238095238095,
+                1.0995238095238096,
+                1.0095238095238095,
+                1.0071428571428571,
+                1.0038095238095237,
+                0.9995238095238095,
+                0.9923809523809524,
+                0.9942857142857143,
+                1.0014285714285713,
+                1.0138095238095237,
+            ],
+        );
+        assert_close(
+            &noisy_step().savitzky_golay(5, 3).unwrap(),
+            &[
+                0.021714285714285714,
+                0.013142857142857144,
+                0.0002857142857142867,
+                0.002857142857142858,
+                -0.09028571428571429,
+                0.3137142857142857,
+                0.4397142857142857,
+                0.3362857142857143,
+                -0.15542857142857144,
+                0.25257142857142856,
+                0.726,
+                1.104,
+                1.0025714285714287,
+                0.9971428571428571,
+                1.0111428571428571,
+                1.0114285714285713,
+                1.0037142857142858,
+                0.9748571428571429,
+                0.9982857142857143,
+                1.0011428571428571,
+                1.0147142857142857,
+            ],
+        );
+        // a polynomial of the fitted degree passes through unchanged, edges included
+        let quadratic = Array1::from_shape_fn(9, |i| (i * i) as f64 - 3.0 * i as f64);
+        assert_close(
+            &M1d::<Volt>::new(quadratic.clone(), Prefix::None)
+                .savitzky_golay(5, 2)
+                .unwrap(),
+            quadratic.as_slice().unwrap(),
+        );
+    }
+
+    #[test]
+    fn ema_follows_a_step() {
+        let a = M1d::<Volt>::new(vec![2.0, 4.0, 4.0, 4.0], Prefix::Micro);
+        let smoothed = a.ema(0.5);
+        assert_eq!(smoothed.prefix(), Prefix::Micro);
+        assert_eq!(smoothed.view(), ndarray::array![2.0, 3.0, 3.5, 3.75]);
+        assert_eq!(a.ema(1.0), a);
+        assert!(
+            M1d::<Volt>::new(Vec::new(), Prefix::None)
+                .ema(0.1)
+                .is_empty()
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "smoothing factor must be in (0, 1]")]
+    fn ema_rejects_zero_alpha() {
+        noisy_step().ema(0.0);
+    }
+
+    #[test]
+    fn detrend_removes_ramp_and_mean() {
+        let ramp = M1d::<Volt>::new(
+            Array1::from_shape_fn(100, |i| 5.0 + 0.25 * i as f64),
+            Prefix::Milli,
+        );
+        let detrended = ramp.detrend_linear();
+        assert_eq!(detrended.prefix(), Prefix::Milli);
+        assert!(detrended.view().iter().all(|v| v.abs() < 1e-12));
+
+        let detrended = noisy_step().detrend_linear();
+        assert_eq!(detrended.len(), 21);
+        assert!(detrended.mean().unwrap().value().abs() < 1e-12);
+        assert_eq!(
+            M1d::<Volt>::new(vec![3.0], Prefix::None).detrend_linear(),
+            M1d::new(vec![0.0], Prefix::None)
+        );
+    }
+
+    #[test]
+    fn highpass_baseline_removes_drift() {
+        let ramp = M1d::<Volt>::new(
+            Array1::from_shape_fn(50, |i| 1.0 + 0.5 * i as f64),
+            Prefix::Milli,
+        );
+        let filtered = ramp.highpass_baseline(9);
+        assert_eq!(filtered.len(), 50);
+        assert_eq!(filtered.prefix(), Prefix::Milli);
+        // a centred average of a ramp is the ramp itself wherever the window is complete
+        assert!(
+            filtered
+                .view()
+                .slice(s![4..46])
+                .iter()
+                .all(|v| v.abs() < 1e-12)
+        );
+        // at the first sample the window holds samples 0..=4, averaging 1 + 0.5 * 2
+        assert!((filtered.view()[0] + 1.0).abs() < 1e-12);
+        let constant = M1d::<Volt>::new(vec![7.0; 5], Prefix::None);
+        assert_eq!(
+            constant.highpass_baseline(3),
+            M1d::new(vec![0.0; 5], Prefix::None)
+        );
+    }
+
+    #[test]
+    fn highpass_baseline_confines_non_finite_samples() {
+        let mut values = vec![2.0; 11];
+        values[3] = f64::NAN;
+        values[7] = f64::INFINITY;
+        let filtered = M1d::<Volt>::new(values, Prefix::None).highpass_baseline(3);
+        let view = filtered.view();
+        // each sample's window is its neighbours, so only samples 2..=4 and 6..=8 are affected
+        assert!(view.slice(s![2..5]).iter().all(|v| v.is_nan()));
+        assert_eq!([view[6], view[8]], [f64::NEG_INFINITY; 2]);
+        assert!(view[7].is_nan());
+        for i in [0, 1, 5, 9, 10] {
+            assert_eq!(view[i], 0.0);
+        }
+    }
+
+    #[test]
+    fn savitzky_golay_propagates_nan_and_validates() {
+        let mut values = vec![0.0; 9];
+        values[4] = f64::NAN;
+        let filtered = M1d::<Volt>::new(values, Prefix::None)
+            .savitzky_golay(3, 1)
+            .unwrap();
+        let nan: Vec<bool> = filtered.view().iter().map(|v| v.is_nan()).collect();
+        assert_eq!(
+            nan,
+            [false, false, false, true, true, true, false, false, false]
+        );
+
+        let step = noisy_step();
+        assert!(matches!(
+            step.savitzky_golay(4, 2),
+            Err(MeasurementError::InvalidOptions(_))
+        ));
+        assert!(matches!(
+            step.savitzky_golay(5, 5),
+            Err(MeasurementError::InvalidOptions(_))
+        ));
+        assert!(matches!(
+            step.savitzky_golay(23, 2),
+            Err(MeasurementError::OutOfRange { .. })
+        ));
+        assert_eq!(step.savitzky_golay(1, 0).unwrap(), step);
+    }
+}
@@ -0,0 +1,269 @@
+//! Ladders of nested instrument ranges, for picking the best range for a signal.
+
+#[cfg(feature = "ndarray")]
+use crate::m1d::M1d;
+use crate::{
+    error::MeasurementError, measurement::Measurement, percentage::Percentage,
+    ranged_measurement::RangedMeasurement, uom::Uom,
+};
+use alloc::vec::Vec;
+
+/// A fixed set of ranges from the most sensitive to the widest, such as the current ranges
+/// of an amplifier (±200 pA, ±2 nA, ±20 nA, ±200 nA).
+///
+/// How much of a range a signal uses is measured from the centre of the range: a value at
+/// the centre uses none of it and a value on a bound uses all of it, so a symmetric range is
+/// used in proportion to the magnitude of the signal.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RangeLadder<U: Uom> {
+    ranges: Vec<RangedMeasurement<U>>,
+}
+
+/// Returns the fraction of `range` used by `m`: `|m - centre| / half-span`, NaN for NaN.
+fn usage<U: Uom>(range: &RangedMeasurement<U>, m: Measurement<U>) -> f64 {
+    let prefix = range.min().prefix();
+    let (min, max) = (range.min().value(), range.max().convert_to(prefix).value());
+    let v = m.convert_to(prefix).value();
+    (v - (min + max) / 2.0).abs() / ((max - min) / 2.0)
+}
+
+impl<U: Uom> RangeLadder<U> {
+    /// Creates a ladder from `ranges`, sorted from the most sensitive to the widest.
+    ///
+    /// # Returns
+    /// The ladder; [`MeasurementError::EmptyInput`] if `ranges` is empty, or
+    /// [`MeasurementError::RangeInvariant`] for the first range whose span is empty or that
+    /// does not strictly contain the previous one.
+    pub fn new(ranges: Vec<RangedMeasurement<U>>) -> Result<Self, MeasurementError> {
+        if ranges.is_empty() {
+            return Err(MeasurementError::EmptyInput("a range ladder needs a range"));
+        }
+        let invalid = |r: &RangedMeasurement<U>, reason| MeasurementError::RangeInvariant {
+            min: r.min().value(),
+            max: r.max().value(),
+            step: r.step().map(|s| s.value()),
+            reason,
+        };
+        if ranges[0].min() >= ranges[0].max() {
+            return Err(invalid(&ranges[0], "min must be below max"));
+        }
+        for pair in ranges.windows(2) {
+            let (inner, outer) = (&pair[0], &pair[1]);
+            let contains = outer.min() <= inner.min() && outer.max() >= inner.max();
+            if !contains || outer.max() - outer.min() <= inner.max() - inner.min() {
+                return Err(invalid(outer, "ladder ranges must be strictly increasing"));
+            }
+        }
+        Ok(Self { ranges })
+    }
+
+    /// Returns the ranges, from the most sensitive to the widest.
+    pub fn ranges(&self) -> &[RangedMeasurement<U>] {
+        &self.ranges
+    }
+
+    /// Returns the most sensitive range that holds `m` while keeping `headroom` of its
+    /// half-span free, e.g. 10% to use at most 90% of the range.
+    ///
+    /// # Returns
+    /// The range, or `None` if `m` is NaN or too large for every range.
+    pub fn best_for(
+        &self,
+        m: Measurement<U>,
+        headroom: Percentage,
+    ) -> Option<&RangedMeasurement<U>> {
+        let limit = 1.0 - headroom.get_value();
+        self.ranges.iter().find(|r| usage(r, m) <= limit)
+    }
+
+    /// Returns the most sensitive range that holds every sample of `trace`, from its most
+    /// negative to its most positive peak, while keeping `headroom` of its half-span free.
+    ///
+    /// NaN samples are ignored; an empty or all-NaN trace fits the most sensitive range.
+    ///
+    /// # Returns
+    /// The range, or `None` if the trace is too large for every range.
+    #[cfg(feature = "ndarray")]
+    pub fn best_for_trace(
+        &self,
+        trace: &M1d<U>,
+        headroom: Percentage,
+    ) -> Option<&RangedMeasurement<U>> {
+        let limit = 1.0 - headroom.get_value();
+        self.ranges.iter().find(|r| trace_usage(r, trace) <= limit)
+    }
+
+    /// Decides whether to leave range `current_idx` for `trace`, with hysteresis.
+    ///
+    /// The signal switches up when it uses more than `up_thresh` of the current range, to the
+    /// most sensitive wider range it uses no more than `up_thresh` of (or the widest range).
+    /// It switches down only when it uses less than `down_thresh` of the current range, to the
+    /// most sensitive narrower range it uses no more than `up_thresh` of, so the new range is
+    /// not left again straight away. Between the two thresholds the range is kept. NaN
+    /// samples are ignored.
+    ///
+    /// # Returns
+    /// The index of the range to switch to, or `None` to stay.
+    ///
+    /// # Panics
+    /// Panics if `current_idx` is out of bounds or `down_thresh` is not below `up_thresh`.
+    #[cfg(feature = "ndarray")]
+    pub fn should_switch(
+        &self,
+        current_idx: usize,
+        trace: &M1d<U>,
+        up_thresh: Percentage,
+        down_thresh: Percentage,
+    ) -> Option<usize> {
+        let (up, down) = (up_thresh.get_value(), down_thresh.get_value());
+        assert!(down < up, "down threshold must be below the up threshold");
+        let current = trace_usage(&self.ranges[current_idx], trace);
+        if current > up {
+            let wider = current_idx + 1..self.ranges.len();
+            let fits = wider
+                .clone()
+                .find(|&i| trace_usage(&self.ranges[i], trace) <= up);
+            fits.or(wider.last())
+        } else if current < down {
+            (0..current_idx).find(|&i| trace_usage(&self.ranges[i], trace) <= up)
+        } else {
+            None
+        }
+    }
+}
+
+/// Returns the largest [`usage`] of `range` by the samples of `trace`, ignoring NaN; zero for
+/// an empty or all-NaN trace.
+#[cfg(feature = "ndarray")]
+fn trace_usage<U: Uom>(range: &RangedMeasurement<U>, trace: &M1d<U>) -> f64 {
+    let (lo, hi) = trace
+        .view()
+        .iter()
+        .filter(|v| !v.is_nan())
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), &v| {
+            (lo.min(v), hi.max(v))
+        });
+    if lo > hi {
+        return 0.0;
+    }
+    let peak = |v| usage(range, Measurement::new(v, trace.prefix()));
+    peak(lo).max(peak(hi))
+}
+
+#[cfg(test)]
+mod ladder_tests {
+    use super::*;
+    use crate::{percentage, prefix::Prefix, uom::Ampere};
+    use alloc::vec;
+
+    fn ladder() -> RangeLadder<Ampere> {
+        RangeLadder::new(vec![
+            RangedMeasurement::new_sym_stepless(200.0, Prefix::Pico),
+            RangedMeasurement::new_sym_stepless(2.0, Prefix::Nano),
+            RangedMeasurement::new_sym_stepless(20.0, Prefix::Nano),
+            RangedMeasurement::new_sym_stepless(200.0, Prefix::Nano),
+        ])
+        .unwrap()
+    }
+
+    /// Returns the index of `range` in the ladder.
+    fn index(
+        ladder: &RangeLadder<Ampere>,
+        range: Option<&RangedMeasurement<Ampere>>,
+    ) -> Option<usize> {
+        range.map(|r| ladder.ranges().iter().position(|l| l == r).unwrap())
+    }
+
+    #[test]
+    fn rejects_invalid_ladders() {
+        assert!(matches!(
+            RangeLadder::<Ampere>::new(vec![]),
+            Err(MeasurementError::EmptyInput(_))
+        ));
+        let unsorted = RangeLadder::<Ampere>::new(vec![
+            RangedMeasurement::new_sym_stepless(2.0, Prefix::Nano),
+            RangedMeasurement::new_sym_stepless(200.0, Prefix::Pico),
+        ]);
+        assert!(matches!(
+            unsorted,
+            Err(MeasurementError::RangeInvariant { min: -200.0, .. })
+        ));
+        let repeated = RangeLadder::<Ampere>::new(vec![
+            RangedMeasurement::new_sym_stepless(2.0, Prefix::Nano),
+            RangedMeasurement::new_sym_stepless(2000.0, Prefix::Pico),
+        ]);
+        assert!(repeated.is_err());
+        let shifted = RangeLadder::<Ampere>::new(vec![
+            RangedMeasurement::new(0.0, 1.0, 0.1, Prefix::Nano),
+            RangedMeasurement::new(0.5, 10.0, 0.1, Prefix::Nano),
+        ]);
+        assert!(shifted.is_err());
+        assert!(
+            RangeLadder::<Ampere>::new(vec![RangedMeasurement::new_sym_stepless(
+                0.0,
+                Prefix::Nano
+            )])
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn best_for_measurements() {
+        let ladder = ladder();
+        let best = |value, prefix, headroom| {
+            index(
+                &ladder,
+                ladder.best_for(Measurement::new(value, prefix), headroom),
+            )
+        };
+        assert_eq!(best(150.0, Prefix::Pico, percentage!(0.0)), Some(0));
+        assert_eq!(best(-200.0, Prefix::Pico, percentage!(0.0)), Some(0));
+        // 190 pA uses 95% of ±200 pA, more than 90% allows
+        assert_eq!(best(190.0, Prefix::Pico, percentage!(0.1)), Some(1));
+        assert_eq!(best(0.19, Prefix::Micro, percentage!(0.1)), None);
+        assert_eq!(best(f64::NAN, Prefix::None, percentage!(0.1)), None);
+    }
+
+    #[cfg(feature = "ndarray")]
+    #[test]
+    fn best_for_traces_straddling_boundaries() {
+        let ladder = ladder();
+        let best = |values: Vec<f64>| {
+            index(
+                &ladder,
+                ladder.best_for_trace(&M1d::new(values, Prefix::Pico), percentage!(0.1)),
+            )
+        };
+        assert_eq!(best(vec![-50.0, 100.0, 179.0]), Some(0));
+        // one sample past 90% of ±200 pA moves the whole trace up
+        assert_eq!(best(vec![-50.0, 100.0, 181.0]), Some(1));
+        assert_eq!(best(vec![-1801.0, 0.0]), Some(2));
+        assert_eq!(best(vec![f64::NAN, 1799.0]), Some(1));
+        assert_eq!(best(vec![]), Some(0));
+        assert_eq!(best(vec![250_000.0]), None);
+    }
+
+    #[cfg(feature = "ndarray")]
+    #[test]
+    fn switching_has_hysteresis() {
+        let ladder = ladder();
+        let (up, down) = (percentage!(0.9), percentage!(0.05));
+        let switch = |current, values: Vec<f64>| {
+            ladder.should_switch(current, &M1d::new(values, Prefix::Nano), up, down)
+        };
+        // 1.9 nA uses 95% of ±2 nA: switch up one range
+        assert_eq!(switch(1, vec![0.5, 1.9]), Some(2));
+        // a large jump skips ranges, and beyond the ladder the widest range is used
+        assert_eq!(switch(0, vec![50.0]), Some(3));
+        assert_eq!(switch(1, vec![500.0]), Some(3));
+        assert_eq!(switch(3, vec![500.0]), None);
+        // between the thresholds the range is kept
+        assert_eq!(switch(2, vec![1.5]), None);
+        assert_eq!(switch(2, vec![-17.0]), None);
+        // 0.9 nA uses 4.5% of ±20 nA, and 45% of ±2 nA
+        assert_eq!(switch(2, vec![0.9]), Some(1));
+        // 0.15 nA fits ±200 pA at 75%
+        assert_eq!(switch(3, vec![-0.15]), Some(0));
+        assert_eq!(switch(0, vec![]), None);
+    }
+}
@@ -1,18 +1,119 @@
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
+#[cfg(feature = "ndarray")]
+pub mod analysis;
+#[cfg(feature = "proptest")]
+pub mod arbitrary;
+#[cfg(feature = "arrow")]
+pub mod arrow_io;
+#[cfg(feature = "ndarray")]
+pub mod channel_set;
+#[cfg(feature = "ndarray")]
+pub mod channels;
+#[cfg(feature = "ndarray")]
+pub mod chunked;
+#[cfg(feature = "cli")]
+pub mod cli;
+pub mod compat;
+#[cfg(feature = "ndarray")]
+pub mod correlation;
+#[cfg(feature = "csv")]
+pub mod csv_io;
+#[cfg(feature = "std")]
+pub mod decibel;
+pub mod dyn_measurement;
+#[cfg(feature = "ndarray")]
+pub mod epochs;
+pub mod error;
+#[cfg(feature = "fft")]
+pub mod fft;
+#[cfg(feature = "ndarray")]
+pub mod filter;
+pub mod ladder;
+#[cfg(feature = "ndarray")]
+pub mod lookup;
+#[cfg(feature = "ndarray")]
 pub mod m1d;
+#[cfg(feature = "ndarray")]
 pub mod m2d;
+mod math;
 pub mod measurement;
+pub mod measurement_int;
+#[cfg(feature = "npy")]
+pub mod npy;
+pub mod ordered;
+#[cfg(all(feature = "ndarray", not(feature = "rayon")))]
+mod par;
 pub mod percentage;
 pub mod prefix;
+#[cfg(feature = "python")]
+pub mod python;
+pub mod quantities;
+pub mod quantity;
 pub mod ranged_measurement;
+pub mod registry;
+#[cfg(feature = "ndarray")]
+pub mod report;
+#[cfg(feature = "ndarray")]
+pub mod sampled;
+pub mod sampling;
+pub mod stats;
+#[cfg(feature = "testing")]
+pub mod testing;
+#[cfg(feature = "ndarray")]
+pub mod timed;
+#[cfg(feature = "std")]
+pub mod uncertain;
 pub mod uom;
+#[cfg(feature = "ndarray")]
+pub mod waveform;
+
+#[doc(hidden)]
+pub mod __private {
+    pub use serde;
+}
 
 // Prelude module
 pub mod prelude {
+    #[cfg(feature = "ndarray")]
+    pub use super::analysis::*;
+    #[cfg(feature = "ndarray")]
+    pub use super::channel_set::*;
+    #[cfg(feature = "ndarray")]
+    pub use super::channels::*;
+    #[cfg(feature = "std")]
+    pub use super::decibel::*;
+    pub use super::dyn_measurement::*;
+    #[cfg(feature = "ndarray")]
+    pub use super::epochs::*;
+    pub use super::error::*;
+    pub use super::ladder::*;
+    #[cfg(feature = "ndarray")]
+    pub use super::lookup::*;
+    #[cfg(feature = "ndarray")]
     pub use super::m1d::*;
+    #[cfg(feature = "ndarray")]
     pub use super::m2d::*;
     pub use super::measurement::*;
+    pub use super::measurement_int::*;
+    pub use super::ordered::*;
     pub use super::percentage::*;
     pub use super::prefix::*;
+    pub use super::quantities::*;
+    pub use super::quantity::*;
     pub use super::ranged_measurement::*;
+    pub use super::registry::*;
+    #[cfg(feature = "ndarray")]
+    pub use super::sampled::*;
+    pub use super::sampling::*;
+    pub use super::stats::*;
+    #[cfg(feature = "ndarray")]
+    pub use super::timed::*;
+    #[cfg(feature = "std")]
+    pub use super::uncertain::*;
     pub use super::uom::*;
+    #[cfg(feature = "ndarray")]
+    pub use ndarray::Axis;
 }
@@ -0,0 +1,255 @@
+//! Calibration curves given as breakpoint tables, such as sensor linearization tables.
+
+use crate::{
+    error::MeasurementError,
+    m1d::{M1d, check_increasing_keys},
+    measurement::Measurement,
+    uom::Uom,
+};
+use ndarray::ArrayView1;
+use serde::{Deserialize, Serialize};
+
+/// What a [`LookupTable`] returns for inputs outside its breakpoints.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OutOfDomain {
+    /// The output of the nearest breakpoint.
+    #[default]
+    Clamp,
+    /// The line through the first or last two breakpoints, continued.
+    Extrapolate,
+}
+
+/// A curve through `(x, y)` breakpoints with strictly increasing `x`, evaluated by linear
+/// interpolation between them, e.g. a thermocouple voltage to temperature table.
+///
+/// Deserialization checks the same invariants as [`LookupTable::new`], so tables can be
+/// loaded from configuration files.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "UncheckedLookupTable<X, Y>")]
+pub struct LookupTable<X: Uom, Y: Uom> {
+    xs: M1d<X>,
+    ys: M1d<Y>,
+    out_of_domain: OutOfDomain,
+}
+
+/// The serialized form of a [`LookupTable`], before validation.
+#[derive(Deserialize)]
+struct UncheckedLookupTable<X: Uom, Y: Uom> {
+    xs: M1d<X>,
+    ys: M1d<Y>,
+    #[serde(default)]
+    out_of_domain: OutOfDomain,
+}
+
+impl<X: Uom, Y: Uom> TryFrom<UncheckedLookupTable<X, Y>> for LookupTable<X, Y> {
+    type Error = MeasurementError;
+
+    fn try_from(raw: UncheckedLookupTable<X, Y>) -> Result<Self, Self::Error> {
+        Self::new(raw.xs, raw.ys, raw.out_of_domain)
+    }
+}
+
+impl<X: Uom, Y: Uom> LookupTable<X, Y> {
+    /// Creates a table from the breakpoint inputs `xs` and outputs `ys`.
+    ///
+    /// # Returns
+    /// The table; [`MeasurementError::ShapeMismatch`] if the lengths differ,
+    /// [`MeasurementError::EmptyInput`] if there are fewer than two breakpoints, or
+    /// [`MeasurementError::NotIncreasing`] at the first input that is not above the previous
+    /// one (including NaN).
+    pub fn new(
+        xs: M1d<X>,
+        ys: M1d<Y>,
+        out_of_domain: OutOfDomain,
+    ) -> Result<Self, MeasurementError> {
+        check_increasing_keys(xs.view(), ys.len(), "a lookup table needs two breakpoints")?;
+        Ok(Self {
+            xs,
+            ys,
+            out_of_domain,
+        })
+    }
+
+    /// Returns the breakpoint inputs.
+    pub fn xs(&self) -> &M1d<X> {
+        &self.xs
+    }
+
+    /// Returns the breakpoint outputs.
+    pub fn ys(&self) -> &M1d<Y> {
+        &self.ys
+    }
+
+    /// Returns what the table does outside its breakpoints.
+    pub fn out_of_domain(&self) -> OutOfDomain {
+        self.out_of_domain
+    }
+
+    /// Returns the output for `x`, in the prefix of the outputs.
+    ///
+    /// An input on a breakpoint returns its output exactly. A NaN input returns NaN.
+    pub fn eval(&self, x: Measurement<X>) -> Measurement<Y> {
+        let v = x.convert_to(self.xs.prefix()).value();
+        let segment = segment_of(self.xs.view(), v);
+        Measurement::new(self.interpolate(v, segment), self.ys.prefix())
+    }
+
+    /// Returns the output for every input of `xs`, in the prefix of the outputs.
+    ///
+    /// Sorted inputs, such as a sweep, are evaluated in a single pass over the breakpoints;
+    /// unsorted inputs are also accepted and give the same results as [`LookupTable::eval`].
+    pub fn eval_array(&self, xs: &M1d<X>) -> M1d<Y> {
+        let inputs = xs.clone().convert_to(self.xs.prefix());
+        let breakpoints = self.xs.view();
+        let last = breakpoints.len() - 2;
+        let mut segment = 0;
+        let values = inputs
+            .view()
+            .iter()
+            .map(|&v| {
+                if v < breakpoints[segment] {
+                    // the inputs went back: search again from the start
+                    segment = segment_of(breakpoints, v);
+                }
+                while segment < last && breakpoints[segment + 1] <= v {
+                    segment += 1;
+                }
+                self.interpolate(v, segment)
+            })
+            .collect::<Vec<_>>();
+        M1d::new(values, self.ys.prefix())
+    }
+
+    /// Interpolates `v`, in the prefix of the inputs, on the segment from breakpoint
+    /// `segment` to the next.
+    fn interpolate(&self, v: f64, segment: usize) -> f64 {
+        let (xs, ys) = (self.xs.view(), self.ys.view());
+        let (x0, x1) = (xs[segment], xs[segment + 1]);
+        let v = match self.out_of_domain {
+            OutOfDomain::Clamp => clamp_to(v, xs),
+            OutOfDomain::Extrapolate => v,
+        };
+        if v == x0 {
+            ys[segment]
+        } else if v == x1 {
+            ys[segment + 1]
+        } else {
+            ys[segment] + (ys[segment + 1] - ys[segment]) * (v - x0) / (x1 - x0)
+        }
+    }
+}
+
+/// Returns the index of the breakpoint starting the segment that holds `v`: the first
+/// segment below the breakpoints and for NaN, the last one above them.
+fn segment_of(xs: ArrayView1<f64>, v: f64) -> usize {
+    // binary search for the number of breakpoints at or below `v`
+    let (mut lo, mut hi) = (0, xs.len());
+    while lo < hi {
+        let mid = (lo + hi) / 2;
+        if xs[mid] <= v {
+            lo = mid + 1;
+        } else {
+            hi = mid;
+        }
+    }
+    lo.clamp(1, xs.len() - 1) - 1
+}
+
+/// Returns `v` limited to the span of the breakpoints `xs`, keeping NaN.
+fn clamp_to(v: f64, xs: ArrayView1<f64>) -> f64 {
+    v.clamp(xs[0], xs[xs.len() - 1])
+}
+
+#[cfg(test)]
+mod lookup_tests {
+    use super::*;
+    use crate::{
+        prefix::Prefix,
+        uom::{Celsius, Volt},
+    };
+    use ndarray::array;
+
+    /// A made-up thermocouple curve: 0, 1, 3 and 6 mV at 0, 25, 50 and 100 °C.
+    fn table(out_of_domain: OutOfDomain) -> LookupTable<Volt, Celsius> {
+        LookupTable::new(
+            M1d::new(vec![0.0, 1.0, 3.0, 6.0], Prefix::Milli),
+            M1d::new(vec![0.0, 25.0, 50.0, 100.0], Prefix::None),
+            out_of_domain,
+        )
+        .unwrap()
+    }
+
+    fn mv(v: f64) -> Measurement<Volt> {
+        Measurement::new(v, Prefix::Milli)
+    }
+
+    #[test]
+    fn returns_breakpoints_exactly() {
+        let t = table(OutOfDomain::Clamp);
+        for (x, y) in [(0.0, 0.0), (1.0, 25.0), (3.0, 50.0), (6.0, 100.0)] {
+            assert_eq!(t.eval(mv(x)).value(), y);
+        }
+        assert_eq!(
+            t.eval(Measurement::new(3000.0, Prefix::Micro)).value(),
+            50.0
+        );
+        assert_eq!(t.eval(mv(2.0)).value(), 37.5);
+        assert_eq!(t.eval(mv(4.5)).value(), 75.0);
+        assert_eq!(t.eval(mv(2.0)).prefix(), Prefix::None);
+        assert!(t.eval(mv(f64::NAN)).value().is_nan());
+    }
+
+    #[test]
+    fn out_of_domain_follows_the_flag() {
+        let clamped = table(OutOfDomain::Clamp);
+        assert_eq!(clamped.eval(mv(-1.0)).value(), 0.0);
+        assert_eq!(clamped.eval(mv(9.0)).value(), 100.0);
+        let extrapolated = table(OutOfDomain::Extrapolate);
+        assert_eq!(extrapolated.eval(mv(-1.0)).value(), -25.0);
+        assert_eq!(extrapolated.eval(mv(9.0)).value(), 150.0);
+    }
+
+    #[test]
+    fn evaluates_arrays_in_one_pass() {
+        let t = table(OutOfDomain::Extrapolate);
+        let sweep = M1d::<Volt>::new(vec![-1.0, 0.0, 0.5, 1.0, 2.0, 6.0, 9.0], Prefix::Milli);
+        assert_eq!(
+            t.eval_array(&sweep).values(),
+            array![-25.0, 0.0, 12.5, 25.0, 37.5, 100.0, 150.0]
+        );
+        let shuffled =
+            M1d::<Volt>::new(vec![4500.0, 500.0, 9000.0, -1000.0, 3000.0], Prefix::Micro);
+        let expected = shuffled
+            .view()
+            .mapv(|v| t.eval(Measurement::new(v, Prefix::Micro)).value());
+        assert_eq!(t.eval_array(&shuffled).values(), expected);
+        let clamped = table(OutOfDomain::Clamp).eval_array(&sweep);
+        assert_eq!(
+            clamped.values(),
+            array![0.0, 0.0, 12.5, 25.0, 37.5, 100.0, 100.0]
+        );
+    }
+
+    #[test]
+    fn validates_and_round_trips_through_serde() {
+        let xs = |v: Vec<f64>| M1d::<Volt>::new(v, Prefix::Milli);
+        let ys = || M1d::<Celsius>::new(vec![0.0, 1.0, 2.0], Prefix::None);
+        assert_eq!(
+            LookupTable::new(xs(vec![0.0, 2.0, 1.0]), ys(), OutOfDomain::Clamp),
+            Err(MeasurementError::NotIncreasing { index: 2 })
+        );
+        assert!(matches!(
+            LookupTable::new(xs(vec![0.0, 1.0]), ys(), OutOfDomain::Clamp),
+            Err(MeasurementError::ShapeMismatch(_))
+        ));
+        let t = table(OutOfDomain::Extrapolate);
+        let json = serde_json::to_string(&t).unwrap();
+        assert_eq!(
+            serde_json::from_str::<LookupTable<Volt, Celsius>>(&json).unwrap(),
+            t
+        );
+        let unsorted = json.replace("1.0,3.0", "3.0,1.0");
+        assert_ne!(unsorted, json);
+        assert!(serde_json::from_str::<LookupTable<Volt, Celsius>>(&unsorted).is_err());
+    }
+}
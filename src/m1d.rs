@@ -1,10 +1,34 @@
-use crate::{prefix::Prefix, prelude::Measurement, uom::Uom};
-use ndarray::Array1;
+#[cfg(not(feature = "rayon"))]
+use crate::par::{ParMapvInplace, ParZip2};
+use crate::{
+    error::MeasurementError,
+    m2d::{lane_percentile, par_lane_sums},
+    math::{canonical_base, compensated_sum, powi10, rescale_decimal},
+    measurement::{
+        Measurement, RoundMode, harmonized_prefix, impl_left_scalar_mul, is_lossy, same_quantity,
+    },
+    percentage::Percentage,
+    prefix::{NicePolicy, Prefix, finest},
+    quantity::Quantity,
+    ranged_measurement::RangedMeasurement,
+    uom::{Dimensionless, Second, Uom, UomDiv, UomMul},
+};
+use ndarray::{Array1, ArrayView1, Axis, ErrorKind, ShapeError, Zip, s};
 use serde::{Deserialize, Serialize};
-use std::marker::PhantomData;
+use std::{
+    cmp::Ordering,
+    fmt,
+    hash::{DefaultHasher, Hash, Hasher},
+    marker::PhantomData,
+    ops::{Div, Mul, Range},
+};
 
 /// A one-dimensional array of measurements with a unit and SI prefix.
 ///
+/// Two arrays are equal if they have the same shape and every pair of elements is equal as
+/// [`Measurement`]s are, in the base unit, so equal arrays have the same
+/// [`M1d::content_hash`].
+///
 /// # Type Parameters
 /// - `U`: The unit of measurement, implementing the [`Uom`] trait.
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -29,11 +53,50 @@ impl<U: Uom> M1d<U> {
         }
     }
 
+    /// Creates a new [`M1d`] from individual measurements, converting each one to `prefix`.
+    ///
+    /// # Arguments
+    /// * `measurements` - The measurements, possibly with differing prefixes.
+    /// * `prefix` - The SI prefix for the array.
+    pub fn from_measurements(measurements: &[Measurement<U>], prefix: Prefix) -> Self {
+        let values = measurements
+            .iter()
+            .map(|m| m.convert_to(prefix).value())
+            .collect::<Vec<f64>>();
+        Self::new(values, prefix)
+    }
+
+    /// Creates a new [`M1d`] from individual measurements, in the prefix [`harmonize`] picks
+    /// for them.
+    ///
+    /// [`harmonize`]: crate::measurement::harmonize
+    pub fn from_harmonized(measurements: &[Measurement<U>]) -> Self {
+        Self::from_measurements(measurements, harmonized_prefix(measurements))
+    }
+
     /// Returns a clone of the underlying values array.
     pub fn values(&self) -> Array1<f64> {
         self.values.clone()
     }
 
+    /// Returns the values, in the array's prefix, as a [`Vec<f64>`].
+    pub fn to_vec(&self) -> Vec<f64> {
+        self.values.to_vec()
+    }
+
+    /// Returns every element as a [`Measurement<U>`] in the array's prefix.
+    pub fn to_measurements(&self) -> Vec<Measurement<U>> {
+        self.values
+            .iter()
+            .map(|v| Measurement::new(*v, self.prefix))
+            .collect()
+    }
+
+    /// Returns a read-only view of the underlying values.
+    pub(crate) fn view(&self) -> ArrayView1<'_, f64> {
+        self.values.view()
+    }
+
     /// Returns the SI prefix associated with this array.
     pub fn prefix(&self) -> Prefix {
         self.prefix
@@ -46,7 +109,7 @@ impl<U: Uom> M1d<U> {
     ///
     /// # Returns
     /// A new [`M1d`] with values converted to the target prefix.
-    fn convert_to(self, pfx: Prefix) -> Self {
+    pub(crate) fn convert_to(self, pfx: Prefix) -> Self {
         let conversion_factor = self.prefix.get_conversion_factor(pfx);
         if conversion_factor == 1.0 {
             self.clone()
@@ -61,43 +124,672 @@ impl<U: Uom> M1d<U> {
         }
     }
 
+    /// Converts the array like [`Quantity::convert_to`] and counts the values the conversion
+    /// lost information on.
+    ///
+    /// # Returns
+    /// The converted array, and the number of values that do not reproduce their original
+    /// bits when converted back, as in [`Measurement::convert_to_checked`].
+    pub fn convert_to_checked(&self, pfx: Prefix) -> (Self, usize) {
+        let converted = self.clone().convert_to(pfx);
+        let back = pfx.get_conversion_factor(self.prefix);
+        let lossy = Zip::from(&self.values)
+            .and(&converted.values)
+            .fold(0, |n, &v, &c| n + usize::from(is_lossy(v, c * back)));
+        (converted, lossy)
+    }
+
+    /// Returns the label of the mean value (e.g., "2.5mV"), or of zero if the array is empty.
+    pub fn label(&self) -> String {
+        self.mean()
+            .unwrap_or(Measurement::new(0, self.prefix))
+            .label()
+    }
+
+    /// Returns a [`Display`](fmt::Display) preview like the array's own, showing `edge_items`
+    /// values at each end of a long array instead of three.
+    ///
+    /// A precision still applies to every value: `format!("{:.1}", a.preview(2))` gives
+    /// `M1d<mV> (1000) [0.0, 1.0, ..., 998.0, 999.0]`.
+    pub fn preview(&self, edge_items: usize) -> Preview<'_, Self> {
+        Preview {
+            array: self,
+            edge_items,
+        }
+    }
+
+    /// Converts the array to a readable prefix chosen from its data.
+    ///
+    /// Equivalent to [`M1d::nice_with`] with the default [`NicePolicy`].
+    pub fn nice(self) -> Self {
+        self.nice_with(&NicePolicy::new())
+    }
+
+    /// Converts the array to the prefix `policy` chooses for the 95th percentile of `|x|`,
+    /// ignoring NaN, so a few outliers do not decide the prefix.
+    ///
+    /// Values are rescaled as in [`Measurement::nice_with`], as the decimals they stand for.
+    ///
+    /// # Returns
+    /// The converted array; empty and all-NaN arrays are returned unchanged.
+    pub fn nice_with(self, policy: &NicePolicy) -> Self {
+        match robust_magnitude(self.values.iter()) {
+            Some(m) => {
+                let prefix = policy.choose(m, self.prefix);
+                let shift = self.prefix.get_exp_value() - prefix.get_exp_value();
+                let mut values = self.values;
+                values.par_mapv_inplace(|x| rescale_decimal(x, shift));
+                M1d::new(values, prefix)
+            }
+            None => self,
+        }
+    }
+
     /// Returns the mean value of all elements as a [`Measurement<U>`].
     ///
+    /// Uses compensated summation, see [`M1d::sum`].
+    ///
     /// # Returns
     /// An `Option<Measurement<U>>` containing the mean, or `None` if the array is empty.
     pub fn mean(&self) -> Option<Measurement<U>> {
-        Some(Measurement::new(self.values.mean()?, self.prefix))
+        let sum = self.sum()?;
+        Some(sum / self.len() as f64)
     }
-    
-    /// Returns the len of the embedded array.
+
+    /// Returns the sum of all elements as a [`Measurement<U>`].
+    ///
+    /// The sum is compensated, so small contributions are not lost against a large running
+    /// total (e.g. mV-scale noise on a 2 V signal over many millions of samples).
+    ///
+    /// # Returns
+    /// An `Option<Measurement<U>>` containing the sum, or `None` if the array is empty.
+    pub fn sum(&self) -> Option<Measurement<U>> {
+        (!self.is_empty())
+            .then(|| Measurement::new(compensated_sum(self.values.iter().copied()), self.prefix))
+    }
+
+    /// Returns the sum of all elements like [`M1d::sum`], on several threads with the `rayon`
+    /// feature.
+    ///
+    /// The array is split into fixed chunks whose compensated sums are combined by a pairwise
+    /// tree, as in [`M2d::par_mean_axis`](crate::m2d::M2d::par_mean_axis), so the result is
+    /// bit-identical across runs and thread counts, though it may differ from
+    /// [`M1d::sum`] in the last bits.
+    ///
+    /// # Returns
+    /// The sum, or `None` if the array is empty.
+    pub fn par_sum(&self) -> Option<Measurement<U>> {
+        (!self.is_empty()).then(|| {
+            let row = self.values.view().insert_axis(Axis(0));
+            Measurement::new(par_lane_sums(row, Axis(1), |_, x| x)[0], self.prefix)
+        })
+    }
+
+    /// Returns the number of elements, matching [`M2d::len`](crate::m2d::M2d::len).
     ///
     /// # Returns
     /// An `usize` as the len.
     pub fn len(&self) -> usize {
         self.values.len()
     }
-    
-    /// Return whether the array has any elements
+
+    /// Returns `true` if the array has no elements, i.e. `len() == 0`.
     pub fn is_empty(&self) -> bool {
         self.values.is_empty()
     }
+
+    /// Compares two arrays element-wise with a relative tolerance, converting prefixes if necessary.
+    ///
+    /// Values closer than `f64::EPSILON` in absolute terms are always considered equal,
+    /// so elements near zero do not need an exact match.
+    ///
+    /// # Arguments
+    /// * `other` - The array to compare with.
+    /// * `max_relative` - The largest allowed difference relative to the larger magnitude.
+    ///
+    /// # Returns
+    /// `true` if the arrays have the same length and every pair of elements is within tolerance.
+    pub fn approx_eq(&self, other: &M1d<U>, max_relative: f64) -> bool {
+        let factor = self.prefix.get_conversion_factor(other.prefix);
+        self.len() == other.len()
+            && self
+                .values
+                .iter()
+                .zip(other.values.iter())
+                .all(|(a, b)| approx_eq_values(a * factor, *b, max_relative))
+    }
+
+    /// Returns a hash of the unit, length and values, for use as a cache key.
+    ///
+    /// Values are hashed in the base unit, so the same data under different prefixes hashes
+    /// identically (e.g. 1.5 mV and 1500 µV) as long as the prefix conversion was exact.
+    /// `-0.0` hashes as `0.0` and every NaN hashes alike, regardless of sign or payload.
+    /// The hash is stable within one build; do not persist it across Rust versions.
+    pub fn content_hash(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        U::uom_str().hash(&mut hasher);
+        self.len().hash(&mut hasher);
+        for &v in &self.values {
+            hasher.write_u64(canonical_base_bits(v, self.prefix));
+        }
+        hasher.finish()
+    }
+
+    /// Multiplies two arrays of different units element-wise (e.g. W·s = J).
+    ///
+    /// The prefixes are combined, and any exponent not representable by a [`Prefix`]
+    /// is folded into the values.
+    ///
+    /// # Returns
+    /// A new [`M1d`] in the product unit, or a [`ShapeError`] if the lengths differ.
+    pub fn hadamard_mul<B: Uom>(
+        &self,
+        other: &M1d<B>,
+    ) -> Result<M1d<<U as UomMul<B>>::Output>, ShapeError>
+    where
+        U: UomMul<B>,
+    {
+        let exp = self.prefix.get_exp_value() + other.prefix.get_exp_value();
+        self.combine_units(other, exp, |a, b| a * b)
+    }
+
+    /// Divides two arrays of different units element-wise (e.g. V/A = Ω).
+    ///
+    /// The prefixes are combined, and any exponent not representable by a [`Prefix`]
+    /// is folded into the values.
+    ///
+    /// # Returns
+    /// A new [`M1d`] in the quotient unit, or a [`ShapeError`] if the lengths differ.
+    pub fn hadamard_div<B: Uom>(
+        &self,
+        other: &M1d<B>,
+    ) -> Result<M1d<<U as UomDiv<B>>::Output>, ShapeError>
+    where
+        U: UomDiv<B>,
+    {
+        let exp = self.prefix.get_exp_value() - other.prefix.get_exp_value();
+        self.combine_units(other, exp, |a, b| a / b)
+    }
+
+    fn combine_units<B: Uom, O: Uom>(
+        &self,
+        other: &M1d<B>,
+        exp: i16,
+        f: impl Fn(f64, f64) -> f64 + Sync + Send,
+    ) -> Result<M1d<O>, ShapeError> {
+        if self.len() != other.len() {
+            return Err(ShapeError::from_kind(ErrorKind::IncompatibleShape));
+        }
+        let (prefix, remainder) = Prefix::from_exp_value(exp);
+        let scale = powi10(remainder as i32);
+        let values = Zip::from(&self.values)
+            .and(&other.values)
+            .par_map_collect(|&a, &b| f(a, b) * scale);
+        Ok(M1d::new(values, prefix))
+    }
+
+    /// Subtracts the mean of the samples in `window` from every element.
+    ///
+    /// # Arguments
+    /// * `window` - The range of indices used to compute the baseline.
+    ///
+    /// # Returns
+    /// A new [`M1d`] with the same prefix and a baseline mean of zero.
+    ///
+    /// # Panics
+    /// Panics if `window` is empty or out of bounds.
+    pub fn baseline_correct(&self, window: Range<usize>) -> M1d<U> {
+        let window = self.values.slice(s![window]);
+        assert!(!window.is_empty(), "baseline window must not be empty");
+        let baseline = compensated_sum(window.iter().copied()) / window.len() as f64;
+        M1d::new(&self.values - baseline, self.prefix)
+    }
+
+    /// Returns, for every sample, the distance to the nearest bound of `range`.
+    ///
+    /// The range is converted to this array's prefix once.
+    ///
+    /// # Returns
+    /// A new [`M1d`] in this array's prefix, positive inside the range, zero on a bound and
+    /// negative outside it. NaN samples have a NaN margin.
+    pub fn margin_to(&self, range: &RangedMeasurement<U>) -> M1d<U> {
+        let (min, max) = self.range_bounds(range);
+        M1d::new(self.values.mapv(|x| (x - min).min(max - x)), self.prefix)
+    }
+
+    /// Returns the worst-case (smallest) margin to `range` over all samples.
+    ///
+    /// # Returns
+    /// The smallest margin in this array's prefix; NaN if any sample is NaN and `+∞` if the
+    /// array is empty.
+    pub fn min_margin(&self, range: &RangedMeasurement<U>) -> Measurement<U> {
+        let (min, max) = self.range_bounds(range);
+        Measurement::new(self.worst_margin(min, max), self.prefix)
+    }
+
+    /// Returns the worst-case margin to `range` as a fraction of its half-span.
+    ///
+    /// # Returns
+    /// `1.0` when every sample sits at the centre of the range (or the array is empty) and
+    /// `0.0` when any sample lies on or beyond a bound, is NaN, or the range has no span.
+    pub fn headroom_fraction(&self, range: &RangedMeasurement<U>) -> Percentage {
+        let (min, max) = self.range_bounds(range);
+        let half_span = (max - min) / 2.0;
+        let worst = self.worst_margin(min, max);
+        let fraction = if half_span > 0.0 && !worst.is_nan() {
+            (worst / half_span).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+        Percentage::new_const(fraction)
+    }
+
+    /// Returns the number of samples strictly above `threshold`, comparing in this array's
+    /// prefix. NaN samples are not counted.
+    pub fn count_above(&self, threshold: Measurement<U>) -> usize {
+        let t = threshold.convert_to(self.prefix).value();
+        self.values.iter().filter(|&&x| x > t).count()
+    }
+
+    /// Returns the number of samples strictly below `threshold`, comparing in this array's
+    /// prefix. NaN samples are not counted.
+    pub fn count_below(&self, threshold: Measurement<U>) -> usize {
+        let t = threshold.convert_to(self.prefix).value();
+        self.values.iter().filter(|&&x| x < t).count()
+    }
+
+    /// Encodes every sample as a signed count of `lsb`, as [`Measurement::to_fixed`] does,
+    /// for uploading a waveform to a device.
+    ///
+    /// # Arguments
+    /// * `lsb` - The value of one count; its sign is ignored.
+    /// * `bits` - The width of the signed integer, from 1 to 32.
+    /// * `mode` - The rounding direction for samples between two counts.
+    ///
+    /// # Returns
+    /// The counts; [`MeasurementError::OutOfRange`] for the first sample that does not fit in
+    /// `bits` bits, or [`MeasurementError::InvalidOptions`] if `bits` is not in `1..=32`.
+    ///
+    /// # Panics
+    /// Panics if `lsb` is zero or not finite.
+    pub fn to_fixed_vec(
+        &self,
+        lsb: Measurement<U>,
+        bits: u32,
+        mode: RoundMode,
+    ) -> Result<Vec<i32>, MeasurementError> {
+        if !(1..=32).contains(&bits) {
+            return Err(MeasurementError::InvalidOptions(
+                "bit width must be between 1 and 32",
+            ));
+        }
+        self.values
+            .iter()
+            .map(|&x| {
+                let count = Measurement::<U>::new(x, self.prefix).to_fixed(lsb, bits, mode)?;
+                Ok(count as i32)
+            })
+            .collect()
+    }
+
+    /// Returns the bounds of `range` as raw values in this array's prefix, converting once.
+    fn range_bounds(&self, range: &RangedMeasurement<U>) -> (f64, f64) {
+        let range = range.convert_to(self.prefix);
+        (range.min().value(), range.max().value())
+    }
+
+    /// Returns the smallest margin to the raw bounds `min` and `max`, in this array's prefix;
+    /// NaN if any sample is NaN and `+∞` if the array is empty.
+    fn worst_margin(&self, min: f64, max: f64) -> f64 {
+        self.values.iter().fold(f64::INFINITY, |acc, &x| {
+            let margin = (x - min).min(max - x);
+            if acc.is_nan() || margin.is_nan() {
+                f64::NAN
+            } else {
+                acc.min(margin)
+            }
+        })
+    }
+}
+
+impl M1d<Dimensionless> {
+    /// Converts each ratio into a [`Percentage`], applying the prefix first.
+    ///
+    /// # Returns
+    /// The percentages, or `None` if any value lies outside `0.0..=1.0` or is NaN.
+    pub fn to_percentage_array(&self) -> Option<Vec<Percentage>> {
+        let factor = self.prefix.get_conversion_factor(Prefix::None);
+        self.values
+            .iter()
+            .map(|&v| {
+                let v = v * factor;
+                (0.0..=1.0).contains(&v).then(|| Percentage::new_const(v))
+            })
+            .collect()
+    }
+}
+
+impl M1d<Second> {
+    /// Creates the time axis `0, dt, 2·dt, …` of `n` samples taken every `dt`.
+    ///
+    /// Every time is computed as `i * dt` rather than accumulated, so late samples do not
+    /// drift; the last one equals [`Measurement::from_samples`]`(n - 1, dt)`.
+    pub fn sample_times(n: usize, dt: Measurement<Second>) -> Self {
+        let dt_value = dt.value();
+        M1d::new(
+            Array1::from_shape_fn(n, |i| i as f64 * dt_value),
+            dt.prefix(),
+        )
+    }
+}
+
+impl<U: Uom> Mul<f64> for M1d<U> {
+    /// Multiplies every element by a scalar, reusing the array's allocation.
+    type Output = M1d<U>;
+    fn mul(mut self, rhs: f64) -> Self::Output {
+        self.values.par_mapv_inplace(|x| x * rhs);
+        self
+    }
+}
+
+impl<U: Uom> Mul<f64> for &M1d<U> {
+    /// Multiplies every element by a scalar.
+    type Output = M1d<U>;
+    fn mul(self, rhs: f64) -> Self::Output {
+        self.clone() * rhs
+    }
+}
+
+// `scalar * array` multiplies every element, reusing the array's allocation when owned.
+impl_left_scalar_mul!(M1d: f64, f32, i8, i16, i32, u8, u16, u32);
+impl_left_scalar_mul!(&M1d: f64, f32, i8, i16, i32, u8, u16, u32);
+
+impl<A: UomMul<B>, B: Uom> Mul<&M1d<B>> for &M1d<A> {
+    /// Multiplies two [`M1d`] arrays of different units element-wise.
+    ///
+    /// # Panics
+    /// Panics if the lengths differ; use [`M1d::hadamard_mul`] for a fallible version.
+    type Output = M1d<A::Output>;
+    fn mul(self, rhs: &M1d<B>) -> Self::Output {
+        self.hadamard_mul(rhs).unwrap_or_else(|_| {
+            panic!(
+                "cannot multiply M1d arrays of lengths {} and {}",
+                self.len(),
+                rhs.len()
+            )
+        })
+    }
+}
+
+impl<A: UomDiv<B>, B: Uom> Div<&M1d<B>> for &M1d<A> {
+    /// Divides two [`M1d`] arrays of different units element-wise.
+    ///
+    /// # Panics
+    /// Panics if the lengths differ; use [`M1d::hadamard_div`] for a fallible version.
+    type Output = M1d<A::Output>;
+    fn div(self, rhs: &M1d<B>) -> Self::Output {
+        self.hadamard_div(rhs).unwrap_or_else(|_| {
+            panic!(
+                "cannot divide M1d arrays of lengths {} and {}",
+                self.len(),
+                rhs.len()
+            )
+        })
+    }
+}
+
+/// Returns `true` if `a` and `b` differ by at most `f64::EPSILON` or by at most
+/// `max_relative` times the larger of their magnitudes.
+pub(crate) fn approx_eq_values(a: f64, b: f64, max_relative: f64) -> bool {
+    if a == b {
+        return true;
+    }
+    let diff = (a - b).abs();
+    diff <= f64::EPSILON || diff <= max_relative * a.abs().max(b.abs())
+}
+
+/// Returns the bits of `value` converted to the base unit, with `-0.0` mapped to `0.0` and
+/// every NaN mapped to one canonical NaN, see [`canonical_base`].
+pub(crate) fn canonical_base_bits(value: f64, prefix: Prefix) -> u64 {
+    canonical_base(value, prefix.get_exp_value() as i32).to_bits()
+}
+
+/// Number of leading and trailing elements shown per axis when a preview is truncated.
+pub(crate) const PREVIEW_EDGE_ITEMS: usize = 3;
+
+/// Formats an [`M1d`] or [`M2d`](crate::m2d::M2d) like its `Display` impl, but with a chosen
+/// number of leading and trailing items per axis; see [`M1d::preview`].
+#[derive(Clone, Copy, Debug)]
+pub struct Preview<'a, T> {
+    pub(crate) array: &'a T,
+    pub(crate) edge_items: usize,
+}
+
+/// Writes `values` as `[a, b, c]`, eliding the middle with `...` when longer than twice
+/// `edge`, and honoring the formatter's precision.
+pub(crate) fn write_preview(
+    f: &mut fmt::Formatter<'_>,
+    values: ArrayView1<f64>,
+    edge: usize,
+) -> fmt::Result {
+    let len = values.len();
+    let truncated = len > 2 * edge;
+    write!(f, "[")?;
+    for (i, v) in values.iter().enumerate() {
+        if truncated && (edge..len - edge).contains(&i) {
+            if i == edge {
+                write!(f, "{}...", if i > 0 { ", " } else { "" })?;
+            }
+            continue;
+        }
+        if i > 0 {
+            write!(f, ", ")?;
+        }
+        match f.precision() {
+            Some(p) => write!(f, "{v:.p$}")?,
+            None => write!(f, "{v}")?,
+        }
+    }
+    write!(f, "]")
+}
+
+impl<U: Uom> Quantity<U> for M1d<U> {
+    fn prefix(&self) -> Prefix {
+        self.prefix
+    }
+
+    fn convert_to(self, pfx: Prefix) -> Self {
+        M1d::convert_to(self, pfx)
+    }
+
+    fn label(&self) -> String {
+        M1d::label(self)
+    }
+
+    fn scale(&mut self, f: f64) {
+        self.values.par_mapv_inplace(|x| x * f);
+    }
+}
+
+impl<U: Uom> fmt::Display for M1d<U> {
+    /// Formats the array as its unit, length and a preview of the values,
+    /// e.g. `M1d<mV> (4) [1, 2, 3, 4]`.
+    ///
+    /// A precision applies to every value. Long arrays show three values at each end; use
+    /// [`M1d::preview`] to choose another count.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.preview(PREVIEW_EDGE_ITEMS).fmt(f)
+    }
+}
+
+impl<U: Uom> fmt::Display for Preview<'_, M1d<U>> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "M1d<{}{}> ({}) ",
+            self.array.prefix.get_label(),
+            U::uom_str(),
+            self.array.len()
+        )?;
+        write_preview(f, self.array.view(), self.edge_items)
+    }
 }
 
 impl<U: Uom> PartialEq for M1d<U> {
-    /// Compares two [`M1d`] arrays for equality, converting prefixes if necessary.
+    /// Compares two [`M1d`] arrays element-wise by their values in the base unit.
     fn eq(&self, other: &Self) -> bool {
-        if self.prefix != other.prefix {
-            self.clone().convert_to(other.prefix()).values == other.values
-        } else {
-            self.values == other.values
+        let (pa, pb) = (self.prefix, other.prefix);
+        self.values.shape() == other.values.shape()
+            && Zip::from(&self.values)
+                .and(&other.values)
+                .all(|&a, &b| same_quantity(a, pa, b, pb))
+    }
+}
+
+impl<U: Uom> From<M1d<U>> for Vec<f64> {
+    /// Moves the values out in the array's prefix, reusing the allocation when the
+    /// elements are stored contiguously and in order.
+    fn from(m: M1d<U>) -> Self {
+        let len = m.len();
+        if !m.values.is_standard_layout() {
+            return m.values.to_vec();
         }
+        let (mut values, offset) = m.values.into_raw_vec_and_offset();
+        values.drain(..offset.unwrap_or(0));
+        values.truncate(len);
+        values
+    }
+}
+
+impl<U: Uom> TryFrom<Vec<Measurement<U>>> for M1d<U> {
+    type Error = MeasurementError;
+
+    /// Collects measurements into an array in the finest of their prefixes.
+    ///
+    /// # Returns
+    /// The array, or [`MeasurementError::EmptyInput`] if the vector is empty, since no prefix
+    /// can be inferred; use [`M1d::from_measurements`] to supply one.
+    fn try_from(measurements: Vec<Measurement<U>>) -> Result<Self, Self::Error> {
+        let prefix = finest(measurements.iter().map(Measurement::prefix)).ok_or(
+            MeasurementError::EmptyInput("cannot infer a prefix from an empty vector"),
+        )?;
+        Ok(Self::from_measurements(&measurements, prefix))
+    }
+}
+
+/// The 95th percentile of `|x|` over the non-NaN `values`, used to pick a readable prefix.
+pub(crate) fn robust_magnitude<'a>(values: impl Iterator<Item = &'a f64>) -> Option<f64> {
+    let magnitudes = values
+        .filter(|v| !v.is_nan())
+        .map(|v| v.abs())
+        .collect::<Array1<f64>>();
+    (!magnitudes.is_empty()).then(|| lane_percentile(magnitudes.view(), 95.0, false))
+}
+
+/// Checks that `keys` pairs with `len` values, has at least two entries and is strictly
+/// increasing, as breakpoint tables and timestamped series require.
+///
+/// # Returns
+/// [`MeasurementError::ShapeMismatch`] if the lengths differ, [`MeasurementError::EmptyInput`]
+/// with `too_short` if there are fewer than two keys, or [`MeasurementError::NotIncreasing`]
+/// at the first key that is not above the previous one (including NaN).
+pub(crate) fn check_increasing_keys(
+    keys: ArrayView1<f64>,
+    len: usize,
+    too_short: &'static str,
+) -> Result<(), MeasurementError> {
+    if keys.len() != len {
+        return Err(ShapeError::from_kind(ErrorKind::IncompatibleShape).into());
+    }
+    if len < 2 {
+        return Err(MeasurementError::EmptyInput(too_short));
+    }
+    let increasing = |i: usize| keys[i].partial_cmp(&keys[i - 1]) == Some(Ordering::Greater);
+    match (1..len).find(|&i| !increasing(i)) {
+        Some(i) => Err(MeasurementError::NotIncreasing { index: i }),
+        None => Ok(()),
     }
 }
 
 #[cfg(test)]
 mod m1d_tests {
     use super::*;
-    use crate::uom::Volt;
+    use crate::uom::{Ampere, Dimensionless, Joule, Second, Volt, Watt};
+    use ndarray::array;
+
+    #[test]
+    fn builds_from_harmonized_measurements() {
+        let m = M1d::from_harmonized(&[
+            Measurement::<Volt>::new(20, Prefix::Milli),
+            Measurement::new(1_500_000, Prefix::Micro),
+            Measurement::new(3, Prefix::Kilo),
+        ]);
+        assert_eq!(m.prefix(), Prefix::None);
+        assert_eq!(m.values(), array![0.02, 1.5, 3000.0]);
+        assert!(M1d::<Volt>::from_harmonized(&[]).is_empty());
+    }
+
+    #[test]
+    fn counts_lossy_conversions() {
+        let trace = M1d::<Ampere>::new(vec![1.0, 0.9, 3.0, 1.8, f64::NAN], Prefix::Femto);
+        let (converted, lossy) = trace.convert_to_checked(Prefix::Tera);
+        assert_eq!(converted.prefix(), Prefix::Tera);
+        assert_eq!(lossy, 2);
+        assert_eq!(trace.convert_to_checked(Prefix::Femto).1, 0);
+        let exact = M1d::<Volt>::new(vec![1.5, -2.25, 0.0], Prefix::Milli);
+        assert_eq!(exact.convert_to_checked(Prefix::Micro).1, 0);
+    }
+
+    #[test]
+    fn counts_against_a_base_threshold() {
+        let trace = M1d::<Volt>::new(vec![-80.0, 5.0, 1000.0, 1500.0, f64::NAN], Prefix::Milli);
+        let threshold = Measurement::base(1.0);
+        assert_eq!(trace.count_above(threshold), 1);
+        assert_eq!(trace.count_below(threshold), 2);
+        // a sample on the threshold is neither above nor below it
+        assert_eq!(trace.count_above(Measurement::new(5.0, Prefix::Milli)), 2);
+        assert_eq!(
+            trace.count_below(Measurement::new(5000.0, Prefix::Micro)),
+            1
+        );
+        assert_eq!(
+            M1d::<Volt>::new(vec![], Prefix::None).count_above(threshold),
+            0
+        );
+    }
+
+    #[test]
+    fn encodes_waveforms_as_fixed_point() {
+        let lsb = Measurement::new(0.125, Prefix::Milli);
+        let wave = M1d::<Volt>::new(vec![0.0, 1.0, -0.5, 0.00006, -0.00006], Prefix::None);
+        assert_eq!(
+            wave.to_fixed_vec(lsb, 32, RoundMode::Nearest),
+            Ok(vec![0, 8000, -4000, 0, 0])
+        );
+        assert_eq!(
+            wave.to_fixed_vec(lsb, 32, RoundMode::Ceil),
+            Ok(vec![0, 8000, -4000, 1, 0])
+        );
+        // ±0.5V needs ±4000 counts, beyond the 12-bit range -2048..=2047
+        assert!(matches!(
+            wave.to_fixed_vec(lsb, 12, RoundMode::Nearest),
+            Err(MeasurementError::OutOfRange {
+                value: 8000.0,
+                min: -2048.0,
+                max: 2047.0
+            })
+        ));
+        assert!(matches!(
+            wave.to_fixed_vec(lsb, 33, RoundMode::Nearest),
+            Err(MeasurementError::InvalidOptions(_))
+        ));
+        let extremes = M1d::<Volt>::new(vec![-256.0, 255.96875], Prefix::None);
+        let lsb = Measurement::new(0.03125, Prefix::None);
+        assert_eq!(
+            extremes.to_fixed_vec(lsb, 14, RoundMode::Nearest),
+            Ok(vec![-8192, 8191])
+        );
+    }
 
     #[test]
     fn get_values() {
@@ -105,6 +797,101 @@ mod m1d_tests {
         assert_eq!(m1d.values(), Array1::from(vec![1.0, 2.0, 3.0]));
     }
 
+    #[test]
+    fn measurement_vec_round_trip() {
+        let measurements = vec![
+            Measurement::<Volt>::new(1.0, Prefix::Milli),
+            Measurement::new(500.0, Prefix::Micro),
+            Measurement::new(2.0, Prefix::None),
+        ];
+        let m1d = M1d::try_from(measurements.clone()).unwrap();
+        assert_eq!(m1d.prefix(), Prefix::Micro);
+        assert_eq!(m1d.to_vec(), vec![1000.0, 500.0, 2_000_000.0]);
+        assert_eq!(m1d.to_measurements(), measurements);
+        let back = M1d::try_from(m1d.to_measurements()).unwrap();
+        assert_eq!(back.prefix(), Prefix::Micro);
+        assert_eq!(Vec::from(back), vec![1000.0, 500.0, 2_000_000.0]);
+
+        let m1d = M1d::from_measurements(&measurements, Prefix::Milli);
+        assert_eq!(m1d.to_vec(), vec![1.0, 0.5, 2000.0]);
+        assert_eq!(
+            M1d::<Volt>::try_from(Vec::new()),
+            Err(MeasurementError::EmptyInput(
+                "cannot infer a prefix from an empty vector"
+            ))
+        );
+        assert!(M1d::<Volt>::from_measurements(&[], Prefix::Milli).is_empty());
+    }
+
+    #[test]
+    fn into_vec_respects_layout() {
+        let values = Array1::from(vec![1.0, 2.0, 3.0, 4.0]);
+        let m1d = M1d::<Volt>::new(values.clone().slice_move(s![1..3]), Prefix::None);
+        assert_eq!(Vec::from(m1d), vec![2.0, 3.0]);
+        let m1d = M1d::<Volt>::new(values.clone().slice_move(s![..;-1]), Prefix::None);
+        assert_eq!(Vec::from(m1d), vec![4.0, 3.0, 2.0, 1.0]);
+        let m1d = M1d::<Volt>::new(values.slice_move(s![..0]), Prefix::None);
+        assert_eq!(Vec::from(m1d), Vec::<f64>::new());
+    }
+
+    #[test]
+    fn sample_times() {
+        let dt = Measurement::<Second>::new(0.1, Prefix::Milli);
+        let t = M1d::sample_times(1_000_001, dt);
+        assert_eq!(t.prefix(), Prefix::Milli);
+        assert_eq!(t.view()[3], 3.0 * 0.1);
+        assert_eq!(
+            Measurement::new(t.view()[1_000_000], t.prefix()),
+            Measurement::<Second>::from_samples(1_000_000, dt)
+        );
+        assert!(M1d::sample_times(0, dt).is_empty());
+    }
+
+    #[test]
+    fn margins_to_range() {
+        let range = RangedMeasurement::<Volt>::new(-1.0, 1.0, 0.1, Prefix::None);
+        let trace = M1d::<Volt>::new(
+            vec![0.0, 500.0, 1000.0, 1200.0, 1500.0, -1100.0, -900.0],
+            Prefix::Milli,
+        );
+        let margins = trace.margin_to(&range);
+        assert_eq!(margins.prefix(), Prefix::Milli);
+        assert_eq!(
+            margins.values(),
+            array![1000.0, 500.0, 0.0, -200.0, -500.0, -100.0, 100.0]
+        );
+        assert_eq!(margins.view().iter().filter(|&&m| m < 0.0).count(), 3);
+        assert_eq!(
+            trace.min_margin(&range),
+            Measurement::new(-500.0, Prefix::Milli)
+        );
+        assert_eq!(trace.headroom_fraction(&range).get_value(), 0.0);
+
+        let inside = M1d::<Volt>::new(vec![0.25, -0.5, 0.0], Prefix::None);
+        assert_eq!(
+            inside.min_margin(&range),
+            Measurement::new(0.5, Prefix::None)
+        );
+        assert_eq!(inside.headroom_fraction(&range).get_value(), 0.5);
+    }
+
+    #[test]
+    fn margins_edge_cases() {
+        let range = RangedMeasurement::<Volt>::new(0.0, 10.0, 1.0, Prefix::Milli);
+        let empty = M1d::<Volt>::new(Vec::new(), Prefix::None);
+        assert_eq!(empty.min_margin(&range).value(), f64::INFINITY);
+        assert_eq!(empty.headroom_fraction(&range).get_value(), 1.0);
+
+        let with_nan = M1d::<Volt>::new(vec![5.0, f64::NAN], Prefix::Milli);
+        assert!(with_nan.margin_to(&range).view()[1].is_nan());
+        assert!(with_nan.min_margin(&range).value().is_nan());
+        assert_eq!(with_nan.headroom_fraction(&range).get_value(), 0.0);
+
+        let point = RangedMeasurement::<Volt>::new(5.0, 5.0, 1.0, Prefix::Milli);
+        let centred = M1d::<Volt>::new(vec![5.0], Prefix::Milli);
+        assert_eq!(centred.headroom_fraction(&point).get_value(), 0.0);
+    }
+
     #[test]
     fn convert_to() {
         let m1d = M1d::<Volt>::new(vec![1.0, 2.0, 3.0], Prefix::Milli);
@@ -128,4 +915,151 @@ mod m1d_tests {
         let m2 = m1d1.clone();
         assert_eq!(m1d1, m2);
     }
+
+    #[test]
+    fn approx_eq() {
+        // 1.005 mV becomes 1004.9999999999999 uV after conversion
+        let a = M1d::<Volt>::new(vec![1.005, 0.0, -2.0], Prefix::Milli);
+        let b = M1d::<Volt>::new(vec![1005.0, 1e-20, -2000.0], Prefix::Micro);
+        assert_ne!(a, b);
+        assert!(a.approx_eq(&b, 1e-12));
+        assert!(b.approx_eq(&a, 1e-12));
+        assert!(!a.approx_eq(&M1d::new(vec![1.005, 0.0, -2.1], Prefix::Milli), 1e-12));
+        assert!(!a.approx_eq(&M1d::new(vec![1.005, 0.0], Prefix::Milli), 1e-12));
+        assert!(
+            !M1d::<Volt>::new(vec![f64::NAN], Prefix::None)
+                .approx_eq(&M1d::new(vec![f64::NAN], Prefix::None), 1e-12)
+        );
+    }
+
+    #[test]
+    fn accumulate_energy() {
+        let power = M1d::<Watt>::new(vec![10.0, 20.0, 30.0], Prefix::Milli);
+        let durations = M1d::<Second>::new(vec![1.0, 2.0, 1.0], Prefix::Milli);
+        let energy: M1d<Joule> = &power * &durations;
+        assert_eq!(energy, M1d::new(vec![10.0, 40.0, 30.0], Prefix::Micro));
+        assert_eq!(energy.sum(), Some(Measurement::new(80.0, Prefix::Micro)));
+        assert_eq!(&energy / &durations, power);
+        assert!(
+            power
+                .hadamard_mul(&M1d::<Second>::new(vec![1.0], Prefix::None))
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn same_unit_ratio() {
+        let a = M1d::<Volt>::new(vec![1.0, 3.0], Prefix::Milli);
+        let b = M1d::<Volt>::new(vec![4.0, 4.0], Prefix::Milli);
+        let ratio: M1d<Dimensionless> = &a / &b;
+        assert_eq!(ratio, M1d::new(vec![0.25, 0.75], Prefix::None));
+        assert_eq!(
+            ratio.to_percentage_array(),
+            Some(vec![
+                Percentage::new_const(0.25),
+                Percentage::new_const(0.75)
+            ])
+        );
+        let gains = M1d::<Dimensionless>::new(vec![500.0, 1500.0], Prefix::Milli);
+        assert_eq!(gains.to_percentage_array(), None);
+    }
+
+    #[test]
+    fn baseline_correct() {
+        let m1d = M1d::<Volt>::new(vec![2.0, 4.0, 5.0, 7.0, 9.0], Prefix::Milli);
+        let corrected = m1d.baseline_correct(0..2);
+        assert!(corrected.values().slice(s![0..2]).mean().unwrap().abs() < 1e-12);
+        assert_eq!(
+            corrected.values(),
+            Array1::from(vec![-1.0, 1.0, 2.0, 4.0, 6.0])
+        );
+        assert_eq!(corrected.prefix(), Prefix::Milli);
+    }
+
+    #[test]
+    fn len_and_is_empty() {
+        let m1d = M1d::<Volt>::new(vec![1.0, 2.0, 3.0], Prefix::Milli);
+        assert_eq!(m1d.len(), 3);
+        assert!(!m1d.is_empty());
+        assert!(M1d::<Volt>::new(Vec::<f64>::new(), Prefix::Milli).is_empty());
+    }
+
+    #[test]
+    fn display() {
+        let m1d = M1d::<Volt>::new(vec![1.0, 2.5, 3.0], Prefix::Milli);
+        assert_eq!(m1d.to_string(), "M1d<mV> (3) [1, 2.5, 3]");
+        assert_eq!(format!("{m1d:.2}"), "M1d<mV> (3) [1.00, 2.50, 3.00]");
+        let long = M1d::<Volt>::new((0..1000).map(f64::from).collect::<Vec<_>>(), Prefix::None);
+        assert_eq!(
+            long.to_string(),
+            "M1d<V> (1000) [0, 1, 2, ..., 997, 998, 999]"
+        );
+        assert_eq!(
+            format!("{:.1}", long.preview(2)),
+            "M1d<V> (1000) [0.0, 1.0, ..., 998.0, 999.0]"
+        );
+        assert_eq!(long.preview(1).to_string(), "M1d<V> (1000) [0, ..., 999]");
+        assert_eq!(
+            format!("{:.3}", m1d.preview(1)),
+            "M1d<mV> (3) [1.000, ..., 3.000]"
+        );
+        assert_eq!(format!("{m1d:8}"), m1d.to_string());
+    }
+
+    #[test]
+    fn content_hash() {
+        let m = M1d::<Volt>::new(array![1.5, -2.25, 3.0, 0.0], Prefix::Milli);
+        let micro = m.clone().convert_to(Prefix::Micro);
+        assert_eq!(micro.values()[0], 1500.0);
+        assert_eq!(m.content_hash(), micro.content_hash());
+        let mut changed = m.values();
+        changed[2] = 3.5;
+        assert_ne!(
+            m.content_hash(),
+            M1d::<Volt>::new(changed, Prefix::Milli).content_hash()
+        );
+        assert_ne!(
+            m.content_hash(),
+            M1d::<Ampere>::new(m.values(), Prefix::Milli).content_hash()
+        );
+        let signed = M1d::<Volt>::new(array![-0.0, f64::NAN], Prefix::None);
+        let canonical = M1d::<Volt>::new(array![0.0, -f64::NAN], Prefix::None);
+        assert_eq!(signed.content_hash(), canonical.content_hash());
+    }
+
+    #[test]
+    fn nice_picks_prefix_from_data() {
+        let decades = M1d::<Volt>::new(
+            array![1.0, 10.0, 100.0, 1e3, 1e4, 1e5, 1e6, 1e7],
+            Prefix::Micro,
+        );
+        let nice = decades.nice();
+        assert_eq!(nice.prefix(), Prefix::None);
+        assert_eq!(nice.values()[7], 10.0);
+
+        let mut values = vec![2000.0; 99];
+        values.push(1e9);
+        let nice = M1d::<Volt>::new(values, Prefix::Micro).nice();
+        assert_eq!(nice.prefix(), Prefix::Milli);
+        assert_eq!(nice.values()[0], 2.0);
+
+        let no_mega = NicePolicy::new().without_prefixes(&[Prefix::Mega]);
+        let big = M1d::<Volt>::new(array![5e6, -2e6, f64::NAN], Prefix::None);
+        assert_eq!(big.clone().nice().prefix(), Prefix::Mega);
+        assert_eq!(big.nice_with(&no_mega).prefix(), Prefix::Kilo);
+
+        let tiny = M1d::<Volt>::new(array![9.99e-16, 1.001e-16], Prefix::Tera).nice();
+        assert_eq!(tiny.prefix(), Prefix::Micro);
+        assert_eq!(tiny.values(), array![999.0, 100.1]);
+    }
+
+    #[test]
+    fn nice_keeps_empty_and_nan_arrays() {
+        let empty = M1d::<Volt>::new(Array1::zeros(0), Prefix::Micro);
+        assert_eq!(empty.nice().prefix(), Prefix::Micro);
+        let nan = M1d::<Volt>::new(array![f64::NAN, f64::NAN], Prefix::Micro);
+        assert_eq!(nan.nice().prefix(), Prefix::Micro);
+        let zeros = M1d::<Volt>::new(array![0.0, 0.0], Prefix::Kilo);
+        assert_eq!(zeros.nice().prefix(), Prefix::Kilo);
+    }
 }
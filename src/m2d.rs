@@ -1,10 +1,51 @@
-use crate::{m1d::M1d, prefix::Prefix, prelude::Measurement, uom::Uom};
-use ndarray::{concatenate, Array2, Axis};
+#[cfg(not(feature = "rayon"))]
+use crate::par::{ParMapvInplace, ParZip1, ParZip2};
+use crate::{
+    error::MeasurementError,
+    m1d::{
+        M1d, PREVIEW_EDGE_ITEMS, Preview, approx_eq_values, canonical_base_bits, robust_magnitude,
+        write_preview,
+    },
+    math::{SlidingSum, compensated_sum, powi10, rescale_decimal},
+    measurement::{impl_left_scalar_mul, same_quantity},
+    percentage::Percentage,
+    prefix::{NicePolicy, Prefix, finest},
+    prelude::Measurement,
+    quantity::Quantity,
+    ranged_measurement::RangedMeasurement,
+    uom::{Product, Uom, UomDiv, UomMul},
+};
+use ndarray::{
+    Array1, Array2, ArrayView1, ArrayView2, ArrayViewMut1, Axis, ErrorKind, Slice, Zip, s,
+};
 use serde::{Deserialize, Serialize};
-use std::marker::PhantomData;
+use std::{
+    collections::BTreeMap,
+    f64::consts::FRAC_PI_2,
+    fmt,
+    hash::{DefaultHasher, Hash, Hasher},
+    marker::PhantomData,
+    ops::{Add, AddAssign, Div, Index, Mul, Range, Sub, SubAssign},
+};
+
+/// Error returned when values do not fit the requested shape, re-exported from `ndarray`.
+pub use ndarray::ShapeError;
+
+/// What [`M2d::windows_axis`] does with a trailing window shorter than the window size.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PartialWindow {
+    /// Skip the partial window.
+    Drop,
+    /// Yield the partial window padded with NaN up to the window size.
+    Pad,
+}
 
 /// A two-dimensional array of measurements with a unit and SI prefix.
 ///
+/// Two arrays are equal if they have the same shape and every pair of elements is equal as
+/// [`Measurement`]s are, in the base unit, so equal arrays have the same
+/// [`M2d::content_hash`].
+///
 /// # Type Parameters
 /// - `U`: The unit of measurement, implementing the [`Uom`] trait.
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -37,11 +78,77 @@ impl<U: Uom> M2d<U> {
         }
     }
 
+    /// Creates a new [`M2d`] of the given shape from row-major `data`.
+    ///
+    /// # Arguments
+    /// * `shape` - The `(rows, columns)` of the new array.
+    /// * `data` - The values in row-major order.
+    /// * `prefix` - The SI prefix for the unit.
+    ///
+    /// # Returns
+    /// A new [`M2d`], or a [`ShapeError`] if `data` does not hold exactly `rows * columns` values.
+    ///
+    /// # Example
+    /// ```
+    /// use typed_measurements::prelude::*;
+    ///
+    /// let m = M2d::<Volt>::from_shape_vec((2, 2), vec![1.0, 2.0, 3.0, 4.0], Prefix::Milli)?;
+    /// assert_eq!(m.shape(), (2, 2));
+    /// # Ok::<(), ShapeError>(())
+    /// ```
+    pub fn from_shape_vec(
+        shape: (usize, usize),
+        data: Vec<f64>,
+        prefix: Prefix,
+    ) -> Result<Self, ShapeError> {
+        Ok(M2d::new(Array2::from_shape_vec(shape, data)?, prefix))
+    }
+
+    /// Creates a new [`M2d`] of the given shape with every element set to `m`.
+    ///
+    /// The array takes the prefix of `m`.
+    pub fn from_elem(shape: (usize, usize), m: Measurement<U>) -> Self {
+        M2d::new(Array2::from_elem(shape, m.value()), m.prefix())
+    }
+
+    /// Creates a new [`M2d`] of the given shape filled with zeros.
+    pub fn zeros(shape: (usize, usize), prefix: Prefix) -> Self {
+        M2d::new(Array2::zeros(shape), prefix)
+    }
+
+    /// Creates a new [`M2d`] of the given shape filled with ones.
+    pub fn ones(shape: (usize, usize), prefix: Prefix) -> Self {
+        M2d::new(Array2::ones(shape), prefix)
+    }
+
+    /// Creates a new [`M2d`] by stacking `rows`, converting each to `prefix`.
+    ///
+    /// # Arguments
+    /// * `rows` - The rows of the new array, all of the same length.
+    /// * `prefix` - The SI prefix of the new array.
+    ///
+    /// # Returns
+    /// A new [`M2d`], or a [`ShapeError`] if the rows differ in length.
+    pub fn from_rows(rows: &[M1d<U>], prefix: Prefix) -> Result<Self, ShapeError> {
+        let ncols = rows.first().map_or(0, |r| r.len());
+        let mut m = M2d::new(Array2::zeros((0, ncols)), prefix);
+        m.reserve_rows(rows.len())?;
+        for row in rows {
+            m.push_row(row)?;
+        }
+        Ok(m)
+    }
+
     /// Returns a clone of the underlying values array.
     pub fn values(&self) -> Array2<f64> {
         self.values.clone()
     }
 
+    /// Returns a read-only view of the underlying values.
+    pub(crate) fn view(&self) -> ArrayView2<'_, f64> {
+        self.values.view()
+    }
+
     /// Returns the SI prefix associated with this array.
     pub fn prefix(&self) -> Prefix {
         self.prefix
@@ -49,150 +156,3103 @@ impl<U: Uom> M2d<U> {
 
     /// Returns the mean value of all elements as a [`Measurement<U>`].
     ///
+    /// Uses compensated summation, see [`M2d::sum`].
+    ///
     /// # Returns
     /// An `Option<Measurement<U>>` containing the mean, or `None` if the array is empty.
     pub fn mean(&self) -> Option<Measurement<U>> {
-        Some(Measurement::new(self.values.mean()?, self.prefix()))
+        let sum = self.sum()?;
+        Some(sum / self.len() as f64)
     }
 
-    /// Returns the mean along the specified axis as an [`M1d<U>`].
+    /// Returns the sum of all elements as a [`Measurement<U>`].
+    ///
+    /// The sum is compensated, so small contributions are not lost against a large running
+    /// total. NaN values propagate; use [`M2d::nan_sum`] to skip them.
+    ///
+    /// # Returns
+    /// An `Option<Measurement<U>>` containing the sum, or `None` if the array is empty.
+    pub fn sum(&self) -> Option<Measurement<U>> {
+        (!self.is_empty())
+            .then(|| Measurement::new(compensated_sum(self.values.iter().copied()), self.prefix))
+    }
+
+    /// Returns the sum of all non-NaN elements as a [`Measurement<U>`].
+    ///
+    /// # Returns
+    /// An `Option<Measurement<U>>` containing the sum, or `None` if the array is empty.
+    pub fn nan_sum(&self) -> Option<Measurement<U>> {
+        (!self.is_empty()).then(|| {
+            let sum = compensated_sum(self.values.iter().copied().filter(|v| !v.is_nan()));
+            Measurement::new(sum, self.prefix)
+        })
+    }
+
+    /// Returns the compensated sum along the specified axis as an [`M1d<U>`].
+    ///
+    /// NaN values propagate.
+    pub fn sum_axis(&self, axis: Axis) -> M1d<U> {
+        let sums = self
+            .values
+            .map_axis(axis, |lane| compensated_sum(lane.iter().copied()));
+        M1d::new(sums, self.prefix)
+    }
+
+    /// Returns the minimum of all elements as a [`Measurement<U>`].
+    ///
+    /// NaN values propagate: if any element is NaN the result is NaN. Use [`M2d::nan_min`] to skip them.
+    ///
+    /// # Returns
+    /// An `Option<Measurement<U>>` containing the minimum, or `None` if the array is empty.
+    pub fn min(&self) -> Option<Measurement<U>> {
+        (!self.is_empty()).then(|| {
+            Measurement::new(
+                self.values
+                    .fold(f64::INFINITY, |a, &b| propagating_min(a, b)),
+                self.prefix,
+            )
+        })
+    }
+
+    /// Returns the maximum of all elements as a [`Measurement<U>`].
+    ///
+    /// NaN values propagate: if any element is NaN the result is NaN. Use [`M2d::nan_max`] to skip them.
+    ///
+    /// # Returns
+    /// An `Option<Measurement<U>>` containing the maximum, or `None` if the array is empty.
+    pub fn max(&self) -> Option<Measurement<U>> {
+        (!self.is_empty()).then(|| {
+            Measurement::new(
+                self.values
+                    .fold(f64::NEG_INFINITY, |a, &b| propagating_max(a, b)),
+                self.prefix,
+            )
+        })
+    }
+
+    /// Returns the minimum of all non-NaN elements as a [`Measurement<U>`].
+    ///
+    /// # Returns
+    /// An `Option<Measurement<U>>` containing the minimum (NaN if every element is NaN),
+    /// or `None` if the array is empty.
+    pub fn nan_min(&self) -> Option<Measurement<U>> {
+        (!self.is_empty())
+            .then(|| Measurement::new(self.values.fold(f64::NAN, |a, &b| a.min(b)), self.prefix))
+    }
+
+    /// Returns the maximum of all non-NaN elements as a [`Measurement<U>`].
+    ///
+    /// # Returns
+    /// An `Option<Measurement<U>>` containing the maximum (NaN if every element is NaN),
+    /// or `None` if the array is empty.
+    pub fn nan_max(&self) -> Option<Measurement<U>> {
+        (!self.is_empty())
+            .then(|| Measurement::new(self.values.fold(f64::NAN, |a, &b| a.max(b)), self.prefix))
+    }
+
+    /// Returns the minimum along the specified axis as an [`M1d<U>`].
+    ///
+    /// NaN values propagate.
+    ///
+    /// # Panics
+    /// Panics if the length of `axis` is zero.
+    pub fn min_axis(&self, axis: Axis) -> M1d<U> {
+        assert!(self.values.len_of(axis) > 0, "min_axis of an empty axis");
+        M1d::new(
+            self.values
+                .fold_axis(axis, f64::INFINITY, |&a, &b| propagating_min(a, b)),
+            self.prefix,
+        )
+    }
+
+    /// Returns the maximum along the specified axis as an [`M1d<U>`].
+    ///
+    /// NaN values propagate.
+    ///
+    /// # Panics
+    /// Panics if the length of `axis` is zero.
+    pub fn max_axis(&self, axis: Axis) -> M1d<U> {
+        assert!(self.values.len_of(axis) > 0, "max_axis of an empty axis");
+        M1d::new(
+            self.values
+                .fold_axis(axis, f64::NEG_INFINITY, |&a, &b| propagating_max(a, b)),
+            self.prefix,
+        )
+    }
+
+    /// Returns the minimum of the non-NaN values along the specified axis as an [`M1d<U>`].
+    ///
+    /// Lanes made only of NaN yield NaN.
+    pub fn nan_min_axis(&self, axis: Axis) -> M1d<U> {
+        M1d::new(
+            self.values.fold_axis(axis, f64::NAN, |a, &b| a.min(b)),
+            self.prefix,
+        )
+    }
+
+    /// Returns the maximum of the non-NaN values along the specified axis as an [`M1d<U>`].
+    ///
+    /// Lanes made only of NaN yield NaN.
+    pub fn nan_max_axis(&self, axis: Axis) -> M1d<U> {
+        M1d::new(
+            self.values.fold_axis(axis, f64::NAN, |a, &b| a.max(b)),
+            self.prefix,
+        )
+    }
+
+    /// Returns the standard deviation of all elements as a [`Measurement<U>`].
+    ///
+    /// NaN values propagate.
     ///
     /// # Arguments
-    /// * `axis` - The axis along which to compute the mean.
+    /// * `ddof` - The delta degrees of freedom.
+    ///
+    /// # Panics
+    /// Panics if `ddof` is negative or greater than the number of elements.
+    pub fn std(&self, ddof: f64) -> Measurement<U> {
+        Measurement::new(self.values.std(ddof), self.prefix)
+    }
+
+    /// Returns the median along the specified axis as an [`M1d<U>`].
+    ///
+    /// Lanes containing NaN yield NaN; use [`M2d::nan_median_axis`] to skip them.
+    /// Lanes are processed in parallel, each on a sorted copy, leaving the array untouched.
+    pub fn median_axis(&self, axis: Axis) -> M1d<U> {
+        self.percentile_axis(axis, 50.0)
+    }
+
+    /// Returns the median of the non-NaN values along the specified axis as an [`M1d<U>`].
+    pub fn nan_median_axis(&self, axis: Axis) -> M1d<U> {
+        self.nan_percentile_axis(axis, 50.0)
+    }
+
+    /// Returns the `q`-th percentile along the specified axis as an [`M1d<U>`].
+    ///
+    /// Values between ranks are linearly interpolated, matching NumPy's default method.
+    /// Lanes containing NaN yield NaN; use [`M2d::nan_percentile_axis`] to skip them.
+    ///
+    /// # Arguments
+    /// * `axis` - The axis along which to compute the percentile.
+    /// * `q` - The percentile, between 0.0 and 100.0 (inclusive).
+    ///
+    /// # Panics
+    /// Panics if `q` is not between 0.0 and 100.0.
+    pub fn percentile_axis(&self, axis: Axis, q: f64) -> M1d<U> {
+        self.lane_percentiles(axis, q, false)
+    }
+
+    /// Returns the `q`-th percentile of the non-NaN values along the specified axis as an [`M1d<U>`].
+    ///
+    /// Lanes made only of NaN yield NaN.
+    ///
+    /// # Panics
+    /// Panics if `q` is not between 0.0 and 100.0.
+    pub fn nan_percentile_axis(&self, axis: Axis, q: f64) -> M1d<U> {
+        self.lane_percentiles(axis, q, true)
+    }
+
+    fn lane_percentiles(&self, axis: Axis, q: f64, skip_nan: bool) -> M1d<U> {
+        assert!(
+            (0.0..=100.0).contains(&q),
+            "percentile must be between 0 and 100"
+        );
+        let values = Zip::from(self.values.lanes(axis))
+            .par_map_collect(|lane| lane_percentile(lane, q, skip_nan));
+        M1d::new(values, self.prefix)
+    }
+
+    /// Returns the covariance matrix, treating rows as variables and columns as observations.
+    ///
+    /// Equivalent to [`M2d::covariance_axis`] with `Axis(1)`.
+    pub fn covariance(&self, ddof: f64) -> M2d<Product<U, U>> {
+        self.covariance_axis(Axis(1), ddof)
+    }
+
+    /// Returns the covariance matrix between the variables of the array, where `axis`
+    /// is the axis holding the observations (`Axis(1)` when each row is a channel).
+    ///
+    /// The result is in the square of the unit, with the squared prefix folded into the
+    /// closest [`Prefix`]: samples in mV give a covariance in µV·V.
+    ///
+    /// # Arguments
+    /// * `axis` - The axis holding the observations.
+    /// * `ddof` - The delta degrees of freedom.
+    pub fn covariance_axis(&self, axis: Axis, ddof: f64) -> M2d<Product<U, U>> {
+        let observations = if axis == Axis(1) {
+            self.values.view()
+        } else {
+            self.values.t()
+        };
+        let n = observations.ncols() as f64;
+        let means = observations
+            .mean_axis(Axis(1))
+            .unwrap_or_else(|| Array1::zeros(observations.nrows()));
+        let centered = &observations - &means.insert_axis(Axis(1));
+        let (prefix, remainder) = Prefix::from_exp_value(2 * self.prefix.get_exp_value());
+        let scale = powi10(remainder as i32);
+        let covariance = centered.dot(&centered.t()) / (n - ddof);
+        M2d::new(covariance * scale, prefix)
+    }
+
+    /// Returns the matrix of Pearson correlation coefficients between rows.
+    ///
+    /// The coefficients are unitless; rows with zero variance yield NaN.
+    pub fn correlation(&self) -> Array2<f64> {
+        let cov = self.covariance(1.0).values;
+        let std = cov.diag().mapv(f64::sqrt);
+        let outer = std
+            .view()
+            .insert_axis(Axis(1))
+            .dot(&std.view().insert_axis(Axis(0)));
+        cov / outer
+    }
+
+    /// Z-scores every row, subtracting its mean and dividing by its (population) standard deviation.
+    ///
+    /// The returned matrix holds unitless z-scores. Rows with zero variance are mapped to zeros
+    /// rather than NaN. The original values can be recovered as `z * std + mean` row by row.
     ///
     /// # Returns
-    /// An `Option<M1d<U>>` containing the mean values, or `None` if the axis is invalid.
-    pub fn mean_axis(&self, axis: Axis) -> Option<M1d<U>> {
-        Some(M1d::new(self.values.mean_axis(axis)?, self.prefix()))
+    /// A tuple of the z-scored matrix, the per-row means and the per-row standard deviations.
+    pub fn normalize_rows(&self) -> (M2d<U>, M1d<U>, M1d<U>) {
+        let means = self
+            .values
+            .mean_axis(Axis(1))
+            .unwrap_or_else(|| Array1::zeros(self.nrows()));
+        let stds = self.values.std_axis(Axis(1), 0.0);
+        let mut z = &self.values - &means.view().insert_axis(Axis(1));
+        Zip::from(z.rows_mut())
+            .and(&stds)
+            .par_for_each(|mut row, &std| {
+                if std == 0.0 {
+                    row.fill(0.0);
+                } else {
+                    row /= std;
+                }
+            });
+        (
+            M2d::new(z, self.prefix),
+            M1d::new(means, self.prefix),
+            M1d::new(stds, self.prefix),
+        )
     }
 
-    /// Returns the std dev along the specified axis as an [`M1d<U>`].
+    /// Replaces every value by its rank within its row, from 1 for the smallest, to compare
+    /// channels regardless of their gain.
+    ///
+    /// Tied values share the mean of their ranks, so `[1, 5, 5, 2]` ranks as
+    /// `[1, 3.5, 3.5, 2]`. NaN values are left out of the ranking and stay NaN.
+    pub fn rank_rows(&self) -> Array2<f64> {
+        let mut ranks = Array2::zeros(self.values.dim());
+        Zip::from(ranks.rows_mut())
+            .and(self.values.rows())
+            .par_for_each(|mut out, row| out.assign(&lane_ranks(row)));
+        ranks
+    }
+
+    /// Quantile-normalizes the rows, mapping every row onto one common distribution while
+    /// keeping the order of its values.
+    ///
+    /// The common distribution is the mean of the sorted rows. A value of rank `r` (see
+    /// [`M2d::rank_rows`]) in a row of `n` non-NaN values takes that distribution at the
+    /// relative position `(r - 1) / (n - 1)`, interpolated linearly, so tied values share
+    /// one value. Rows with NaN values are stretched onto the full distribution to build it
+    /// and to be mapped onto it; their NaN values stay NaN.
+    pub fn quantile_normalize_rows(&self) -> M2d<U> {
+        let sorted_rows = self
+            .values
+            .rows()
+            .into_iter()
+            .map(|row| {
+                let mut valid = row
+                    .iter()
+                    .copied()
+                    .filter(|v| !v.is_nan())
+                    .collect::<Vec<_>>();
+                valid.sort_by(f64::total_cmp);
+                valid
+            })
+            .filter(|valid| !valid.is_empty())
+            .collect::<Vec<_>>();
+        let last = self.ncols().saturating_sub(1).max(1) as f64;
+        let profile = (0..self.ncols())
+            .map(|k| {
+                let p = k as f64 / last;
+                let sum = compensated_sum(sorted_rows.iter().map(|s| sorted_quantile(s, p)));
+                sum / sorted_rows.len() as f64
+            })
+            .collect::<Vec<_>>();
+        let mut normalized = Array2::zeros(self.values.dim());
+        Zip::from(normalized.rows_mut())
+            .and(self.values.rows())
+            .par_for_each(|mut out, row| {
+                let ranks = lane_ranks(row);
+                let n = ranks.iter().filter(|r| !r.is_nan()).count();
+                out.assign(&ranks.mapv(|r| match n {
+                    _ if r.is_nan() => f64::NAN,
+                    1 => sorted_quantile(&profile, 0.5),
+                    n => sorted_quantile(&profile, (r - 1.0) / (n - 1) as f64),
+                }));
+            });
+        M2d::new(normalized, self.prefix)
+    }
+
+    /// Maps every value onto `[0, 1]` relative to the bounds of `range`.
+    ///
+    /// Values outside the range map outside `[0, 1]`; they are not clamped.
     ///
     /// # Arguments
-    /// * `axis` - The axis along which to compute the std dev.
+    /// * `range` - The reference range, converted to this array's prefix once.
+    pub fn normalize_rows_to_range(&self, range: &RangedMeasurement<U>) -> Array2<f64> {
+        let range = range.convert_to(self.prefix);
+        let (min, span) = (range.min().value(), (range.max() - range.min()).value());
+        self.values.mapv(|x| (x - min) / span)
+    }
+
+    /// Decimates along `axis` by averaging consecutive blocks of `factor` samples.
+    ///
+    /// When the length of `axis` is not a multiple of `factor`, the trailing partial block is
+    /// averaged on its own, so the result has `ceil(len / factor)` samples along `axis`.
+    ///
+    /// # Panics
+    /// Panics if `factor` is zero.
+    pub fn decimate_axis(&self, axis: Axis, factor: usize) -> M2d<U> {
+        self.map_blocks(axis, factor, |block| block.mean().unwrap_or(f64::NAN))
+    }
+
+    /// Decimates along `axis`, keeping the minimum and maximum of every block of `factor`
+    /// samples, e.g. to draw the envelope of a long recording.
+    ///
+    /// NaN values propagate. The trailing partial block is kept, as in [`M2d::decimate_axis`].
     ///
     /// # Returns
-    /// An `M1d<U>` containing the std values.
-    pub fn std_axis(&self, axis: Axis, ddof: f64) -> M1d<U> {
-        M1d::new(self.values.std_axis(axis, ddof), self.prefix())
+    /// A tuple of the per-block minimum and maximum matrices.
+    ///
+    /// # Panics
+    /// Panics if `factor` is zero.
+    pub fn min_max_decimate_axis(&self, axis: Axis, factor: usize) -> (M2d<U>, M2d<U>) {
+        (
+            self.map_blocks(axis, factor, |block| {
+                block.fold(f64::INFINITY, |a, &b| propagating_min(a, b))
+            }),
+            self.map_blocks(axis, factor, |block| {
+                block.fold(f64::NEG_INFINITY, |a, &b| propagating_max(a, b))
+            }),
+        )
     }
-    /// Returns a clone of the underlying values array.
+
+    /// Returns the moving average over `window` consecutive samples along `axis`.
+    ///
+    /// Only full windows are averaged, so the result has `len - window + 1` samples along
+    /// `axis` (none if `window` exceeds the length). NaN and infinite samples only affect the
+    /// windows that contain them.
+    ///
+    /// # Panics
+    /// Panics if `window` is zero.
+    pub fn rolling_mean_axis(&self, axis: Axis, window: usize) -> M2d<U> {
+        assert!(window > 0, "window must be positive");
+        let len = self.values.len_of(axis);
+        let mut shape = self.values.raw_dim();
+        shape[axis.index()] = (len + 1).saturating_sub(window);
+        let mut out = Array2::zeros(shape);
+        Zip::from(self.values.lanes(axis))
+            .and(out.lanes_mut(axis))
+            .par_for_each(|lane, mut out| {
+                let mut sum = SlidingSum::default();
+                lane.iter().take(window).for_each(|v| sum.push(*v));
+                for (i, o) in out.iter_mut().enumerate() {
+                    if i > 0 {
+                        sum.push(lane[i + window - 1]);
+                        sum.pop(lane[i - 1]);
+                    }
+                    let samples = lane.slice(s![i..i + window]);
+                    *o = sum.total(samples.iter().copied()) / window as f64;
+                }
+            });
+        M2d::new(out, self.prefix)
+    }
+
+    fn map_blocks(
+        &self,
+        axis: Axis,
+        factor: usize,
+        f: impl Fn(ArrayView1<f64>) -> f64 + Sync + Send,
+    ) -> M2d<U> {
+        assert!(factor > 0, "decimation factor must be positive");
+        let mut shape = self.values.raw_dim();
+        shape[axis.index()] = self.values.len_of(axis).div_ceil(factor);
+        let mut out = Array2::zeros(shape);
+        Zip::from(self.values.lanes(axis))
+            .and(out.lanes_mut(axis))
+            .par_for_each(|lane, mut out| {
+                for (k, o) in out.iter_mut().enumerate() {
+                    let end = ((k + 1) * factor).min(lane.len());
+                    *o = f(lane.slice(s![k * factor..end]));
+                }
+            });
+        M2d::new(out, self.prefix)
+    }
+
+    /// Averages each consecutive block of `k` rows, e.g. `k` repeated sweeps of one condition.
+    ///
+    /// NaN values propagate; use [`M2d::nan_group_mean_rows`] to skip them.
     ///
     /// # Returns
-    /// A copy of the internal `Array2<f64>`.
-    pub fn label(&self) -> String {
-        self.mean()
-            .map_or(Measurement::new(0, self.prefix()), |f| f)
-            .label()
+    /// An [`M2d`] with `nrows / k` rows, or a [`ShapeError`] if `k` is zero or does not divide
+    /// the number of rows.
+    pub fn group_mean_rows(&self, k: usize) -> Result<M2d<U>, ShapeError> {
+        self.group_rows(k, |g| lane_mean_std(g, 0.0, false).0)
     }
 
-    /// Converts the array to a different SI prefix, scaling all values accordingly.
+    /// Like [`M2d::group_mean_rows`], but ignores NaN values.
     ///
-    /// # Arguments
-    /// * `pfx` - The target SI prefix.
+    /// Columns of a group containing only NaN yield NaN.
+    pub fn nan_group_mean_rows(&self, k: usize) -> Result<M2d<U>, ShapeError> {
+        self.group_rows(k, |g| lane_mean_std(g, 0.0, true).0)
+    }
+
+    /// Returns the standard deviation of each consecutive block of `k` rows.
+    ///
+    /// NaN values propagate; use [`M2d::nan_group_std_rows`] to skip them. Groups with no more
+    /// than `ddof` values yield a non-finite result.
     ///
     /// # Returns
-    /// A new [`M2d`] with values converted to the target prefix.
-    pub fn convert_to(self, pfx: Prefix) -> Self {
-        let conversion_factor = self.prefix.get_conversion_factor(pfx);
-        if conversion_factor == 1.0 {
-            self.clone()
-        } else {
-            let mut s = self;
-            s.values.par_mapv_inplace(|x| x * conversion_factor);
-            Self {
-                values: s.values,
-                prefix: pfx,
-                uom: PhantomData,
-            }
-        }
+    /// An [`M2d`] with `nrows / k` rows, or a [`ShapeError`] if `k` is zero or does not divide
+    /// the number of rows.
+    pub fn group_std_rows(&self, k: usize, ddof: f64) -> Result<M2d<U>, ShapeError> {
+        self.group_rows(k, |g| lane_mean_std(g, ddof, false).1)
+    }
+
+    /// Like [`M2d::group_std_rows`], but ignores NaN values.
+    pub fn nan_group_std_rows(&self, k: usize, ddof: f64) -> Result<M2d<U>, ShapeError> {
+        self.group_rows(k, |g| lane_mean_std(g, ddof, true).1)
     }
 
-    /// Returns the length of the inside 2d array.
+    /// Averages the rows sharing the same label.
+    ///
+    /// NaN values propagate; use [`M2d::nan_group_by_rows`] to skip them.
+    ///
+    /// # Arguments
+    /// * `labels` - One label per row.
     ///
     /// # Returns
-    /// The length of the inside 2d array as usize.
-    pub fn len(&self) -> usize {
-        self.values.len()
+    /// A `(label, mean row)` pair per distinct label, sorted by label.
+    ///
+    /// # Panics
+    /// Panics if `labels` does not have one entry per row.
+    pub fn group_by_rows(&self, labels: &[usize]) -> Vec<(usize, M1d<U>)> {
+        self.group_by(labels, false)
     }
-    
-    /// Return whether the array has any elements
-    pub fn is_empty(&self) -> bool {
-        self.values.is_empty()
+
+    /// Like [`M2d::group_by_rows`], but ignores NaN values.
+    pub fn nan_group_by_rows(&self, labels: &[usize]) -> Vec<(usize, M1d<U>)> {
+        self.group_by(labels, true)
     }
-    
-    /// Concatenate arrays along the given axis.
-    pub fn concatenate_axis(&self, other: &M2d<U>, axis: Axis) -> M2d<U> {
-        let other = if self.prefix != other.prefix {
-            other.clone().convert_to(self.prefix())
-        } else {
-            other.clone()
+
+    fn group_rows(
+        &self,
+        k: usize,
+        f: impl Fn(ArrayView1<f64>) -> f64 + Sync + Send,
+    ) -> Result<M2d<U>, ShapeError> {
+        if k == 0 || !self.nrows().is_multiple_of(k) {
+            return Err(ShapeError::from_kind(ErrorKind::IncompatibleShape));
+        }
+        Ok(self.map_blocks(Axis(0), k, f))
+    }
+
+    fn group_by(&self, labels: &[usize], skip_nan: bool) -> Vec<(usize, M1d<U>)> {
+        assert_eq!(labels.len(), self.nrows(), "expected one label per row");
+        let mut groups: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
+        for (i, &label) in labels.iter().enumerate() {
+            groups.entry(label).or_default().push(i);
+        }
+        groups
+            .into_iter()
+            .map(|(label, rows)| {
+                let group = self.values.select(Axis(0), &rows);
+                let mean: Array1<f64> = group
+                    .columns()
+                    .into_iter()
+                    .map(|c| lane_mean_std(c, 0.0, skip_nan).0)
+                    .collect();
+                (label, M1d::new(mean, self.prefix))
+            })
+            .collect()
+    }
+
+    /// Returns an iterator over windows of `size` samples along `axis`, advancing by `stride`.
+    ///
+    /// Each window is copied into a new [`M2d`]. A trailing window that runs past the end is
+    /// handled according to `partial`; windows never start past the end of the axis.
+    ///
+    /// # Arguments
+    /// * `axis` - The axis to slide along, usually `Axis(1)` for samples.
+    /// * `size` - The number of samples per window.
+    /// * `stride` - The number of samples between the starts of consecutive windows.
+    /// * `partial` - Whether to drop or pad a trailing partial window.
+    ///
+    /// # Panics
+    /// Panics if `size` or `stride` is zero, or if `axis` is out of bounds.
+    ///
+    /// # Example
+    /// ```
+    /// use typed_measurements::prelude::*;
+    ///
+    /// let m = M2d::<Volt>::zeros((2, 10), Prefix::Milli);
+    /// assert_eq!(m.windows_axis(Axis(1), 4, 4, PartialWindow::Drop).count(), 2);
+    /// assert_eq!(m.windows_axis(Axis(1), 4, 4, PartialWindow::Pad).count(), 3);
+    /// ```
+    pub fn windows_axis(
+        &self,
+        axis: Axis,
+        size: usize,
+        stride: usize,
+        partial: PartialWindow,
+    ) -> impl Iterator<Item = M2d<U>> + '_ {
+        assert!(
+            size > 0 && stride > 0,
+            "window size and stride must be positive"
+        );
+        let len = self.values.len_of(axis);
+        let count = match partial {
+            PartialWindow::Drop if len < size => 0,
+            PartialWindow::Drop => (len - size) / stride + 1,
+            PartialWindow::Pad => len
+                .div_ceil(stride)
+                .min(len.saturating_sub(size).div_ceil(stride) + 1),
         };
-        M2d::new(concatenate![axis, self.values(), other.values()], self.prefix())
+        (0..count).map(move |k| {
+            let start = k * stride;
+            let end = (start + size).min(len);
+            let window = self.values.slice_axis(axis, Slice::from(start..end));
+            if end - start == size {
+                return M2d::new(window.to_owned(), self.prefix);
+            }
+            let mut shape = self.values.raw_dim();
+            shape[axis.index()] = size;
+            let mut padded = Array2::from_elem(shape, f64::NAN);
+            padded
+                .slice_axis_mut(axis, Slice::from(0..end - start))
+                .assign(&window);
+            M2d::new(padded, self.prefix)
+        })
     }
-}
 
-impl<U: Uom> PartialEq for M2d<U> {
-    /// Compares two [`M2d`] arrays for equality, converting prefixes if necessary.
-    fn eq(&self, other: &Self) -> bool {
-        if self.prefix != other.prefix {
-            self.clone().convert_to(other.prefix()).values == other.values
-        } else {
-            self.values == other.values
+    /// Splits the array into `n` equal, non-overlapping parts along `axis`.
+    ///
+    /// # Returns
+    /// The `n` parts in order, or a [`ShapeError`] if `n` is zero or does not divide the length
+    /// of `axis`.
+    ///
+    /// # Panics
+    /// Panics if `axis` is out of bounds.
+    pub fn split_axis(&self, axis: Axis, n: usize) -> Result<Vec<M2d<U>>, ShapeError> {
+        let len = self.values.len_of(axis);
+        if n == 0 || !len.is_multiple_of(n) {
+            return Err(ShapeError::from_kind(ErrorKind::IncompatibleShape));
         }
+        let size = len / n;
+        Ok((0..n)
+            .map(|k| {
+                let part = self
+                    .values
+                    .slice_axis(axis, Slice::from(k * size..(k + 1) * size));
+                M2d::new(part.to_owned(), self.prefix)
+            })
+            .collect())
     }
-}
 
-#[cfg(test)]
-mod m2d_tests {
-    use super::*;
-    use crate::uom::Volt;
+    /// Returns a mask flagging every element outside `range`, optionally scaled by `tol`.
+    ///
+    /// Uses the same (exclusive) bounds as [`RangedMeasurement::is_in_range`], so values lying
+    /// exactly on a bound, as well as NaN, are flagged. The range is converted to this array's
+    /// prefix once.
+    pub fn out_of_range_mask(
+        &self,
+        range: &RangedMeasurement<U>,
+        tol: Option<Percentage>,
+    ) -> Array2<bool> {
+        let (min, max) = self.bounds(range, tol);
+        self.values.mapv(|x| !(x > min && x < max))
+    }
 
-    #[test]
-    fn get_values() {
-        let m = M2d::<Volt>::new(
-            Array2::from_shape_vec((2, 2), vec![1000.0, 2000.0, 3000.0, 4000.0]).unwrap(),
-            Prefix::Milli,
-        );
-        assert_eq!(
-            m.values(),
-            Array2::from_shape_vec((2, 2), vec![1000.0, 2000.0, 3000.0, 4000.0]).unwrap()
-        );
+    /// Returns, for every lane along `axis`, the fraction of its samples outside `range`.
+    ///
+    /// With `Axis(1)` there is one entry per row (channel).
+    pub fn fraction_out_of_range_axis(
+        &self,
+        range: &RangedMeasurement<U>,
+        axis: Axis,
+    ) -> Vec<Percentage> {
+        let (min, max) = self.bounds(range, None);
+        self.values
+            .lanes(axis)
+            .into_iter()
+            .map(|lane| {
+                let out = lane.iter().filter(|&&x| !(x > min && x < max)).count();
+                Percentage::new_const(if lane.is_empty() {
+                    0.0
+                } else {
+                    out as f64 / lane.len() as f64
+                })
+            })
+            .collect()
     }
 
-    #[test]
-    fn convert_to() {
-        let m = M2d::<Volt>::new(
-            Array2::from_shape_vec((2, 2), vec![1.0, 2.0, 3.0, 4.0]).unwrap(),
-            Prefix::Milli,
-        );
-        assert_eq!(
-            m.convert_to(Prefix::Micro).values(),
-            Array2::from_shape_vec((2, 2), vec![1000.0, 2000.0, 3000.0, 4000.0]).unwrap()
-        );
+    /// Returns the indices of the rows whose fraction of samples outside `range` exceeds
+    /// `threshold`.
+    pub fn rows_exceeding(
+        &self,
+        range: &RangedMeasurement<U>,
+        threshold: Percentage,
+    ) -> Vec<usize> {
+        self.fraction_out_of_range_axis(range, Axis(1))
+            .into_iter()
+            .enumerate()
+            .filter(|(_, f)| f.get_value() > threshold.get_value())
+            .map(|(i, _)| i)
+            .collect()
     }
 
-    #[test]
-    fn convert_to_2() {
-        let m1 = M2d::<Volt>::new(
-            Array2::from_shape_vec((2, 2), vec![1.0, 2.0, 3.0, 4.0]).unwrap(),
-            Prefix::Milli,
-        );
-        let m2 = M2d::<Volt>::new(
-            Array2::from_shape_vec((2, 2), vec![1000.0, 2000.0, 3000.0, 4000.0]).unwrap(),
-            Prefix::Micro,
-        );
-        assert_eq!(m1, m2);
+    fn bounds(&self, range: &RangedMeasurement<U>, tol: Option<Percentage>) -> (f64, f64) {
+        let range = range.convert_to(self.prefix);
+        let p = tol.map_or(1.0, |p| p.get_value());
+        (range.min().value() * p, range.max().value() * p)
     }
 
-    #[test]
-    fn convert_to_3() {
-        // should short-circuit if prefixes are the same
-        let m = M2d::<Volt>::new(
-            Array2::from_shape_vec((2, 2), vec![1.0, 2.0, 3.0, 4.0]).unwrap(),
-            Prefix::Milli,
-        );
-        let m2 = m.clone();
-        assert_eq!(m, m2);
+    /// Compares two arrays element-wise with a relative tolerance, converting prefixes if necessary.
+    ///
+    /// Uses the same tolerance rules as [`M1d::approx_eq`].
+    ///
+    /// # Arguments
+    /// * `other` - The array to compare with.
+    /// * `max_relative` - The largest allowed difference relative to the larger magnitude.
+    ///
+    /// # Returns
+    /// `true` if the arrays have the same shape and every pair of elements is within tolerance.
+    pub fn approx_eq(&self, other: &M2d<U>, max_relative: f64) -> bool {
+        let factor = self.prefix.get_conversion_factor(other.prefix);
+        self.shape() == other.shape()
+            && Zip::from(&self.values)
+                .and(&other.values)
+                .all(|&a, &b| approx_eq_values(a * factor, b, max_relative))
+    }
+
+    /// Returns a hash of the unit, shape and values, for use as a cache key.
+    ///
+    /// Values are hashed in the base unit and in logical row-major order, so the same data
+    /// hashes identically under different prefixes (when the conversion was exact) and
+    /// memory layouts. `-0.0` and `0.0` hash alike, as do all NaNs. See [`M1d::content_hash`].
+    pub fn content_hash(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        U::uom_str().hash(&mut hasher);
+        self.shape().hash(&mut hasher);
+        for &v in &self.values {
+            hasher.write_u64(canonical_base_bits(v, self.prefix));
+        }
+        hasher.finish()
+    }
+
+    /// Returns a one-line summary of the minimum, maximum, mean and standard deviation,
+    /// each formatted with [`Measurement::nice`].
+    ///
+    /// # Example
+    /// ```
+    /// use typed_measurements::prelude::*;
+    /// use ndarray::Array2;
+    ///
+    /// let m = M2d::<Volt>::new(Array2::from_elem((2, 2), 1500.0), Prefix::Milli);
+    /// assert_eq!(m.summary(), "min: 1.5V, max: 1.5V, mean: 1.5V, std: 0mV");
+    /// ```
+    pub fn summary(&self) -> String {
+        // `nice` cannot pick a prefix for zero or non-finite values.
+        let nice = |m: Measurement<U>| if m.value().is_normal() { m.nice() } else { m }.label();
+        match (self.min(), self.max(), self.mean()) {
+            (Some(min), Some(max), Some(mean)) => format!(
+                "min: {}, max: {}, mean: {}, std: {}",
+                nice(min),
+                nice(max),
+                nice(mean),
+                nice(self.std(0.0))
+            ),
+            _ => "empty".to_string(),
+        }
+    }
+
+    /// Returns the `(row, column)` index of the maximum element.
+    ///
+    /// Like NumPy, the index of the first NaN is returned if the array contains any.
+    /// Use [`M2d::nan_argmax`] to skip NaN values.
+    ///
+    /// # Returns
+    /// The index of the maximum, or `None` if the array is empty.
+    pub fn argmax(&self) -> Option<(usize, usize)> {
+        if let Some((idx, _)) = self.values.indexed_iter().find(|(_, v)| v.is_nan()) {
+            return Some(idx);
+        }
+        self.nan_argmax()
+    }
+
+    /// Returns the `(row, column)` index of the maximum non-NaN element.
+    ///
+    /// # Returns
+    /// The index of the maximum, or `None` if the array is empty or contains only NaN.
+    pub fn nan_argmax(&self) -> Option<(usize, usize)> {
+        self.values
+            .indexed_iter()
+            .filter(|(_, v)| !v.is_nan())
+            .fold(
+                None,
+                |best: Option<((usize, usize), f64)>, (idx, &v)| match best {
+                    Some((_, b)) if b >= v => best,
+                    _ => Some((idx, v)),
+                },
+            )
+            .map(|(idx, _)| idx)
+    }
+
+    /// Returns the mean along the specified axis as an [`M1d<U>`].
+    ///
+    /// # Arguments
+    /// * `axis` - The axis along which to compute the mean.
+    ///
+    /// # Returns
+    /// An `Option<M1d<U>>` containing the mean values, or `None` if the axis has length zero.
+    pub fn mean_axis(&self, axis: Axis) -> Option<M1d<U>> {
+        let n = self.values.len_of(axis);
+        (n > 0).then(|| M1d::new(self.sum_axis(axis).values() / n as f64, self.prefix()))
+    }
+
+    /// Returns the mean along the specified axis in a readable prefix, see [`M1d::nice`].
+    ///
+    /// # Returns
+    /// An `Option<M1d<U>>` containing the mean values, or `None` if the axis has length zero.
+    pub fn mean_axis_nice(&self, axis: Axis) -> Option<M1d<U>> {
+        self.mean_axis(axis).map(M1d::nice)
+    }
+
+    /// Returns the std dev along the specified axis as an [`M1d<U>`].
+    ///
+    /// # Arguments
+    /// * `axis` - The axis along which to compute the std dev.
+    /// * `ddof` - The delta degrees of freedom.
+    ///
+    /// # Returns
+    /// An `M1d<U>` containing the std values; a `ddof` equal to the axis length yields
+    /// non-finite values.
+    ///
+    /// # Panics
+    /// Panics if `axis` is out of bounds, or if `ddof` is negative or greater than the axis
+    /// length; see [`M2d::try_std_axis`] for a fallible version.
+    pub fn std_axis(&self, axis: Axis, ddof: f64) -> M1d<U> {
+        M1d::new(self.values.std_axis(axis, ddof), self.prefix())
+    }
+
+    /// Returns the std dev along the specified axis, validating `axis` and `ddof` first.
+    ///
+    /// # Arguments
+    /// * `axis` - The axis along which to compute the std dev.
+    /// * `ddof` - The delta degrees of freedom, between 0 and the axis length minus 1.
+    ///
+    /// # Returns
+    /// An `M1d<U>` containing the std values, [`MeasurementError::ShapeMismatch`] if `axis`
+    /// is out of bounds, or [`MeasurementError::OutOfRange`] if `ddof` is not in range.
+    pub fn try_std_axis(&self, axis: Axis, ddof: f64) -> Result<M1d<U>, MeasurementError> {
+        if axis.index() >= self.values.ndim() {
+            return Err(ShapeError::from_kind(ErrorKind::OutOfBounds).into());
+        }
+        let max = self.values.len_of(axis) as f64 - 1.0;
+        if !(0.0..=max).contains(&ddof) {
+            return Err(MeasurementError::OutOfRange {
+                value: ddof,
+                min: 0.0,
+                max,
+            });
+        }
+        Ok(self.std_axis(axis, ddof))
+    }
+
+    /// Returns the std dev along the specified axis in a readable prefix, see [`M1d::nice`].
+    ///
+    /// # Panics
+    /// Panics under the same conditions as [`M2d::std_axis`].
+    pub fn std_axis_nice(&self, axis: Axis, ddof: f64) -> M1d<U> {
+        self.std_axis(axis, ddof).nice()
+    }
+
+    /// Returns the mean along `axis` like [`M2d::mean_axis`], on several threads with the
+    /// `rayon` feature.
+    ///
+    /// # Determinism
+    /// Every lane is split into fixed chunks of 4096 elements, whatever the number of
+    /// threads; each chunk is summed with compensation and the chunk sums are combined by a
+    /// pairwise tree in a fixed order. The result is therefore bit-identical across runs,
+    /// thread counts and builds with or without `rayon`, though it may differ from
+    /// [`M2d::mean_axis`] in the last bits.
+    ///
+    /// # Returns
+    /// The mean values, or `None` if the axis has length zero.
+    ///
+    /// # Panics
+    /// Panics if `axis` is out of bounds.
+    pub fn par_mean_axis(&self, axis: Axis) -> Option<M1d<U>> {
+        let n = self.values.len_of(axis);
+        let sums = par_lane_sums(self.values.view(), axis, |_, x| x);
+        (n > 0).then(|| M1d::new(sums / n as f64, self.prefix))
+    }
+
+    /// Returns the std dev along `axis` like [`M2d::std_axis`], on several threads with the
+    /// `rayon` feature.
+    ///
+    /// Both the means and the sums of squared deviations are computed as in
+    /// [`M2d::par_mean_axis`], so the result is bit-identical across runs and thread counts.
+    ///
+    /// # Panics
+    /// Panics if `axis` is out of bounds, or if `ddof` is negative or greater than the axis
+    /// length.
+    pub fn par_std_axis(&self, axis: Axis, ddof: f64) -> M1d<U> {
+        let n = self.values.len_of(axis) as f64;
+        assert!(
+            (0.0..=n).contains(&ddof),
+            "ddof must be between 0 and the axis length"
+        );
+        let view = self.values.view();
+        let means = par_lane_sums(view, axis, |_, x| x) / n;
+        let squares = par_lane_sums(view, axis, |lane, x| (x - means[lane]).powi(2));
+        M1d::new(squares.mapv(|s| (s / (n - ddof)).sqrt()), self.prefix)
+    }
+
+    /// Returns the variance along the specified axis.
+    ///
+    /// The values are in the square of the array's prefix (e.g. µV² for a Micro array), since
+    /// the squared unit has no SI prefix of its own in general.
+    ///
+    /// # Arguments
+    /// * `axis` - The axis along which to compute the variance.
+    /// * `ddof` - The delta degrees of freedom.
+    ///
+    /// # Panics
+    /// Panics under the same conditions as [`M2d::std_axis`].
+    pub fn var_axis(&self, axis: Axis, ddof: f64) -> Array1<f64> {
+        self.values.var_axis(axis, ddof)
+    }
+
+    /// Returns the mean along `axis` and its standard error, the sample standard deviation
+    /// (`ddof` of 1) divided by `√n`, e.g. the average of repeated sweeps and its error bars.
+    ///
+    /// NaN values propagate; use [`M2d::nan_mean_sem_axis`] to skip them. Lanes with fewer
+    /// than two values have a NaN standard error.
+    ///
+    /// # Panics
+    /// Panics if `axis` is out of bounds.
+    pub fn mean_sem_axis(&self, axis: Axis) -> (M1d<U>, M1d<U>) {
+        self.mean_sem(axis, false)
+    }
+
+    /// Like [`M2d::mean_sem_axis`], but ignores NaN values, so `n` counts the other values of
+    /// each lane.
+    pub fn nan_mean_sem_axis(&self, axis: Axis) -> (M1d<U>, M1d<U>) {
+        self.mean_sem(axis, true)
+    }
+
+    /// Returns the mean along `axis` with the lower and upper bounds of its `confidence`
+    /// interval, `mean ± t * sem`.
+    ///
+    /// `t` is the critical value of Student's t-distribution with `n - 1` degrees of freedom,
+    /// not the normal approximation, so intervals over a few sweeps are as wide as they
+    /// should be (±3.18 standard errors at 95% for 4 sweeps, rather than ±1.96).
+    ///
+    /// NaN values propagate; use [`M2d::nan_mean_ci_axis`] to skip them. Lanes with fewer
+    /// than two values have NaN bounds.
+    ///
+    /// # Returns
+    /// The mean, the lower bound and the upper bound.
+    ///
+    /// # Panics
+    /// Panics if `axis` is out of bounds.
+    pub fn mean_ci_axis(&self, axis: Axis, confidence: Percentage) -> (M1d<U>, M1d<U>, M1d<U>) {
+        self.mean_ci(axis, confidence, false)
+    }
+
+    /// Like [`M2d::mean_ci_axis`], but ignores NaN values, so each lane has its own number of
+    /// degrees of freedom.
+    pub fn nan_mean_ci_axis(&self, axis: Axis, confidence: Percentage) -> (M1d<U>, M1d<U>, M1d<U>) {
+        self.mean_ci(axis, confidence, true)
+    }
+
+    /// Returns the mean, standard error and count of every lane along `axis`.
+    fn lane_mean_sems(&self, axis: Axis, skip_nan: bool) -> Array1<(f64, f64, usize)> {
+        Zip::from(self.values.lanes(axis)).par_map_collect(|lane| {
+            let n = lane.iter().filter(|v| !(skip_nan && v.is_nan())).count();
+            let (mean, std) = lane_mean_std(lane, 1.0, skip_nan);
+            (mean, std / (n as f64).sqrt(), n)
+        })
+    }
+
+    fn mean_sem(&self, axis: Axis, skip_nan: bool) -> (M1d<U>, M1d<U>) {
+        let stats = self.lane_mean_sems(axis, skip_nan);
+        (
+            M1d::new(stats.mapv(|(mean, _, _)| mean), self.prefix),
+            M1d::new(stats.mapv(|(_, sem, _)| sem), self.prefix),
+        )
+    }
+
+    fn mean_ci(
+        &self,
+        axis: Axis,
+        confidence: Percentage,
+        skip_nan: bool,
+    ) -> (M1d<U>, M1d<U>, M1d<U>) {
+        let stats = self.lane_mean_sems(axis, skip_nan);
+        // lanes share their count unless NaN values are skipped
+        let mut critical = BTreeMap::new();
+        let half_widths = stats.mapv(|(_, sem, n)| {
+            let t = *critical
+                .entry(n)
+                .or_insert_with(|| t_critical(confidence.get_value(), n.saturating_sub(1)));
+            t * sem
+        });
+        let bound = |sign: f64| {
+            let values = Zip::from(&stats)
+                .and(&half_widths)
+                .map_collect(|&(mean, _, _), &h| mean + sign * h);
+            M1d::new(values, self.prefix)
+        };
+        let mean = M1d::new(stats.mapv(|(mean, _, _)| mean), self.prefix);
+        (mean, bound(-1.0), bound(1.0))
+    }
+
+    /// Returns the label of the mean value (e.g., "2.5mV"), or of zero if the array is empty.
+    pub fn label(&self) -> String {
+        self.mean()
+            .map_or(Measurement::new(0, self.prefix()), |f| f)
+            .label()
+    }
+
+    /// Returns a [`Display`](fmt::Display) preview like the array's own, keeping
+    /// `edge_items` rows and columns at each end of a large array instead of three.
+    pub fn preview(&self, edge_items: usize) -> Preview<'_, Self> {
+        Preview {
+            array: self,
+            edge_items,
+        }
+    }
+
+    /// Converts the array to a different SI prefix, scaling all values accordingly.
+    ///
+    /// # Arguments
+    /// * `pfx` - The target SI prefix.
+    ///
+    /// # Returns
+    /// A new [`M2d`] with values converted to the target prefix.
+    pub fn convert_to(self, pfx: Prefix) -> Self {
+        let conversion_factor = self.prefix.get_conversion_factor(pfx);
+        if conversion_factor == 1.0 {
+            self.clone()
+        } else {
+            let mut s = self;
+            s.values.par_mapv_inplace(|x| x * conversion_factor);
+            Self {
+                values: s.values,
+                prefix: pfx,
+                uom: PhantomData,
+            }
+        }
+    }
+
+    /// Converts the array to a readable prefix chosen from its data.
+    ///
+    /// Equivalent to [`M2d::nice_with`] with the default [`NicePolicy`].
+    pub fn nice(self) -> Self {
+        self.nice_with(&NicePolicy::new())
+    }
+
+    /// Converts the array to the prefix `policy` chooses for the 95th percentile of `|x|`,
+    /// ignoring NaN, so a few outliers do not decide the prefix.
+    ///
+    /// Values are rescaled as in [`Measurement::nice_with`], as the decimals they stand for.
+    ///
+    /// # Returns
+    /// The converted array; empty and all-NaN arrays are returned unchanged.
+    pub fn nice_with(self, policy: &NicePolicy) -> Self {
+        match robust_magnitude(self.values.iter()) {
+            Some(m) => {
+                let prefix = policy.choose(m, self.prefix);
+                let shift = self.prefix.get_exp_value() - prefix.get_exp_value();
+                let mut values = self.values;
+                values.par_mapv_inplace(|x| rescale_decimal(x, shift));
+                M2d::new(values, prefix)
+            }
+            None => self,
+        }
+    }
+
+    /// Returns the number of rows.
+    pub fn nrows(&self) -> usize {
+        self.values.nrows()
+    }
+
+    /// Returns the number of columns.
+    pub fn ncols(&self) -> usize {
+        self.values.ncols()
+    }
+
+    /// Returns the shape of the array as `(rows, columns)`.
+    pub fn shape(&self) -> (usize, usize) {
+        self.values.dim()
+    }
+
+    /// Returns the element at row `i` and column `j` as a [`Measurement<U>`].
+    ///
+    /// # Returns
+    /// An `Option<Measurement<U>>`, or `None` if the indices are out of bounds.
+    pub fn get(&self, i: usize, j: usize) -> Option<Measurement<U>> {
+        self.values
+            .get((i, j))
+            .map(|v| Measurement::new(*v, self.prefix))
+    }
+
+    /// Sets the element at row `i` and column `j`, converting `m` to this array's prefix.
+    ///
+    /// # Arguments
+    /// * `i` - The row index.
+    /// * `j` - The column index.
+    /// * `m` - The new value.
+    ///
+    /// # Panics
+    /// Panics if the indices are out of bounds.
+    pub fn set(&mut self, i: usize, j: usize, m: Measurement<U>) {
+        self.values[(i, j)] = m.convert_to(self.prefix).value();
+    }
+
+    /// Returns the row at index `i` as an [`M1d<U>`], copying only that row.
+    ///
+    /// # Returns
+    /// An `Option<M1d<U>>`, or `None` if `i` is out of bounds.
+    pub fn row(&self, i: usize) -> Option<M1d<U>> {
+        (i < self.nrows()).then(|| M1d::new(self.values.row(i).to_owned(), self.prefix))
+    }
+
+    /// Returns the column at index `j` as an [`M1d<U>`], copying only that column.
+    ///
+    /// # Returns
+    /// An `Option<M1d<U>>`, or `None` if `j` is out of bounds.
+    pub fn column(&self, j: usize) -> Option<M1d<U>> {
+        (j < self.ncols()).then(|| M1d::new(self.values.column(j).to_owned(), self.prefix))
+    }
+
+    /// Returns an iterator over the rows, each copied into an [`M1d<U>`].
+    pub fn rows(&self) -> impl Iterator<Item = M1d<U>> + '_ {
+        self.values
+            .rows()
+            .into_iter()
+            .map(move |r| M1d::new(r.to_owned(), self.prefix))
+    }
+
+    /// Applies `f` to every row, passed as an [`M1d<U>`], and stacks the results.
+    ///
+    /// The results are converted to the finest of their prefixes, so `f` may return rows in
+    /// any prefix. See [`M2d::par_map_rows`] for a parallel version.
+    ///
+    /// # Returns
+    /// The mapped array, or [`MeasurementError::ShapeMismatch`] if `f` changes the length of
+    /// a row.
+    pub fn map_rows<F: FnMut(&M1d<U>) -> M1d<U>>(
+        &self,
+        mut f: F,
+    ) -> Result<M2d<U>, MeasurementError> {
+        let rows = self.rows().map(|row| f(&row)).collect::<Vec<_>>();
+        self.stack_mapped_rows(rows)
+    }
+
+    /// Applies `f` to every row like [`M2d::map_rows`], on several threads with the `rayon`
+    /// feature.
+    pub fn par_map_rows<F: Fn(&M1d<U>) -> M1d<U> + Sync + Send>(
+        &self,
+        f: F,
+    ) -> Result<M2d<U>, MeasurementError> {
+        let prefix = self.prefix;
+        let rows = Zip::from(self.values.rows())
+            .par_map_collect(|row| f(&M1d::new(row.to_owned(), prefix)));
+        self.stack_mapped_rows(rows.into_iter().collect())
+    }
+
+    /// Applies `f` to the values of every row in place, in this array's prefix, without
+    /// allocating. See [`M2d::par_map_rows_in_place`] for a parallel version.
+    pub fn map_rows_in_place<F: FnMut(&mut ArrayViewMut1<f64>)>(&mut self, mut f: F) {
+        for mut row in self.values.rows_mut() {
+            f(&mut row);
+        }
+    }
+
+    /// Applies `f` to the values of every row in place like [`M2d::map_rows_in_place`], on
+    /// several threads with the `rayon` feature.
+    pub fn par_map_rows_in_place<F: Fn(&mut ArrayViewMut1<f64>) + Sync + Send>(&mut self, f: F) {
+        Zip::from(self.values.rows_mut()).par_for_each(|mut row| f(&mut row));
+    }
+
+    /// Stacks the rows returned by a row mapping, checking that they kept the row length.
+    fn stack_mapped_rows(&self, rows: Vec<M1d<U>>) -> Result<M2d<U>, MeasurementError> {
+        if rows.iter().any(|row| row.len() != self.ncols()) {
+            return Err(ShapeError::from_kind(ErrorKind::IncompatibleShape).into());
+        }
+        if rows.is_empty() {
+            return Ok(M2d::new(Array2::zeros(self.values.dim()), self.prefix));
+        }
+        let prefix = finest(rows.iter().map(M1d::prefix)).unwrap_or(self.prefix);
+        Ok(M2d::from_rows(&rows, prefix)?)
+    }
+
+    /// Returns an iterator over the columns, each copied into an [`M1d<U>`].
+    pub fn columns(&self) -> impl Iterator<Item = M1d<U>> + '_ {
+        self.values
+            .columns()
+            .into_iter()
+            .map(move |c| M1d::new(c.to_owned(), self.prefix))
+    }
+
+    /// Returns the total number of elements (rows × columns), not the number of rows.
+    ///
+    /// Use [`M2d::nrows`], [`M2d::ncols`] or [`M2d::shape`] for the individual dimensions.
+    ///
+    /// # Returns
+    /// The number of elements of the inside 2d array as usize.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Returns `true` if the array has no elements, i.e. it has zero rows or zero columns.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Concatenate arrays along the given axis.
+    ///
+    /// # Panics
+    /// Panics if the arrays differ in length along the other axis; see
+    /// [`M2d::try_concatenate_axis`] for a fallible version.
+    pub fn concatenate_axis(&self, other: &M2d<U>, axis: Axis) -> M2d<U> {
+        self.try_concatenate_axis(other, axis)
+            .unwrap_or_else(|e| panic!("cannot concatenate along {axis:?}: {e}"))
+    }
+
+    /// Concatenates arrays along the given axis, converting `other` to this array's prefix.
+    ///
+    /// # Returns
+    /// The joined array, or [`MeasurementError::ShapeMismatch`] if the arrays differ in
+    /// length along the other axis.
+    pub fn try_concatenate_axis(
+        &self,
+        other: &M2d<U>,
+        axis: Axis,
+    ) -> Result<M2d<U>, MeasurementError> {
+        let other = other.clone().convert_to(self.prefix());
+        let values = ndarray::concatenate(axis, &[self.values.view(), other.values.view()])?;
+        Ok(M2d::new(values, self.prefix()))
+    }
+
+    /// Returns a copy of the sub-array covering `rows` × `cols`.
+    ///
+    /// # Returns
+    /// A new [`M2d`], or a [`ShapeError`] if either range exceeds the array bounds.
+    pub fn slice(&self, rows: Range<usize>, cols: Range<usize>) -> Result<M2d<U>, ShapeError> {
+        if rows.start > rows.end
+            || cols.start > cols.end
+            || rows.end > self.nrows()
+            || cols.end > self.ncols()
+        {
+            return Err(ShapeError::from_kind(ErrorKind::OutOfBounds));
+        }
+        Ok(M2d::new(
+            self.values.slice(s![rows, cols]).to_owned(),
+            self.prefix,
+        ))
+    }
+
+    /// Returns a copy of the rows at `indices`, in the given order.
+    ///
+    /// Indices may repeat, which is useful to remap channels.
+    ///
+    /// # Returns
+    /// A new [`M2d`], or a [`ShapeError`] if any index is out of bounds.
+    pub fn select_rows(&self, indices: &[usize]) -> Result<M2d<U>, ShapeError> {
+        self.select(Axis(0), indices)
+    }
+
+    /// Returns a copy of the columns at `indices`, in the given order.
+    ///
+    /// Indices may repeat.
+    ///
+    /// # Returns
+    /// A new [`M2d`], or a [`ShapeError`] if any index is out of bounds.
+    pub fn select_columns(&self, indices: &[usize]) -> Result<M2d<U>, ShapeError> {
+        self.select(Axis(1), indices)
+    }
+
+    fn select(&self, axis: Axis, indices: &[usize]) -> Result<M2d<U>, ShapeError> {
+        let len = self.values.len_of(axis);
+        if indices.iter().any(|&i| i >= len) {
+            return Err(ShapeError::from_kind(ErrorKind::OutOfBounds));
+        }
+        Ok(M2d::new(self.values.select(axis, indices), self.prefix))
+    }
+
+    /// Removes the row at index `i` in place.
+    ///
+    /// # Returns
+    /// The removed row, or a [`ShapeError`] if `i` is out of bounds.
+    pub fn remove_row(&mut self, i: usize) -> Result<M1d<U>, ShapeError> {
+        let row = self
+            .row(i)
+            .ok_or(ShapeError::from_kind(ErrorKind::OutOfBounds))?;
+        self.values.remove_index(Axis(0), i);
+        Ok(row)
+    }
+
+    /// Swaps the rows at indices `i` and `j` in place.
+    ///
+    /// # Returns
+    /// A [`ShapeError`] if either index is out of bounds.
+    pub fn swap_rows(&mut self, i: usize, j: usize) -> Result<(), ShapeError> {
+        let n = self.nrows();
+        if i >= n || j >= n {
+            return Err(ShapeError::from_kind(ErrorKind::OutOfBounds));
+        }
+        if i != j {
+            let (mut a, mut b) = self.values.multi_slice_mut((s![i, ..], s![j, ..]));
+            Zip::from(&mut a).and(&mut b).for_each(std::mem::swap);
+        }
+        Ok(())
+    }
+
+    /// Returns the transpose as a new, contiguous row-major [`M2d`].
+    pub fn t(&self) -> M2d<U> {
+        M2d::new(
+            self.values.t().as_standard_layout().into_owned(),
+            self.prefix,
+        )
+    }
+
+    /// Transposes the array in place by swapping its axes.
+    ///
+    /// No data is moved, so the result is no longer in row-major layout;
+    /// call [`M2d::to_standard_layout`] if contiguous rows are required.
+    pub fn transpose_in_place(&mut self) {
+        self.values.swap_axes(0, 1);
+    }
+
+    /// Returns whether the data is stored contiguously in row-major ("C") order.
+    pub fn is_standard_layout(&self) -> bool {
+        self.values.is_standard_layout()
+    }
+
+    /// Returns the array with its data stored contiguously in row-major ("C") order,
+    /// copying only if it is not already.
+    pub fn to_standard_layout(self) -> Self {
+        if self.is_standard_layout() {
+            self
+        } else {
+            M2d::new(self.values.as_standard_layout().into_owned(), self.prefix)
+        }
+    }
+
+    /// Appends a row in place, converting it to this array's prefix if necessary.
+    ///
+    /// The existing data is left untouched if the row has the wrong length.
+    ///
+    /// # Returns
+    /// A [`ShapeError`] if the length of `row` differs from the number of columns.
+    pub fn push_row(&mut self, row: &M1d<U>) -> Result<(), ShapeError> {
+        self.push_lane(row, Axis(0))
+    }
+
+    /// Appends a column in place, converting it to this array's prefix if necessary.
+    ///
+    /// The existing data is left untouched if the column has the wrong length.
+    ///
+    /// # Returns
+    /// A [`ShapeError`] if the length of `column` differs from the number of rows.
+    pub fn push_column(&mut self, column: &M1d<U>) -> Result<(), ShapeError> {
+        self.push_lane(column, Axis(1))
+    }
+
+    /// Reserves capacity for at least `additional` more rows, so that subsequent calls to
+    /// [`M2d::push_row`] do not reallocate.
+    pub fn reserve_rows(&mut self, additional: usize) -> Result<(), ShapeError> {
+        self.values.reserve_rows(additional)
+    }
+
+    fn push_lane(&mut self, lane: &M1d<U>, axis: Axis) -> Result<(), ShapeError> {
+        let converted;
+        let lane = if lane.prefix() == self.prefix {
+            lane
+        } else {
+            converted = lane.clone().convert_to(self.prefix);
+            &converted
+        };
+        self.values.append(axis, lane.view().insert_axis(axis))
+    }
+
+    /// Adds two arrays element-wise, converting `other` to this array's prefix if necessary.
+    ///
+    /// # Returns
+    /// A new [`M2d`] in this array's prefix, or a [`ShapeError`] if the shapes differ.
+    pub fn try_add(&self, other: &M2d<U>) -> Result<M2d<U>, ShapeError> {
+        self.zip_with(other, |a, b| a + b)
+    }
+
+    /// Subtracts `other` element-wise, converting it to this array's prefix if necessary.
+    ///
+    /// # Returns
+    /// A new [`M2d`] in this array's prefix, or a [`ShapeError`] if the shapes differ.
+    pub fn try_sub(&self, other: &M2d<U>) -> Result<M2d<U>, ShapeError> {
+        self.zip_with(other, |a, b| a - b)
+    }
+
+    /// Adds `other` element-wise in place, without allocating a new array.
+    ///
+    /// # Returns
+    /// A [`ShapeError`] if the shapes differ, in which case `self` is left untouched.
+    pub fn try_add_assign(&mut self, other: &M2d<U>) -> Result<(), ShapeError> {
+        self.zip_with_in_place(other, |a, b| *a += b)
+    }
+
+    /// Subtracts `other` element-wise in place, without allocating a new array.
+    ///
+    /// # Returns
+    /// A [`ShapeError`] if the shapes differ, in which case `self` is left untouched.
+    pub fn try_sub_assign(&mut self, other: &M2d<U>) -> Result<(), ShapeError> {
+        self.zip_with_in_place(other, |a, b| *a -= b)
+    }
+
+    /// Multiplies two arrays of different units element-wise (e.g. V·A = W).
+    ///
+    /// The prefixes are combined, and any exponent not representable by a [`Prefix`]
+    /// is folded into the values.
+    ///
+    /// # Returns
+    /// A new [`M2d`] in the product unit, or a [`ShapeError`] if the shapes differ.
+    pub fn hadamard_mul<B: Uom>(
+        &self,
+        other: &M2d<B>,
+    ) -> Result<M2d<<U as UomMul<B>>::Output>, ShapeError>
+    where
+        U: UomMul<B>,
+    {
+        let exp = self.prefix.get_exp_value() + other.prefix.get_exp_value();
+        self.combine_units(other, exp, |a, b| a * b)
+    }
+
+    /// Divides two arrays of different units element-wise (e.g. V/A = Ω).
+    ///
+    /// The prefixes are combined, and any exponent not representable by a [`Prefix`]
+    /// is folded into the values.
+    ///
+    /// # Returns
+    /// A new [`M2d`] in the quotient unit, or a [`ShapeError`] if the shapes differ.
+    pub fn hadamard_div<B: Uom>(
+        &self,
+        other: &M2d<B>,
+    ) -> Result<M2d<<U as UomDiv<B>>::Output>, ShapeError>
+    where
+        U: UomDiv<B>,
+    {
+        let exp = self.prefix.get_exp_value() - other.prefix.get_exp_value();
+        self.combine_units(other, exp, |a, b| a / b)
+    }
+
+    fn combine_units<B: Uom, O: Uom>(
+        &self,
+        other: &M2d<B>,
+        exp: i16,
+        f: impl Fn(f64, f64) -> f64 + Sync + Send,
+    ) -> Result<M2d<O>, ShapeError> {
+        if self.shape() != other.shape() {
+            return Err(ShapeError::from_kind(ErrorKind::IncompatibleShape));
+        }
+        let (prefix, remainder) = Prefix::from_exp_value(exp);
+        let scale = powi10(remainder as i32);
+        let values = Zip::from(&self.values)
+            .and(&other.values)
+            .par_map_collect(|&a, &b| f(a, b) * scale);
+        Ok(M2d::new(values, prefix))
+    }
+
+    fn zip_with(
+        &self,
+        other: &M2d<U>,
+        f: impl Fn(f64, f64) -> f64 + Sync + Send,
+    ) -> Result<M2d<U>, ShapeError> {
+        if self.shape() != other.shape() {
+            return Err(ShapeError::from_kind(ErrorKind::IncompatibleShape));
+        }
+        let cf = other.prefix.get_conversion_factor(self.prefix);
+        let values = Zip::from(&self.values)
+            .and(&other.values)
+            .par_map_collect(|&a, &b| f(a, b * cf));
+        Ok(M2d::new(values, self.prefix))
+    }
+
+    fn zip_with_in_place(
+        &mut self,
+        other: &M2d<U>,
+        f: impl Fn(&mut f64, f64) + Sync + Send,
+    ) -> Result<(), ShapeError> {
+        if self.shape() != other.shape() {
+            return Err(ShapeError::from_kind(ErrorKind::IncompatibleShape));
+        }
+        let cf = other.prefix.get_conversion_factor(self.prefix);
+        Zip::from(&mut self.values)
+            .and(&other.values)
+            .par_for_each(|a, &b| f(a, b * cf));
+        Ok(())
+    }
+
+    /// Subtracts from each row the mean of its own samples in `window`.
+    ///
+    /// # Arguments
+    /// * `window` - The range of column indices used to compute each row's baseline.
+    ///
+    /// # Returns
+    /// A new [`M2d`] with the same prefix where every row has a baseline mean of zero.
+    ///
+    /// # Panics
+    /// Panics if `window` is empty or out of bounds.
+    pub fn baseline_correct_rows(&self, window: Range<usize>) -> M2d<U> {
+        let baselines = self
+            .values
+            .slice(s![.., window])
+            .mean_axis(Axis(1))
+            .expect("baseline window must not be empty");
+        M2d::new(&self.values - &baselines.insert_axis(Axis(1)), self.prefix)
+    }
+
+    /// Subtracts a reference trace from every row, e.g. to remove a common-mode signal.
+    ///
+    /// # Arguments
+    /// * `row` - The reference trace, converted to this array's prefix if necessary.
+    ///
+    /// # Returns
+    /// A new [`M2d`], or a [`ShapeError`] if the length of `row` differs from the number of columns.
+    pub fn subtract_row(&self, row: &M1d<U>) -> Result<M2d<U>, ShapeError> {
+        if row.len() != self.values.ncols() {
+            return Err(ShapeError::from_kind(ErrorKind::IncompatibleShape));
+        }
+        let row = row.clone().convert_to(self.prefix).values();
+        Ok(M2d::new(&self.values - &row, self.prefix))
+    }
+}
+
+/// Number of elements each partial sum of the deterministic parallel reductions covers.
+///
+/// It is fixed rather than derived from the number of threads, so the partial sums and the
+/// order they are combined in only depend on the shape of the data.
+const PAR_CHUNK: usize = 4096;
+
+/// Sums `f(lane, x)` over every lane of `values` along `axis`: fixed chunks of
+/// [`PAR_CHUNK`] elements are summed with compensation in parallel, then each lane's chunk
+/// sums are combined by [`tree_sum`]. The result is bit-identical for any number of threads.
+pub(crate) fn par_lane_sums(
+    values: ArrayView2<f64>,
+    axis: Axis,
+    f: impl Fn(usize, f64) -> f64 + Sync + Send,
+) -> Array1<f64> {
+    let len = values.len_of(axis);
+    let across = Axis(1 - axis.index());
+    let chunks = (values.len_of(across), len.div_ceil(PAR_CHUNK));
+    let partials =
+        Zip::from(&Array2::from_shape_fn(chunks, |ij| ij)).par_map_collect(|&(lane, chunk)| {
+            let start = chunk * PAR_CHUNK;
+            let lane_values = values.index_axis(across, lane);
+            let part = lane_values.slice(s![start..(start + PAR_CHUNK).min(len)]);
+            compensated_sum(part.iter().map(|&x| f(lane, x)))
+        });
+    partials.rows().into_iter().map(tree_sum).collect()
+}
+
+/// Sums `values` pairwise, halving recursively, so the order of the additions only depends
+/// on the length.
+fn tree_sum(values: ArrayView1<f64>) -> f64 {
+    match values.len() {
+        0 => 0.0,
+        1 => values[0],
+        n => {
+            let (left, right) = values.split_at(Axis(0), n / 2);
+            tree_sum(left) + tree_sum(right)
+        }
+    }
+}
+
+/// Percentile of a single lane, computed on a sorted copy with linear interpolation.
+pub(crate) fn lane_percentile(lane: ArrayView1<f64>, q: f64, skip_nan: bool) -> f64 {
+    let mut sorted: Vec<f64> = if skip_nan {
+        lane.iter().copied().filter(|v| !v.is_nan()).collect()
+    } else if lane.iter().any(|v| v.is_nan()) {
+        return f64::NAN;
+    } else {
+        lane.to_vec()
+    };
+    if sorted.is_empty() {
+        return f64::NAN;
+    }
+    sorted.sort_by(f64::total_cmp);
+    sorted_quantile(&sorted, q / 100.0)
+}
+
+/// Quantile `p` in `[0, 1]` of non-empty sorted values, with linear interpolation.
+fn sorted_quantile(sorted: &[f64], p: f64) -> f64 {
+    let rank = p * (sorted.len() - 1) as f64;
+    let (lo, hi) = (rank.floor() as usize, rank.ceil() as usize);
+    sorted[lo] + (sorted[hi] - sorted[lo]) * (rank - lo as f64)
+}
+
+/// Fractional ranks of a single lane, from 1, with tied values sharing the mean of their
+/// ranks and NaN values left as NaN.
+fn lane_ranks(lane: ArrayView1<f64>) -> Array1<f64> {
+    let mut order = (0..lane.len())
+        .filter(|&i| !lane[i].is_nan())
+        .collect::<Vec<_>>();
+    order.sort_by(|&i, &j| lane[i].total_cmp(&lane[j]));
+    let mut ranks = Array1::from_elem(lane.len(), f64::NAN);
+    let mut start = 0;
+    while start < order.len() {
+        let tied = order[start..]
+            .iter()
+            .take_while(|&&i| lane[i] == lane[order[start]])
+            .count();
+        // sorted positions start..start + tied share the ranks start + 1 to start + tied
+        let rank = start as f64 + (tied + 1) as f64 / 2.0;
+        for &i in &order[start..start + tied] {
+            ranks[i] = rank;
+        }
+        start += tied;
+    }
+    ranks
+}
+
+/// Mean and standard deviation of a single lane, optionally skipping NaN values.
+fn lane_mean_std(lane: ArrayView1<f64>, ddof: f64, skip_nan: bool) -> (f64, f64) {
+    let valid = || lane.iter().copied().filter(|v| !(skip_nan && v.is_nan()));
+    let n = valid().count() as f64;
+    let mean = compensated_sum(valid()) / n;
+    let var = compensated_sum(valid().map(|v| (v - mean).powi(2))) / (n - ddof);
+    (mean, var.sqrt())
+}
+
+/// Returns the two-sided critical value of Student's t-distribution, the `t` for which
+/// `P(|T| <= t) = confidence` with `dof` degrees of freedom; NaN for no degree of freedom.
+///
+/// The closed-form distribution for integer degrees of freedom (Abramowitz & Stegun 26.7.3
+/// and 26.7.4) is inverted by bisection on `θ = atan(t / √dof)`.
+fn t_critical(confidence: f64, dof: usize) -> f64 {
+    if dof == 0 {
+        return f64::NAN;
+    }
+    if confidence <= 0.0 {
+        return 0.0;
+    }
+    if confidence >= 1.0 {
+        return f64::INFINITY;
+    }
+    let coverage = |theta: f64| {
+        let (sin, cos) = theta.sin_cos();
+        if dof == 1 {
+            return theta / FRAC_PI_2;
+        }
+        let (mut term, mut sum) = (1.0, 1.0);
+        for k in (2 + dof % 2..dof).step_by(2) {
+            term *= cos * cos * (k - 1) as f64 / k as f64;
+            sum += term;
+        }
+        if dof.is_multiple_of(2) {
+            sin * sum
+        } else {
+            (theta + sin * cos * sum) / FRAC_PI_2
+        }
+    };
+    let (mut lo, mut hi) = (0.0, FRAC_PI_2);
+    for _ in 0..64 {
+        let mid = (lo + hi) / 2.0;
+        if coverage(mid) < confidence {
+            lo = mid;
+        } else {
+            hi = mid;
+        }
+    }
+    ((lo + hi) / 2.0).tan() * (dof as f64).sqrt()
+}
+
+/// Minimum of two values, propagating NaN.
+fn propagating_min(a: f64, b: f64) -> f64 {
+    if a.is_nan() || b.is_nan() {
+        f64::NAN
+    } else {
+        a.min(b)
+    }
+}
+
+/// Maximum of two values, propagating NaN.
+fn propagating_max(a: f64, b: f64) -> f64 {
+    if a.is_nan() || b.is_nan() {
+        f64::NAN
+    } else {
+        a.max(b)
+    }
+}
+
+impl<U: Uom> Quantity<U> for M2d<U> {
+    fn prefix(&self) -> Prefix {
+        self.prefix
+    }
+
+    fn convert_to(self, pfx: Prefix) -> Self {
+        M2d::convert_to(self, pfx)
+    }
+
+    fn label(&self) -> String {
+        M2d::label(self)
+    }
+
+    fn scale(&mut self, f: f64) {
+        self.values.par_mapv_inplace(|x| x * f);
+    }
+}
+
+impl<U: Uom> fmt::Display for M2d<U> {
+    /// Formats the array as its unit, shape and a preview of the values.
+    ///
+    /// Only the first and last rows and columns are shown for large arrays,
+    /// so the output stays small regardless of the array size. As for [`M1d`], a precision
+    /// applies to every value; use [`M2d::preview`] to keep more or fewer rows and columns.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.preview(PREVIEW_EDGE_ITEMS).fmt(f)
+    }
+}
+
+impl<U: Uom> fmt::Display for Preview<'_, M2d<U>> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (rows, cols) = self.array.shape();
+        write!(
+            f,
+            "M2d<{}{}> ({rows}×{cols})",
+            self.array.prefix.get_label(),
+            U::uom_str()
+        )?;
+        let edge = self.edge_items;
+        let truncated = rows > 2 * edge;
+        for (i, row) in self.array.values.rows().into_iter().enumerate() {
+            if truncated && (edge..rows - edge).contains(&i) {
+                if i == edge {
+                    write!(f, "\n ...")?;
+                }
+                continue;
+            }
+            write!(f, "{}", if i == 0 { "\n[" } else { "\n " })?;
+            write_preview(f, row, edge)?;
+        }
+        if rows > 0 {
+            write!(f, "]")?;
+        }
+        Ok(())
+    }
+}
+
+impl<U: Uom> Index<(usize, usize)> for M2d<U> {
+    /// Returns the raw value at `(row, column)`, expressed in the array's prefix.
+    ///
+    /// # Panics
+    /// Panics if the indices are out of bounds.
+    type Output = f64;
+    fn index(&self, index: (usize, usize)) -> &Self::Output {
+        &self.values[index]
+    }
+}
+
+impl<U: Uom> Add for &M2d<U> {
+    /// Adds two [`M2d`] arrays element-wise, converting to the left operand's prefix.
+    ///
+    /// # Panics
+    /// Panics if the shapes differ; use [`M2d::try_add`] for a fallible version.
+    type Output = M2d<U>;
+    fn add(self, rhs: Self) -> Self::Output {
+        self.try_add(rhs).unwrap_or_else(|_| {
+            panic!(
+                "cannot add M2d arrays of shapes {:?} and {:?}",
+                self.shape(),
+                rhs.shape()
+            )
+        })
+    }
+}
+
+impl<U: Uom> Sub for &M2d<U> {
+    /// Subtracts two [`M2d`] arrays element-wise, converting to the left operand's prefix.
+    ///
+    /// # Panics
+    /// Panics if the shapes differ; use [`M2d::try_sub`] for a fallible version.
+    type Output = M2d<U>;
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.try_sub(rhs).unwrap_or_else(|_| {
+            panic!(
+                "cannot subtract M2d arrays of shapes {:?} and {:?}",
+                self.shape(),
+                rhs.shape()
+            )
+        })
+    }
+}
+
+impl<A: UomMul<B>, B: Uom> Mul<&M2d<B>> for &M2d<A> {
+    /// Multiplies two [`M2d`] arrays of different units element-wise.
+    ///
+    /// # Panics
+    /// Panics if the shapes differ; use [`M2d::hadamard_mul`] for a fallible version.
+    type Output = M2d<A::Output>;
+    fn mul(self, rhs: &M2d<B>) -> Self::Output {
+        self.hadamard_mul(rhs).unwrap_or_else(|_| {
+            panic!(
+                "cannot multiply M2d arrays of shapes {:?} and {:?}",
+                self.shape(),
+                rhs.shape()
+            )
+        })
+    }
+}
+
+impl<A: UomDiv<B>, B: Uom> Div<&M2d<B>> for &M2d<A> {
+    /// Divides two [`M2d`] arrays of different units element-wise.
+    ///
+    /// # Panics
+    /// Panics if the shapes differ; use [`M2d::hadamard_div`] for a fallible version.
+    type Output = M2d<A::Output>;
+    fn div(self, rhs: &M2d<B>) -> Self::Output {
+        self.hadamard_div(rhs).unwrap_or_else(|_| {
+            panic!(
+                "cannot divide M2d arrays of shapes {:?} and {:?}",
+                self.shape(),
+                rhs.shape()
+            )
+        })
+    }
+}
+
+impl<U: Uom> AddAssign<&M2d<U>> for M2d<U> {
+    /// Adds an [`M2d`] element-wise in place, converting it to this array's prefix.
+    ///
+    /// # Panics
+    /// Panics if the shapes differ; use [`M2d::try_add_assign`] for a fallible version.
+    fn add_assign(&mut self, rhs: &M2d<U>) {
+        if self.try_add_assign(rhs).is_err() {
+            panic!(
+                "cannot add M2d arrays of shapes {:?} and {:?}",
+                self.shape(),
+                rhs.shape()
+            );
+        }
+    }
+}
+
+impl<U: Uom> SubAssign<&M2d<U>> for M2d<U> {
+    /// Subtracts an [`M2d`] element-wise in place, converting it to this array's prefix.
+    ///
+    /// # Panics
+    /// Panics if the shapes differ; use [`M2d::try_sub_assign`] for a fallible version.
+    fn sub_assign(&mut self, rhs: &M2d<U>) {
+        if self.try_sub_assign(rhs).is_err() {
+            panic!(
+                "cannot subtract M2d arrays of shapes {:?} and {:?}",
+                self.shape(),
+                rhs.shape()
+            );
+        }
+    }
+}
+
+impl<U: Uom> Mul<f64> for M2d<U> {
+    /// Multiplies every element by a scalar, reusing the array's allocation.
+    type Output = M2d<U>;
+    fn mul(mut self, rhs: f64) -> Self::Output {
+        self.values.par_mapv_inplace(|x| x * rhs);
+        self
+    }
+}
+
+impl<U: Uom> Mul<f64> for &M2d<U> {
+    /// Multiplies every element by a scalar.
+    type Output = M2d<U>;
+    fn mul(self, rhs: f64) -> Self::Output {
+        self.clone() * rhs
+    }
+}
+
+// `scalar * array` multiplies every element, reusing the array's allocation when owned.
+impl_left_scalar_mul!(M2d: f64, f32, i8, i16, i32, u8, u16, u32);
+impl_left_scalar_mul!(&M2d: f64, f32, i8, i16, i32, u8, u16, u32);
+
+impl<U: Uom> Div<f64> for M2d<U> {
+    /// Divides every element by a scalar, reusing the array's allocation.
+    type Output = M2d<U>;
+    fn div(mut self, rhs: f64) -> Self::Output {
+        self.values.par_mapv_inplace(|x| x / rhs);
+        self
+    }
+}
+
+impl<U: Uom> Div<f64> for &M2d<U> {
+    /// Divides every element by a scalar.
+    type Output = M2d<U>;
+    fn div(self, rhs: f64) -> Self::Output {
+        self.clone() / rhs
+    }
+}
+
+impl<U: Uom> PartialEq for M2d<U> {
+    /// Compares two [`M2d`] arrays element-wise by their values in the base unit.
+    fn eq(&self, other: &Self) -> bool {
+        let (pa, pb) = (self.prefix, other.prefix);
+        self.values.shape() == other.values.shape()
+            && Zip::from(&self.values)
+                .and(&other.values)
+                .all(|&a, &b| same_quantity(a, pa, b, pb))
+    }
+}
+
+#[cfg(test)]
+mod m2d_tests {
+    use super::*;
+    use crate::percentage;
+    use crate::uom::{Ampere, Ohm, Volt, Watt};
+
+    #[test]
+    fn content_hash() {
+        let m =
+            M2d::<Volt>::from_shape_vec((2, 3), vec![1.0, 2.5, -3.0, 4.0, 0.0, 6.0], Prefix::Milli)
+                .unwrap();
+        let micro = m.clone().convert_to(Prefix::Micro);
+        assert_eq!(m.content_hash(), micro.content_hash());
+        let mut changed = m.clone();
+        changed.set(1, 2, Measurement::new(6.5, Prefix::Milli));
+        assert_ne!(m.content_hash(), changed.content_hash());
+        let reshaped =
+            M2d::<Volt>::from_shape_vec((3, 2), m.values().into_iter().collect(), Prefix::Milli)
+                .unwrap();
+        assert_ne!(m.content_hash(), reshaped.content_hash());
+        let mut fortran = Array2::zeros(ndarray::ShapeBuilder::f((2, 3)));
+        fortran.assign(&m.values());
+        let column_major = M2d::<Volt>::new(fortran, Prefix::Milli);
+        assert_eq!(m.content_hash(), column_major.content_hash());
+    }
+
+    #[test]
+    fn try_concatenate_axis() {
+        let a = M2d::<Volt>::new(Array2::<f64>::ones((2, 3)), Prefix::Milli);
+        let b = M2d::<Volt>::new(Array2::<f64>::ones((1, 3)), Prefix::None);
+        let joined = a.try_concatenate_axis(&b, Axis(0)).unwrap();
+        assert_eq!(joined.shape(), (3, 3));
+        assert_eq!(
+            joined.get(2, 0),
+            Some(Measurement::new(1000.0, Prefix::Milli))
+        );
+        assert_eq!(
+            a.try_concatenate_axis(&b, Axis(1)),
+            Err(MeasurementError::ShapeMismatch(ShapeError::from_kind(
+                ErrorKind::IncompatibleShape
+            )))
+        );
+    }
+
+    #[test]
+    fn get_values() {
+        let m = M2d::<Volt>::new(
+            Array2::from_shape_vec((2, 2), vec![1000.0, 2000.0, 3000.0, 4000.0]).unwrap(),
+            Prefix::Milli,
+        );
+        assert_eq!(
+            m.values(),
+            Array2::from_shape_vec((2, 2), vec![1000.0, 2000.0, 3000.0, 4000.0]).unwrap()
+        );
+    }
+
+    #[test]
+    fn convert_to() {
+        let m = M2d::<Volt>::new(
+            Array2::from_shape_vec((2, 2), vec![1.0, 2.0, 3.0, 4.0]).unwrap(),
+            Prefix::Milli,
+        );
+        assert_eq!(
+            m.convert_to(Prefix::Micro).values(),
+            Array2::from_shape_vec((2, 2), vec![1000.0, 2000.0, 3000.0, 4000.0]).unwrap()
+        );
+    }
+
+    #[test]
+    fn convert_to_2() {
+        let m1 = M2d::<Volt>::new(
+            Array2::from_shape_vec((2, 2), vec![1.0, 2.0, 3.0, 4.0]).unwrap(),
+            Prefix::Milli,
+        );
+        let m2 = M2d::<Volt>::new(
+            Array2::from_shape_vec((2, 2), vec![1000.0, 2000.0, 3000.0, 4000.0]).unwrap(),
+            Prefix::Micro,
+        );
+        assert_eq!(m1, m2);
+    }
+
+    #[test]
+    fn convert_to_3() {
+        // should short-circuit if prefixes are the same
+        let m = M2d::<Volt>::new(
+            Array2::from_shape_vec((2, 2), vec![1.0, 2.0, 3.0, 4.0]).unwrap(),
+            Prefix::Milli,
+        );
+        let m2 = m.clone();
+        assert_eq!(m, m2);
+    }
+
+    #[test]
+    fn map_rows_matches_processing_each_row() {
+        let m = M2d::<Volt>::new(
+            ndarray::array![
+                [1.0, 3.0, 2.0, 6.0],
+                [0.5, -1.0, 4.0, 2.5],
+                [7.0, 7.0, 7.0, 7.0]
+            ],
+            Prefix::Milli,
+        );
+        let detrended = m.map_rows(M1d::detrend_linear).unwrap();
+        assert_eq!(detrended.prefix(), Prefix::Milli);
+        for (i, row) in m.rows().enumerate() {
+            assert_eq!(detrended.row(i), Some(row.detrend_linear()), "row {i}");
+        }
+        assert_eq!(m.par_map_rows(M1d::detrend_linear).unwrap(), detrended);
+        // rows returned in a finer prefix are stacked in it
+        let in_uv = m.map_rows(|r| r.clone().convert_to(Prefix::Micro)).unwrap();
+        assert_eq!(in_uv.prefix(), Prefix::Micro);
+        assert_eq!(in_uv, m);
+        let shortened = m.map_rows(|r| M1d::new(r.to_vec()[1..].to_vec(), r.prefix()));
+        assert!(matches!(shortened, Err(MeasurementError::ShapeMismatch(_))));
+        let empty = M2d::<Volt>::zeros((0, 4), Prefix::Milli);
+        assert_eq!(empty.map_rows(M1d::detrend_linear).unwrap().shape(), (0, 4));
+    }
+
+    #[test]
+    fn map_rows_in_place_keeps_the_prefix() {
+        let mut m = M2d::<Volt>::new(ndarray::array![[1.0, 2.0], [3.0, 5.0]], Prefix::Milli);
+        m.map_rows_in_place(|row| {
+            let first = row[0];
+            row.mapv_inplace(|v| v - first);
+        });
+        assert_eq!(m.values(), ndarray::array![[0.0, 1.0], [0.0, 2.0]]);
+        m.par_map_rows_in_place(|row| row.mapv_inplace(|v| v * 2.0));
+        assert_eq!(m.values(), ndarray::array![[0.0, 2.0], [0.0, 4.0]]);
+        assert_eq!(m.prefix(), Prefix::Milli);
+    }
+
+    #[test]
+    fn baseline_correct_rows() {
+        let m = M2d::<Volt>::new(
+            Array2::from_shape_vec((2, 4), vec![1.0, 3.0, 5.0, 6.0, 10.0, 10.0, 12.0, 8.0])
+                .unwrap(),
+            Prefix::Milli,
+        );
+        let corrected = m.baseline_correct_rows(0..2);
+        let baselines = corrected
+            .values()
+            .slice(s![.., 0..2])
+            .mean_axis(Axis(1))
+            .unwrap();
+        assert!(baselines.iter().all(|b| b.abs() < 1e-12));
+        assert_eq!(
+            corrected.values(),
+            Array2::from_shape_vec((2, 4), vec![-1.0, 1.0, 3.0, 4.0, 0.0, 0.0, 2.0, -2.0]).unwrap()
+        );
+        assert_eq!(corrected.prefix(), Prefix::Milli);
+    }
+
+    #[test]
+    fn subtract_row() {
+        let m = M2d::<Volt>::new(
+            Array2::from_shape_vec((2, 3), vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]).unwrap(),
+            Prefix::Milli,
+        );
+        let reference = M1d::<Volt>::new(vec![1000.0, 1000.0, 2000.0], Prefix::Micro);
+        let corrected = m.subtract_row(&reference).unwrap();
+        assert_eq!(
+            corrected.values(),
+            Array2::from_shape_vec((2, 3), vec![0.0, 1.0, 1.0, 3.0, 4.0, 4.0]).unwrap()
+        );
+        assert_eq!(corrected.prefix(), Prefix::Milli);
+    }
+
+    #[test]
+    fn subtract_row_wrong_length() {
+        let m = M2d::<Volt>::new(
+            Array2::from_shape_vec((2, 3), vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]).unwrap(),
+            Prefix::Milli,
+        );
+        let reference = M1d::<Volt>::new(vec![1.0, 2.0], Prefix::Milli);
+        assert!(m.subtract_row(&reference).is_err());
+    }
+
+    fn non_square() -> M2d<Volt> {
+        M2d::<Volt>::new(
+            Array2::from_shape_vec((2, 3), vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]).unwrap(),
+            Prefix::Milli,
+        )
+    }
+
+    #[test]
+    fn dimensions() {
+        let m = non_square();
+        assert_eq!(m.nrows(), 2);
+        assert_eq!(m.ncols(), 3);
+        assert_eq!(m.shape(), (2, 3));
+        assert_eq!(m.len(), 6);
+        assert!(!m.is_empty());
+    }
+
+    #[test]
+    fn from_shape_vec() {
+        let m =
+            M2d::<Volt>::from_shape_vec((2, 3), vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0], Prefix::Milli)
+                .unwrap();
+        assert_eq!(m, non_square());
+        let err = M2d::<Volt>::from_shape_vec((2, 3), vec![1.0, 2.0], Prefix::Milli).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::OutOfBounds);
+    }
+
+    #[test]
+    fn filled_constructors() {
+        let m = M2d::from_elem((2, 2), Measurement::<Volt>::new(3.0, Prefix::Milli));
+        assert_eq!(m.prefix(), Prefix::Milli);
+        assert_eq!(m.values(), Array2::from_elem((2, 2), 3.0));
+        assert_eq!(
+            M2d::<Volt>::zeros((1, 2), Prefix::None).values(),
+            Array2::<f64>::zeros((1, 2))
+        );
+        assert_eq!(
+            M2d::<Volt>::ones((2, 1), Prefix::None).values(),
+            Array2::<f64>::ones((2, 1))
+        );
+    }
+
+    #[test]
+    fn empty_dimensions() {
+        let no_rows = M2d::<Volt>::new(Array2::zeros((0, 3)), Prefix::None);
+        assert_eq!(no_rows.shape(), (0, 3));
+        assert_eq!(no_rows.len(), 0);
+        assert!(no_rows.is_empty());
+        let no_columns = M2d::<Volt>::new(Array2::zeros((3, 0)), Prefix::None);
+        assert_eq!(no_columns.nrows(), 3);
+        assert_eq!(no_columns.len(), 0);
+        assert!(no_columns.is_empty());
+    }
+
+    #[test]
+    fn row_and_column() {
+        let m = non_square();
+        assert_eq!(m.row(1), Some(M1d::new(vec![4.0, 5.0, 6.0], Prefix::Milli)));
+        assert_eq!(m.column(2), Some(M1d::new(vec![3.0, 6.0], Prefix::Milli)));
+        assert_eq!(m.row(2), None);
+        assert_eq!(m.column(3), None);
+    }
+
+    #[test]
+    fn rows_and_columns() {
+        let m = non_square();
+        let rows: Vec<_> = m.rows().collect();
+        let columns: Vec<_> = m.columns().collect();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(columns.len(), 3);
+        assert_eq!(rows[0], M1d::new(vec![1.0, 2.0, 3.0], Prefix::Milli));
+        assert_eq!(columns[1], M1d::new(vec![2.0, 5.0], Prefix::Milli));
+    }
+
+    #[test]
+    fn get_element() {
+        let m = non_square();
+        assert_eq!(m.get(1, 2), Some(Measurement::new(6.0, Prefix::Milli)));
+        assert_eq!(m.get(2, 0), None);
+        assert_eq!(m[(0, 1)], 2.0);
+    }
+
+    #[test]
+    fn set_converts_prefix() {
+        let mut m = non_square();
+        m.set(0, 1, Measurement::new(250.0, Prefix::Micro));
+        assert_eq!(m[(0, 1)], 0.25);
+        assert_eq!(m.prefix(), Prefix::Milli);
+        assert_eq!(m.get(0, 1), Some(Measurement::new(250.0, Prefix::Micro)));
+    }
+
+    #[test]
+    fn push_rows_matches_from_rows() {
+        let rows: Vec<_> = (0..1000)
+            .map(|i| M1d::<Volt>::new(vec![i as f64, 2.0 * i as f64, 3.0], Prefix::Milli))
+            .collect();
+        let mut m = M2d::<Volt>::new(Array2::zeros((0, 3)), Prefix::Milli);
+        m.reserve_rows(rows.len()).unwrap();
+        for row in &rows {
+            m.push_row(row).unwrap();
+        }
+        assert_eq!(m.shape(), (1000, 3));
+        assert_eq!(m, M2d::from_rows(&rows, Prefix::Milli).unwrap());
+    }
+
+    #[test]
+    fn push_row_converts_prefix() {
+        let mut m = non_square();
+        m.push_row(&M1d::new(vec![7000.0, 8000.0, 9000.0], Prefix::Micro))
+            .unwrap();
+        assert_eq!(m.row(2), Some(M1d::new(vec![7.0, 8.0, 9.0], Prefix::Milli)));
+    }
+
+    #[test]
+    fn push_row_wrong_length_keeps_data() {
+        let mut m = non_square();
+        let short = M1d::new(vec![1.0, 2.0], Prefix::Milli);
+        assert!(m.push_row(&short).is_err());
+        assert_eq!(m, non_square());
+    }
+
+    #[test]
+    fn push_column() {
+        let mut m = non_square();
+        m.push_column(&M1d::new(vec![0.007, 0.008], Prefix::None))
+            .unwrap();
+        assert_eq!(m.shape(), (2, 4));
+        assert_eq!(m.column(3), Some(M1d::new(vec![7.0, 8.0], Prefix::Milli)));
+        assert!(m.push_column(&M1d::new(vec![1.0], Prefix::Milli)).is_err());
+        assert_eq!(m.shape(), (2, 4));
+    }
+
+    fn stats_matrix() -> M2d<Volt> {
+        M2d::<Volt>::new(
+            Array2::from_shape_vec((2, 3), vec![1.0, 5.0, 3.0, 4.0, 2.0, 6.0]).unwrap(),
+            Prefix::Milli,
+        )
+    }
+
+    #[test]
+    fn sum() {
+        let m = stats_matrix();
+        assert_eq!(m.sum(), Some(Measurement::new(21.0, Prefix::Milli)));
+        assert_eq!(
+            m.sum_axis(Axis(0)),
+            M1d::new(vec![5.0, 7.0, 9.0], Prefix::Milli)
+        );
+        assert_eq!(
+            m.sum_axis(Axis(1)),
+            M1d::new(vec![9.0, 12.0], Prefix::Milli)
+        );
+        let empty = M2d::<Volt>::new(Array2::zeros((0, 3)), Prefix::Milli);
+        assert_eq!(empty.sum(), None);
+    }
+
+    #[test]
+    fn min_max() {
+        let m = stats_matrix();
+        assert_eq!(m.min(), Some(Measurement::new(1.0, Prefix::Milli)));
+        assert_eq!(m.max(), Some(Measurement::new(6.0, Prefix::Milli)));
+        assert_eq!(
+            m.min_axis(Axis(0)),
+            M1d::new(vec![1.0, 2.0, 3.0], Prefix::Milli)
+        );
+        assert_eq!(
+            m.max_axis(Axis(0)),
+            M1d::new(vec![4.0, 5.0, 6.0], Prefix::Milli)
+        );
+        assert_eq!(m.min_axis(Axis(1)), M1d::new(vec![1.0, 2.0], Prefix::Milli));
+        assert_eq!(m.max_axis(Axis(1)), M1d::new(vec![5.0, 6.0], Prefix::Milli));
+    }
+
+    #[test]
+    fn std_and_argmax() {
+        let m = stats_matrix();
+        let std = m.std(0.0);
+        assert!((std.value() - (35.0f64 / 12.0).sqrt()).abs() < 1e-12);
+        assert_eq!(std.prefix(), Prefix::Milli);
+        assert_eq!(m.argmax(), Some((1, 2)));
+    }
+
+    #[test]
+    fn nan_policy() {
+        let mut m = stats_matrix();
+        m.set(0, 1, Measurement::new(f64::NAN, Prefix::Milli));
+        assert!(m.sum().unwrap().value().is_nan());
+        assert!(m.max().unwrap().value().is_nan());
+        assert!(m.max_axis(Axis(0)).values()[1].is_nan());
+        assert_eq!(m.argmax(), Some((0, 1)));
+        assert_eq!(m.nan_sum(), Some(Measurement::new(16.0, Prefix::Milli)));
+        assert_eq!(m.nan_min(), Some(Measurement::new(1.0, Prefix::Milli)));
+        assert_eq!(m.nan_max(), Some(Measurement::new(6.0, Prefix::Milli)));
+        assert_eq!(
+            m.nan_max_axis(Axis(0)),
+            M1d::new(vec![4.0, 2.0, 6.0], Prefix::Milli)
+        );
+        assert_eq!(
+            m.nan_min_axis(Axis(1)),
+            M1d::new(vec![1.0, 2.0], Prefix::Milli)
+        );
+        assert_eq!(m.nan_argmax(), Some((1, 2)));
+    }
+
+    fn assert_close(a: &M1d<Volt>, expected: &[f64]) {
+        assert_eq!(a.len(), expected.len());
+        for (x, e) in a.values().iter().zip(expected) {
+            assert!((x - e).abs() < 1e-12, "{x} != {e}");
+        }
+    }
+
+    #[test]
+    fn t_critical_matches_tables() {
+        let table = [
+            (1, 12.706204736174696, 63.65674116287399),
+            (2, 4.302652729749464, 9.924843200918284),
+            (3, 3.182446305284263, 5.84090930973337),
+            (9, 2.262157162798205, 3.2498355415921),
+            (30, 2.0422724563012373, 2.7499956535670305),
+        ];
+        for (dof, t95, t99) in table {
+            assert!((t_critical(0.95, dof) - t95).abs() < 1e-9 * t95, "{dof}");
+            assert!((t_critical(0.99, dof) - t99).abs() < 1e-9 * t99, "{dof}");
+        }
+        assert!(t_critical(0.95, 0).is_nan());
+        assert_eq!(t_critical(1.0, 5), f64::INFINITY);
+        assert_eq!(t_critical(0.0, 5), 0.0);
+    }
+
+    #[test]
+    fn ranks_rows_with_ties_and_nan() {
+        let m = M2d::<Volt>::new(
+            ndarray::array![
+                [5.0, 2.0, 3.0, 4.0],
+                [4.0, 1.0, 4.0, 2.0],
+                [2.0, f64::NAN, 2.0, 2.0]
+            ],
+            Prefix::Milli,
+        );
+        let ranks = m.rank_rows();
+        assert_eq!(ranks.row(0), ndarray::array![4.0, 1.0, 2.0, 3.0]);
+        assert_eq!(ranks.row(1), ndarray::array![3.5, 1.0, 3.5, 2.0]);
+        assert_eq!(ranks.row(2).to_vec()[2..], [2.0, 2.0]);
+        assert_eq!(ranks[(2, 0)], 2.0);
+        assert!(ranks[(2, 1)].is_nan());
+    }
+
+    #[test]
+    fn quantile_normalizes_rows() {
+        // sorted rows [2, 3, 4, 5], [1, 2, 4, 4] and [3, 4, 6, 8] average to
+        // [2, 3, 14/3, 17/3]; the tied 4s of the second row share 31/6
+        let m = M2d::<Volt>::new(
+            ndarray::array![
+                [5.0, 2.0, 3.0, 4.0],
+                [4.0, 1.0, 4.0, 2.0],
+                [3.0, 4.0, 6.0, 8.0]
+            ],
+            Prefix::Milli,
+        );
+        let q = m.quantile_normalize_rows();
+        assert_eq!(q.prefix(), Prefix::Milli);
+        let expected = ndarray::array![
+            [17.0 / 3.0, 2.0, 3.0, 14.0 / 3.0],
+            [31.0 / 6.0, 2.0, 31.0 / 6.0, 3.0],
+            [2.0, 3.0, 14.0 / 3.0, 17.0 / 3.0],
+        ];
+        assert!(
+            q.approx_eq(&M2d::new(expected, Prefix::Milli), 1e-12),
+            "{q}"
+        );
+        // the second row is stretched from two values onto three positions
+        let gaps = M2d::<Volt>::new(
+            ndarray::array![[1.0, 2.0, 3.0], [10.0, f64::NAN, 30.0]],
+            Prefix::None,
+        );
+        let q = gaps.quantile_normalize_rows().values();
+        assert_eq!(q.row(0), ndarray::array![5.5, 11.0, 16.5]);
+        assert_eq!((q[(1, 0)], q[(1, 2)]), (5.5, 16.5));
+        assert!(q[(1, 1)].is_nan());
+    }
+
+    #[test]
+    fn sweep_means_with_error_bars() {
+        // four sweeps of three samples, the last column with a missing sample
+        let sweeps = M2d::<Volt>::new(
+            ndarray::array![
+                [1.0, 10.0, 2.0],
+                [2.0, 10.0, f64::NAN],
+                [3.0, 10.0, 4.0],
+                [6.0, 10.0, 6.0]
+            ],
+            Prefix::Milli,
+        );
+        let head = |m: &M1d<Volt>| M1d::new(m.values().slice(s![..2]).to_owned(), m.prefix());
+        let (mean, sem) = sweeps.mean_sem_axis(Axis(0));
+        assert_eq!(mean.prefix(), Prefix::Milli);
+        assert_close(&head(&mean), &[3.0, 10.0]);
+        assert_close(&head(&sem), &[1.0801234497346435, 0.0]);
+        assert!(mean.view()[2].is_nan() && sem.view()[2].is_nan());
+        let (mean, sem) = sweeps.nan_mean_sem_axis(Axis(0));
+        assert_close(&mean, &[3.0, 10.0, 4.0]);
+        assert_close(&sem, &[1.0801234497346435, 0.0, 1.1547005383792517]);
+
+        let (mean, lower, upper) = sweeps.mean_ci_axis(Axis(0), percentage!(0.95));
+        assert_close(&head(&mean), &[3.0, 10.0]);
+        assert_close(&head(&lower), &[-0.43743488185830817, 10.0]);
+        assert_close(&head(&upper), &[6.437434881858308, 10.0]);
+        assert!(lower.view()[2].is_nan() && upper.view()[2].is_nan());
+        // the last column has only 2 degrees of freedom left
+        let (_, lower, upper) = sweeps.nan_mean_ci_axis(Axis(0), percentage!(0.95));
+        assert_close(&lower, &[-0.43743488185830817, 10.0, -0.9682754235006561]);
+        assert_close(&upper, &[6.437434881858308, 10.0, 8.968275423500657]);
+
+        let single = M2d::<Volt>::new(ndarray::array![[1.0, 2.0]], Prefix::None);
+        let (mean, lower, _) = single.mean_ci_axis(Axis(0), percentage!(0.95));
+        assert_eq!(mean.values(), ndarray::array![1.0, 2.0]);
+        assert!(lower.view().iter().all(|v| v.is_nan()));
+    }
+
+    fn percentile_matrix() -> M2d<Volt> {
+        M2d::<Volt>::new(
+            Array2::from_shape_vec(
+                (3, 5),
+                vec![
+                    3.0, 1.0, 4.0, 1.5, 5.0, 9.0, 2.0, 6.0, 5.0, 3.0, 5.0, 8.0, 9.0, 7.0, 9.5,
+                ],
+            )
+            .unwrap(),
+            Prefix::Milli,
+        )
+    }
+
+    // expected values computed with numpy.median / numpy.percentile
+    #[test]
+    fn median_axis() {
+        let m = percentile_matrix();
+        assert_close(&m.median_axis(Axis(1)), &[3.0, 5.0, 8.0]);
+        assert_close(&m.median_axis(Axis(0)), &[5.0, 2.0, 6.0, 5.0, 5.0]);
+        assert_eq!(m.median_axis(Axis(0)).prefix(), Prefix::Milli);
+    }
+
+    #[test]
+    fn percentile_axis() {
+        let m = percentile_matrix();
+        assert_close(&m.percentile_axis(Axis(1), 25.0), &[1.5, 3.0, 7.0]);
+        assert_close(&m.percentile_axis(Axis(1), 10.0), &[1.2, 2.4, 5.8]);
+        assert_close(
+            &m.percentile_axis(Axis(0), 90.0),
+            &[8.2, 6.8, 8.4, 6.6, 8.6],
+        );
+        assert_close(&m.percentile_axis(Axis(0), 0.0), &[3.0, 1.0, 4.0, 1.5, 3.0]);
+        assert_close(
+            &m.percentile_axis(Axis(0), 100.0),
+            &[9.0, 8.0, 9.0, 7.0, 9.5],
+        );
+    }
+
+    #[test]
+    fn nan_median_axis() {
+        let mut m = percentile_matrix();
+        m.set(1, 0, Measurement::new(f64::NAN, Prefix::Milli));
+        assert!(m.median_axis(Axis(1)).values()[1].is_nan());
+        assert_close(&m.nan_median_axis(Axis(1)), &[3.0, 4.0, 8.0]);
+        assert_close(&m.nan_median_axis(Axis(0)), &[4.0, 2.0, 6.0, 5.0, 5.0]);
+        // the source array is left untouched
+        assert_eq!(m.get(0, 0), Some(Measurement::new(3.0, Prefix::Milli)));
+    }
+
+    #[test]
+    fn slice() {
+        let m = percentile_matrix();
+        let sub = m.slice(1..3, 2..4).unwrap();
+        assert_eq!(
+            sub.values(),
+            Array2::from_shape_vec((2, 2), vec![6.0, 5.0, 9.0, 7.0]).unwrap()
+        );
+        assert_eq!(sub.prefix(), Prefix::Milli);
+        assert_eq!(m.slice(1..1, 0..5).unwrap().shape(), (0, 5));
+        assert!(m.slice(0..4, 0..1).is_err());
+        assert!(m.slice(0..1, 3..6).is_err());
+    }
+
+    #[test]
+    fn select_rows_and_columns() {
+        let m = percentile_matrix();
+        let remapped = m.select_rows(&[2, 0, 2]).unwrap();
+        assert_eq!(remapped.shape(), (3, 5));
+        assert_eq!(remapped.row(0), m.row(2));
+        assert_eq!(remapped.row(1), m.row(0));
+        assert_eq!(remapped.row(2), m.row(2));
+        let columns = m.select_columns(&[4, 1]).unwrap();
+        assert_eq!(columns.column(0), m.column(4));
+        assert_eq!(columns.column(1), m.column(1));
+        assert!(m.select_rows(&[0, 3]).is_err());
+        assert!(m.select_columns(&[5]).is_err());
+    }
+
+    #[test]
+    fn empty_selection() {
+        let m = percentile_matrix();
+        assert_eq!(m.select_rows(&[]).unwrap().shape(), (0, 5));
+        assert_eq!(m.select_columns(&[]).unwrap().shape(), (3, 0));
+    }
+
+    #[test]
+    fn remove_and_swap_rows() {
+        let mut m = percentile_matrix();
+        let original = m.clone();
+        m.swap_rows(0, 2).unwrap();
+        assert_eq!(m.row(0), original.row(2));
+        assert_eq!(m.row(2), original.row(0));
+        assert!(m.swap_rows(0, 3).is_err());
+        let removed = m.remove_row(1).unwrap();
+        assert_eq!(Some(removed), original.row(1));
+        assert_eq!(m.shape(), (2, 5));
+        assert_eq!(m.row(1), original.row(0));
+        assert!(m.remove_row(2).is_err());
+    }
+
+    #[test]
+    fn add_and_sub() {
+        let a = non_square();
+        let b = M2d::<Volt>::new(
+            Array2::from_shape_vec((2, 3), vec![1000.0, 1000.0, 1000.0, 500.0, 500.0, 500.0])
+                .unwrap(),
+            Prefix::Micro,
+        );
+        let sum = &a + &b;
+        assert_eq!(sum.prefix(), Prefix::Milli);
+        assert_eq!(
+            sum.values(),
+            Array2::from_shape_vec((2, 3), vec![2.0, 3.0, 4.0, 4.5, 5.5, 6.5]).unwrap()
+        );
+        let diff = &a - &b;
+        assert_eq!(
+            diff.values(),
+            Array2::from_shape_vec((2, 3), vec![0.0, 1.0, 2.0, 3.5, 4.5, 5.5]).unwrap()
+        );
+        assert_eq!(a.try_add(&b).unwrap(), sum);
+    }
+
+    #[test]
+    fn add_shape_mismatch() {
+        let a = non_square();
+        let b = M2d::<Volt>::new(Array2::zeros((3, 2)), Prefix::Milli);
+        assert!(a.try_add(&b).is_err());
+        assert!(a.try_sub(&b).is_err());
+        let mut c = a.clone();
+        assert!(c.try_add_assign(&b).is_err());
+        assert_eq!(c, a);
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot add M2d arrays of shapes (2, 3) and (3, 2)")]
+    fn add_operator_shape_mismatch() {
+        let b = M2d::<Volt>::new(Array2::zeros((3, 2)), Prefix::Milli);
+        let _ = &non_square() + &b;
+    }
+
+    #[test]
+    fn add_assign_in_place() {
+        let mut m = non_square();
+        m += &M2d::new(Array2::from_elem((2, 3), 0.001), Prefix::None);
+        assert_eq!(m.prefix(), Prefix::Milli);
+        assert_eq!(
+            m.values(),
+            Array2::from_shape_vec((2, 3), vec![2.0, 3.0, 4.0, 5.0, 6.0, 7.0]).unwrap()
+        );
+        m -= &non_square();
+        assert_eq!(m.values(), Array2::from_elem((2, 3), 1.0));
+    }
+
+    #[test]
+    fn scalar_mul_and_div() {
+        let m = non_square();
+        assert_eq!(
+            (&m * 2.0).values(),
+            Array2::from_shape_vec((2, 3), vec![2.0, 4.0, 6.0, 8.0, 10.0, 12.0]).unwrap()
+        );
+        assert_eq!(m.clone() * 4.0 / 4.0, m);
+        assert_eq!((&m / 2.0).prefix(), Prefix::Milli);
+    }
+
+    #[test]
+    fn volt_times_ampere() {
+        let v = M2d::<Volt>::new(
+            Array2::from_shape_vec((2, 2), vec![1.0, 2.0, 3.0, 4.0]).unwrap(),
+            Prefix::Milli,
+        );
+        let i = M2d::<Ampere>::new(
+            Array2::from_shape_vec((2, 2), vec![2.0, 0.5, 1.0, 10.0]).unwrap(),
+            Prefix::Kilo,
+        );
+        let p: M2d<Watt> = &v * &i;
+        assert_eq!(p.prefix(), Prefix::None);
+        assert_eq!(
+            p.values(),
+            Array2::from_shape_vec((2, 2), vec![2.0, 1.0, 3.0, 40.0]).unwrap()
+        );
+    }
+
+    #[test]
+    fn unit_product_folds_exponent_remainder() {
+        let v = M2d::<Volt>::new(Array2::from_elem((1, 2), 2.0), Prefix::Tera);
+        let i = M2d::<Ampere>::new(Array2::from_elem((1, 2), 3.0), Prefix::Kilo);
+        let p = v.hadamard_mul(&i).unwrap();
+        assert_eq!(p.prefix(), Prefix::Tera);
+        assert_eq!(p.values(), Array2::from_elem((1, 2), 6000.0));
+    }
+
+    #[test]
+    fn volt_over_ampere() {
+        let v = M2d::<Volt>::new(Array2::from_elem((2, 2), 10.0), Prefix::Milli);
+        let i = M2d::<Ampere>::new(Array2::from_elem((2, 2), 2.0), Prefix::Pico);
+        let r: M2d<Ohm> = v.hadamard_div(&i).unwrap();
+        assert_eq!(r.prefix(), Prefix::Giga);
+        assert_eq!(r.values(), Array2::from_elem((2, 2), 5.0));
+        let wrong = M2d::<Ampere>::new(Array2::zeros((1, 2)), Prefix::Pico);
+        assert!(v.hadamard_div(&wrong).is_err());
+    }
+
+    #[test]
+    fn transpose() {
+        let m = non_square();
+        let t = m.t();
+        assert_eq!(t.shape(), (3, 2));
+        assert_eq!(t.prefix(), Prefix::Milli);
+        assert!(t.is_standard_layout());
+        assert_eq!(t.t(), m);
+        for i in 0..m.ncols() {
+            assert_eq!(t.row(i), m.column(i));
+        }
+    }
+
+    #[test]
+    fn transpose_in_place() {
+        let mut m = non_square();
+        m.transpose_in_place();
+        assert_eq!(m, non_square().t());
+        assert!(!m.is_standard_layout());
+        let m = m.to_standard_layout();
+        assert!(m.is_standard_layout());
+        assert_eq!(m, non_square().t());
+    }
+
+    fn covariance_matrix() -> M2d<Volt> {
+        let data = (0..3)
+            .flat_map(|i| {
+                (0..1000).map(move |j| {
+                    (i as f64 * 0.7 + j as f64 * 0.013).sin() * (i + 1) as f64
+                        + ((j * 7919) % 1000) as f64 / 1000.0
+                })
+            })
+            .collect();
+        M2d::new(
+            Array2::from_shape_vec((3, 1000), data).unwrap(),
+            Prefix::Milli,
+        )
+    }
+
+    fn assert_all_close(a: &Array2<f64>, expected: &[[f64; 3]; 3]) {
+        for ((i, j), v) in a.indexed_iter() {
+            assert!(
+                (v - expected[i][j]).abs() < 1e-9,
+                "{v} != {}",
+                expected[i][j]
+            );
+        }
+    }
+
+    // reference values computed with the numpy.cov / numpy.corrcoef formulas
+    #[test]
+    fn covariance() {
+        let m = covariance_matrix();
+        let cov = m.covariance(1.0);
+        assert_eq!(cov.shape(), (3, 3));
+        assert_eq!(cov.prefix(), Prefix::Micro);
+        assert_all_close(
+            &cov.values(),
+            &[
+                [0.5688442242308135, 0.8336721595664663, 0.3483948372425966],
+                [0.8336721595664663, 2.0971142256483106, 2.4517957167495297],
+                [0.3483948372425966, 2.4517957167495297, 4.720383933987646],
+            ],
+        );
+        let transposed = m.t().covariance_axis(Axis(0), 1.0);
+        assert!(
+            (&transposed - &cov)
+                .values()
+                .iter()
+                .all(|d| d.abs() < 1e-12)
+        );
+        // mV² is 1e-6 V²; a kilo prefix squares into mega with no remainder left to fold in
+        assert!(
+            (cov.clone().convert_to(Prefix::None).values()[[0, 0]] - 0.5688442242308135e-6).abs()
+                < 1e-15
+        );
+        let kilo = M2d::<Volt>::new(m.values(), Prefix::Kilo).covariance(1.0);
+        assert_eq!(kilo.prefix(), Prefix::Mega);
+        assert_eq!(kilo.values(), cov.values());
+        let tera = M2d::<Volt>::new(m.values(), Prefix::Tera).covariance(1.0);
+        assert_eq!(tera.prefix(), Prefix::Tera);
+        assert!((tera.values()[[0, 0]] - 0.5688442242308135e12).abs() < 1e-3);
+    }
+
+    #[test]
+    fn correlation() {
+        let corr = covariance_matrix().correlation();
+        assert_all_close(
+            &corr,
+            &[
+                [1.0, 0.7632864960061722, 0.21261131446834913],
+                [0.7632864960061722, 1.0, 0.7792639348313094],
+                [0.21261131446834913, 0.7792639348313094, 1.0],
+            ],
+        );
+    }
+
+    #[test]
+    fn normalize_rows() {
+        let m = M2d::<Volt>::new(
+            Array2::from_shape_vec(
+                (3, 4),
+                vec![1.0, 2.0, 3.0, 4.0, 5.0, 5.0, 5.0, 5.0, -2.0, 0.5, 7.25, 1e3],
+            )
+            .unwrap(),
+            Prefix::Milli,
+        );
+        let (z, means, stds) = m.normalize_rows();
+        assert_eq!(z.row(1), Some(M1d::new(vec![0.0; 4], Prefix::Milli)));
+        assert!(z.values().iter().all(|v| v.is_finite()));
+        for (i, row) in z.rows().enumerate() {
+            let mean = row.values().mean().unwrap();
+            assert!(mean.abs() < 1e-12);
+            let reconstructed = row.values() * stds.values()[i] + means.values()[i];
+            let original = m.row(i).unwrap().values();
+            assert!(
+                reconstructed
+                    .iter()
+                    .zip(original.iter())
+                    .all(|(a, b)| (a - b).abs() < 1e-12)
+            );
+        }
+    }
+
+    #[test]
+    fn normalize_rows_to_range() {
+        let m = non_square();
+        let range = RangedMeasurement::<Volt>::new_sym_stepless(0.004, Prefix::None);
+        let normalized = m.normalize_rows_to_range(&range);
+        assert_eq!(
+            normalized,
+            Array2::from_shape_vec((2, 3), vec![0.625, 0.75, 0.875, 1.0, 1.125, 1.25]).unwrap()
+        );
+    }
+    fn ramp_matrix() -> M2d<Volt> {
+        M2d::new(
+            Array2::from_shape_vec((2, 7), (0..14).map(f64::from).collect()).unwrap(),
+            Prefix::Milli,
+        )
+    }
+
+    #[test]
+    fn decimate_axis() {
+        let m = ramp_matrix();
+        let d = m.decimate_axis(Axis(1), 3);
+        assert_eq!(d.shape(), (2, 3));
+        assert_eq!(d.prefix(), Prefix::Milli);
+        assert_eq!(
+            d.values(),
+            Array2::from_shape_vec((2, 3), vec![1.0, 4.0, 6.0, 8.0, 11.0, 13.0]).unwrap()
+        );
+        let d = m.decimate_axis(Axis(0), 2);
+        assert_eq!(d.shape(), (1, 7));
+        assert_eq!(d.row(0).unwrap().values()[0], 3.5);
+        assert_eq!(m.decimate_axis(Axis(1), 7).shape(), (2, 1));
+        assert_eq!(m.decimate_axis(Axis(1), 10).shape(), (2, 1));
+    }
+
+    #[test]
+    fn min_max_decimate_axis() {
+        let mut m = ramp_matrix();
+        m.set(1, 1, Measurement::new(100.0, Prefix::Milli));
+        let (min, max) = m.min_max_decimate_axis(Axis(1), 2);
+        assert_eq!(min.shape(), (2, 4));
+        assert_eq!(
+            min.values(),
+            Array2::from_shape_vec((2, 4), vec![0.0, 2.0, 4.0, 6.0, 7.0, 9.0, 11.0, 13.0]).unwrap()
+        );
+        assert_eq!(
+            max.values(),
+            Array2::from_shape_vec((2, 4), vec![1.0, 3.0, 5.0, 6.0, 100.0, 10.0, 12.0, 13.0])
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn rolling_mean_axis() {
+        let m = ramp_matrix();
+        let r = m.rolling_mean_axis(Axis(1), 3);
+        assert_eq!(r.shape(), (2, 5));
+        assert_eq!(
+            r.row(0),
+            Some(M1d::new(vec![1.0, 2.0, 3.0, 4.0, 5.0], Prefix::Milli))
+        );
+        let r = m.rolling_mean_axis(Axis(0), 2);
+        assert_eq!(r.shape(), (1, 7));
+        assert_eq!(r[(0, 0)], 3.5);
+        assert_eq!(m.rolling_mean_axis(Axis(1), 8).shape(), (2, 0));
+    }
+
+    #[test]
+    fn rolling_mean_axis_non_finite() {
+        let (nan, inf) = (f64::NAN, f64::INFINITY);
+        let m = M2d::<Volt>::new(
+            ndarray::array![
+                [1.0, 2.0, nan, 4.0, 5.0, 6.0, 7.0],
+                [1.0, inf, 3.0, 4.0, -inf, 6.0, 7.0]
+            ],
+            Prefix::Milli,
+        );
+        let r = m.rolling_mean_axis(Axis(1), 2);
+        assert!(r[(0, 1)].is_nan() && r[(0, 2)].is_nan());
+        assert_eq!(r[(0, 0)], 1.5);
+        assert_eq!(r.values().row(0).slice(s![3..]).to_vec(), [4.5, 5.5, 6.5]);
+        assert_eq!(r.values().row(1).to_vec(), [inf, inf, 3.5, -inf, -inf, 6.5]);
+        // a window spanning both infinities is NaN
+        let r = m.rolling_mean_axis(Axis(1), 4);
+        assert!(r[(1, 1)].is_nan());
+        assert_eq!(r.values().row(1).to_vec()[2..], [-inf, -inf]);
+        // so does a window whose finite samples overflow
+        let r = M2d::<Volt>::new(
+            ndarray::array![[f64::MAX, f64::MAX, 1.0, 1.0]],
+            Prefix::None,
+        )
+        .rolling_mean_axis(Axis(1), 2);
+        assert_eq!(r.values().row(0).to_vec(), [inf, f64::MAX / 2.0, 1.0]);
+    }
+
+    #[test]
+    fn out_of_range() {
+        let m = M2d::<Volt>::new(
+            Array2::from_shape_vec(
+                (3, 4),
+                vec![
+                    0.1, -0.2, 0.3, 0.0, 1.0, 1.0, -1.0, 0.5, 0.2, 0.95, -0.3, 0.1,
+                ],
+            )
+            .unwrap(),
+            Prefix::None,
+        );
+        let range = RangedMeasurement::<Volt>::new_sym_stepless(1000, Prefix::Milli);
+        let mask = m.out_of_range_mask(&range, None);
+        assert_eq!(mask.row(1).to_vec(), vec![true, true, true, false]);
+        assert_eq!(mask.iter().filter(|&&b| b).count(), 3);
+        let tight = m.out_of_range_mask(&range, Some(percentage!(0.9)));
+        assert_eq!(tight.iter().filter(|&&b| b).count(), 4);
+        let fractions = m.fraction_out_of_range_axis(&range, Axis(1));
+        assert_eq!(
+            fractions,
+            vec![percentage!(0.0), percentage!(0.75), percentage!(0.0)]
+        );
+        assert_eq!(m.rows_exceeding(&range, percentage!(0.5)), vec![1]);
+    }
+
+    #[test]
+    fn approx_eq() {
+        let a = M2d::<Volt>::from_shape_vec((1, 2), vec![1.005, 0.0], Prefix::Milli).unwrap();
+        let b = M2d::<Volt>::from_shape_vec((1, 2), vec![1005.0, 0.0], Prefix::Micro).unwrap();
+        assert_ne!(a, b);
+        assert!(a.approx_eq(&b, 1e-12));
+        assert!(!a.approx_eq(&a.t(), 1e-12));
+        assert!(!a.approx_eq(&(&a * 1.01), 1e-3));
+    }
+
+    fn sweeps() -> M2d<Volt> {
+        // row r is [r, 10r]
+        M2d::new(
+            Array2::from_shape_fn((9, 2), |(i, j)| (i * [1, 10][j]) as f64),
+            Prefix::Milli,
+        )
+    }
+
+    #[test]
+    fn group_mean_rows() {
+        let means = sweeps().group_mean_rows(3).unwrap();
+        assert_eq!(means.prefix(), Prefix::Milli);
+        assert_eq!(
+            means.values(),
+            Array2::from_shape_vec((3, 2), vec![1.0, 10.0, 4.0, 40.0, 7.0, 70.0]).unwrap()
+        );
+        let stds = sweeps().group_std_rows(3, 1.0).unwrap();
+        assert_eq!(
+            stds.values(),
+            Array2::from_shape_vec((3, 2), vec![1.0, 10.0, 1.0, 10.0, 1.0, 10.0]).unwrap()
+        );
+        assert_eq!(
+            sweeps().group_mean_rows(2).unwrap_err().kind(),
+            ErrorKind::IncompatibleShape
+        );
+        assert!(sweeps().group_mean_rows(0).is_err());
+    }
+
+    #[test]
+    fn nan_group_rows() {
+        let mut m = sweeps();
+        m.set(0, 0, Measurement::new(f64::NAN, Prefix::Milli));
+        assert!(m.group_mean_rows(3).unwrap()[(0, 0)].is_nan());
+        let means = m.nan_group_mean_rows(3).unwrap();
+        assert_eq!(means[(0, 0)], 1.5);
+        assert_eq!(means[(1, 0)], 4.0);
+        let stds = m.nan_group_std_rows(3, 0.0).unwrap();
+        assert_eq!(stds[(0, 0)], 0.5);
+    }
+
+    #[test]
+    fn group_by_rows() {
+        let groups = sweeps().group_by_rows(&[2, 0, 2, 0, 2, 0, 2, 0, 2]);
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[0].0, 0);
+        assert_eq!(groups[0].1, M1d::new(vec![4.0, 40.0], Prefix::Milli));
+        assert_eq!(groups[1].0, 2);
+        assert_eq!(groups[1].1, M1d::new(vec![4.0, 40.0], Prefix::Milli));
+        let mut m = sweeps();
+        m.set(0, 1, Measurement::new(f64::NAN, Prefix::Milli));
+        let groups = m.nan_group_by_rows(&[0, 0, 1, 1, 1, 1, 1, 1, 1]);
+        assert_eq!(groups[0].1, M1d::new(vec![0.5, 10.0], Prefix::Milli));
+    }
+
+    #[test]
+    fn windows_axis() {
+        let m = ramp_matrix();
+        let drop: Vec<_> = m.windows_axis(Axis(1), 3, 2, PartialWindow::Drop).collect();
+        assert_eq!(drop.len(), 3);
+        assert_eq!(
+            drop[1].row(0),
+            Some(M1d::new(vec![2.0, 3.0, 4.0], m.prefix()))
+        );
+        let pad: Vec<_> = m.windows_axis(Axis(1), 3, 2, PartialWindow::Pad).collect();
+        assert_eq!(pad.len(), 3);
+        let last = m
+            .windows_axis(Axis(1), 3, 3, PartialWindow::Pad)
+            .last()
+            .unwrap();
+        assert_eq!(last.shape(), (2, 3));
+        assert_eq!(last[(0, 0)], 6.0);
+        assert!(last[(0, 1)].is_nan() && last[(1, 2)].is_nan());
+        assert_eq!(
+            m.windows_axis(Axis(1), 3, 3, PartialWindow::Drop).count(),
+            2
+        );
+        assert_eq!(
+            m.windows_axis(Axis(1), 8, 1, PartialWindow::Drop).count(),
+            0
+        );
+        assert_eq!(m.windows_axis(Axis(1), 8, 1, PartialWindow::Pad).count(), 1);
+        assert_eq!(
+            m.windows_axis(Axis(0), 1, 1, PartialWindow::Drop).count(),
+            2
+        );
+    }
+
+    #[test]
+    fn windows_reassemble() {
+        let m = M2d::<Volt>::new(
+            Array2::from_shape_fn((3, 12), |(i, j)| (i * 12 + j) as f64),
+            Prefix::Micro,
+        );
+        let windows: Vec<_> = m.windows_axis(Axis(1), 4, 4, PartialWindow::Drop).collect();
+        let joined = windows[1..].iter().fold(windows[0].clone(), |acc, w| {
+            acc.concatenate_axis(w, Axis(1))
+        });
+        assert_eq!(joined, m);
+        let parts = m.split_axis(Axis(1), 3).unwrap();
+        assert_eq!(parts, windows);
+        let rows = m.split_axis(Axis(0), 3).unwrap();
+        assert_eq!(rows[2].row(0), m.row(2));
+        assert_eq!(
+            m.split_axis(Axis(1), 5).unwrap_err().kind(),
+            ErrorKind::IncompatibleShape
+        );
+        assert!(m.split_axis(Axis(1), 0).is_err());
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(
+            non_square().to_string(),
+            "M2d<mV> (2×3)\n[[1, 2, 3]\n [4, 5, 6]]"
+        );
+        assert_eq!(
+            format!("{:.1}", non_square()),
+            "M2d<mV> (2×3)\n[[1.0, 2.0, 3.0]\n [4.0, 5.0, 6.0]]"
+        );
+        let empty = M2d::<Volt>::new(Array2::zeros((0, 3)), Prefix::None);
+        assert_eq!(empty.to_string(), "M2d<V> (0×3)");
+    }
+
+    #[test]
+    fn display_truncated() {
+        let m = M2d::<Volt>::new(
+            Array2::from_shape_fn((1000, 1000), |(i, j)| (i * 1000 + j) as f64),
+            Prefix::Micro,
+        );
+        assert_eq!(
+            m.to_string(),
+            "M2d<uV> (1000×1000)\n\
+             [[0, 1, 2, ..., 997, 998, 999]\n \
+             [1000, 1001, 1002, ..., 1997, 1998, 1999]\n \
+             [2000, 2001, 2002, ..., 2997, 2998, 2999]\n \
+             ...\n \
+             [997000, 997001, 997002, ..., 997997, 997998, 997999]\n \
+             [998000, 998001, 998002, ..., 998997, 998998, 998999]\n \
+             [999000, 999001, 999002, ..., 999997, 999998, 999999]]"
+        );
+        assert_eq!(
+            m.preview(1).to_string(),
+            "M2d<uV> (1000×1000)\n[[0, ..., 999]\n ...\n [999000, ..., 999999]]"
+        );
+    }
+
+    #[test]
+    fn summary() {
+        let m = M2d::<Volt>::new(
+            Array2::from_shape_vec((2, 2), vec![1000.0, 2000.0, 3000.0, 4000.0]).unwrap(),
+            Prefix::Micro,
+        );
+        assert_eq!(
+            m.summary(),
+            "min: 1mV, max: 4mV, mean: 2.5mV, std: 1.118033988749895mV"
+        );
+        let empty = M2d::<Volt>::new(Array2::zeros((0, 0)), Prefix::None);
+        assert_eq!(empty.summary(), "empty");
+    }
+
+    #[test]
+    fn nice_picks_prefix_from_data() {
+        let m = M2d::<Ampere>::from_shape_vec(
+            (2, 4),
+            vec![1e3, 2e4, 3e5, f64::NAN, -4e5, 5e5, 6e5, 7e5],
+            Prefix::Pico,
+        )
+        .unwrap()
+        .nice();
+        assert_eq!(m.prefix(), Prefix::Nano);
+        assert_eq!(m[(1, 3)], 700.0);
+        let tiny = M2d::<Ampere>::new(ndarray::array![[9.99e-16]], Prefix::Tera).nice();
+        assert_eq!((tiny[(0, 0)], tiny.prefix()), (999.0, Prefix::Micro));
+        let all_nan = M2d::<Ampere>::new(Array2::from_elem((2, 2), f64::NAN), Prefix::Pico);
+        assert_eq!(all_nan.nice().prefix(), Prefix::Pico);
+        let empty = M2d::<Ampere>::new(Array2::zeros((0, 3)), Prefix::Pico);
+        assert_eq!(empty.nice().prefix(), Prefix::Pico);
+    }
+
+    #[test]
+    fn try_std_axis_validates_ddof() {
+        let m =
+            M2d::<Volt>::from_shape_vec((2, 3), vec![1.0, 2.0, 3.0, 4.0, 6.0, 8.0], Prefix::None)
+                .unwrap();
+        assert_eq!(
+            m.try_std_axis(Axis(1), 1.0).unwrap(),
+            m.std_axis(Axis(1), 1.0)
+        );
+        assert_eq!(m.try_std_axis(Axis(1), 2.0).unwrap().len(), 2);
+        for ddof in [3.0, 4.0, -1.0, f64::NAN] {
+            assert!(matches!(
+                m.try_std_axis(Axis(1), ddof),
+                Err(MeasurementError::OutOfRange { .. })
+            ));
+        }
+        assert_eq!(
+            m.try_std_axis(Axis(0), 2.0).unwrap_err().to_string(),
+            "2 is outside the range [0, 1]"
+        );
+        assert!(matches!(
+            m.try_std_axis(Axis(2), 0.0),
+            Err(MeasurementError::ShapeMismatch(_))
+        ));
+        assert_eq!(m.var_axis(Axis(1), 1.0), ndarray::array![1.0, 4.0]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn std_axis_panics_for_ddof_above_length() {
+        let m = M2d::<Volt>::new(Array2::zeros((2, 3)), Prefix::None);
+        m.std_axis(Axis(1), 4.0);
+    }
+
+    /// Rows of mixed magnitudes, long enough to span several chunks, whose sums depend on
+    /// the order of the additions.
+    fn mixed_magnitudes() -> M2d<Volt> {
+        let values = Array2::from_shape_fn((3, 3 * PAR_CHUNK + 17), |(i, j)| {
+            (j as f64 * 0.618 + i as f64).sin() * 10f64.powi((j % 9) as i32 - 4)
+        });
+        M2d::new(values, Prefix::Milli)
+    }
+
+    #[test]
+    fn parallel_statistics_match_sequential_ones() {
+        let m = mixed_magnitudes();
+        for axis in [Axis(0), Axis(1)] {
+            let (par, seq) = (m.par_mean_axis(axis).unwrap(), m.mean_axis(axis).unwrap());
+            assert!(par.approx_eq(&seq, 1e-9), "{axis:?}");
+            let (par, seq) = (m.par_std_axis(axis, 1.0), m.std_axis(axis, 1.0));
+            assert!(par.approx_eq(&seq, 1e-9), "{axis:?}");
+        }
+        let row = m.row(1).unwrap();
+        let (par, seq) = (row.par_sum().unwrap().value(), row.sum().unwrap().value());
+        assert!((par - seq).abs() <= 1e-9 * seq.abs(), "{par} {seq}");
+        let empty = M2d::<Volt>::new(Array2::zeros((0, 3)), Prefix::None);
+        assert_eq!(empty.par_mean_axis(Axis(0)), None);
+        assert_eq!(empty.par_mean_axis(Axis(1)).unwrap().len(), 0);
+        assert_eq!(M1d::<Volt>::new(vec![], Prefix::None).par_sum(), None);
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn parallel_statistics_are_bit_identical_across_thread_counts() {
+        let m = mixed_magnitudes();
+        let row = m.row(0).unwrap();
+        let bits = |a: M1d<Volt>| a.values().mapv(f64::to_bits);
+        let run = |threads| {
+            let pool = rayon::ThreadPoolBuilder::new()
+                .num_threads(threads)
+                .build()
+                .unwrap();
+            pool.install(|| {
+                (
+                    bits(m.par_mean_axis(Axis(0)).unwrap()),
+                    bits(m.par_mean_axis(Axis(1)).unwrap()),
+                    bits(m.par_std_axis(Axis(1), 1.0)),
+                    row.par_sum().unwrap().value().to_bits(),
+                )
+            })
+        };
+        let single = run(1);
+        for threads in [2, 3, 8] {
+            assert_eq!(run(threads), single, "{threads} threads");
+        }
+    }
+
+    #[test]
+    fn axis_statistics_in_readable_prefix() {
+        let m = M2d::<Volt>::from_shape_vec(
+            (2, 4),
+            vec![
+                1.0e-6, 3.0e-6, 1.0e-6, 3.0e-6, 2.0e-3, 4.0e-3, 2.0e-3, 4.0e-3,
+            ],
+            Prefix::Kilo,
+        )
+        .unwrap();
+        let std = m.std_axis_nice(Axis(1), 0.0);
+        assert_eq!(std.prefix(), Prefix::Milli);
+        assert!((std.values()[0] - 1.0).abs() < 1e-9);
+        assert!((std.values()[1] - 1000.0).abs() < 1e-9);
+        let mean = m.mean_axis_nice(Axis(1)).unwrap();
+        assert_eq!(mean.prefix(), Prefix::None);
+        assert!((mean.values()[1] - 3.0).abs() < 1e-12);
+        assert_eq!(m.std_axis(Axis(1), 0.0).prefix(), Prefix::Kilo);
+        let empty = M2d::<Volt>::new(Array2::zeros((2, 0)), Prefix::Kilo);
+        assert!(empty.mean_axis_nice(Axis(1)).is_none());
     }
 }
@@ -0,0 +1,254 @@
+//! Float helpers for the core types that also build without `std`.
+//!
+//! With the `std` feature they are the usual `f64` methods; without it they fall back to
+//! exact integer-power arithmetic, which is all the prefix logic needs.
+
+/// Returns `10^exp`, like `10f64.powi(exp)`.
+pub(crate) fn powi10(exp: i32) -> f64 {
+    #[cfg(feature = "std")]
+    {
+        10f64.powi(exp)
+    }
+    #[cfg(not(feature = "std"))]
+    {
+        let p = (0..exp.unsigned_abs()).fold(1.0, |acc, _| acc * 10.0);
+        if exp < 0 { 1.0 / p } else { p }
+    }
+}
+
+/// `10^k` for `k` in `-27..=27`, every exponent difference between two prefixes, each the
+/// correctly rounded literal (`10f64.powi(k)` is off by one ulp for some negative `k`).
+const POWERS_OF_TEN: [f64; 55] = [
+    1e-27, 1e-26, 1e-25, 1e-24, 1e-23, 1e-22, 1e-21, 1e-20, 1e-19, 1e-18, 1e-17, 1e-16, 1e-15,
+    1e-14, 1e-13, 1e-12, 1e-11, 1e-10, 1e-9, 1e-8, 1e-7, 1e-6, 1e-5, 1e-4, 1e-3, 1e-2, 1e-1, 1e0,
+    1e1, 1e2, 1e3, 1e4, 1e5, 1e6, 1e7, 1e8, 1e9, 1e10, 1e11, 1e12, 1e13, 1e14, 1e15, 1e16, 1e17,
+    1e18, 1e19, 1e20, 1e21, 1e22, 1e23, 1e24, 1e25, 1e26, 1e27,
+];
+
+/// Returns `10^exp` from [`POWERS_OF_TEN`], usable in const contexts.
+///
+/// # Panics
+/// Panics if `exp` is outside `-27..=27`.
+pub(crate) const fn pow10(exp: i16) -> f64 {
+    assert!(exp >= -27 && exp <= 27, "exponent outside the prefix range");
+    POWERS_OF_TEN[(exp + 27) as usize]
+}
+
+/// Returns `value * 10^exp` with `-0.0` mapped to `0.0` and every NaN mapped to one NaN, so
+/// equal quantities have equal bits.
+///
+/// Negative exponents divide by an exact power of ten instead of multiplying by an inexact
+/// one, so that exactly converted values (e.g. 1.5 mV and 1500 µV) yield the same result.
+pub(crate) fn canonical_base(value: f64, exp: i32) -> f64 {
+    let base = if exp < 0 {
+        value / powi10(-exp)
+    } else {
+        value * powi10(exp)
+    };
+    if base.is_nan() {
+        f64::NAN
+    } else if base == 0.0 {
+        0.0
+    } else {
+        base
+    }
+}
+
+/// Returns `value * 10^shift` as the decimal `value` stands for, e.g. 999 rather than
+/// 999.0000000000001 for 9.99e-16 shifted by 18.
+///
+/// The product (or, for a negative `shift`, the quotient by an exact power of ten) is snapped
+/// to 15 significant digits when that moves it by no more than the rounding error of the
+/// conversion itself.
+///
+/// # Panics
+/// Panics if `shift` is outside `-27..=27`.
+pub(crate) fn rescale_decimal(value: f64, shift: i16) -> f64 {
+    let scaled = if shift < 0 {
+        value / pow10(-shift)
+    } else {
+        value * pow10(shift)
+    };
+    if shift == 0 || !scaled.is_finite() || scaled == 0.0 {
+        return scaled;
+    }
+    // only exact powers of ten give the nearest double to the decimal
+    let digits = 14 - floor_log10(scaled.abs());
+    if digits.abs() > 22 {
+        return scaled;
+    }
+    let snapped = if digits < 0 {
+        round(scaled / powi10(-digits)) * powi10(-digits)
+    } else {
+        round(scaled * powi10(digits)) / powi10(digits)
+    };
+    if (snapped - scaled).abs() <= scaled.abs() * f64::EPSILON {
+        snapped
+    } else {
+        scaled
+    }
+}
+
+/// Returns `floor(log10(x))` for finite `x > 0`, corrected so that `10^result <= x`.
+pub(crate) fn floor_log10(x: f64) -> i32 {
+    #[cfg(feature = "std")]
+    let mut exp = x.log10().floor() as i32;
+    #[cfg(not(feature = "std"))]
+    let mut exp = 0;
+    while powi10(exp) > x {
+        exp -= 1;
+    }
+    while powi10(exp + 1) <= x {
+        exp += 1;
+    }
+    exp
+}
+
+/// Returns the integer part of `x`, like `x.trunc()`.
+pub(crate) fn trunc(x: f64) -> f64 {
+    #[cfg(feature = "std")]
+    {
+        x.trunc()
+    }
+    #[cfg(not(feature = "std"))]
+    {
+        // every f64 of magnitude 2^52 or more (and every non-finite value) is already integral
+        if x.abs() < 4_503_599_627_370_496.0 {
+            x as i64 as f64
+        } else {
+            x
+        }
+    }
+}
+
+/// Returns the largest integer not greater than `x`, like `x.floor()`.
+pub(crate) fn floor(x: f64) -> f64 {
+    let t = trunc(x);
+    if t > x { t - 1.0 } else { t }
+}
+
+/// Returns the smallest integer not less than `x`, like `x.ceil()`.
+pub(crate) fn ceil(x: f64) -> f64 {
+    let t = trunc(x);
+    if t < x { t + 1.0 } else { t }
+}
+
+/// Returns the integer closest to `x`, halfway cases away from zero, like `x.round()`.
+pub(crate) fn round(x: f64) -> f64 {
+    let t = trunc(x);
+    match x - t {
+        d if d >= 0.5 => t + 1.0,
+        d if d <= -0.5 => t - 1.0,
+        _ => t,
+    }
+}
+
+/// Returns the integer closest to `x`, halfway cases to the even integer, like
+/// `x.round_ties_even()`.
+pub(crate) fn round_ties_even(x: f64) -> f64 {
+    #[cfg(feature = "std")]
+    {
+        x.round_ties_even()
+    }
+    #[cfg(not(feature = "std"))]
+    {
+        let t = trunc(x);
+        let d = x - t;
+        if (d == 0.5 || d == -0.5) && t % 2.0 == 0.0 {
+            t
+        } else {
+            round(x)
+        }
+    }
+}
+
+/// Neumaier's compensated running sum, accurate to about one rounding error regardless of
+/// the number or order of the terms.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub(crate) struct CompensatedSum {
+    sum: f64,
+    compensation: f64,
+}
+
+impl CompensatedSum {
+    /// Adds `v` to the sum, keeping the low-order bits lost to rounding.
+    pub(crate) fn add(&mut self, v: f64) {
+        let t = self.sum + v;
+        if t.is_finite() {
+            self.compensation += if self.sum.abs() >= v.abs() {
+                (self.sum - t) + v
+            } else {
+                (v - t) + self.sum
+            };
+        }
+        self.sum = t;
+    }
+
+    /// Returns the compensated total; infinities and NaN propagate as in a plain sum.
+    pub(crate) fn total(&self) -> f64 {
+        if self.sum.is_finite() {
+            self.sum + self.compensation
+        } else {
+            self.sum
+        }
+    }
+}
+
+/// A [`CompensatedSum`] over a sliding window that NaN and infinities cannot poison.
+///
+/// Non-finite samples are counted instead of summed, so once they leave the window the running
+/// sum is exact again; while one is inside, [`SlidingSum::total`] sums the window afresh.
+#[cfg(feature = "ndarray")]
+#[derive(Clone, Copy, Debug, Default)]
+pub(crate) struct SlidingSum {
+    sum: CompensatedSum,
+    non_finite: usize,
+}
+
+#[cfg(feature = "ndarray")]
+impl SlidingSum {
+    /// Adds a sample entering the window.
+    pub(crate) fn push(&mut self, v: f64) {
+        if v.is_finite() {
+            self.sum.add(v);
+        } else {
+            self.non_finite += 1;
+        }
+    }
+
+    /// Removes a sample leaving the window; it must have been pushed before.
+    pub(crate) fn pop(&mut self, v: f64) {
+        if v.is_finite() {
+            self.sum.add(-v);
+        } else {
+            self.non_finite -= 1;
+        }
+    }
+
+    /// Returns the sum of `window`, the samples pushed and not yet popped.
+    ///
+    /// The window is only iterated when it holds a non-finite sample or when the finite samples
+    /// overflowed, in which case the running sum is rebuilt from it.
+    pub(crate) fn total(&mut self, window: impl IntoIterator<Item = f64>) -> f64 {
+        if self.non_finite > 0 {
+            return compensated_sum(window);
+        }
+        if !self.sum.total().is_finite() {
+            self.sum = CompensatedSum::default();
+            window.into_iter().for_each(|v| self.sum.add(v));
+        }
+        self.sum.total()
+    }
+}
+
+/// Returns the compensated sum of `values`, see [`CompensatedSum`].
+#[cfg(feature = "ndarray")]
+pub(crate) fn compensated_sum(values: impl IntoIterator<Item = f64>) -> f64 {
+    values
+        .into_iter()
+        .fold(CompensatedSum::default(), |mut acc, v| {
+            acc.add(v);
+            acc
+        })
+        .total()
+}
@@ -1,13 +1,109 @@
-use crate::{prefix::Prefix, uom::Uom};
-use serde::{Deserialize, Serialize};
-use std::{
+#[cfg(feature = "std")]
+use crate::uom::{Per, Product};
+use crate::{
+    error::MeasurementError,
+    math::{
+        CompensatedSum, canonical_base, ceil, floor, pow10, powi10, rescale_decimal, round, trunc,
+    },
+    prefix::{NicePolicy, Prefix},
+    uom::{AffineUom, Celsius, Dimensionless, Kelvin, ScaledUnit, Second, Uom, UomDiv, UomMul},
+};
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::{
     cmp::Ordering,
+    fmt::{self, Write},
+    iter::Sum,
     marker::PhantomData,
-    ops::{Add, Sub},
+    ops::{Add, Div, Mul, Sub},
+    str::FromStr,
 };
+use serde::{Deserialize, Serialize};
+
+/// Formatting options for [`Measurement::label_with`].
+///
+/// The default style matches [`Measurement::label`].
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct LabelStyle {
+    /// Digits after the decimal point, or `None` for the shortest exact representation.
+    pub precision: Option<usize>,
+    /// Whether to put a space between the value and the unit ("100 mV").
+    pub space: bool,
+}
+
+/// Rounding direction for [`Measurement::round_to_decimals`],
+/// [`Measurement::round_to_multiple_of`] and [`RangedMeasurement::snap`].
+///
+/// [`RangedMeasurement::snap`]: crate::ranged_measurement::RangedMeasurement::snap
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RoundMode {
+    /// To the closest candidate, halfway cases away from zero.
+    Nearest,
+    /// Toward negative infinity.
+    Floor,
+    /// Toward positive infinity.
+    Ceil,
+    /// Toward zero, so the magnitude never grows.
+    TowardZero,
+}
+
+impl RoundMode {
+    /// Rounds `x` to an integer in this mode.
+    ///
+    /// Values within `1e-9` of an integer are taken to be that integer first, so that
+    /// e.g. `0.3 / 0.1` floors to 3 rather than 2.
+    pub(crate) fn apply(self, x: f64) -> f64 {
+        let nearest = round(x);
+        let x = if (x - nearest).abs() <= 1e-9 * x.abs().max(1.0) {
+            nearest
+        } else {
+            x
+        };
+        self.apply_exact(x)
+    }
+
+    /// Rounds `x` to an integer in this mode, without snapping nearby integers first.
+    pub(crate) fn apply_exact(self, x: f64) -> f64 {
+        match self {
+            Self::Nearest => round(x),
+            Self::Floor => floor(x),
+            Self::Ceil => ceil(x),
+            Self::TowardZero => trunc(x),
+        }
+    }
+}
+
+/// Returns `true` if `back`, a value converted away and back, differs from the
+/// `original` bits; two NaNs are considered equal.
+pub(crate) fn is_lossy(original: f64, back: f64) -> bool {
+    original.to_bits() != back.to_bits() && !(original.is_nan() && back.is_nan())
+}
+
+/// Returns `2^exp` for `exp` below 64.
+fn powi2(exp: u32) -> f64 {
+    (1u64 << exp) as f64
+}
 
 /// Represents a physical measurement with a value, SI prefix, and unit.
 ///
+/// # Const contexts
+/// [`Measurement::new_const`], [`Measurement::value`], [`Measurement::prefix`],
+/// [`Measurement::scaled`] and [`Measurement::convert_exp`] can be used to derive constants
+/// at compile time. Everything else, including the arithmetic operators, comparisons,
+/// [`Measurement::convert_to`], labels and parsing, is runtime-only.
+///
+/// # Equality
+/// Measurements are compared by their values converted to the base unit, the same
+/// canonical form [`OrderedMeasurement`](crate::ordered::OrderedMeasurement) uses, so
+/// `==` is symmetric and transitive across prefixes: 1.5mV equals 1500µV and 0.0015V.
+/// The float caveats remain: values a conversion rounds differently (0.7mV converted to µV
+/// may not equal 700µV), values closer than the rounding of that conversion (which may then
+/// compare equal even in the same prefix), and magnitudes that overflow or underflow in the
+/// base unit. NaN equals nothing, and `-0.0` equals `0.0`.
+///
 /// # Type Parameters
 /// - `U`: The unit of measurement, implementing the [`Uom`] trait.
 #[derive(Clone, Copy, Debug, Serialize, Deserialize)]
@@ -32,14 +128,142 @@ impl<U: Uom> Measurement<U> {
         }
     }
 
+    /// Creates a new [`Measurement`] in a const context.
+    ///
+    /// # Arguments
+    /// * `value` - The numeric value of the measurement.
+    /// * `prefix` - The SI prefix for the unit.
+    pub const fn new_const(value: f64, prefix: Prefix) -> Self {
+        Self {
+            value,
+            prefix,
+            uom: PhantomData,
+        }
+    }
+
     /// Returns the numeric value of the measurement.
-    pub fn value(&self) -> f64 {
+    pub const fn value(&self) -> f64 {
         self.value
     }
 
+    /// Creates a measurement of `value` in the base unit, without a prefix.
+    pub const fn base(value: f64) -> Self {
+        Self::new_const(value, Prefix::None)
+    }
+
+    /// Compares the measurement with `v` in the base unit, converting the measurement first,
+    /// so 1500 mV compares greater than 1.0.
+    ///
+    /// `Measurement` deliberately does not implement `PartialOrd<f64>`: `m > 5.0` would have
+    /// to guess whether 5.0 is in the base unit or in the prefix of `m`, and reads the same
+    /// either way. Naming the base unit here, or comparing with [`Measurement::base`], keeps
+    /// the intent visible.
+    ///
+    /// # Returns
+    /// The ordering, or `None` if either value is NaN.
+    pub fn cmp_base(&self, v: f64) -> Option<Ordering> {
+        self.partial_cmp(&Self::base(v))
+    }
+
+    /// Multiplies the value by `f`, keeping the prefix (e.g. a full scale divided by 256).
+    pub const fn scaled(self, f: f64) -> Self {
+        Self::new_const(self.value * f, self.prefix)
+    }
+
+    /// Moves the prefix by `exp_delta` powers of ten and rescales the value to match, so
+    /// 1.5V moved by -3 is 1500mV. The const counterpart of [`Measurement::convert_to`].
+    ///
+    /// # Panics
+    /// Panics, at compile time in a const context, if the new exponent has no SI prefix.
+    pub const fn convert_exp(self, exp_delta: i16) -> Self {
+        let Some(prefix) = Prefix::from_exact_exp_value(self.prefix.get_exp_value() + exp_delta)
+        else {
+            panic!("the shifted exponent has no SI prefix");
+        };
+        Self::new_const(self.value * pow10(-exp_delta), prefix)
+    }
+
     /// Returns a string label combining value, prefix, and unit (e.g., "1.0mV").
+    ///
+    /// Negative zero is written as `0`, and magnitudes below `1e-7` or from `1e21` on in
+    /// scientific notation (e.g. "5e-320mV"), so subnormal and huge values stay short. The
+    /// stored value is not changed.
     pub fn label(&self) -> String {
-        self.value.to_string() + self.prefix.get_label() + &U::uom()
+        let value = format_value(self.value, None);
+        format!("{value}{}{}", self.prefix.get_label(), U::uom_str())
+    }
+
+    /// Returns a label formatted according to `style` (e.g., "12.50 mV").
+    ///
+    /// Values are written as in [`Measurement::label`]; in scientific notation the
+    /// precision counts the digits after the point of the mantissa.
+    pub fn label_with(&self, style: &LabelStyle) -> String {
+        let (pfx, uom) = (self.prefix.get_label(), U::uom_str());
+        let sep = if style.space { " " } else { "" };
+        let value = format_value(self.value, style.precision);
+        format!("{value}{sep}{pfx}{uom}")
+    }
+
+    /// Returns a label of at most `n` characters: the [`Measurement::label`] if it fits,
+    /// otherwise the value in scientific notation with as many digits as fit.
+    ///
+    /// # Returns
+    /// The label, or `None` if even a one-digit mantissa does not fit in `n` characters.
+    pub fn label_max_chars(&self, n: usize) -> Option<String> {
+        let label = self.label();
+        if label.chars().count() <= n {
+            return Some(label);
+        }
+        let value = if self.value == 0.0 { 0.0 } else { self.value };
+        let (pfx, uom) = (self.prefix.get_label(), U::uom_str());
+        // 17 significant digits round-trip every f64
+        (0..17)
+            .rev()
+            .map(|p| format!("{value:.p$e}{pfx}{uom}"))
+            .find(|s| s.chars().count() <= n)
+    }
+
+    /// Returns a label whose value is written as the shortest decimal, with at most
+    /// `max_decimals` digits after the point, that matches the value up to the rounding of
+    /// a prefix conversion or of arithmetic (e.g. "0.3V" for 0.1V + 0.2V, where
+    /// [`Measurement::label`] writes "0.30000000000000004V").
+    ///
+    /// Values that are exact decimals in the source prefix are thus written without
+    /// `...0001` or `...9999` tails. Other values are rounded to `max_decimals` digits,
+    /// trailing zeros trimmed. Non-finite values and magnitudes from `1e21` on are written
+    /// as in [`Measurement::label`].
+    pub fn label_exact_decimal(&self, max_decimals: u32) -> String {
+        let (pfx, uom) = (self.prefix.get_label(), U::uom_str());
+        let value = self.value;
+        if !value.is_finite() || value.abs() >= 1e21 {
+            return format!("{}{pfx}{uom}", format_value(value, None));
+        }
+        let decimal = (0..=max_decimals)
+            .find_map(|d| exact_decimal(value, d))
+            .unwrap_or_else(|| {
+                let rounded = format!("{:.*}", max_decimals as usize, value);
+                trim_decimal(&rounded).to_string()
+            });
+        format!("{decimal}{pfx}{uom}")
+    }
+
+    /// Returns a spelled-out label (e.g., "12.5 millivolts"), for reports and screen readers.
+    ///
+    /// The unit name is singular only for a value of exactly 1.
+    pub fn long_label(&self) -> String {
+        let name = if self.value == 1.0 {
+            U::name()
+        } else {
+            U::plural()
+        };
+        format!(
+            "{} {}{}",
+            format_value(self.value, None),
+            self.prefix.name(),
+            name
+        )
+        .trim_end()
+        .to_string()
     }
 
     /// Converts the measurement to a different SI prefix, scaling the value accordingly.
@@ -57,25 +281,197 @@ impl<U: Uom> Measurement<U> {
         }
     }
 
+    /// Converts the measurement like [`Measurement::convert_to`] and reports whether the
+    /// conversion lost information.
+    ///
+    /// # Returns
+    /// The converted measurement, and `true` if converting it back to the original prefix
+    /// does not reproduce the original bits, e.g. 0.9 fA converted to TA. NaN round-trips
+    /// to NaN and is never reported as lossy.
+    pub fn convert_to_checked(&self, pfx: Prefix) -> (Self, bool) {
+        let converted = self.convert_to(pfx);
+        let back = converted.convert_to(self.prefix).value;
+        (converted, is_lossy(self.value, back))
+    }
+
     /// Returns the SI prefix associated with this measurement.
-    pub fn prefix(&self) -> Prefix {
+    pub const fn prefix(&self) -> Prefix {
         self.prefix
     }
 
-    /// Returns a "nice" representation of the measurement, adjusting the prefix for readability.
-    pub fn nice(self) -> Self {
-        let original_prefix = self.prefix();
-        let (e, s) = if self.value > 1.0 {
-            (self.value, 1)
+    /// Divides by a measurement of any unit, yielding the generic quotient unit [`Per`].
+    ///
+    /// Use the `/` operator on references to get a named unit where one is mapped
+    /// (e.g. V/A = Ω).
+    #[cfg(feature = "std")]
+    pub fn per<B: Uom>(&self, other: &Measurement<B>) -> Measurement<Per<U, B>> {
+        let exp = self.prefix.get_exp_value() - other.prefix.get_exp_value();
+        combine(self.value / other.value, exp)
+    }
+
+    /// Multiplies by a measurement of any unit, yielding the generic product unit [`Product`].
+    ///
+    /// Use the `*` operator on references to get a named unit where one is mapped
+    /// (e.g. V·A = W).
+    #[cfg(feature = "std")]
+    pub fn times<B: Uom>(&self, other: &Measurement<B>) -> Measurement<Product<U, B>> {
+        let exp = self.prefix.get_exp_value() + other.prefix.get_exp_value();
+        combine(self.value * other.value, exp)
+    }
+
+    /// Creates a measurement from a value in a [`ScaledUnit`], e.g. 5 minutes as 300 s.
+    ///
+    /// The result has no prefix.
+    pub fn from_scaled<V: Into<f64>>(value: V, unit: &ScaledUnit<U>) -> Self {
+        Measurement::new(value.into() * unit.factor(), Prefix::None)
+    }
+
+    /// Returns the value expressed in a [`ScaledUnit`], regardless of the stored prefix.
+    pub fn as_scaled(&self, unit: &ScaledUnit<U>) -> f64 {
+        self.convert_to(Prefix::None).value / unit.factor()
+    }
+
+    /// Returns a label in a [`ScaledUnit`] (e.g., "5 min").
+    pub fn label_in_scaled(&self, unit: &ScaledUnit<U>) -> String {
+        format!("{} {}", self.as_scaled(unit), unit.label())
+    }
+
+    /// Rounds the value to `n` decimal places in the current prefix.
+    ///
+    /// # Arguments
+    /// * `n` - The number of decimal places to keep.
+    /// * `mode` - The rounding direction.
+    ///
+    /// # Returns
+    /// A new [`Measurement`] in the same prefix (e.g. 1.25mV to one decimal is 1.3mV with
+    /// [`RoundMode::Nearest`] and 1.2mV with [`RoundMode::TowardZero`]).
+    pub fn round_to_decimals(&self, n: u32, mode: RoundMode) -> Self {
+        // beyond 10^400 every finite value is already integral
+        let scale = powi10(n.min(400) as i32);
+        let scaled = self.value * scale;
+        if !scaled.is_finite() {
+            return *self;
+        }
+        Measurement::new(mode.apply(scaled) / scale, self.prefix)
+    }
+
+    /// Rounds the value to a multiple of `step`, which may be in a different prefix.
+    ///
+    /// # Arguments
+    /// * `step` - The quantization step; its sign is ignored.
+    /// * `mode` - The rounding direction.
+    ///
+    /// # Returns
+    /// A new [`Measurement`] in the same prefix (e.g. -1.25V floored to a multiple of 0.5V
+    /// is -1.5V).
+    ///
+    /// # Panics
+    /// Panics if `step` is zero or not finite.
+    pub fn round_to_multiple_of(&self, step: Measurement<U>, mode: RoundMode) -> Self {
+        let step = step.convert_to(self.prefix).value.abs();
+        assert!(
+            step > 0.0 && step.is_finite(),
+            "step must be non-zero and finite"
+        );
+        let q = self.value / step;
+        if !q.is_finite() {
+            return *self;
+        }
+        let k = mode.apply(q);
+        // divide by integral reciprocals so that e.g. 7 × 0.1 comes out as exactly 0.7
+        let inv = 1.0 / step;
+        let value = if inv >= 1.0 && (inv - round(inv)).abs() <= 1e-9 * inv {
+            k / round(inv)
         } else {
-            (1.0 / self.value, -1)
+            k * step
         };
-        let exp = e.log10() as i16;
-        if exp < 3 {
-            return self;
+        Measurement::new(value, self.prefix)
+    }
+
+    /// Encodes the measurement as a signed integer number of `lsb` steps, as used by device
+    /// protocols (e.g. voltages sent as counts of 1/8 mV).
+    ///
+    /// The value is converted to the prefix of `lsb` before dividing, so e.g. 1 V in counts
+    /// of 0.125 mV is exactly 8000. A quotient within a few ulps of an integer counts as that
+    /// integer; anything further away is rounded with `mode`.
+    ///
+    /// # Arguments
+    /// * `lsb` - The value of one count; its sign is ignored.
+    /// * `bits` - The width of the signed (two's complement) integer, from 1 to 64.
+    /// * `mode` - The rounding direction for values between two counts.
+    ///
+    /// # Returns
+    /// The count; [`MeasurementError::OutOfRange`] if it does not fit in `bits` bits or the
+    /// value is not finite, or [`MeasurementError::InvalidOptions`] if `bits` is not in
+    /// `1..=64`.
+    ///
+    /// # Panics
+    /// Panics if `lsb` is zero or not finite.
+    pub fn to_fixed(
+        &self,
+        lsb: Measurement<U>,
+        bits: u32,
+        mode: RoundMode,
+    ) -> Result<i64, MeasurementError> {
+        if !(1..=64).contains(&bits) {
+            return Err(MeasurementError::InvalidOptions(
+                "bit width must be between 1 and 64",
+            ));
         }
-        let (p, _) = Prefix::from_exp_value(exp * s);
-        self.convert_to(p * original_prefix)
+        let step = lsb.value.abs();
+        assert!(
+            step > 0.0 && step.is_finite(),
+            "lsb must be non-zero and finite"
+        );
+        let q = self.convert_to(lsb.prefix).value / step;
+        let nearest = round(q);
+        let k = if (q - nearest).abs() <= 4.0 * f64::EPSILON * q.abs().max(1.0) {
+            nearest
+        } else {
+            mode.apply_exact(q)
+        };
+        // powers of two are exact, so the bounds hold even for 64 bits
+        let min = -powi2(bits - 1);
+        if k >= min && k < -min {
+            Ok(k as i64)
+        } else {
+            Err(MeasurementError::OutOfRange {
+                value: q,
+                min,
+                max: -min - 1.0,
+            })
+        }
+    }
+
+    /// Decodes `counts` steps of `lsb` into a measurement in the prefix of `lsb`, the
+    /// inverse of [`Measurement::to_fixed`].
+    ///
+    /// Counts beyond ±2^53 are not all representable as `f64` and are rounded.
+    pub fn from_fixed(counts: i64, lsb: Measurement<U>) -> Self {
+        Self::new(counts as f64 * lsb.value.abs(), lsb.prefix)
+    }
+
+    /// Returns a "nice" representation of the measurement, adjusting the prefix for readability.
+    ///
+    /// Equivalent to [`Measurement::nice_with`] with the default [`NicePolicy`], which puts
+    /// the value in `[1, 1000)` where a prefix allows it (e.g. 4700pF becomes 4.7nF).
+    pub fn nice(self) -> Self {
+        self.nice_with(&NicePolicy::new())
+    }
+
+    /// Converts the measurement to the prefix chosen by `policy`.
+    ///
+    /// # Arguments
+    /// * `policy` - The allowed prefixes and target mantissa range.
+    ///
+    /// # Returns
+    /// A new [`Measurement`] with the same value in the chosen prefix; zero and non-finite
+    /// values keep their prefix. The value is rescaled as the decimal it stands for, so
+    /// 9.99e-16 TV becomes 999 µV rather than 999.0000000000001 µV.
+    pub fn nice_with(&self, policy: &NicePolicy) -> Self {
+        let prefix = policy.choose(self.value, self.prefix);
+        let shift = self.prefix.get_exp_value() - prefix.get_exp_value();
+        Self::new(rescale_decimal(self.value, shift), prefix)
     }
 }
 
@@ -107,31 +503,226 @@ impl<U: Uom> Sub for Measurement<U> {
     }
 }
 
+impl<U: Uom> Sum for Measurement<U> {
+    /// Sums measurements in the prefix of the first one with compensated summation, so small
+    /// terms are not lost against a large running total. An empty iterator sums to zero with
+    /// no prefix.
+    fn sum<I: Iterator<Item = Self>>(mut iter: I) -> Self {
+        let Some(first) = iter.next() else {
+            return Measurement::new(0, Prefix::None);
+        };
+        let mut sum = CompensatedSum::default();
+        sum.add(first.value);
+        iter.for_each(|m| sum.add(m.convert_to(first.prefix).value));
+        Measurement::new(sum.total(), first.prefix)
+    }
+}
+
+impl<'a, U: Uom> Sum<&'a Measurement<U>> for Measurement<U> {
+    fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.copied().sum()
+    }
+}
+
+impl<U: Uom> fmt::Display for Measurement<U> {
+    /// Formats the measurement like [`Measurement::label`], e.g. `1.5mV`.
+    ///
+    /// A precision applies to the value (`{:.2}` gives `1.50mV`) and a width pads the whole
+    /// label (`{:>8}` gives `   1.5mV`), left-aligned by default.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let style = LabelStyle {
+            precision: f.precision(),
+            space: false,
+        };
+        pad(f, &self.label_with(&style))
+    }
+}
+
+/// A value as written in labels, with `-0` as `0` and magnitudes outside `[1e-7, 1e21)` in
+/// scientific notation, with `precision` digits after the point if given.
+///
+/// It is formatted in place, so a label is still built with a single allocation.
+pub(crate) struct LabelValue {
+    value: f64,
+    precision: Option<usize>,
+}
+
+impl fmt::Display for LabelValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let value = if self.value == 0.0 { 0.0 } else { self.value };
+        let abs = value.abs();
+        let scientific = abs.is_finite() && abs != 0.0 && !(1e-7..1e21).contains(&abs);
+        match (self.precision, scientific) {
+            (Some(p), true) => write!(f, "{value:.p$e}"),
+            (Some(p), false) => write!(f, "{value:.p$}"),
+            (None, true) => write!(f, "{value:e}"),
+            (None, false) => write!(f, "{value}"),
+        }
+    }
+}
+
+/// Returns `value` as written in labels, see [`LabelValue`].
+pub(crate) fn format_value(value: f64, precision: Option<usize>) -> LabelValue {
+    LabelValue { value, precision }
+}
+
+/// Returns `value` written with `decimals` digits after the point, trailing zeros trimmed,
+/// if that decimal matches `value` within the tolerance of a prefix conversion.
+///
+/// The digits come from the integer numerator of `value * 10^decimals`, so no float
+/// formatting artifact can reach them; the result is parsed back to check it.
+fn exact_decimal(value: f64, decimals: u32) -> Option<String> {
+    let tolerance = |x: f64| 4.0 * f64::EPSILON * x.abs().max(f64::MIN_POSITIVE);
+    let scaled = value * powi10(decimals as i32);
+    // beyond 2^53 the numerator has no exact digits left to recover
+    if scaled.abs() >= 9_007_199_254_740_992.0 {
+        return None;
+    }
+    let numerator = round(scaled);
+    if (scaled - numerator).abs() > tolerance(scaled) {
+        return None;
+    }
+    let digits = (numerator.abs() as u64).to_string();
+    let d = decimals as usize;
+    let digits = format!("{digits:0>width$}", width = d + 1);
+    let (int, frac) = digits.split_at(digits.len() - d);
+    let sign = if numerator < 0.0 { "-" } else { "" };
+    let decimal = format!("{sign}{}", trim_decimal(&format!("{int}.{frac}")));
+    let parsed: f64 = decimal.parse().ok()?;
+    ((parsed - value).abs() <= tolerance(value)).then_some(decimal)
+}
+
+/// Trims the trailing zeros of a decimal and then its point, writing negative zero as `0`.
+fn trim_decimal(s: &str) -> &str {
+    let s = if s.contains('.') {
+        s.trim_end_matches('0').trim_end_matches('.')
+    } else {
+        s
+    };
+    if s == "-0" { "0" } else { s }
+}
+
+/// Writes `s` padded to the formatter's width with its fill and alignment, ignoring the
+/// precision, which the callers have already applied to the numbers inside `s`.
+pub(crate) fn pad(f: &mut fmt::Formatter<'_>, s: &str) -> fmt::Result {
+    let len = s.chars().count();
+    let Some(fill) = f.width().and_then(|w| w.checked_sub(len)) else {
+        return f.write_str(s);
+    };
+    let (before, after) = match f.align() {
+        Some(fmt::Alignment::Right) => (fill, 0),
+        Some(fmt::Alignment::Center) => (fill / 2, fill - fill / 2),
+        Some(fmt::Alignment::Left) | None => (0, fill),
+    };
+    let c = f.fill();
+    (0..before).try_for_each(|_| f.write_char(c))?;
+    f.write_str(s)?;
+    (0..after).try_for_each(|_| f.write_char(c))
+}
+
+/// Formats every measurement in one common prefix, joined by `", "`.
+///
+/// The prefix puts the largest finite magnitude in `[1, 1000)`, as for
+/// [`RangedMeasurement::tick_labels`], so a column of values never switches prefix from one
+/// line to the next (e.g. `0.5mV, 1mV, 2000mV` becomes `0.0005V, 0.001V, 2V`).
+///
+/// # Returns
+/// The joined labels, or an empty string for an empty slice. If no value is finite and
+/// non-zero, the prefix of the first measurement is used.
+///
+/// [`RangedMeasurement::tick_labels`]: crate::ranged_measurement::RangedMeasurement::tick_labels
+pub fn format_all<U: Uom>(measurements: &[Measurement<U>], style: &LabelStyle) -> String {
+    let Some(first) = measurements.first() else {
+        return String::new();
+    };
+    let largest = measurements
+        .iter()
+        .map(|m| m.convert_to(Prefix::None).value.abs())
+        .filter(|v| v.is_finite())
+        .fold(0.0, f64::max);
+    let prefix = if largest > 0.0 {
+        NicePolicy::new().choose(largest, Prefix::None)
+    } else {
+        first.prefix
+    };
+    measurements
+        .iter()
+        .map(|m| m.convert_to(prefix).label_with(style))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Converts every measurement to one prefix chosen for the collection, e.g. before writing
+/// a report table from values gathered with mixed prefixes.
+///
+/// The prefix is the one [`NicePolicy`] picks for the median finite, non-zero magnitude, so
+/// a few very small or very large values do not decide it. Conversion keeps the order of
+/// the values.
+///
+/// # Returns
+/// The chosen prefix. If no value is finite and non-zero, the prefix of the first
+/// measurement is kept, or [`Prefix::None`] for an empty slice.
+pub fn harmonize<U: Uom>(items: &mut [Measurement<U>]) -> Prefix {
+    let prefix = harmonized_prefix(items);
+    harmonize_to(items, prefix);
+    prefix
+}
+
+/// Converts every measurement to `prefix` in place.
+pub fn harmonize_to<U: Uom>(items: &mut [Measurement<U>], prefix: Prefix) {
+    for m in items {
+        *m = m.convert_to(prefix);
+    }
+}
+
+/// Returns the prefix [`harmonize`] converts `items` to.
+pub(crate) fn harmonized_prefix<U: Uom>(items: &[Measurement<U>]) -> Prefix {
+    let Some(first) = items.first() else {
+        return Prefix::None;
+    };
+    let mut magnitudes = items
+        .iter()
+        .map(|m| m.convert_to(Prefix::None).value.abs())
+        .filter(|v| v.is_finite() && *v != 0.0)
+        .collect::<Vec<_>>();
+    if magnitudes.is_empty() {
+        return first.prefix;
+    }
+    magnitudes.sort_by(f64::total_cmp);
+    NicePolicy::new().choose(magnitudes[magnitudes.len() / 2], Prefix::None)
+}
+
+/// Returns the value of `value` in `prefix` in the base unit, in the canonical form that
+/// equality compares.
+fn base_value(value: f64, prefix: Prefix) -> f64 {
+    canonical_base(value, prefix.get_exp_value() as i32)
+}
+
+/// Returns `true` if `a` in `pa` and `b` in `pb` are the same quantity, compared in the
+/// base unit so that the comparison is symmetric and transitive. NaN equals nothing.
+pub(crate) fn same_quantity(a: f64, pa: Prefix, b: f64, pb: Prefix) -> bool {
+    base_value(a, pa) == base_value(b, pb)
+}
+
 impl<U: Uom> PartialEq for Measurement<U> {
-    /// Checks equality between two [`Measurement`]s, converting to the same prefix if necessary.
+    /// Checks equality between two [`Measurement`]s by their values in the base unit.
     fn eq(&self, other: &Self) -> bool {
-        self.convert_to(other.prefix()).value == other.value
+        same_quantity(self.value, self.prefix, other.value, other.prefix)
     }
 }
 
 impl<U: Uom> PartialOrd for Measurement<U> {
-    /// Compares two [`Measurement`]s, converting to the same prefix if necessary.
+    /// Compares two [`Measurement`]s by their values in the base unit, consistently with
+    /// equality.
+    ///
+    /// # Returns
+    /// The ordering, or `None` if either value is NaN.
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        let m1 = self.convert_to(other.prefix()).value();
-        let m2 = other.value();
-        if m1.is_nan() || m2.is_nan() {
-            None
-        } else if m1 == m2 {
-            Some(Ordering::Equal)
-        } else if m1 < m2 {
-            Some(Ordering::Less)
-        } else {
-            Some(Ordering::Greater)
-        }
+        base_value(self.value, self.prefix).partial_cmp(&base_value(other.value, other.prefix))
     }
 }
 
-impl<U: Uom, I: Into<f64>> std::ops::Mul<I> for Measurement<U> {
+impl<U: Uom, I: Into<f64>> Mul<I> for Measurement<U> {
     /// Multiplies a [`Measurement`] by a scalar.
     type Output = Measurement<U>;
     fn mul(self, rhs: I) -> Self::Output {
@@ -139,7 +730,37 @@ impl<U: Uom, I: Into<f64>> std::ops::Mul<I> for Measurement<U> {
     }
 }
 
-impl<U: Uom, I: Into<f64>> std::ops::Div<I> for Measurement<U> {
+/// Implements `scalar * x` for scalar types, as `x * f64::from(scalar)`, where `x` is a
+/// [`Measurement`] or, with a leading `&`, a reference to an array type.
+///
+/// Only concrete types can be listed: a blanket impl over `Into<f64>` on the left would
+/// conflict with the multiplication impls of the standard library.
+macro_rules! impl_left_scalar_mul {
+    ($target:ident: $($t:ty),*) => {$(
+        impl<U: Uom> Mul<$target<U>> for $t {
+            #[doc = concat!("Multiplies a scalar by a [`", stringify!($target), "`].")]
+            type Output = $target<U>;
+            fn mul(self, rhs: $target<U>) -> Self::Output {
+                rhs * f64::from(self)
+            }
+        }
+    )*};
+    (&$target:ident: $($t:ty),*) => {$(
+        impl<U: Uom> Mul<&$target<U>> for $t {
+            #[doc = concat!("Multiplies a scalar by a [`", stringify!($target), "`].")]
+            type Output = $target<U>;
+            fn mul(self, rhs: &$target<U>) -> Self::Output {
+                rhs * f64::from(self)
+            }
+        }
+    )*};
+}
+#[cfg(feature = "ndarray")]
+pub(crate) use impl_left_scalar_mul;
+
+impl_left_scalar_mul!(Measurement: f64, f32, i8, i16, i32, u8, u16, u32);
+
+impl<U: Uom, I: Into<f64>> Div<I> for Measurement<U> {
     /// Divides a [`Measurement`] by a scalar.
     type Output = Measurement<U>;
     fn div(self, rhs: I) -> Self::Output {
@@ -147,10 +768,566 @@ impl<U: Uom, I: Into<f64>> std::ops::Div<I> for Measurement<U> {
     }
 }
 
+impl<U: AffineUom> Measurement<U> {
+    /// Converts to the base unit, applying the scale and offset regardless of the stored prefix.
+    ///
+    /// # Returns
+    /// The measurement in [`AffineUom::Base`], with no prefix.
+    pub fn to_base(&self) -> Measurement<U::Base> {
+        let value = self.convert_to(Prefix::None).value;
+        Measurement::new(value * U::SCALE + U::OFFSET, Prefix::None)
+    }
+
+    /// Converts a measurement in the base unit into this affine unit, with no prefix.
+    pub fn from_base(base: &Measurement<U::Base>) -> Self {
+        let value = base.convert_to(Prefix::None).value;
+        Measurement::new((value - U::OFFSET) / U::SCALE, Prefix::None)
+    }
+}
+
+impl Measurement<Celsius> {
+    /// Converts to Kelvin (e.g. 25 m°C is 0.025 °C, i.e. 273.175 K).
+    pub fn to_kelvin(&self) -> Measurement<Kelvin> {
+        self.to_base()
+    }
+
+    /// Returns the temperature difference `self - other` as a Kelvin interval.
+    ///
+    /// The `-` operator also works between two Celsius values, but its result is still typed
+    /// as Celsius; this method makes the "difference, not temperature" meaning explicit.
+    pub fn delta(&self, other: &Measurement<Celsius>) -> Measurement<Kelvin> {
+        let d = *self - *other;
+        Measurement::new(d.value * Celsius::SCALE, d.prefix)
+    }
+}
+
+impl Measurement<Second> {
+    /// Creates a duration from minutes.
+    pub fn from_minutes<V: Into<f64>>(minutes: V) -> Self {
+        Self::from_scaled(minutes, &ScaledUnit::MINUTE)
+    }
+
+    /// Creates a duration from hours.
+    pub fn from_hours<V: Into<f64>>(hours: V) -> Self {
+        Self::from_scaled(hours, &ScaledUnit::HOUR)
+    }
+
+    /// Returns the duration in minutes.
+    pub fn as_minutes(&self) -> f64 {
+        self.as_scaled(&ScaledUnit::MINUTE)
+    }
+
+    /// Returns the duration in hours.
+    pub fn as_hours(&self) -> f64 {
+        self.as_scaled(&ScaledUnit::HOUR)
+    }
+
+    /// Creates the duration of `n` samples taken every `dt`, in the prefix of `dt`.
+    ///
+    /// The result is exact as long as `n * dt.value()` is representable, which holds for
+    /// integer intervals up to 2^53 (about 9·10^15) in total.
+    pub fn from_samples(n: usize, dt: Measurement<Second>) -> Self {
+        Self::new(n as f64 * dt.value(), dt.prefix)
+    }
+
+    /// Returns the number of whole samples taken every `dt` that fit in this duration.
+    ///
+    /// Both durations are compared in the finer of their prefixes, and a ratio within a few
+    /// ulps of an integer counts as that integer, so e.g. 300 ms at 100 ms is 3 samples
+    /// even though `0.3 / 0.1` is slightly below 3 in floating point.
+    ///
+    /// # Panics
+    /// Panics if this duration is negative or not finite, or if `dt` is not positive and finite.
+    pub fn num_samples(&self, dt: Measurement<Second>) -> usize {
+        floor(self.sample_ratio(dt).0) as usize
+    }
+
+    /// Returns the part of this duration left over after [`Measurement::num_samples`] whole
+    /// samples, in this duration's prefix. It lies in `[0, dt)`.
+    ///
+    /// # Panics
+    /// Panics under the same conditions as [`Measurement::num_samples`].
+    pub fn sample_remainder(&self, dt: Measurement<Second>) -> Self {
+        let (ratio, prefix) = self.sample_ratio(dt);
+        let total = self.convert_to(prefix).value();
+        let dt = dt.convert_to(prefix).value();
+        let remainder = (total - floor(ratio) * dt).max(0.0);
+        Self::new(remainder, prefix).convert_to(self.prefix)
+    }
+
+    /// Returns `self / dt`, snapped to a nearby integer, and the prefix it was computed in.
+    fn sample_ratio(&self, dt: Measurement<Second>) -> (f64, Prefix) {
+        assert!(
+            self.value >= 0.0 && self.value.is_finite(),
+            "duration must be non-negative and finite"
+        );
+        assert!(
+            dt.value > 0.0 && dt.value.is_finite(),
+            "sampling interval must be positive and finite"
+        );
+        let prefix = if self.prefix.get_exp_value() < dt.prefix.get_exp_value() {
+            self.prefix
+        } else {
+            dt.prefix
+        };
+        let ratio = self.convert_to(prefix).value() / dt.convert_to(prefix).value();
+        let nearest = round(ratio);
+        if (ratio - nearest).abs() <= 4.0 * f64::EPSILON * ratio {
+            (nearest, prefix)
+        } else {
+            (ratio, prefix)
+        }
+    }
+}
+
+impl Measurement<Kelvin> {
+    /// Converts to degrees Celsius, with no prefix.
+    pub fn to_celsius(&self) -> Measurement<Celsius> {
+        Measurement::from_base(self)
+    }
+}
+
+/// Builds a measurement from a combined exponent, folding any remainder not representable
+/// by a [`Prefix`] into the value.
+fn combine<U: Uom>(value: f64, exp: i16) -> Measurement<U> {
+    let (prefix, remainder) = Prefix::from_exp_value(exp);
+    Measurement::new(value * powi10(remainder as i32), prefix)
+}
+
+impl<A: UomMul<B>, B: Uom> Mul<&Measurement<B>> for &Measurement<A> {
+    /// Multiplies two [`Measurement`]s of different units (e.g. V·A = W), combining prefixes.
+    type Output = Measurement<A::Output>;
+    fn mul(self, rhs: &Measurement<B>) -> Self::Output {
+        combine(
+            self.value * rhs.value,
+            self.prefix.get_exp_value() + rhs.prefix.get_exp_value(),
+        )
+    }
+}
+
+impl<A: UomDiv<B>, B: Uom> Div<&Measurement<B>> for &Measurement<A> {
+    /// Divides two [`Measurement`]s of different units (e.g. V/A = Ω), combining prefixes.
+    type Output = Measurement<A::Output>;
+    fn div(self, rhs: &Measurement<B>) -> Self::Output {
+        combine(
+            self.value / rhs.value,
+            self.prefix.get_exp_value() - rhs.prefix.get_exp_value(),
+        )
+    }
+}
+
+impl From<Measurement<Dimensionless>> for f64 {
+    /// Returns the ratio as a plain number, applying the prefix (e.g. 1.5k becomes 1500).
+    fn from(m: Measurement<Dimensionless>) -> Self {
+        m.convert_to(Prefix::None).value
+    }
+}
+
+impl From<f64> for Measurement<Dimensionless> {
+    /// Wraps a plain number as a dimensionless measurement with no prefix.
+    fn from(value: f64) -> Self {
+        Measurement::new(value, Prefix::None)
+    }
+}
+
+/// Errors raised while parsing a [`Measurement`] from a label such as `"1.5mV"`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ParseMeasurementError {
+    /// The label does not start with a number.
+    Value(String),
+    /// The label does not end with the expected unit.
+    Unit { expected: String, found: String },
+    /// The text between the number and the unit is not a known prefix.
+    Prefix(String),
+    /// The label does not end with any registered unit.
+    UnknownUnit(String),
+    /// The number could be read with its separator either as a decimal point or as a
+    /// thousands separator, e.g. `"1,234"` when both are allowed by [`ParseOptions`].
+    Ambiguous(String),
+    /// The unit label splits into a prefix and a registered unit in more than one way, e.g.
+    /// `"m"` as meters or as milli with no unit; the readings are listed longest unit first.
+    AmbiguousUnit(Vec<Interpretation>),
+}
+
+/// One way of splitting a unit label into a prefix and a unit, as listed by
+/// [`ParseMeasurementError::AmbiguousUnit`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Interpretation {
+    /// The prefix read from the start of the label.
+    pub prefix: Prefix,
+    /// The label of the unit read from the end of the label.
+    pub unit: &'static str,
+}
+
+// prefixes are fieldless, so equality is total
+impl Eq for Interpretation {}
+
+impl fmt::Display for Interpretation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.prefix, self.unit) {
+            (Prefix::None, unit) => write!(f, "`{unit}` without a prefix"),
+            (prefix, "") => write!(f, "{} with no unit", prefix.name()),
+            (prefix, unit) => write!(f, "{} `{unit}`", prefix.name()),
+        }
+    }
+}
+
+impl fmt::Display for ParseMeasurementError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Value(s) => write!(f, "`{s}` does not start with a number"),
+            Self::Unit { expected, found } => {
+                write!(f, "unit mismatch: expected `{expected}`, found `{found}`")
+            }
+            Self::Prefix(p) => write!(f, "unknown prefix `{p}`"),
+            Self::UnknownUnit(u) => write!(f, "unknown unit in `{u}`"),
+            Self::Ambiguous(s) => write!(
+                f,
+                "`{s}` is ambiguous: its separator may mark decimals or group thousands"
+            ),
+            Self::AmbiguousUnit(readings) => {
+                let Some(first) = readings.first() else {
+                    return write!(f, "ambiguous unit");
+                };
+                let label = format!("{}{}", first.prefix.get_label(), first.unit);
+                write!(f, "`{label}` is ambiguous: it may be read as {first}")?;
+                readings[1..].iter().try_for_each(|r| write!(f, ", or {r}"))
+            }
+        }
+    }
+}
+
+impl core::error::Error for ParseMeasurementError {}
+
+impl<U: Uom> FromStr for Measurement<U> {
+    type Err = ParseMeasurementError;
+
+    /// Parses a label such as `"1.5mV"` or `"-2e3 uA"`, the inverse of [`Measurement::label`].
+    ///
+    /// The unit may also be written as one of its [`Uom::aliases`], e.g. `"50 ksps"` for
+    /// 50 kHz; labels are still written with the canonical unit.
+    ///
+    /// The unit is matched first, as a suffix, and only the remainder is read as a prefix.
+    /// This resolves labels where a unit and a prefix share a symbol: `"5mm"` is 5 millimeters
+    /// and `"5m"` is 5 meters, never 5 milli-meters with a missing unit.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse_with(s, &ParseOptions::default())
+    }
+}
+
+impl<U: Uom> Measurement<U> {
+    /// Parses a label like [`Measurement::from_str`], reading the number with `options`,
+    /// e.g. `"1,25mV"` with a decimal comma.
+    ///
+    /// # Returns
+    /// The measurement, or [`ParseMeasurementError::Ambiguous`] if both a decimal comma and
+    /// thousands separators are allowed and the number could be read either way.
+    pub fn parse_with(s: &str, options: &ParseOptions) -> Result<Self, ParseMeasurementError> {
+        let (value, label) = split_value_with(s, options)?;
+        Ok(Measurement::new(value, parse_unit::<U>(label)?))
+    }
+}
+
+/// Returns the prefix of a unit label such as `"mV"`, checking that it ends with the label
+/// of `U` or one of its [`Uom::aliases`].
+pub(crate) fn parse_unit<U: Uom>(label: &str) -> Result<Prefix, ParseMeasurementError> {
+    let mut prefixes = core::iter::once(U::uom_str())
+        .chain(U::aliases().iter().copied())
+        .filter_map(|unit| label.strip_suffix(unit));
+    let first = prefixes.next().ok_or_else(|| ParseMeasurementError::Unit {
+        expected: U::uom(),
+        found: label.to_string(),
+    })?;
+    core::iter::once(first)
+        .chain(prefixes)
+        .find_map(Prefix::from_label)
+        .ok_or_else(|| ParseMeasurementError::Prefix(first.to_string()))
+}
+
+/// How numbers are written in labels and files, for [`Measurement::parse_with`],
+/// [`RangedMeasurement::from_label_with`](crate::ranged_measurement::RangedMeasurement::from_label_with)
+/// and the CSV importer.
+///
+/// The default reads numbers as [`str::parse`] does, with a decimal point and no grouping.
+///
+/// With both options set, a number whose only separator is followed by exactly three
+/// digits, such as `"1,234"` or `"1.234"`, is rejected as
+/// [`ParseMeasurementError::Ambiguous`]: it is 1.234 by these options, but just as likely
+/// 1234 written by someone else.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ParseOptions {
+    /// Whether the decimal separator is a comma (`"1,25"`) rather than a point.
+    pub decimal_comma: bool,
+    /// Whether the integer part may be grouped by thousands, with a point when
+    /// `decimal_comma` is set (`"1.234,56"`) and with a comma otherwise (`"1,234.56"`).
+    pub allow_thousands_sep: bool,
+}
+
+impl ParseOptions {
+    /// Returns the separator between the numbers of a list, such as the bounds of a range or
+    /// the cells of a CSV row: `;` when a comma may appear in a number, `,` otherwise.
+    pub fn list_separator(&self) -> char {
+        if self.decimal_comma || self.allow_thousands_sep {
+            ';'
+        } else {
+            ','
+        }
+    }
+
+    /// Rewrites the leading number of `s`, e.g. `"1.234,5"`, in the form [`str::parse`]
+    /// reads, `"1234.5"`.
+    ///
+    /// # Returns
+    /// The rewritten number and the length of the text it replaces.
+    fn normalize(&self, s: &str) -> Result<(String, usize), ParseMeasurementError> {
+        let (decimal, group) = if self.decimal_comma {
+            (',', '.')
+        } else {
+            ('.', ',')
+        };
+        let sign = usize::from(s.starts_with(['+', '-']));
+        let len = sign
+            + s[sign..]
+                .find(|c: char| !(c.is_ascii_digit() || c == ',' || c == '.'))
+                .unwrap_or(s.len() - sign);
+        let (int, frac) = match s[sign..len].split_once(decimal) {
+            Some((int, frac)) => (int, Some(frac)),
+            None => (&s[sign..len], None),
+        };
+        let invalid = || ParseMeasurementError::Value(s.to_string());
+        if frac.is_some_and(|f| f.contains([',', '.'])) {
+            return Err(invalid());
+        }
+        let groups = int.split(group).collect::<Vec<_>>();
+        let single_separator = match frac {
+            Some(frac) => groups.len() == 1 && frac.len() == 3,
+            None => groups.len() == 2 && groups[1].len() == 3,
+        };
+        if self.decimal_comma && self.allow_thousands_sep && single_separator && groups[0] != "0" {
+            return Err(ParseMeasurementError::Ambiguous(s.to_string()));
+        }
+        if groups.len() > 1 {
+            let grouped = (1..=3).contains(&groups[0].len())
+                && !groups[0].starts_with('0')
+                && groups[1..].iter().all(|g| g.len() == 3);
+            if !(self.allow_thousands_sep && grouped) {
+                return Err(invalid());
+            }
+        }
+        let mut number = format!("{}{}", &s[..sign], groups.concat());
+        if let Some(frac) = frac {
+            number.push('.');
+            number.push_str(frac);
+        }
+        Ok((number, len))
+    }
+}
+
+/// Splits a label into its leading number, written as `options` allow, and the trimmed
+/// remainder, like [`split_value`].
+pub(crate) fn split_value_with<'a>(
+    s: &'a str,
+    options: &ParseOptions,
+) -> Result<(f64, &'a str), ParseMeasurementError> {
+    if *options == ParseOptions::default() {
+        return split_value(s);
+    }
+    let s = s.trim();
+    let (number, len) = options.normalize(s)?;
+    let rest = &s[len..];
+    // the exponent, if any, is still in `rest`
+    let candidate = format!("{number}{rest}");
+    let (value, remainder) = split_value(&candidate)?;
+    if remainder.len() > rest.len() {
+        return Err(ParseMeasurementError::Value(s.to_string()));
+    }
+    Ok((value, &rest[rest.len() - remainder.len()..]))
+}
+
+/// Parses `s` as a number alone, written as `options` allow.
+pub(crate) fn parse_number(s: &str, options: &ParseOptions) -> Result<f64, ParseMeasurementError> {
+    match split_value_with(s, options)? {
+        (value, "") => Ok(value),
+        _ => Err(ParseMeasurementError::Value(s.trim().to_string())),
+    }
+}
+
+/// Splits a label such as `"1.5 mV"` into its leading number and the trimmed remainder.
+///
+/// # Returns
+/// The longest leading substring that parses as an `f64`, and the text after it.
+pub(crate) fn split_value(s: &str) -> Result<(f64, &str), ParseMeasurementError> {
+    let s = s.trim();
+    s.char_indices()
+        .map(|(i, _)| i)
+        .chain([s.len()])
+        .rev()
+        .find_map(|i| Some((s[..i].parse::<f64>().ok()?, s[i..].trim_start())))
+        .ok_or_else(|| ParseMeasurementError::Value(s.to_string()))
+}
+
 #[cfg(test)]
 mod measurement_tests {
     use super::*;
-    use crate::uom::Volt;
+    use crate::uom::{
+        Ampere, Celsius, Dimensionless, Farad, Gram, Hertz, Kelvin, Liter, Meter, Mole, Ohm,
+        Pascal, Second, Siemens, Volt,
+    };
+
+    #[test]
+    fn equality_is_symmetric_across_prefixes() {
+        let v = Measurement::<Volt>::new(0.05, Prefix::None);
+        let uv = Measurement::<Volt>::new(50000.0, Prefix::Micro);
+        // 50000 * 1e-6 is not 0.05, but both are 0.05 V in the base unit
+        assert_eq!(v, uv);
+        assert_eq!(uv, v);
+        assert_eq!(v.partial_cmp(&uv), Some(Ordering::Equal));
+        let mut readings = vec![v, uv, v.convert_to(Prefix::Milli), uv.scaled(2.0)];
+        readings.dedup();
+        assert_eq!(readings, [v, uv.scaled(2.0)]);
+        assert_ne!(
+            Measurement::<Volt>::new(f64::NAN, Prefix::None),
+            v * f64::NAN
+        );
+        assert_eq!(Measurement::<Volt>::new(-0.0, Prefix::Milli), v * 0.0);
+    }
+
+    #[test]
+    fn exact_decimal_labels_hide_conversion_artifacts() {
+        let v = |value, from, to| Measurement::<Volt>::new(value, from).convert_to(to);
+        let (none, milli, micro) = (Prefix::None, Prefix::Milli, Prefix::Micro);
+        // the plain label shows the rounding of the arithmetic
+        let sum = Measurement::<Volt>::new(0.1 + 0.2, none);
+        assert_eq!(sum.label(), "0.30000000000000004V");
+        let table = [
+            (v(0.1, milli, micro), "100uV"),
+            (v(0.2, milli, micro), "200uV"),
+            (v(0.3, milli, micro), "300uV"),
+            (v(0.1, milli, none), "0.0001V"),
+            (v(0.3, micro, milli), "0.0003mV"),
+            (v(0.3, none, micro), "300000uV"),
+            (v(-0.7, milli, micro), "-700uV"),
+            (v(100.0, micro, milli), "0.1mV"),
+            (sum, "0.3V"),
+            (Measurement::new(1.25, none), "1.25V"),
+            (Measurement::new(-0.0, none), "0V"),
+        ];
+        for (m, expected) in table {
+            assert_eq!(m.label_exact_decimal(9), expected, "{}", m.label());
+        }
+        // values that are no short decimal are rounded
+        let third = Measurement::<Volt>::new(1.0 / 3.0, milli);
+        assert_eq!(third.label_exact_decimal(4), "0.3333mV");
+        assert_eq!(v(0.1, milli, none).label_exact_decimal(3), "0V");
+        assert_eq!(
+            Measurement::<Volt>::new(f64::NAN, milli).label_exact_decimal(3),
+            "NaNmV"
+        );
+        assert_eq!(
+            Measurement::<Volt>::new(2e21, none).label_exact_decimal(3),
+            "2e21V"
+        );
+    }
+
+    #[test]
+    fn reports_lossy_conversions() {
+        let fa = |v| Measurement::<Ampere>::new(v, Prefix::Femto);
+        // 1 fA survives the trip to TA and back, 0.9 fA does not
+        let (m, lossy) = fa(1.0).convert_to_checked(Prefix::Tera);
+        assert_eq!((m.value(), m.prefix(), lossy), (1e-27, Prefix::Tera, false));
+        assert!(fa(0.9).convert_to_checked(Prefix::Tera).1);
+        let mv = |v| Measurement::<Volt>::new(v, Prefix::Milli);
+        assert!(!mv(1.5).convert_to_checked(Prefix::Micro).1);
+        assert!(mv(0.7).convert_to_checked(Prefix::Micro).1);
+        assert!(!mv(0.7).convert_to_checked(Prefix::Milli).1);
+        // overflow and underflow are lossy, NaN is not
+        let big = Measurement::<Volt>::new(1e300, Prefix::Tera);
+        assert!(big.convert_to_checked(Prefix::Femto).1);
+        let tiny = Measurement::<Volt>::new(5e-324, Prefix::Milli);
+        assert!(tiny.convert_to_checked(Prefix::Kilo).1);
+        assert!(!mv(f64::NAN).convert_to_checked(Prefix::Micro).1);
+        assert!(!mv(-0.0).convert_to_checked(Prefix::Micro).1);
+    }
+
+    #[test]
+    fn fixed_point_round_trips_at_the_extremes() {
+        let lsb = Measurement::<Volt>::new(0.125, Prefix::Milli);
+        for bits in [8, 16, 24, 32, 53] {
+            let max = (1i64 << (bits - 1)) - 1;
+            for counts in [-max - 1, -max, -1, 0, 1, max] {
+                let m = Measurement::from_fixed(counts, lsb);
+                assert_eq!(m.to_fixed(lsb, bits, RoundMode::Nearest), Ok(counts));
+                let base = m.convert_to(Prefix::None);
+                assert_eq!(base.to_fixed(lsb, bits, RoundMode::Floor), Ok(counts));
+            }
+            let above = Measurement::from_fixed(max + 1, lsb);
+            let below = Measurement::from_fixed(-max - 2, lsb);
+            assert!(matches!(
+                above.to_fixed(lsb, bits, RoundMode::Nearest),
+                Err(MeasurementError::OutOfRange { .. })
+            ));
+            assert!(below.to_fixed(lsb, bits, RoundMode::Nearest).is_err());
+        }
+        let min = Measurement::from_fixed(i64::MIN, lsb);
+        assert_eq!(min.to_fixed(lsb, 64, RoundMode::Nearest), Ok(i64::MIN));
+        assert!(
+            min.scaled(2.0)
+                .to_fixed(lsb, 64, RoundMode::Nearest)
+                .is_err()
+        );
+        assert_eq!(
+            Measurement::from_fixed(-3, lsb),
+            Measurement::new(-0.375, Prefix::Milli)
+        );
+    }
+
+    #[test]
+    fn fixed_point_rounds_sub_lsb_values() {
+        let lsb = Measurement::<Volt>::new(0.125, Prefix::Milli);
+        // -1.0625mV is -8.5 counts
+        let m = Measurement::<Volt>::new(-1062.5, Prefix::Micro);
+        assert_eq!(m.to_fixed(lsb, 16, RoundMode::Nearest), Ok(-9));
+        assert_eq!(m.to_fixed(lsb, 16, RoundMode::Floor), Ok(-9));
+        assert_eq!(m.to_fixed(lsb, 16, RoundMode::Ceil), Ok(-8));
+        assert_eq!(m.to_fixed(lsb, 16, RoundMode::TowardZero), Ok(-8));
+        let tiny = Measurement::<Volt>::new(0.05, Prefix::Milli);
+        assert_eq!(tiny.to_fixed(lsb, 16, RoundMode::Nearest), Ok(0));
+        assert_eq!(tiny.to_fixed(lsb, 16, RoundMode::Ceil), Ok(1));
+        // a negative lsb is taken by magnitude, and 0.3 in steps of 0.1 is exactly 3
+        let step = Measurement::<Volt>::new(-0.1, Prefix::None);
+        let m = Measurement::<Volt>::new(0.3, Prefix::None);
+        assert_eq!(m.to_fixed(step, 8, RoundMode::Floor), Ok(3));
+        // near the top of a wide range, a fraction of a count still rounds by the mode
+        let big = Measurement::<Volt>::new(2_000_000_000.6, Prefix::None);
+        let unit = Measurement::new(1.0, Prefix::None);
+        assert_eq!(big.to_fixed(unit, 32, RoundMode::Floor), Ok(2_000_000_000));
+        assert_eq!(
+            big.to_fixed(unit, 32, RoundMode::Nearest),
+            Ok(2_000_000_001)
+        );
+        let nan = Measurement::<Volt>::new(f64::NAN, Prefix::None);
+        assert!(nan.to_fixed(unit, 32, RoundMode::Nearest).is_err());
+        assert!(matches!(
+            big.to_fixed(unit, 0, RoundMode::Nearest),
+            Err(MeasurementError::InvalidOptions(_))
+        ));
+    }
+
+    #[test]
+    fn compares_against_base_values() {
+        let m = Measurement::<Volt>::new(1500.0, Prefix::Milli);
+        assert_eq!(m.cmp_base(1.0), Some(Ordering::Greater));
+        assert_eq!(m.cmp_base(1.5), Some(Ordering::Equal));
+        assert_eq!(m.cmp_base(5.0), Some(Ordering::Less));
+        assert_eq!(m.cmp_base(f64::NAN), None);
+        assert!(m < Measurement::base(5.0));
+        assert_eq!(
+            Measurement::<Volt>::base(0.25),
+            Measurement::new(250.0, Prefix::Milli)
+        );
+        assert_eq!(Measurement::<Volt>::base(0.25).prefix(), Prefix::None);
+    }
 
     #[test]
     fn kilo_plus_kilo() {
@@ -265,4 +1442,642 @@ mod measurement_tests {
         let a = Measurement::<Volt>::new(1, Prefix::Milli);
         assert_eq!(a, a);
     }
+
+    #[test]
+    fn nice_with_policy() {
+        let no_giga = NicePolicy::new().without_prefixes(&[Prefix::Tera, Prefix::Giga]);
+        let v = Measurement::<Volt>::new(2.5e9, Prefix::None);
+        assert_eq!(v.nice().label(), "2.5GV");
+        assert_eq!(v.nice_with(&no_giga).label(), "2500MV");
+        let gauge = NicePolicy::new().with_range(0.1, 100.0);
+        let v = Measurement::<Volt>::new(250, Prefix::Milli);
+        assert_eq!(v.nice().label(), "250mV");
+        assert_eq!(v.nice_with(&gauge).label(), "0.25V");
+        let r = Measurement::<Ohm>::new(-2500, Prefix::None).nice();
+        assert_eq!(r.label(), "-2.5kΩ");
+        let v = Measurement::<Volt>::new(9.99e-16, Prefix::Tera).nice();
+        assert_eq!((v.value(), v.prefix()), (999.0, Prefix::Micro));
+        assert_eq!(
+            Measurement::<Volt>::new(1.001e-15, Prefix::Tera)
+                .nice()
+                .label(),
+            "1.001mV"
+        );
+        // a value with more than 15 significant digits keeps them
+        let v = Measurement::<Volt>::new(0.12345678901234568, Prefix::None).nice();
+        assert_eq!(v.value(), 0.12345678901234568 * 1e3);
+    }
+
+    #[test]
+    fn seal_resistance_nice() {
+        let seal = Measurement::<Ohm>::new(2500, Prefix::Mega).nice();
+        assert_eq!(seal.prefix(), Prefix::Giga);
+        assert_eq!(seal.label(), "2.5GΩ");
+        let seal = Measurement::<Ohm>::new(2.5e9, Prefix::None).nice();
+        assert_eq!(seal, Measurement::new(2.5, Prefix::Giga));
+    }
+
+    #[test]
+    fn capacitance_nice() {
+        let c = Measurement::<Farad>::new(12e-12, Prefix::None).nice();
+        assert_eq!(c.label(), "12pF");
+        let c = Measurement::<Farad>::new(4700, Prefix::Pico).nice();
+        assert_eq!(c.label(), "4.7nF");
+    }
+
+    #[test]
+    fn conductance_label() {
+        let g = Measurement::<Siemens>::new(0.4, Prefix::Nano).convert_to(Prefix::Pico);
+        assert_eq!(g.label(), "400pS");
+    }
+
+    #[test]
+    fn parse_label() {
+        let m: Measurement<Volt> = "1.5mV".parse().unwrap();
+        assert_eq!((m.value(), m.prefix()), (1.5, Prefix::Milli));
+        let m: Measurement<Volt> = " -2e3 uV ".parse().unwrap();
+        assert_eq!((m.value(), m.prefix()), (-2000.0, Prefix::Micro));
+        let m: Measurement<Volt> = "42V".parse().unwrap();
+        assert_eq!(m.prefix(), Prefix::None);
+        let m: Measurement<Ohm> = "2.5GΩ".parse().unwrap();
+        assert_eq!(m, Measurement::new(2.5, Prefix::Giga));
+    }
+
+    #[test]
+    fn parse_rate_aliases() {
+        let khz = Measurement::<Hertz>::new(20.0, Prefix::Kilo);
+        for s in ["20kHz", "20 kHz", "20 ksps", "20ksps"] {
+            let m: Measurement<Hertz> = s.parse().unwrap();
+            assert_eq!((m.value(), m.prefix()), (20.0, Prefix::Kilo), "{s}");
+            // formatting always uses the canonical label
+            assert_eq!(m.label(), khz.label(), "{s}");
+        }
+        assert_eq!(
+            "1.25 MHz".parse(),
+            Ok(Measurement::<Hertz>::new(1.25, Prefix::Mega))
+        );
+        assert_eq!(
+            "20 kHzz".parse::<Measurement<Hertz>>(),
+            Err(ParseMeasurementError::Unit {
+                expected: "Hz".to_string(),
+                found: "kHzz".to_string(),
+            })
+        );
+        assert_eq!(
+            "20 xsps".parse::<Measurement<Hertz>>(),
+            Err(ParseMeasurementError::Prefix("x".to_string()))
+        );
+        // an alias of one unit is not a label of another
+        assert!("20ksps".parse::<Measurement<Second>>().is_err());
+    }
+
+    #[test]
+    fn parse_unit_before_prefix() {
+        let m: Measurement<Meter> = "5mm".parse().unwrap();
+        assert_eq!((m.value(), m.prefix()), (5.0, Prefix::Milli));
+        let m: Measurement<Meter> = "5m".parse().unwrap();
+        assert_eq!((m.value(), m.prefix()), (5.0, Prefix::None));
+        let m: Measurement<Meter> = "12µm".parse().unwrap();
+        assert_eq!(m.prefix(), Prefix::Micro);
+        let m: Measurement<Mole> = "2mmol".parse().unwrap();
+        assert_eq!((m.value(), m.prefix()), (2.0, Prefix::Milli));
+        let m: Measurement<Gram> = "1.2kg".parse().unwrap();
+        assert_eq!(m.prefix(), Prefix::Kilo);
+        let m: Measurement<Kelvin> = "310K".parse().unwrap();
+        assert_eq!(m.prefix(), Prefix::None);
+        let m: Measurement<Dimensionless> = "5m".parse().unwrap();
+        assert_eq!(m.prefix(), Prefix::Milli);
+    }
+
+    #[test]
+    fn parse_two_char_unit() {
+        let m: Measurement<Pascal> = "12.5kPa".parse().unwrap();
+        assert_eq!((m.value(), m.prefix()), (12.5, Prefix::Kilo));
+        let m: Measurement<Pascal> = "101325Pa".parse().unwrap();
+        assert_eq!(m.prefix(), Prefix::None);
+        let m: Measurement<Liter> = "250uL".parse().unwrap();
+        assert_eq!(m.label(), "250uL");
+    }
+
+    #[test]
+    fn parse_multibyte_labels() {
+        let m: Measurement<Ohm> = "5µΩ".parse().unwrap();
+        assert_eq!((m.value(), m.prefix()), (5.0, Prefix::Micro));
+        let m: Measurement<Ohm> = "3 kΩ".parse().unwrap();
+        assert_eq!((m.value(), m.prefix()), (3.0, Prefix::Kilo));
+        assert_eq!(
+            "3kV".parse::<Measurement<Ohm>>(),
+            Err(ParseMeasurementError::Unit {
+                expected: "Ω".to_string(),
+                found: "kV".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(
+            "5mV".parse::<Measurement<Meter>>(),
+            Err(ParseMeasurementError::Unit {
+                expected: "m".to_string(),
+                found: "mV".to_string()
+            })
+        );
+        assert_eq!(
+            "5xV".parse::<Measurement<Volt>>(),
+            Err(ParseMeasurementError::Prefix("x".to_string()))
+        );
+        assert_eq!(
+            "mV".parse::<Measurement<Volt>>(),
+            Err(ParseMeasurementError::Value("mV".to_string()))
+        );
+    }
+
+    #[test]
+    fn parse_localized_numbers() {
+        let parse = |s, decimal_comma, allow_thousands_sep| {
+            let options = ParseOptions {
+                decimal_comma,
+                allow_thousands_sep,
+            };
+            Measurement::<Volt>::parse_with(s, &options)
+        };
+        let mv = |v: f64| Ok(Measurement::new(v, Prefix::Milli));
+        // European
+        assert_eq!(parse("1,25mV", true, false), mv(1.25));
+        assert_eq!(parse("-0,5 mV", true, false), mv(-0.5));
+        assert_eq!(parse("1,5e3mV", true, false), mv(1500.0));
+        assert_eq!(parse("1.234,56mV", true, true), mv(1234.56));
+        assert_eq!(parse("12.345.678mV", true, true), mv(12_345_678.0));
+        assert_eq!(parse("0,125mV", true, true), mv(0.125));
+        assert!(parse("1.234,56mV", true, false).is_err());
+        assert!(parse("1.25mV", true, false).is_err());
+        // English
+        assert_eq!(parse("1,234.5mV", false, true), mv(1234.5));
+        assert_eq!(parse("1,234mV", false, true), mv(1234.0));
+        assert_eq!(parse("1.25mV", false, false), mv(1.25));
+        assert!(parse("12,34mV", false, true).is_err());
+        assert!(parse("1,234,56mV", false, true).is_err());
+        assert!(parse("0,234mV", false, true).is_err());
+        // the unit is still checked
+        assert!(matches!(
+            parse("1,5mA", true, false),
+            Err(ParseMeasurementError::Unit { .. })
+        ));
+    }
+
+    #[test]
+    fn parse_rejects_ambiguous_separators() {
+        let options = ParseOptions {
+            decimal_comma: true,
+            allow_thousands_sep: true,
+        };
+        for s in ["1,234mV", "1.234mV", "-12,500 mV"] {
+            let e = Measurement::<Volt>::parse_with(s, &options).unwrap_err();
+            assert!(matches!(e, ParseMeasurementError::Ambiguous(_)), "{s}");
+        }
+        assert_eq!(
+            Measurement::<Volt>::parse_with("1,234mV", &options)
+                .unwrap_err()
+                .to_string(),
+            "`1,234mV` is ambiguous: its separator may mark decimals or group thousands"
+        );
+        // other digit counts only fit one reading
+        let parse = |s| Measurement::<Volt>::parse_with(s, &options).map(|m| m.value());
+        assert_eq!(parse("1,23mV"), Ok(1.23));
+        assert_eq!(parse("1,2345mV"), Ok(1.2345));
+        assert_eq!(parse("1.234,0mV"), Ok(1234.0));
+    }
+
+    #[test]
+    fn labels_of_extreme_values() {
+        let zero = Measurement::<Volt>::new(-0.0, Prefix::Milli);
+        assert_eq!(zero.label(), "0mV");
+        assert_eq!(format!("{zero:.2}"), "0.00mV");
+        assert_eq!(zero.long_label(), "0 millivolts");
+        assert!(zero.value().is_sign_negative());
+        let subnormal = Measurement::<Volt>::new(5e-320, Prefix::Milli);
+        assert_eq!(subnormal.label(), "5e-320mV");
+        assert_eq!(format!("{subnormal:.2}"), "5.00e-320mV");
+        let huge = Measurement::<Volt>::new(1e308, Prefix::None);
+        assert_eq!(huge.label(), "1e308V");
+        assert_eq!(huge.label().parse::<Measurement<Volt>>(), Ok(huge));
+        // the plain notation is kept in between
+        assert_eq!(
+            Measurement::<Volt>::new(1e-7, Prefix::None).label(),
+            "0.0000001V"
+        );
+        assert_eq!(
+            Measurement::<Volt>::new(1e20, Prefix::None).label(),
+            "100000000000000000000V"
+        );
+    }
+
+    #[test]
+    fn labels_within_a_length() {
+        let m = Measurement::<Volt>::new(1.23456789, Prefix::Milli);
+        assert_eq!(m.label_max_chars(12).as_deref(), Some("1.23456789mV"));
+        assert_eq!(m.label_max_chars(11).as_deref(), Some("1.23457e0mV"));
+        assert_eq!(m.label_max_chars(5).as_deref(), Some("1e0mV"));
+        assert_eq!(m.label_max_chars(4), None);
+        let huge = Measurement::<Volt>::new(-1.7e300, Prefix::None);
+        assert_eq!(huge.label_max_chars(10).as_deref(), Some("-1.7e300V"));
+        assert_eq!(huge.label_max_chars(8).as_deref(), Some("-2e300V"));
+    }
+
+    #[test]
+    fn label_round_trip() {
+        let m = Measurement::<Meter>::new(0.25, Prefix::Milli);
+        assert_eq!(m.label().parse::<Measurement<Meter>>(), Ok(m));
+    }
+
+    #[test]
+    fn dimensionless() {
+        let gain = Measurement::<Dimensionless>::new(1.5, Prefix::Kilo);
+        assert_eq!(gain.label(), "1.5k");
+        assert_eq!(Measurement::<Dimensionless>::from(0.25).label(), "0.25");
+        assert_eq!(f64::from(gain), 1500.0);
+        let ratio: Measurement<Dimensionless> = 2.0.into();
+        assert_eq!(ratio, Measurement::new(2.0, Prefix::None));
+    }
+
+    #[test]
+    fn round_to_decimals_modes() {
+        use RoundMode::*;
+        let cases = [
+            (1.25, [1.3, 1.2, 1.3, 1.2]),
+            (-1.25, [-1.3, -1.3, -1.2, -1.2]),
+            (1.24, [1.2, 1.2, 1.3, 1.2]),
+            (-1.26, [-1.3, -1.3, -1.2, -1.2]),
+            (0.3, [0.3, 0.3, 0.3, 0.3]),
+            (-0.05, [-0.1, -0.1, 0.0, 0.0]),
+        ];
+        for (v, expected) in cases {
+            let m = Measurement::<Volt>::new(v, Prefix::Milli);
+            for (mode, e) in [Nearest, Floor, Ceil, TowardZero].into_iter().zip(expected) {
+                let r = m.round_to_decimals(1, mode);
+                assert_eq!(r.value(), e, "{v} {mode:?}");
+                assert_eq!(r.prefix(), Prefix::Milli);
+            }
+        }
+        let m = Measurement::<Volt>::new(2.5, Prefix::None);
+        assert_eq!(m.round_to_decimals(0, Nearest).value(), 3.0);
+        assert_eq!(m.round_to_decimals(u32::MAX, Floor).value(), 2.5);
+    }
+
+    #[test]
+    fn round_to_multiple_of_modes() {
+        use RoundMode::*;
+        let half = Measurement::<Volt>::new(0.5, Prefix::None);
+        let cases = [
+            (-1.25, [-1.5, -1.5, -1.0, -1.0]),
+            (1.25, [1.5, 1.0, 1.5, 1.0]),
+            (-0.75, [-1.0, -1.0, -0.5, -0.5]),
+            (1.1, [1.0, 1.0, 1.5, 1.0]),
+            (-1.0, [-1.0, -1.0, -1.0, -1.0]),
+        ];
+        for (v, expected) in cases {
+            let m = Measurement::<Volt>::new(v, Prefix::None);
+            for (mode, e) in [Nearest, Floor, Ceil, TowardZero].into_iter().zip(expected) {
+                assert_eq!(
+                    m.round_to_multiple_of(half, mode).value(),
+                    e,
+                    "{v} {mode:?}"
+                );
+            }
+        }
+        let tenth = Measurement::<Volt>::new(0.1, Prefix::None);
+        let m = Measurement::<Volt>::new(0.3, Prefix::None);
+        assert_eq!(m.round_to_multiple_of(tenth, Floor).value(), 0.3);
+        let m = Measurement::<Volt>::new(0.68, Prefix::None);
+        assert_eq!(m.round_to_multiple_of(tenth, Ceil).value(), 0.7);
+    }
+
+    #[test]
+    fn round_to_multiple_of_other_prefix() {
+        let m = Measurement::<Volt>::new(1234, Prefix::Micro);
+        let step = Measurement::<Volt>::new(1, Prefix::Milli);
+        let r = m.round_to_multiple_of(step, RoundMode::Nearest);
+        assert_eq!(r.prefix(), Prefix::Micro);
+        assert_eq!(r.value(), 1000.0);
+        assert_eq!(
+            m.round_to_multiple_of(step, RoundMode::Ceil).value(),
+            2000.0
+        );
+        let m = Measurement::<Volt>::new(1.2345, Prefix::Milli);
+        let step = Measurement::<Volt>::new(5, Prefix::Micro);
+        assert_eq!(
+            m.round_to_multiple_of(step, RoundMode::Nearest).value(),
+            1.235
+        );
+        assert_eq!(
+            m.round_to_multiple_of(step, RoundMode::TowardZero).value(),
+            1.23
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "step must be non-zero and finite")]
+    fn round_to_zero_step_panics() {
+        let m = Measurement::<Volt>::new(1, Prefix::None);
+        m.round_to_multiple_of(Measurement::new(0, Prefix::None), RoundMode::Nearest);
+    }
+
+    #[test]
+    fn label_with_style() {
+        let m = Measurement::<Volt>::new(12.5, Prefix::Milli);
+        assert_eq!(m.label_with(&LabelStyle::default()), m.label());
+        let style = LabelStyle {
+            precision: Some(2),
+            space: true,
+        };
+        assert_eq!(m.label_with(&style), "12.50 mV");
+    }
+
+    #[test]
+    fn long_label() {
+        assert_eq!(
+            Measurement::<Volt>::new(12.5, Prefix::Milli).long_label(),
+            "12.5 millivolts"
+        );
+        assert_eq!(
+            Measurement::<Volt>::new(1, Prefix::None).long_label(),
+            "1 volt"
+        );
+        assert_eq!(
+            Measurement::<Siemens>::new(3, Prefix::Nano).long_label(),
+            "3 nanosiemens"
+        );
+        assert_eq!(
+            Measurement::<Dimensionless>::new(2, Prefix::None).long_label(),
+            "2"
+        );
+    }
+
+    #[test]
+    fn unit_algebra() {
+        let v = Measurement::<Volt>::new(10, Prefix::Milli);
+        let i = Measurement::<Ampere>::new(2, Prefix::Pico);
+        let r: Measurement<Ohm> = &v / &i;
+        assert_eq!(r, Measurement::new(5, Prefix::Giga));
+        let ratio: Measurement<Dimensionless> = &v / &v;
+        assert_eq!(f64::from(ratio), 1.0);
+        let q = &i * &Measurement::<Second>::new(3, Prefix::Milli);
+        assert_eq!(q.label(), "6fC");
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn generic_compounds() {
+        let v = Measurement::<Volt>::new(10, Prefix::Milli);
+        let t = Measurement::<Second>::new(2, Prefix::Micro);
+        let slew = v.per(&t);
+        assert_eq!(slew.label(), "5kV/s");
+        let back: Measurement<Volt> = &slew * &t;
+        assert_eq!(back, v);
+        let charge = Measurement::<Ampere>::new(3, Prefix::Nano).times(&t);
+        assert_eq!(charge.label(), "6fA·s");
+        let per_volt = charge.per(&v);
+        assert_eq!(per_volt.label(), "0.6pA·s/V");
+        let current: Measurement<Ampere> = &charge / &t;
+        assert_eq!(current, Measurement::new(3, Prefix::Nano));
+    }
+
+    fn assert_close<U: Uom>(m: Measurement<U>, expected: f64) {
+        let v = m.convert_to(Prefix::None).value();
+        assert!((v - expected).abs() < 1e-9, "{v} != {expected}");
+    }
+
+    #[test]
+    fn celsius_to_kelvin() {
+        assert_close(
+            Measurement::<Celsius>::new(0, Prefix::None).to_kelvin(),
+            273.15,
+        );
+        assert_close(
+            Measurement::<Celsius>::new(-40, Prefix::None).to_kelvin(),
+            233.15,
+        );
+        assert_close(
+            Measurement::<Celsius>::new(-273.15, Prefix::None).to_kelvin(),
+            0.0,
+        );
+        assert_close(
+            Measurement::<Celsius>::new(25, Prefix::Milli).to_kelvin(),
+            273.175,
+        );
+    }
+
+    #[test]
+    fn kelvin_to_celsius() {
+        assert_close(
+            Measurement::<Kelvin>::new(273.15, Prefix::None).to_celsius(),
+            0.0,
+        );
+        assert_close(
+            Measurement::<Kelvin>::new(233.15, Prefix::None).to_celsius(),
+            -40.0,
+        );
+        assert_close(
+            Measurement::<Kelvin>::new(0, Prefix::None).to_celsius(),
+            -273.15,
+        );
+        assert_close(
+            Measurement::<Kelvin>::new(310150, Prefix::Milli).to_celsius(),
+            37.0,
+        );
+    }
+
+    #[test]
+    fn celsius_delta() {
+        let a = Measurement::<Celsius>::new(37, Prefix::None);
+        let b = Measurement::<Celsius>::new(21, Prefix::None);
+        assert_eq!(a.delta(&b), Measurement::<Kelvin>::new(16, Prefix::None));
+    }
+
+    #[test]
+    fn display_honors_precision_and_width() {
+        let m = Measurement::<Volt>::new(1.5, Prefix::Milli);
+        assert_eq!(m.to_string(), "1.5mV");
+        assert_eq!(format!("{m:.2}"), "1.50mV");
+        assert_eq!(format!("{m:>8}"), "   1.5mV");
+        assert_eq!(format!("{m:8.1}|"), "1.5mV   |");
+        assert_eq!(format!("{m:*^9.0}"), "***2mV***");
+        let pair = (m, Measurement::<Ohm>::new(-20, Prefix::Kilo));
+        assert_eq!(
+            format!("({:.3}, {:.3})", pair.0, pair.1),
+            "(1.500mV, -20.000kΩ)"
+        );
+    }
+
+    #[test]
+    fn format_all_shares_one_prefix() {
+        let column = [
+            Measurement::<Volt>::new(500, Prefix::Micro),
+            Measurement::new(1, Prefix::Milli),
+            Measurement::new(20, Prefix::Milli),
+        ];
+        assert_eq!(
+            format_all(&column, &LabelStyle::default()),
+            "0.5mV, 1mV, 20mV"
+        );
+        let style = LabelStyle {
+            precision: Some(3),
+            space: true,
+        };
+        let mut column = column.to_vec();
+        column.push(Measurement::new(2, Prefix::None));
+        assert_eq!(
+            format_all(&column, &style),
+            "0.001 V, 0.001 V, 0.020 V, 2.000 V"
+        );
+        column.push(Measurement::new(f64::NAN, Prefix::Kilo));
+        assert_eq!(format_all(&column[3..], &LabelStyle::default()), "2V, NaNV");
+        assert_eq!(format_all::<Volt>(&[], &style), "");
+        let zeros = [Measurement::<Volt>::new(0, Prefix::Nano); 2];
+        assert_eq!(format_all(&zeros, &LabelStyle::default()), "0nV, 0nV");
+    }
+
+    #[test]
+    fn harmonizes_to_the_median_magnitude() {
+        let original = [
+            Measurement::<Volt>::new(2, Prefix::Kilo),
+            Measurement::new(5, Prefix::Micro),
+            Measurement::new(1.5, Prefix::None),
+            Measurement::new(20, Prefix::Milli),
+            Measurement::new(300_000, Prefix::Micro),
+        ];
+        let mut items = original;
+        // the median magnitude is 300mV
+        assert_eq!(harmonize(&mut items), Prefix::Milli);
+        assert!(items.iter().all(|m| m.prefix() == Prefix::Milli));
+        assert_eq!(items, original);
+        let rank = |ms: &[Measurement<Volt>]| {
+            let mut idx = (0..ms.len()).collect::<Vec<_>>();
+            idx.sort_by(|&i, &j| ms[i].partial_cmp(&ms[j]).unwrap());
+            idx
+        };
+        assert_eq!(rank(&items), rank(&original));
+        assert_eq!(items[0].value(), 2_000_000.0);
+        harmonize_to(&mut items, Prefix::Kilo);
+        assert_eq!(items[2], Measurement::new(0.0015, Prefix::Kilo));
+        let mut empty: [Measurement<Volt>; 0] = [];
+        assert_eq!(harmonize(&mut empty), Prefix::None);
+        let mut zeros = [Measurement::<Volt>::new(0, Prefix::Nano); 2];
+        assert_eq!(harmonize(&mut zeros), Prefix::Nano);
+    }
+
+    #[test]
+    fn minutes_and_hours() {
+        let d = Measurement::<Second>::from_minutes(5);
+        assert_eq!(d, Measurement::new(300, Prefix::None));
+        assert_eq!(d.as_minutes(), 5.0);
+        assert_eq!(d.label_in_scaled(&ScaledUnit::MINUTE), "5 min");
+        let h = Measurement::<Second>::from_hours(2);
+        assert_eq!(h.as_hours(), 2.0);
+        assert_eq!(
+            Measurement::<Second>::new(90000, Prefix::Milli).as_minutes(),
+            1.5
+        );
+    }
+
+    const FULL_SCALE: Measurement<Volt> = Measurement::new_const(2.5, Prefix::None);
+    const MAX_STEP: Measurement<Volt> = FULL_SCALE.scaled(1.0 / 256.0);
+    const MAX_STEP_MV: Measurement<Volt> = MAX_STEP.convert_exp(-3);
+    const _: () = assert!(MAX_STEP_MV.value() == 9.765625);
+
+    #[test]
+    fn const_arithmetic() {
+        assert_eq!(MAX_STEP.value(), 0.009765625);
+        assert_eq!(MAX_STEP.prefix(), Prefix::None);
+        assert_eq!(MAX_STEP_MV.prefix(), Prefix::Milli);
+        assert_eq!(MAX_STEP_MV, MAX_STEP);
+        const KILO: Measurement<Volt> = Measurement::new_const(1500.0, Prefix::None).convert_exp(3);
+        assert_eq!((KILO.value(), KILO.prefix()), (1.5, Prefix::Kilo));
+        // the const path agrees bit for bit with the runtime conversion
+        for from in Prefix::ALL {
+            for to in Prefix::ALL {
+                let m = Measurement::<Volt>::new(1.234, from);
+                let delta = to.get_exp_value() - from.get_exp_value();
+                assert_eq!(m.convert_exp(delta).value(), m.convert_to(to).value());
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "the shifted exponent has no SI prefix")]
+    fn convert_exp_needs_a_prefix() {
+        Measurement::<Volt>::new(1.0, Prefix::None).convert_exp(1);
+    }
+
+    #[test]
+    fn sample_counts() {
+        let dt = Measurement::<Second>::new(100, Prefix::Milli);
+        let d = Measurement::<Second>::from_samples(3, dt);
+        assert_eq!(d, Measurement::new(300, Prefix::Milli));
+        assert_eq!(
+            Measurement::<Second>::new(0.3, Prefix::None).num_samples(dt),
+            3
+        );
+        assert_eq!(
+            Measurement::<Second>::new(0.3, Prefix::None).sample_remainder(dt),
+            Measurement::new(0, Prefix::None)
+        );
+
+        // 1 s at 300 µs: 3333 samples and 100 µs left over
+        let total = Measurement::<Second>::new(1, Prefix::None);
+        let dt = Measurement::<Second>::new(300, Prefix::Micro);
+        assert_eq!(total.num_samples(dt), 3333);
+        let remainder = total.sample_remainder(dt);
+        assert_eq!(remainder.prefix(), Prefix::None);
+        assert!((remainder.value() - 100e-6).abs() < 1e-12);
+        assert_eq!(
+            Measurement::<Second>::new(0, Prefix::None).num_samples(dt),
+            0
+        );
+    }
+
+    #[test]
+    fn sample_counts_beyond_u32() {
+        let dt = Measurement::<Second>::new(20, Prefix::Micro);
+        for n in [1usize << 40, 123_456_789_012_345, 450_359_962_737_049] {
+            let d = Measurement::<Second>::from_samples(n, dt);
+            assert_eq!(d.num_samples(dt), n);
+            assert_eq!(d.sample_remainder(dt).value(), 0.0);
+        }
+        // 11.5 days at 1 ns, given in seconds: compared in nanoseconds
+        let d = Measurement::<Second>::new(1_000_000, Prefix::None);
+        assert_eq!(
+            d.num_samples(Measurement::new(1, Prefix::Nano)),
+            1_000_000_000_000_000
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "sampling interval must be positive and finite")]
+    fn sample_counts_reject_zero_interval() {
+        Measurement::<Second>::new(1, Prefix::None).num_samples(Measurement::new(0, Prefix::None));
+    }
+
+    #[test]
+    fn scaled_units_are_never_nice() {
+        assert_eq!(
+            Measurement::<Second>::from_minutes(5).nice().label(),
+            "300s"
+        );
+        assert_eq!(Measurement::<Second>::from_hours(2).nice().label(), "7.2ks");
+    }
+
+    #[test]
+    fn pressure_and_length() {
+        let atm = Measurement::<Pascal>::from_scaled(760, &ScaledUnit::MMHG);
+        assert!((atm.value() - 101325.0).abs() < 0.1);
+        assert!((atm.as_scaled(&ScaledUnit::MMHG) - 760.0).abs() < 1e-9);
+        let inch = Measurement::<Meter>::from_scaled(1, &ScaledUnit::INCH);
+        assert_eq!(inch.convert_to(Prefix::Milli).value(), 25.4);
+        assert_eq!(
+            Measurement::<Meter>::new(50.8, Prefix::Milli).label_in_scaled(&ScaledUnit::INCH),
+            "2 in"
+        );
+    }
 }
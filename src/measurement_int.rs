@@ -0,0 +1,573 @@
+//! Measurements with integer values, for counts, codes and timings that must stay exact.
+//!
+//! A [`MeasurementInt`] only changes prefix when the result is still an integer, so 1500 µs
+//! converts to 1.5 ms with [`Measurement`] but is an error here rather than a truncation.
+
+use crate::{
+    error::MeasurementError,
+    math::{canonical_base, round},
+    measurement::Measurement,
+    prefix::Prefix,
+    ranged_measurement::RangedMeasurement,
+    uom::Uom,
+};
+use alloc::{format, string::String};
+use core::{
+    cmp::Ordering,
+    fmt,
+    marker::PhantomData,
+    ops::{Add, Mul, Sub},
+};
+use serde::{Deserialize, Serialize};
+
+/// A measurement whose value is an `i64` count of its prefixed unit, e.g. 1500 µs.
+///
+/// Comparisons are exact across prefixes, and arithmetic reports overflow instead of
+/// wrapping.
+///
+/// # Type Parameters
+/// - `U`: The unit of measurement, implementing the [`Uom`] trait.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct MeasurementInt<U: Uom> {
+    value: i64,
+    prefix: Prefix,
+    #[serde(skip)]
+    uom: PhantomData<U>,
+}
+
+/// Returns the finer of two prefixes.
+fn finer(a: Prefix, b: Prefix) -> Prefix {
+    if a.get_exp_value() <= b.get_exp_value() {
+        a
+    } else {
+        b
+    }
+}
+
+impl<U: Uom> MeasurementInt<U> {
+    /// Creates a new [`MeasurementInt`] with the given value and prefix.
+    ///
+    /// # Arguments
+    /// * `value` - The integer value of the measurement.
+    /// * `prefix` - The SI prefix for the unit.
+    pub const fn new(value: i64, prefix: Prefix) -> Self {
+        Self {
+            value,
+            prefix,
+            uom: PhantomData,
+        }
+    }
+
+    /// Converts `m` to `prefix` and takes the result as an integer.
+    ///
+    /// Results within a few ulps of an integer are taken to be that integer, so that e.g.
+    /// 4.03 ms is 4030 µs even though `4.03 * 1000` is not exactly 4030 in `f64`.
+    ///
+    /// # Returns
+    /// The integer measurement; [`MeasurementError::NotIntegral`] if the converted value is
+    /// not an integer or not finite, or [`MeasurementError::IntegerOverflow`] if it does not
+    /// fit in an `i64`.
+    pub fn from_measurement(m: Measurement<U>, prefix: Prefix) -> Result<Self, MeasurementError> {
+        let exp = m.prefix().get_exp_value() - prefix.get_exp_value();
+        let q = canonical_base(m.value(), exp as i32);
+        let nearest = round(q);
+        if !q.is_finite() || (q - nearest).abs() > 4.0 * f64::EPSILON * q.abs().max(1.0) {
+            return Err(MeasurementError::NotIntegral { value: q });
+        }
+        // -2^63 and 2^63 are exact, so the bounds hold at the extremes
+        if nearest < i64::MIN as f64 || nearest >= -(i64::MIN as f64) {
+            return Err(MeasurementError::IntegerOverflow(
+                "the value does not fit in i64",
+            ));
+        }
+        Ok(Self::new(nearest as i64, prefix))
+    }
+
+    /// Returns the integer value of the measurement.
+    pub const fn value(&self) -> i64 {
+        self.value
+    }
+
+    /// Returns the SI prefix associated with this measurement.
+    pub const fn prefix(&self) -> Prefix {
+        self.prefix
+    }
+
+    /// Returns the label of the measurement (e.g., "1500us").
+    pub fn label(&self) -> String {
+        format!("{}{}{}", self.value, self.prefix.get_label(), U::uom_str())
+    }
+
+    /// Converts the measurement to a different SI prefix, exactly.
+    ///
+    /// # Returns
+    /// The converted measurement; [`MeasurementError::NotIntegral`] if the value is not a
+    /// whole number of the target unit, e.g. 1500 µs in ms, or
+    /// [`MeasurementError::IntegerOverflow`] if it does not fit in an `i64`, e.g. 10000 s in fs.
+    pub fn convert_to(&self, pfx: Prefix) -> Result<Self, MeasurementError> {
+        let diff = self.prefix.get_exp_value() - pfx.get_exp_value();
+        if self.value == 0 || diff == 0 {
+            return Ok(Self::new(self.value, pfx));
+        }
+        let factor = 10i64.checked_pow(u32::from(diff.unsigned_abs()));
+        if diff > 0 {
+            factor
+                .and_then(|f| self.value.checked_mul(f))
+                .map(|value| Self::new(value, pfx))
+                .ok_or(MeasurementError::IntegerOverflow(
+                    "the value does not fit in i64",
+                ))
+        } else {
+            match factor {
+                Some(f) if self.value % f == 0 => Ok(Self::new(self.value / f, pfx)),
+                // a divisor beyond i64 cannot divide a non-zero i64
+                _ => Err(MeasurementError::NotIntegral {
+                    value: canonical_base(self.value as f64, i32::from(diff)),
+                }),
+            }
+        }
+    }
+
+    /// Returns the value scaled to `pfx`, a prefix at least as fine, or `None` if it does not
+    /// fit in an `i128`.
+    fn widened(&self, pfx: Prefix) -> Option<i128> {
+        let diff = (self.prefix.get_exp_value() - pfx.get_exp_value()) as u32;
+        10i128
+            .checked_pow(diff)?
+            .checked_mul(i128::from(self.value))
+    }
+
+    /// Converts both operands to the finer of their prefixes and combines their values.
+    fn combine(
+        self,
+        rhs: Self,
+        op: fn(i64, i64) -> Option<i64>,
+        name: &'static str,
+    ) -> Result<Self, MeasurementError> {
+        let pfx = finer(self.prefix, rhs.prefix);
+        let (a, b) = (self.convert_to(pfx)?, rhs.convert_to(pfx)?);
+        op(a.value, b.value)
+            .map(|value| Self::new(value, pfx))
+            .ok_or(MeasurementError::IntegerOverflow(name))
+    }
+
+    /// Adds two measurements in the finer of their prefixes.
+    ///
+    /// # Returns
+    /// The sum, or [`MeasurementError::IntegerOverflow`] if it does not fit in an `i64`.
+    pub fn checked_add(self, rhs: Self) -> Result<Self, MeasurementError> {
+        self.combine(rhs, i64::checked_add, "addition")
+    }
+
+    /// Subtracts `rhs` in the finer of the two prefixes.
+    ///
+    /// # Returns
+    /// The difference, or [`MeasurementError::IntegerOverflow`] if it does not fit in an
+    /// `i64`.
+    pub fn checked_sub(self, rhs: Self) -> Result<Self, MeasurementError> {
+        self.combine(rhs, i64::checked_sub, "subtraction")
+    }
+
+    /// Multiplies the measurement by an integer.
+    ///
+    /// # Returns
+    /// The product, or [`MeasurementError::IntegerOverflow`] if it does not fit in an `i64`.
+    pub fn checked_mul(self, rhs: i64) -> Result<Self, MeasurementError> {
+        self.value
+            .checked_mul(rhs)
+            .map(|value| Self::new(value, self.prefix))
+            .ok_or(MeasurementError::IntegerOverflow("multiplication"))
+    }
+}
+
+impl<U: Uom> Add for MeasurementInt<U> {
+    /// Adds two [`MeasurementInt`]s, as [`MeasurementInt::checked_add`].
+    ///
+    /// # Panics
+    /// Panics on overflow.
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self::Output {
+        self.checked_add(rhs).unwrap_or_else(|e| panic!("{e}"))
+    }
+}
+
+impl<U: Uom> Sub for MeasurementInt<U> {
+    /// Subtracts two [`MeasurementInt`]s, as [`MeasurementInt::checked_sub`].
+    ///
+    /// # Panics
+    /// Panics on overflow.
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.checked_sub(rhs).unwrap_or_else(|e| panic!("{e}"))
+    }
+}
+
+impl<U: Uom> Mul<i64> for MeasurementInt<U> {
+    /// Multiplies a [`MeasurementInt`] by an integer, as [`MeasurementInt::checked_mul`].
+    ///
+    /// # Panics
+    /// Panics on overflow.
+    type Output = Self;
+    fn mul(self, rhs: i64) -> Self::Output {
+        self.checked_mul(rhs).unwrap_or_else(|e| panic!("{e}"))
+    }
+}
+
+impl<U: Uom> PartialEq for MeasurementInt<U> {
+    /// Checks exact equality, converting to the same prefix if necessary.
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<U: Uom> Eq for MeasurementInt<U> {}
+
+impl<U: Uom> PartialOrd for MeasurementInt<U> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<U: Uom> Ord for MeasurementInt<U> {
+    /// Compares exactly, scaling both values to the finer prefix in `i128`.
+    fn cmp(&self, other: &Self) -> Ordering {
+        let pfx = finer(self.prefix, other.prefix);
+        match (self.widened(pfx), other.widened(pfx)) {
+            (Some(a), Some(b)) => a.cmp(&b),
+            // only the coarser value can overflow, and then it is beyond any i64
+            (None, _) => self.value.cmp(&0),
+            (_, None) => 0.cmp(&other.value),
+        }
+    }
+}
+
+impl<U: Uom> fmt::Display for MeasurementInt<U> {
+    /// Formats the measurement like [`MeasurementInt::label`], e.g. `1500us`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(&self.label())
+    }
+}
+
+impl<U: Uom> From<MeasurementInt<U>> for Measurement<U> {
+    /// Converts to a floating-point measurement in the same prefix, exact up to 2^53.
+    fn from(m: MeasurementInt<U>) -> Self {
+        Measurement::new(m.value as f64, m.prefix)
+    }
+}
+
+impl<U: Uom> TryFrom<Measurement<U>> for MeasurementInt<U> {
+    type Error = MeasurementError;
+
+    /// Takes the value of `m` as an integer in the same prefix, as
+    /// [`MeasurementInt::from_measurement`].
+    fn try_from(m: Measurement<U>) -> Result<Self, Self::Error> {
+        Self::from_measurement(m, m.prefix())
+    }
+}
+
+/// A range of integer measurements with a positive step, such as protocol times in whole
+/// microseconds.
+///
+/// Deserialization checks the same invariants as [`RangedMeasurementInt::new`].
+///
+/// # Type Parameters
+/// - `U`: The unit of measurement, implementing the [`Uom`] trait.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+#[serde(try_from = "UncheckedRangedMeasurementInt")]
+pub struct RangedMeasurementInt<U: Uom> {
+    min: i64,
+    max: i64,
+    step: i64,
+    prefix: Prefix,
+    #[serde(skip)]
+    uom: PhantomData<U>,
+}
+
+/// The serialized form of a [`RangedMeasurementInt`], before validation.
+#[derive(Deserialize)]
+struct UncheckedRangedMeasurementInt {
+    min: i64,
+    max: i64,
+    step: i64,
+    prefix: Prefix,
+}
+
+impl<U: Uom> TryFrom<UncheckedRangedMeasurementInt> for RangedMeasurementInt<U> {
+    type Error = MeasurementError;
+
+    fn try_from(raw: UncheckedRangedMeasurementInt) -> Result<Self, Self::Error> {
+        Self::new(raw.min, raw.max, raw.step, raw.prefix)
+    }
+}
+
+impl<U: Uom> RangedMeasurementInt<U> {
+    /// Creates a new range from `min` to `max` in steps of `step`, all in `prefix`.
+    ///
+    /// # Returns
+    /// The range, or [`MeasurementError::RangeInvariant`] if `min` is not below `max`, or
+    /// `step` is not positive or exceeds the span.
+    pub fn new(min: i64, max: i64, step: i64, prefix: Prefix) -> Result<Self, MeasurementError> {
+        let invariant = |reason| MeasurementError::RangeInvariant {
+            min: min as f64,
+            max: max as f64,
+            step: Some(step as f64),
+            reason,
+        };
+        if min >= max {
+            return Err(invariant("min must be less than max"));
+        }
+        if step <= 0 {
+            return Err(invariant("step must be positive"));
+        }
+        if i128::from(step) > i128::from(max) - i128::from(min) {
+            return Err(invariant("step exceeds the span"));
+        }
+        Ok(Self {
+            min,
+            max,
+            step,
+            prefix,
+            uom: PhantomData,
+        })
+    }
+
+    /// Returns the minimum of the range.
+    pub fn min(&self) -> MeasurementInt<U> {
+        MeasurementInt::new(self.min, self.prefix)
+    }
+
+    /// Returns the maximum of the range.
+    pub fn max(&self) -> MeasurementInt<U> {
+        MeasurementInt::new(self.max, self.prefix)
+    }
+
+    /// Returns the step of the range.
+    pub fn step(&self) -> MeasurementInt<U> {
+        MeasurementInt::new(self.step, self.prefix)
+    }
+
+    /// Returns the SI prefix of the range.
+    pub fn prefix(&self) -> Prefix {
+        self.prefix
+    }
+
+    /// Returns `true` if `m` lies within the range, bounds included.
+    pub fn contains(&self, m: MeasurementInt<U>) -> bool {
+        self.min() <= m && m <= self.max()
+    }
+
+    /// Returns the label of the range (e.g., "[0,1000,250]us").
+    pub fn label(&self) -> String {
+        let (pfx, uom) = (self.prefix.get_label(), U::uom_str());
+        format!("[{},{},{}]{pfx}{uom}", self.min, self.max, self.step)
+    }
+
+    /// Converts the range to a different SI prefix, exactly.
+    ///
+    /// # Returns
+    /// The converted range, or the error of [`MeasurementInt::convert_to`] for the first
+    /// bound or step that does not convert exactly.
+    pub fn convert_to(&self, pfx: Prefix) -> Result<Self, MeasurementError> {
+        let (min, max, step) = (
+            self.min().convert_to(pfx)?,
+            self.max().convert_to(pfx)?,
+            self.step().convert_to(pfx)?,
+        );
+        Self::new(min.value, max.value, step.value, pfx)
+    }
+
+    /// Returns every step of the range, `min + k * step` up to and including `max`.
+    ///
+    /// The values are computed exactly, so the last one is `max` whenever the span is a
+    /// multiple of the step.
+    pub fn iter(&self) -> impl Iterator<Item = MeasurementInt<U>> + use<U> {
+        let (min, step, prefix) = (i128::from(self.min), i128::from(self.step), self.prefix);
+        let steps = (i128::from(self.max) - min) / step;
+        (0..=steps).map(move |k| MeasurementInt::new((min + k * step) as i64, prefix))
+    }
+}
+
+impl<U: Uom> PartialEq for RangedMeasurementInt<U> {
+    /// Checks that the bounds and steps are exactly equal, whatever their prefixes.
+    fn eq(&self, other: &Self) -> bool {
+        (self.min(), self.max(), self.step()) == (other.min(), other.max(), other.step())
+    }
+}
+
+impl<U: Uom> Eq for RangedMeasurementInt<U> {}
+
+impl<U: Uom> fmt::Display for RangedMeasurementInt<U> {
+    /// Formats the range like [`RangedMeasurementInt::label`], e.g. `[0,1000,250]us`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(&self.label())
+    }
+}
+
+impl<U: Uom> From<RangedMeasurementInt<U>> for RangedMeasurement<U> {
+    /// Converts to a floating-point range in the same prefix, exact up to 2^53.
+    fn from(r: RangedMeasurementInt<U>) -> Self {
+        RangedMeasurement::new(r.min as f64, r.max as f64, r.step as f64, r.prefix)
+    }
+}
+
+#[cfg(test)]
+mod measurement_int_tests {
+    use super::*;
+    use crate::uom::{Second, Volt};
+    use alloc::{string::ToString, vec::Vec};
+
+    fn us(v: i64) -> MeasurementInt<Second> {
+        MeasurementInt::new(v, Prefix::Micro)
+    }
+
+    #[test]
+    fn converts_only_when_exact() {
+        assert_eq!(
+            us(2000).convert_to(Prefix::Milli),
+            Ok(MeasurementInt::new(2, Prefix::Milli))
+        );
+        assert_eq!(
+            us(1500).convert_to(Prefix::Milli),
+            Err(MeasurementError::NotIntegral { value: 1.5 })
+        );
+        assert_eq!(
+            MeasurementInt::<Second>::new(3, Prefix::None).convert_to(Prefix::Micro),
+            Ok(us(3_000_000))
+        );
+        assert!(matches!(
+            MeasurementInt::<Second>::new(10_000, Prefix::None).convert_to(Prefix::Femto),
+            Err(MeasurementError::IntegerOverflow(_))
+        ));
+        assert!(matches!(
+            us(1).convert_to(Prefix::Tera),
+            Err(MeasurementError::NotIntegral { .. })
+        ));
+        assert_eq!(
+            MeasurementInt::<Second>::new(0, Prefix::Tera).convert_to(Prefix::Femto),
+            Ok(MeasurementInt::new(0, Prefix::Femto))
+        );
+    }
+
+    #[test]
+    fn converts_from_and_to_floats() {
+        let ms = |v: f64| Measurement::<Second>::new(v, Prefix::Milli);
+        assert_eq!(
+            MeasurementInt::from_measurement(ms(1.5), Prefix::Micro),
+            Ok(us(1500))
+        );
+        // 4.03 * 1000 is 4030.0000000000005 in f64
+        assert_eq!(
+            MeasurementInt::from_measurement(ms(4.03), Prefix::Micro),
+            Ok(us(4030))
+        );
+        assert_eq!(
+            MeasurementInt::from_measurement(ms(0.0015), Prefix::Micro),
+            Err(MeasurementError::NotIntegral { value: 1.5 })
+        );
+        assert!(MeasurementInt::try_from(ms(2.5)).is_err());
+        assert!(MeasurementInt::try_from(ms(f64::NAN)).is_err());
+        assert!(matches!(
+            MeasurementInt::try_from(ms(1e19)),
+            Err(MeasurementError::IntegerOverflow(_))
+        ));
+        let min = Measurement::<Second>::new(i64::MIN as f64, Prefix::Micro);
+        assert_eq!(MeasurementInt::try_from(min), Ok(us(i64::MIN)));
+        assert_eq!(Measurement::from(us(1500)), ms(1.5));
+    }
+
+    #[test]
+    fn arithmetic_checks_overflow() {
+        let sum = MeasurementInt::new(1, Prefix::Milli) + us(250);
+        assert_eq!((sum.value(), sum.prefix()), (1250, Prefix::Micro));
+        assert_eq!(us(1000) - MeasurementInt::new(1, Prefix::Milli), us(0));
+        assert_eq!(us(250) * 4, MeasurementInt::new(1, Prefix::Milli));
+        assert_eq!(
+            us(i64::MAX).checked_add(us(1)),
+            Err(MeasurementError::IntegerOverflow("addition"))
+        );
+        assert!(us(i64::MIN).checked_sub(us(1)).is_err());
+        assert!(us(i64::MAX).checked_mul(2).is_err());
+        // the coarser operand overflows when converted to the finer prefix
+        assert!(
+            MeasurementInt::<Second>::new(i64::MAX, Prefix::Milli)
+                .checked_add(us(0))
+                .is_err()
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "integer overflow: addition")]
+    fn operators_panic_on_overflow() {
+        let _ = us(i64::MAX) + us(1);
+    }
+
+    #[test]
+    fn compares_exactly_across_prefixes() {
+        assert_eq!(MeasurementInt::new(1, Prefix::Milli), us(1000));
+        assert!(MeasurementInt::new(1, Prefix::Milli) < us(1001));
+        let huge = MeasurementInt::<Volt>::new(1, Prefix::Tera);
+        let tiny = MeasurementInt::<Volt>::new(i64::MAX, Prefix::Femto);
+        assert!(huge > tiny);
+        assert!(MeasurementInt::new(-1, Prefix::Tera) < tiny);
+        assert_eq!(us(1500).to_string(), "1500us");
+        assert_eq!(format!("{:>8}", us(15)), "    15us");
+    }
+
+    #[test]
+    fn ranges_step_exactly() {
+        let r = RangedMeasurementInt::<Second>::new(0, 1000, 250, Prefix::Micro).unwrap();
+        let steps = r.iter().map(|m| m.value()).collect::<Vec<_>>();
+        assert_eq!(steps, [0, 250, 500, 750, 1000]);
+        assert_eq!(r.label(), "[0,1000,250]us");
+        assert!(r.contains(MeasurementInt::new(1, Prefix::Milli)));
+        assert!(!r.contains(us(1001)));
+        // the last step stays below max when the span is not a multiple of the step
+        let r = RangedMeasurementInt::<Second>::new(0, 1000, 300, Prefix::Micro).unwrap();
+        assert_eq!(r.iter().last(), Some(us(900)));
+        let r = RangedMeasurementInt::<Second>::new(i64::MIN, i64::MAX, i64::MAX, Prefix::None)
+            .unwrap();
+        assert_eq!(r.iter().count(), 3);
+        let ns = r.convert_to(Prefix::Nano);
+        assert!(matches!(ns, Err(MeasurementError::IntegerOverflow(_))));
+        let ms = RangedMeasurementInt::<Second>::new(0, 1000, 250, Prefix::Micro)
+            .unwrap()
+            .convert_to(Prefix::Milli);
+        assert_eq!(ms, Err(MeasurementError::NotIntegral { value: 0.25 }));
+        assert_eq!(
+            RangedMeasurement::from(
+                RangedMeasurementInt::<Second>::new(0, 10, 5, Prefix::Milli).unwrap()
+            ),
+            RangedMeasurement::new(0.0, 10.0, 5.0, Prefix::Milli)
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_ranges() {
+        let err = |min, max, step| match RangedMeasurementInt::<Second>::new(
+            min,
+            max,
+            step,
+            Prefix::Micro,
+        ) {
+            Err(MeasurementError::RangeInvariant { reason, .. }) => reason,
+            r => panic!("{r:?}"),
+        };
+        assert_eq!(err(10, 10, 1), "min must be less than max");
+        assert_eq!(err(0, 10, 0), "step must be positive");
+        assert_eq!(err(0, 10, 11), "step exceeds the span");
+    }
+
+    #[test]
+    fn deserialization_validates() {
+        let r = RangedMeasurementInt::<Second>::new(0, 1000, 250, Prefix::Micro).unwrap();
+        let json = serde_json::to_string(&r).unwrap();
+        assert_eq!(json, r#"{"min":0,"max":1000,"step":250,"prefix":"Micro"}"#);
+        assert_eq!(
+            serde_json::from_str::<RangedMeasurementInt<Second>>(&json).unwrap(),
+            r
+        );
+        let zero_step = r#"{"min":0,"max":1000,"step":0,"prefix":"Micro"}"#;
+        let e = serde_json::from_str::<RangedMeasurementInt<Second>>(zero_step).unwrap_err();
+        assert!(e.to_string().contains("step must be positive"), "{e}");
+    }
+}
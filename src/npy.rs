@@ -0,0 +1,367 @@
+//! NumPy `.npy` import and export for [`M1d`] and [`M2d`], enabled by the `npy` feature.
+//!
+//! Arrays are written as NPY v1.0 files holding little-endian `f64` data in C order.
+//! The `.npy` format has no room for units, so [`M2d::write_npy_with_meta`] and
+//! [`M1d::write_npy_with_meta`] store the prefix and unit in an adjacent `.json` sidecar.
+
+use crate::{m1d::M1d, m2d::M2d, prefix::Prefix, uom::Uom};
+use ndarray::{Array2, ShapeBuilder};
+use serde::{Deserialize, Serialize};
+use std::{
+    fmt,
+    fs::File,
+    io::{BufReader, BufWriter, Read, Write},
+    path::Path,
+};
+
+const MAGIC: &[u8] = b"\x93NUMPY";
+
+/// Errors raised while reading or writing `.npy` files.
+#[derive(Debug)]
+pub enum NpyError {
+    /// An I/O error from the underlying reader or writer.
+    Io(std::io::Error),
+    /// A file that is not a supported `.npy` file.
+    Format(String),
+    /// A sidecar whose unit does not match the expected one.
+    UnitMismatch { expected: String, found: String },
+    /// A sidecar that could not be read or written.
+    Json(serde_json::Error),
+}
+
+impl fmt::Display for NpyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "{e}"),
+            Self::Format(m) => write!(f, "invalid npy file: {m}"),
+            Self::UnitMismatch { expected, found } => {
+                write!(f, "unit mismatch: expected `{expected}`, found `{found}`")
+            }
+            Self::Json(e) => write!(f, "invalid npy sidecar: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for NpyError {}
+
+impl From<std::io::Error> for NpyError {
+    fn from(e: std::io::Error) -> Self {
+        Self::Io(e)
+    }
+}
+
+impl From<serde_json::Error> for NpyError {
+    fn from(e: serde_json::Error) -> Self {
+        Self::Json(e)
+    }
+}
+
+/// Contents of the `.json` sidecar written next to a `.npy` file.
+#[derive(Serialize, Deserialize)]
+struct NpyMeta {
+    unit: String,
+    prefix: Prefix,
+}
+
+impl NpyMeta {
+    fn write<U: Uom>(path: &Path, prefix: Prefix) -> Result<(), NpyError> {
+        let meta = NpyMeta {
+            unit: U::uom(),
+            prefix,
+        };
+        let file = BufWriter::new(File::create(path.with_extension("json"))?);
+        serde_json::to_writer(file, &meta)?;
+        Ok(())
+    }
+
+    fn read<U: Uom>(path: &Path) -> Result<Prefix, NpyError> {
+        let file = BufReader::new(File::open(path.with_extension("json"))?);
+        let meta: NpyMeta = serde_json::from_reader(file)?;
+        if meta.unit != U::uom_str() {
+            return Err(NpyError::UnitMismatch {
+                expected: U::uom(),
+                found: meta.unit,
+            });
+        }
+        Ok(meta.prefix)
+    }
+}
+
+/// Writes an NPY v1.0 header followed by `data` as little-endian `f64`.
+fn write_npy<'a, W: Write>(
+    mut w: W,
+    shape: &[usize],
+    data: impl Iterator<Item = &'a f64>,
+) -> Result<(), NpyError> {
+    let shape = match shape {
+        [n] => format!("({n},)"),
+        _ => format!(
+            "({})",
+            shape
+                .iter()
+                .map(|n| n.to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    };
+    let mut header = format!("{{'descr': '<f8', 'fortran_order': False, 'shape': {shape}, }}");
+    // magic (6) + version (2) + header length (2) + header + newline, padded to 64 bytes
+    let padding = (64 - (MAGIC.len() + 4 + header.len() + 1) % 64) % 64;
+    header.push_str(&" ".repeat(padding));
+    header.push('\n');
+    let header_len =
+        u16::try_from(header.len()).map_err(|_| NpyError::Format("header too long".to_string()))?;
+    w.write_all(MAGIC)?;
+    w.write_all(&[1, 0])?;
+    w.write_all(&header_len.to_le_bytes())?;
+    w.write_all(header.as_bytes())?;
+    for v in data {
+        w.write_all(&v.to_le_bytes())?;
+    }
+    w.flush()?;
+    Ok(())
+}
+
+/// Extracts the value following `'key':` in an NPY header dictionary.
+fn header_value<'a>(header: &'a str, key: &str) -> Result<&'a str, NpyError> {
+    let pattern = format!("'{key}':");
+    let start = header
+        .find(&pattern)
+        .ok_or_else(|| NpyError::Format(format!("missing `{key}` in header")))?
+        + pattern.len();
+    Ok(header[start..].trim_start())
+}
+
+/// Reads an NPY file, returning its shape, whether it is in Fortran order, and its data.
+///
+/// Only `f64` data (`<f8` or `>f8`) is supported; other dtypes are rejected.
+fn read_npy<R: Read>(mut r: R) -> Result<(Vec<usize>, bool, Vec<f64>), NpyError> {
+    let mut preamble = [0u8; 8];
+    r.read_exact(&mut preamble)?;
+    if &preamble[..6] != MAGIC {
+        return Err(NpyError::Format("missing magic string".to_string()));
+    }
+    let header_len = match preamble[6] {
+        1 => {
+            let mut len = [0u8; 2];
+            r.read_exact(&mut len)?;
+            u16::from_le_bytes(len) as usize
+        }
+        2 | 3 => {
+            let mut len = [0u8; 4];
+            r.read_exact(&mut len)?;
+            u32::from_le_bytes(len) as usize
+        }
+        v => return Err(NpyError::Format(format!("unsupported version {v}"))),
+    };
+    let mut header = vec![0u8; header_len];
+    r.read_exact(&mut header)?;
+    let header = String::from_utf8_lossy(&header);
+
+    let descr = header_value(&header, "descr")?;
+    let big_endian = if descr.starts_with("'<f8'") {
+        false
+    } else if descr.starts_with("'>f8'") {
+        true
+    } else {
+        return Err(NpyError::Format(format!(
+            "unsupported dtype {}, only f8 is supported",
+            descr.split(',').next().unwrap_or_default()
+        )));
+    };
+    let fortran_order = header_value(&header, "fortran_order")?.starts_with("True");
+    let shape = header_value(&header, "shape")?;
+    let shape = shape
+        .strip_prefix('(')
+        .and_then(|s| s.split_once(')'))
+        .map(|(s, _)| s)
+        .ok_or_else(|| NpyError::Format("malformed shape".to_string()))?
+        .split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(|s| {
+            s.parse::<usize>()
+                .map_err(|_| NpyError::Format(format!("malformed shape dimension `{s}`")))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let len = shape.iter().product::<usize>();
+    let mut bytes = vec![0u8; len * 8];
+    r.read_exact(&mut bytes)?;
+    let data = bytes
+        .chunks_exact(8)
+        .map(|b| {
+            let b = b.try_into().expect("chunks of 8 bytes");
+            if big_endian {
+                f64::from_be_bytes(b)
+            } else {
+                f64::from_le_bytes(b)
+            }
+        })
+        .collect();
+    Ok((shape, fortran_order, data))
+}
+
+impl<U: Uom> M1d<U> {
+    /// Writes the values as a one-dimensional NPY v1.0 file of little-endian `f64`.
+    ///
+    /// The prefix and unit are not stored; see [`M1d::write_npy_with_meta`].
+    pub fn write_npy<W: Write>(&self, w: W) -> Result<(), NpyError> {
+        write_npy(w, &[self.len()], self.view().iter())
+    }
+
+    /// Reads a one-dimensional `f64` NPY file, interpreting the values in `prefix`.
+    ///
+    /// # Returns
+    /// A new [`M1d`], or an [`NpyError`] if the file is not a one-dimensional `f64` array.
+    pub fn read_npy<R: Read>(r: R, prefix: Prefix) -> Result<Self, NpyError> {
+        match read_npy(r)? {
+            (shape, _, data) if shape.len() == 1 => Ok(M1d::new(data, prefix)),
+            (shape, _, _) => Err(NpyError::Format(format!(
+                "expected a 1-dimensional array, found shape {shape:?}"
+            ))),
+        }
+    }
+
+    /// Writes the values to `path` and the prefix and unit to a `.json` file next to it.
+    pub fn write_npy_with_meta<P: AsRef<Path>>(&self, path: P) -> Result<(), NpyError> {
+        let path = path.as_ref();
+        self.write_npy(BufWriter::new(File::create(path)?))?;
+        NpyMeta::write::<U>(path, self.prefix())
+    }
+
+    /// Reads a file written by [`M1d::write_npy_with_meta`], validating the unit.
+    pub fn read_npy_with_meta<P: AsRef<Path>>(path: P) -> Result<Self, NpyError> {
+        let path = path.as_ref();
+        let prefix = NpyMeta::read::<U>(path)?;
+        Self::read_npy(BufReader::new(File::open(path)?), prefix)
+    }
+}
+
+impl<U: Uom> M2d<U> {
+    /// Writes the values as a two-dimensional NPY v1.0 file of little-endian `f64` in C order.
+    ///
+    /// The prefix and unit are not stored; see [`M2d::write_npy_with_meta`].
+    pub fn write_npy<W: Write>(&self, w: W) -> Result<(), NpyError> {
+        let (rows, cols) = self.shape();
+        let data = (0..rows).flat_map(|i| (0..cols).map(move |j| (i, j)));
+        write_npy(w, &[rows, cols], data.map(|idx| &self[idx]))
+    }
+
+    /// Reads a two-dimensional `f64` NPY file, interpreting the values in `prefix`.
+    ///
+    /// Both C-order and Fortran-order files are accepted.
+    ///
+    /// # Returns
+    /// A new [`M2d`], or an [`NpyError`] if the file is not a two-dimensional `f64` array.
+    pub fn read_npy<R: Read>(r: R, prefix: Prefix) -> Result<Self, NpyError> {
+        let (shape, fortran_order, data) = read_npy(r)?;
+        let &[rows, cols] = shape.as_slice() else {
+            return Err(NpyError::Format(format!(
+                "expected a 2-dimensional array, found shape {shape:?}"
+            )));
+        };
+        let values = Array2::from_shape_vec((rows, cols).set_f(fortran_order), data)
+            .map_err(|e| NpyError::Format(e.to_string()))?;
+        Ok(M2d::new(values, prefix).to_standard_layout())
+    }
+
+    /// Writes the values to `path` and the prefix and unit to a `.json` file next to it.
+    pub fn write_npy_with_meta<P: AsRef<Path>>(&self, path: P) -> Result<(), NpyError> {
+        let path = path.as_ref();
+        self.write_npy(BufWriter::new(File::create(path)?))?;
+        NpyMeta::write::<U>(path, self.prefix())
+    }
+
+    /// Reads a file written by [`M2d::write_npy_with_meta`], validating the unit.
+    pub fn read_npy_with_meta<P: AsRef<Path>>(path: P) -> Result<Self, NpyError> {
+        let path = path.as_ref();
+        let prefix = NpyMeta::read::<U>(path)?;
+        Self::read_npy(BufReader::new(File::open(path)?), prefix)
+    }
+}
+
+#[cfg(test)]
+mod npy_tests {
+    use super::*;
+    use crate::uom::{Ampere, Volt};
+
+    const FIXTURES: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures");
+
+    fn fixture(name: &str) -> Vec<u8> {
+        std::fs::read(format!("{FIXTURES}/{name}")).unwrap()
+    }
+
+    fn expected_m2d() -> M2d<Volt> {
+        M2d::new(
+            Array2::from_shape_vec((2, 3), vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]).unwrap(),
+            Prefix::Milli,
+        )
+    }
+
+    // the fixtures follow the NPY v1.0 layout written by numpy.save
+    #[test]
+    fn m2d_write_matches_fixture() {
+        let mut buf = Vec::new();
+        expected_m2d().write_npy(&mut buf).unwrap();
+        assert_eq!(buf, fixture("m2d_2x3_c.npy"));
+    }
+
+    #[test]
+    fn m2d_read_fixtures() {
+        for name in ["m2d_2x3_c.npy", "m2d_2x3_f.npy", "m2d_2x3_be.npy"] {
+            let m = M2d::<Volt>::read_npy(fixture(name).as_slice(), Prefix::Milli).unwrap();
+            assert_eq!(m, expected_m2d(), "{name}");
+        }
+    }
+
+    #[test]
+    fn m1d_round_trip() {
+        let bytes = fixture("m1d_4.npy");
+        let m = M1d::<Volt>::read_npy(bytes.as_slice(), Prefix::Micro).unwrap();
+        let values = m.values();
+        assert_eq!(m.prefix(), Prefix::Micro);
+        assert_eq!(values[0], 0.5);
+        assert!(values[2].is_nan());
+        assert_eq!(values[3], 1e-3);
+        let mut buf = Vec::new();
+        m.write_npy(&mut buf).unwrap();
+        assert_eq!(buf, bytes);
+    }
+
+    #[test]
+    fn rejects_unsupported_inputs() {
+        let f4 = fixture("m1d_4_f4.npy");
+        assert!(matches!(
+            M1d::<Volt>::read_npy(f4.as_slice(), Prefix::None),
+            Err(NpyError::Format(_))
+        ));
+        let m2d = fixture("m2d_2x3_c.npy");
+        assert!(M1d::<Volt>::read_npy(m2d.as_slice(), Prefix::None).is_err());
+        let m1d = fixture("m1d_4.npy");
+        assert!(M2d::<Volt>::read_npy(m1d.as_slice(), Prefix::None).is_err());
+        assert!(M2d::<Volt>::read_npy(&b"not an npy file"[..], Prefix::None).is_err());
+    }
+
+    #[test]
+    fn sidecar_round_trip() {
+        let dir =
+            std::env::temp_dir().join(format!("typed_measurements_npy_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("trace.npy");
+        expected_m2d().write_npy_with_meta(&path).unwrap();
+        assert_eq!(
+            M2d::<Volt>::read_npy_with_meta(&path).unwrap(),
+            expected_m2d()
+        );
+        assert!(matches!(
+            M2d::<Ampere>::read_npy_with_meta(&path),
+            Err(NpyError::UnitMismatch { .. })
+        ));
+        let m1d = M1d::<Volt>::new(vec![1.0, 2.0], Prefix::Kilo);
+        let path = dir.join("m1d.npy");
+        m1d.write_npy_with_meta(&path).unwrap();
+        assert_eq!(M1d::<Volt>::read_npy_with_meta(&path).unwrap(), m1d);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,168 @@
+//! A totally ordered, hashable key form of [`Measurement`], e.g. for `BTreeMap` keys.
+
+use crate::{math::canonical_base, measurement::Measurement, prefix::Prefix, uom::Uom};
+use core::{
+    cmp::Ordering,
+    hash::{Hash, Hasher},
+};
+
+/// A [`Measurement`] compared by its value in the base unit, with a total order.
+///
+/// Equal quantities in different prefixes, such as 1.5 mV and 1500 µV, are the same key as
+/// long as the prefix conversion is exact. `-0.0` equals `0.0`, and every NaN equals every
+/// other NaN and sorts after `+∞`.
+///
+/// The measurement a key was created from is remembered, so converting back returns it
+/// unchanged, prefix included.
+///
+/// # Example
+/// ```
+/// use std::collections::BTreeMap;
+/// use typed_measurements::prelude::*;
+///
+/// let mut gains = BTreeMap::new();
+/// gains.insert(OrderedMeasurement::from(Measurement::<Volt>::new(1.5, Prefix::Milli)), 0.98);
+/// let key = Measurement::<Volt>::new(1500.0, Prefix::Micro).into();
+/// assert_eq!(gains.get(&key), Some(&0.98));
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct OrderedMeasurement<U: Uom> {
+    base: f64,
+    original: Measurement<U>,
+}
+
+impl<U: Uom> OrderedMeasurement<U> {
+    /// Creates the key of `m`.
+    pub fn new(m: Measurement<U>) -> Self {
+        Self {
+            base: canonical_base(m.value(), m.prefix().get_exp_value() as i32),
+            original: m,
+        }
+    }
+
+    /// Returns the canonical value in the base unit that the key is compared by.
+    pub fn base_value(&self) -> f64 {
+        self.base
+    }
+
+    /// Returns the key as a measurement in the base unit, without a prefix.
+    pub fn as_base(&self) -> Measurement<U> {
+        Measurement::new(self.base, Prefix::None)
+    }
+
+    /// Returns the measurement the key was created from.
+    pub fn measurement(&self) -> Measurement<U> {
+        self.original
+    }
+}
+
+impl<U: Uom> From<Measurement<U>> for OrderedMeasurement<U> {
+    fn from(m: Measurement<U>) -> Self {
+        Self::new(m)
+    }
+}
+
+impl<U: Uom> From<OrderedMeasurement<U>> for Measurement<U> {
+    /// Returns the measurement the key was created from, in its original prefix.
+    fn from(m: OrderedMeasurement<U>) -> Self {
+        m.original
+    }
+}
+
+impl<U: Uom> PartialEq for OrderedMeasurement<U> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<U: Uom> Eq for OrderedMeasurement<U> {}
+
+impl<U: Uom> PartialOrd for OrderedMeasurement<U> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<U: Uom> Ord for OrderedMeasurement<U> {
+    /// Orders by base value; the canonical NaN sorts after `+∞`.
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.base.total_cmp(&other.base)
+    }
+}
+
+impl<U: Uom> Hash for OrderedMeasurement<U> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.base.to_bits().hash(state);
+    }
+}
+
+#[cfg(test)]
+mod ordered_tests {
+    use super::*;
+    use crate::uom::Volt;
+    use alloc::{collections::BTreeMap, vec::Vec};
+
+    fn key(value: f64, prefix: Prefix) -> OrderedMeasurement<Volt> {
+        Measurement::new(value, prefix).into()
+    }
+
+    #[test]
+    fn mixed_prefixes_collapse() {
+        let mut table = BTreeMap::new();
+        table.insert(key(1.5, Prefix::Milli), "a");
+        table.insert(key(1500.0, Prefix::Micro), "b");
+        table.insert(key(0.0015, Prefix::None), "c");
+        table.insert(key(2.0, Prefix::None), "d");
+        assert_eq!(table.len(), 2);
+        // the first key is kept, the last value wins
+        let (first, value) = table.iter().next().unwrap();
+        assert_eq!(first.measurement().prefix(), Prefix::Milli);
+        assert_eq!(*value, "c");
+        assert_eq!(table.get(&key(1_500_000.0, Prefix::Nano)), Some(&"c"));
+        assert_eq!(table.get(&key(2000.0, Prefix::Milli)), Some(&"d"));
+        assert_eq!(table.get(&key(2.0, Prefix::Milli)), None);
+    }
+
+    #[test]
+    fn total_order_with_nan_last() {
+        let mut keys = [
+            key(f64::NAN, Prefix::None),
+            key(f64::INFINITY, Prefix::None),
+            key(1.0, Prefix::Kilo),
+            key(-0.0, Prefix::None),
+            key(-5.0, Prefix::Milli),
+            key(0.0, Prefix::Micro),
+            key(-f64::NAN, Prefix::Milli),
+        ];
+        keys.sort();
+        let values: Vec<f64> = keys.iter().map(OrderedMeasurement::base_value).collect();
+        assert_eq!(values[..5], [-0.005, 0.0, 0.0, 1000.0, f64::INFINITY]);
+        assert!(values[5].is_nan() && values[6].is_nan());
+        assert_eq!(key(-0.0, Prefix::None), key(0.0, Prefix::Mega));
+        assert_eq!(key(f64::NAN, Prefix::None), key(-f64::NAN, Prefix::Pico));
+    }
+
+    #[test]
+    fn round_trips_to_measurement() {
+        let m = Measurement::<Volt>::new(250.0, Prefix::Micro);
+        let k = OrderedMeasurement::from(m);
+        assert_eq!(Measurement::from(k).prefix(), Prefix::Micro);
+        assert_eq!(Measurement::from(k).value(), 250.0);
+        assert_eq!(k.as_base().prefix(), Prefix::None);
+        assert_eq!(k.as_base(), m);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn hash_matches_eq() {
+        use std::collections::HashSet;
+        let set: HashSet<_> = [
+            key(1.5, Prefix::Milli),
+            key(1500.0, Prefix::Micro),
+            key(-0.0, Prefix::None),
+            key(0.0, Prefix::Kilo),
+        ]
+        .into();
+        assert_eq!(set.len(), 2);
+    }
+}
@@ -0,0 +1,79 @@
+//! Sequential stand-ins for ndarray's rayon methods, used when the `rayon` feature is off.
+//!
+//! They share the names and closure bounds of the parallel methods, so call sites and the
+//! public API are the same in both builds; only the execution is sequential.
+
+use ndarray::{Array, ArrayBase, DataMut, Dimension, NdProducer, Zip};
+
+/// Sequential `par_mapv_inplace`.
+pub(crate) trait ParMapvInplace<A> {
+    fn par_mapv_inplace<F: Fn(A) -> A + Sync + Send>(&mut self, f: F);
+}
+
+impl<A: Clone, S: DataMut<Elem = A>, D: Dimension> ParMapvInplace<A> for ArrayBase<S, D> {
+    fn par_mapv_inplace<F: Fn(A) -> A + Sync + Send>(&mut self, f: F) {
+        self.mapv_inplace(f)
+    }
+}
+
+/// Sequential `par_for_each` and `par_map_collect` on a single-producer [`Zip`].
+pub(crate) trait ParZip1<P1: NdProducer, D> {
+    fn par_for_each<F: Fn(P1::Item) + Sync + Send>(self, f: F);
+    fn par_map_collect<R: Send>(self, f: impl Fn(P1::Item) -> R + Sync + Send) -> Array<R, D>;
+}
+
+impl<P1: NdProducer<Dim = D>, D: Dimension> ParZip1<P1, D> for Zip<(P1,), D> {
+    fn par_for_each<F: Fn(P1::Item) + Sync + Send>(self, f: F) {
+        self.for_each(f)
+    }
+
+    fn par_map_collect<R: Send>(self, f: impl Fn(P1::Item) -> R + Sync + Send) -> Array<R, D> {
+        self.map_collect(f)
+    }
+}
+
+/// Sequential `par_for_each` and `par_map_collect` on a two-producer [`Zip`].
+pub(crate) trait ParZip2<P1: NdProducer, P2: NdProducer, D> {
+    fn par_for_each<F: Fn(P1::Item, P2::Item) + Sync + Send>(self, f: F);
+    fn par_map_collect<R: Send>(
+        self,
+        f: impl Fn(P1::Item, P2::Item) -> R + Sync + Send,
+    ) -> Array<R, D>;
+}
+
+impl<P1: NdProducer<Dim = D>, P2: NdProducer<Dim = D>, D: Dimension> ParZip2<P1, P2, D>
+    for Zip<(P1, P2), D>
+{
+    fn par_for_each<F: Fn(P1::Item, P2::Item) + Sync + Send>(self, f: F) {
+        self.for_each(f)
+    }
+
+    fn par_map_collect<R: Send>(
+        self,
+        f: impl Fn(P1::Item, P2::Item) -> R + Sync + Send,
+    ) -> Array<R, D> {
+        self.map_collect(f)
+    }
+}
+
+#[cfg(test)]
+mod par_tests {
+    use super::*;
+    use ndarray::{Axis, array};
+
+    #[test]
+    fn matches_sequential_methods() {
+        let mut a = array![[1.0, 2.0], [3.0, 4.0]];
+        a.par_mapv_inplace(|x| x * 10.0);
+        assert_eq!(a, array![[10.0, 20.0], [30.0, 40.0]]);
+        let sums = Zip::from(a.lanes(Axis(1))).par_map_collect(|lane| lane.sum());
+        assert_eq!(sums, array![30.0, 70.0]);
+        let b = array![[1.0, 1.0], [2.0, 2.0]];
+        let diff = Zip::from(&a).and(&b).par_map_collect(|&x, &y| x - y);
+        assert_eq!(diff, array![[9.0, 19.0], [28.0, 38.0]]);
+        Zip::from(&mut a).and(&b).par_for_each(|x, &y| *x += y);
+        assert_eq!(a, array![[11.0, 21.0], [32.0, 42.0]]);
+        Zip::from(&mut a).par_for_each(|x| *x -= 1.0);
+        assert_eq!(a, array![[10.0, 20.0], [31.0, 41.0]]);
+    }
+}
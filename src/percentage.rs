@@ -40,7 +40,7 @@ macro_rules! percentage {
 }
 
 #[cfg(test)]
-mod percentage {
+mod percentage_tests {
     use super::*;
 
     #[test]
@@ -1,3 +1,4 @@
+use crate::{error::MeasurementError, math::pow10};
 use serde::{Deserialize, Serialize};
 
 /// Represents a SI unit prefix (e.g., kilo, mega, milli).
@@ -16,8 +17,22 @@ pub enum Prefix {
 }
 
 impl Prefix {
+    /// Every prefix, from the largest to the smallest.
+    pub const ALL: [Prefix; 10] = [
+        Self::Tera,
+        Self::Giga,
+        Self::Mega,
+        Self::Kilo,
+        Self::None,
+        Self::Milli,
+        Self::Micro,
+        Self::Nano,
+        Self::Pico,
+        Self::Femto,
+    ];
+
     /// Returns the exponent value associated with the prefix (e.g., Kilo = 3, Mega = 6).
-    pub fn get_exp_value(&self) -> i16 {
+    pub const fn get_exp_value(&self) -> i16 {
         match self {
             Self::Tera => 12,
             Self::Giga => 9,
@@ -49,12 +64,52 @@ impl Prefix {
             e if (-3..0).contains(&e) => (Self::Milli, exp - Self::Milli.get_exp_value()),
             e if (-6..-3).contains(&e) => (Self::Micro, exp - Self::Micro.get_exp_value()),
             e if (-9..-6).contains(&e) => (Self::Nano, exp - Self::Nano.get_exp_value()),
-            e if (-12..-9).contains(&e) => (Self::Nano, exp - Self::Pico.get_exp_value()),
+            e if (-12..-9).contains(&e) => (Self::Pico, exp - Self::Pico.get_exp_value()),
             e if e < -12 => (Self::Femto, exp - Self::Femto.get_exp_value()),
             _ => panic!("should have caught everything"),
         }
     }
 
+    /// Multiplies two prefixes, combining their exponents.
+    ///
+    /// # Returns
+    /// The combined prefix, or [`MeasurementError::PrefixOverflow`] if the exponent is
+    /// outside Tera..=Femto (e.g. Tera × Tera).
+    pub fn checked_mul(self, rhs: Self) -> Result<Self, MeasurementError> {
+        Self::from_exact_exp(self.get_exp_value() + rhs.get_exp_value())
+    }
+
+    /// Divides two prefixes, subtracting their exponents.
+    ///
+    /// # Returns
+    /// The resulting prefix, or [`MeasurementError::PrefixOverflow`] if the exponent is
+    /// outside Tera..=Femto (e.g. Tera / Femto).
+    pub fn checked_div(self, rhs: Self) -> Result<Self, MeasurementError> {
+        Self::from_exact_exp(self.get_exp_value() - rhs.get_exp_value())
+    }
+
+    /// Returns the prefix for an exponent that maps to one without a remainder.
+    fn from_exact_exp(exp: i16) -> Result<Self, MeasurementError> {
+        Self::from_exact_exp_value(exp).ok_or(MeasurementError::PrefixOverflow { exp })
+    }
+
+    /// Returns the prefix whose exponent is exactly `exp`, if any.
+    pub(crate) const fn from_exact_exp_value(exp: i16) -> Option<Self> {
+        match exp {
+            12 => Some(Self::Tera),
+            9 => Some(Self::Giga),
+            6 => Some(Self::Mega),
+            3 => Some(Self::Kilo),
+            0 => Some(Self::None),
+            -3 => Some(Self::Milli),
+            -6 => Some(Self::Micro),
+            -9 => Some(Self::Nano),
+            -12 => Some(Self::Pico),
+            -15 => Some(Self::Femto),
+            _ => None,
+        }
+    }
+
     /// Returns the conversion factor between two prefixes as a `f64`.
     ///
     /// # Arguments
@@ -62,13 +117,12 @@ impl Prefix {
     ///
     /// # Returns
     /// The factor by which to multiply to convert from `self` to `other`.
-    pub fn get_conversion_factor(&self, other: Self) -> f64 {
-        let exp = self.get_exp_value() - other.get_exp_value();
-        10f64.powi(exp as i32)
+    pub const fn get_conversion_factor(&self, other: Self) -> f64 {
+        pow10(self.get_exp_value() - other.get_exp_value())
     }
 
     /// Returns the string label for the prefix (e.g., "k" for kilo).
-    pub fn get_label(&self) -> &str {
+    pub fn get_label(&self) -> &'static str {
         match self {
             Self::Tera => "T",
             Self::Giga => "G",
@@ -82,37 +136,228 @@ impl Prefix {
             Self::Femto => "f",
         }
     }
+
+    /// Returns the full name of the prefix (e.g., "kilo"), empty for [`Prefix::None`].
+    pub fn name(&self) -> &str {
+        match self {
+            Self::Tera => "tera",
+            Self::Giga => "giga",
+            Self::Mega => "mega",
+            Self::Kilo => "kilo",
+            Self::None => "",
+            Self::Milli => "milli",
+            Self::Micro => "micro",
+            Self::Nano => "nano",
+            Self::Pico => "pico",
+            Self::Femto => "femto",
+        }
+    }
+
+    /// Returns the prefix matching a string label (e.g. "k" for kilo), the inverse of
+    /// [`Prefix::get_label`].
+    ///
+    /// Both "u" and "µ" are accepted for micro.
+    ///
+    /// # Returns
+    /// The matching [`Prefix`], or `None` if the label is unknown.
+    pub fn from_label(label: &str) -> Option<Self> {
+        match label {
+            "T" => Some(Self::Tera),
+            "G" => Some(Self::Giga),
+            "M" => Some(Self::Mega),
+            "k" => Some(Self::Kilo),
+            "" => Some(Self::None),
+            "m" => Some(Self::Milli),
+            "u" | "µ" => Some(Self::Micro),
+            "n" => Some(Self::Nano),
+            "p" => Some(Self::Pico),
+            "f" => Some(Self::Femto),
+            _ => None,
+        }
+    }
+}
+
+/// Returns the finest (smallest) of `prefixes`, or `None` if there are none, e.g. the prefix
+/// that combines arrays without losing resolution.
+#[cfg(feature = "ndarray")]
+pub(crate) fn finest(prefixes: impl IntoIterator<Item = Prefix>) -> Option<Prefix> {
+    prefixes.into_iter().min_by_key(Prefix::get_exp_value)
+}
+
+/// Rules for choosing a readable prefix, used by [`Measurement::nice_with`] and the other
+/// `nice_with` methods.
+///
+/// The default policy allows every prefix, targets a mantissa in `[1, 1000)` and keeps the
+/// current prefix when it already qualifies; it is what `nice()` uses.
+///
+/// [`Measurement::nice_with`]: crate::measurement::Measurement::nice_with
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct NicePolicy {
+    allowed: u16,
+    min: f64,
+    max: f64,
+    prefer_current: bool,
+}
+
+impl NicePolicy {
+    /// Creates the default policy.
+    pub const fn new() -> Self {
+        Self {
+            allowed: (1 << Prefix::ALL.len()) - 1,
+            min: 1.0,
+            max: 1000.0,
+            prefer_current: true,
+        }
+    }
+
+    /// Restricts the policy to the given prefixes.
+    pub fn with_prefixes(mut self, prefixes: &[Prefix]) -> Self {
+        self.allowed = prefixes.iter().fold(0, |acc, p| acc | Self::bit(*p));
+        self
+    }
+
+    /// Removes the given prefixes from the allowed set (e.g. Tera and Giga for voltages).
+    pub fn without_prefixes(mut self, prefixes: &[Prefix]) -> Self {
+        self.allowed &= !prefixes.iter().fold(0, |acc, p| acc | Self::bit(*p));
+        self
+    }
+
+    /// Sets the target interval `[min, max)` for the absolute value of the mantissa.
+    ///
+    /// # Panics
+    /// Panics unless `0 < min < max` and both are finite.
+    pub fn with_range(mut self, min: f64, max: f64) -> Self {
+        assert!(
+            min > 0.0 && min < max && max.is_finite(),
+            "nice range must satisfy 0 < min < max"
+        );
+        self.min = min;
+        self.max = max;
+        self
+    }
+
+    /// Sets whether a prefix that already puts the value in range is kept even when
+    /// another allowed prefix does too.
+    pub fn prefer_current(mut self, prefer: bool) -> Self {
+        self.prefer_current = prefer;
+        self
+    }
+
+    /// Returns `true` if `prefix` may be chosen.
+    pub fn allows(&self, prefix: Prefix) -> bool {
+        self.allowed & Self::bit(prefix) != 0
+    }
+
+    /// Picks the prefix for a magnitude `value` currently expressed in `current`.
+    ///
+    /// Among the allowed prefixes that put the mantissa in range, `current` wins if
+    /// preferred, then the largest. When none does, the one closest to the range (by ratio)
+    /// is used. Zero, non-finite values and an empty prefix set keep `current`.
+    pub(crate) fn choose(&self, value: f64, current: Prefix) -> Prefix {
+        let value = value.abs();
+        if value == 0.0 || !value.is_finite() || self.allowed == 0 {
+            return current;
+        }
+        // how far the mantissa in `p` is outside the range, as a ratio; 0 when inside
+        let distance = |p: Prefix| {
+            let m = value * current.get_conversion_factor(p);
+            if m >= self.min * (1.0 - 1e-12) && m < self.max * (1.0 - 1e-12) {
+                0.0
+            } else {
+                (self.min / m).max(m / self.max)
+            }
+        };
+        if self.prefer_current && self.allows(current) && distance(current) == 0.0 {
+            return current;
+        }
+        Prefix::ALL
+            .into_iter()
+            .filter(|p| self.allows(*p))
+            .map(|p| (p, distance(p)))
+            .fold(None, |best: Option<(Prefix, f64)>, (p, d)| match best {
+                Some((_, bd)) if bd <= d => best,
+                _ => Some((p, d)),
+            })
+            .map_or(current, |(p, _)| p)
+    }
+
+    fn bit(prefix: Prefix) -> u16 {
+        1 << prefix as u16
+    }
+}
+
+impl Default for NicePolicy {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 #[allow(clippy::suspicious_arithmetic_impl)]
-impl std::ops::Mul for Prefix {
+impl core::ops::Mul for Prefix {
     /// Multiplies two [`Prefix`] values, combining their exponents.
     ///
     /// # Panics
-    /// Panics if the resulting exponent does not map to a valid prefix.
+    /// Panics if the resulting exponent does not map to a valid prefix; see
+    /// [`Prefix::checked_mul`] for a fallible version.
     type Output = Self;
     fn mul(self, rhs: Self) -> Self::Output {
-        let exp = self.get_exp_value() + rhs.get_exp_value();
-        match Prefix::from_exp_value(exp) {
-            (p, 0) => p,
-            _ => panic!("Should never be here"),
+        self.checked_mul(rhs).unwrap_or_else(|e| panic!("{e}"))
+    }
+}
+
+/// Serde helpers writing a [`Prefix`] as its symbol (`"m"`, `""`) in human-readable
+/// formats such as JSON, and as the enum in binary formats, for `#[serde(with = ...)]`.
+///
+/// Reading accepts both the symbol and the variant name (`"Milli"`), so data written before
+/// a field switched to symbols stays readable.
+pub(crate) mod symbol {
+    use super::Prefix;
+    use alloc::string::String;
+    use serde::{
+        Deserialize, Deserializer, Serialize, Serializer,
+        de::{Error, value::StrDeserializer},
+    };
+
+    /// A prefix serialized as by [`serialize`], for hand-written `Serialize` impls.
+    pub(crate) struct Symbol(pub(crate) Prefix);
+
+    impl Serialize for Symbol {
+        fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+            serialize(&self.0, s)
+        }
+    }
+
+    pub(crate) fn serialize<S: Serializer>(prefix: &Prefix, s: S) -> Result<S::Ok, S::Error> {
+        if s.is_human_readable() {
+            s.serialize_str(prefix.get_label())
+        } else {
+            prefix.serialize(s)
+        }
+    }
+
+    pub(crate) fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<Prefix, D::Error> {
+        if !d.is_human_readable() {
+            return Prefix::deserialize(d);
+        }
+        let s = String::deserialize(d)?;
+        match Prefix::from_label(&s) {
+            Some(prefix) => Ok(prefix),
+            None => Prefix::deserialize(StrDeserializer::<D::Error>::new(&s))
+                .map_err(|_| D::Error::custom(format_args!("unknown prefix `{s}`"))),
         }
     }
 }
 
 #[allow(clippy::suspicious_arithmetic_impl)]
-impl std::ops::Div for Prefix {
+impl core::ops::Div for Prefix {
     /// Divides two [`Prefix`] values, subtracting their exponents.
     ///
     /// # Panics
-    /// Panics if the resulting exponent does not map to a valid prefix.
+    /// Panics if the resulting exponent does not map to a valid prefix; see
+    /// [`Prefix::checked_div`] for a fallible version.
     type Output = Self;
     fn div(self, rhs: Self) -> Self::Output {
-        let exp = self.get_exp_value() - rhs.get_exp_value();
-        match Prefix::from_exp_value(exp) {
-            (p, 0) => p,
-            _ => panic!("Should never be here"),
-        }
+        self.checked_div(rhs).unwrap_or_else(|e| panic!("{e}"))
     }
 }
 
@@ -140,6 +385,95 @@ mod prefix_tests {
         assert_eq!(Prefix::Mega / Prefix::Kilo, Prefix::Kilo);
     }
 
+    #[test]
+    fn checked_overflow() {
+        assert_eq!(Prefix::Kilo.checked_mul(Prefix::Milli), Ok(Prefix::None));
+        assert_eq!(Prefix::Pico.checked_div(Prefix::Kilo), Ok(Prefix::Femto));
+        assert_eq!(
+            Prefix::Tera.checked_mul(Prefix::Tera),
+            Err(MeasurementError::PrefixOverflow { exp: 24 })
+        );
+        let e = Prefix::Femto.checked_div(Prefix::Giga).unwrap_err();
+        assert_eq!(e.to_string(), "exponent 10^-24 has no SI prefix");
+    }
+
+    #[test]
+    #[should_panic(expected = "exponent 10^24 has no SI prefix")]
+    fn mul_overflow_panics() {
+        let _ = Prefix::Tera * Prefix::Tera;
+    }
+
+    #[test]
+    fn label_round_trip() {
+        for p in [
+            Prefix::Tera,
+            Prefix::Giga,
+            Prefix::Mega,
+            Prefix::Kilo,
+            Prefix::None,
+            Prefix::Milli,
+            Prefix::Micro,
+            Prefix::Nano,
+            Prefix::Pico,
+            Prefix::Femto,
+        ] {
+            assert_eq!(Prefix::from_label(p.get_label()), Some(p));
+        }
+        assert_eq!(Prefix::from_label("µ"), Some(Prefix::Micro));
+        assert_eq!(Prefix::from_label("x"), None);
+    }
+
+    #[test]
+    fn nice_policy_default() {
+        let policy = NicePolicy::default();
+        assert_eq!(policy, NicePolicy::new());
+        assert_eq!(policy.choose(2500.0, Prefix::Milli), Prefix::None);
+        assert_eq!(policy.choose(-0.5, Prefix::None), Prefix::Milli);
+        assert_eq!(policy.choose(999.0, Prefix::Kilo), Prefix::Kilo);
+        assert_eq!(policy.choose(0.0, Prefix::Kilo), Prefix::Kilo);
+        assert_eq!(policy.choose(f64::NAN, Prefix::Nano), Prefix::Nano);
+        assert_eq!(policy.choose(1e30, Prefix::None), Prefix::Tera);
+    }
+
+    #[test]
+    fn nice_policy_restricted_prefixes() {
+        let policy = NicePolicy::new().without_prefixes(&[Prefix::Tera, Prefix::Giga]);
+        assert!(!policy.allows(Prefix::Giga));
+        assert_eq!(policy.choose(2.5e9, Prefix::None), Prefix::Mega);
+        assert_eq!(policy.choose(2.5, Prefix::Tera), Prefix::Mega);
+        let policy = NicePolicy::new().with_prefixes(&[Prefix::None, Prefix::Milli]);
+        assert_eq!(policy.choose(5e-9, Prefix::None), Prefix::Milli);
+        assert_eq!(policy.choose(5.0, Prefix::Kilo), Prefix::None);
+        let policy = NicePolicy::new().with_prefixes(&[]);
+        assert_eq!(policy.choose(5e9, Prefix::None), Prefix::None);
+    }
+
+    #[test]
+    fn nice_policy_range_and_ties() {
+        let policy = NicePolicy::new().with_range(0.1, 100.0);
+        assert_eq!(policy.choose(250.0, Prefix::Milli), Prefix::None);
+        assert_eq!(policy.choose(50.0, Prefix::Milli), Prefix::Milli);
+        let wide = NicePolicy::new().with_range(1.0, 1e6);
+        assert_eq!(wide.choose(2000.0, Prefix::None), Prefix::None);
+        assert_eq!(
+            wide.prefer_current(false).choose(2000.0, Prefix::None),
+            Prefix::Kilo
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "nice range must satisfy 0 < min < max")]
+    fn nice_policy_invalid_range() {
+        let _ = NicePolicy::new().with_range(10.0, 1.0);
+    }
+
+    #[test]
+    fn pico_from_exp_value() {
+        assert_eq!(Prefix::from_exp_value(-12), (Prefix::Pico, 0));
+        assert_eq!(Prefix::from_exp_value(-10), (Prefix::Pico, 2));
+        assert_eq!(Prefix::Nano * Prefix::Milli, Prefix::Pico);
+    }
+
     #[test]
     #[should_panic]
     fn femto_div_by_femto() {
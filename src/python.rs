@@ -0,0 +1,410 @@
+//! Python bindings for measurements and arrays, enabled by the `python` feature.
+//!
+//! Python has no compile-time units, so the classes carry their unit as a label, like
+//! [`DynMeasurement`] and [`DynM1d`]; operations between different units raise `ValueError`.
+//! Prefixes are passed as labels (`"m"`, `"k"`, `""`). Array data is exposed to NumPy as
+//! read-only views without copying.
+//!
+//! Call [`register`] from the `#[pymodule]` of an extension crate to add the classes:
+//!
+//! ```ignore
+//! #[pymodule]
+//! fn measurements(m: &Bound<'_, PyModule>) -> PyResult<()> {
+//!     typed_measurements::python::register(m)
+//! }
+//! ```
+
+use crate::{
+    dyn_measurement::{DynM1d, DynMeasurement, UnitMismatch},
+    m1d::M1d,
+    m2d::M2d,
+    math::compensated_sum,
+    measurement::Measurement,
+    prefix::Prefix,
+    uom::Uom,
+};
+use ndarray::{Array2, ArrayBase, Data, Dimension};
+use numpy::{PyArray, PyReadonlyArray1, PyReadonlyArray2};
+use pyo3::{basic::CompareOp, exceptions::PyValueError, prelude::*};
+
+/// Adds the `Measurement`, `M1d` and `M2d` classes to `module`.
+pub fn register(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add_class::<PyMeasurement>()?;
+    module.add_class::<PyM1d>()?;
+    module.add_class::<PyM2d>()
+}
+
+fn parse_prefix(label: &str) -> PyResult<Prefix> {
+    Prefix::from_label(label)
+        .ok_or_else(|| PyValueError::new_err(format!("unknown prefix `{label}`")))
+}
+
+fn unit_error(e: UnitMismatch) -> PyErr {
+    PyValueError::new_err(e.to_string())
+}
+
+/// Returns a read-only NumPy array sharing the memory of `array`, which `owner` holds.
+///
+/// # Safety
+/// `array` must be owned by `owner` and must not be mutated or moved while `owner` is alive,
+/// which holds for the arrays of the frozen classes below.
+unsafe fn borrow_array<'py, S, D>(
+    array: &ArrayBase<S, D>,
+    owner: Bound<'py, PyAny>,
+) -> PyResult<Bound<'py, PyArray<f64, D>>>
+where
+    S: Data<Elem = f64>,
+    D: Dimension,
+{
+    let view = unsafe { PyArray::borrow_from_array(array, owner) };
+    view.getattr("flags")?.setattr("writeable", false)?;
+    Ok(view)
+}
+
+/// Returns the compensated mean of `values`, or `None` if there are none.
+fn mean_of<'a>(values: impl ExactSizeIterator<Item = &'a f64>) -> Option<f64> {
+    let len = values.len();
+    (len > 0).then(|| compensated_sum(values.copied()) / len as f64)
+}
+
+/// A measurement with a runtime unit, exposed to Python as `Measurement`.
+#[pyclass(name = "Measurement", module = "typed_measurements", frozen)]
+#[derive(Clone, Debug)]
+pub struct PyMeasurement {
+    inner: DynMeasurement,
+}
+
+impl PyMeasurement {
+    /// Returns the wrapped measurement.
+    pub fn as_dyn(&self) -> &DynMeasurement {
+        &self.inner
+    }
+}
+
+#[pymethods]
+impl PyMeasurement {
+    /// `Measurement(value, unit, prefix="")`, e.g. `Measurement(1.5, "V", "m")` for 1.5 mV.
+    #[new]
+    #[pyo3(signature = (value, unit, prefix = ""))]
+    fn py_new(value: f64, unit: &str, prefix: &str) -> PyResult<Self> {
+        Ok(DynMeasurement::new(value, parse_prefix(prefix)?, unit).into())
+    }
+
+    #[getter]
+    fn value(&self) -> f64 {
+        self.inner.value()
+    }
+
+    #[getter]
+    fn prefix(&self) -> &'static str {
+        self.inner.prefix().get_label()
+    }
+
+    #[getter]
+    fn unit(&self) -> &str {
+        self.inner.unit()
+    }
+
+    fn convert_to(&self, prefix: &str) -> PyResult<Self> {
+        Ok(self.inner.convert_to(parse_prefix(prefix)?).into())
+    }
+
+    fn label(&self) -> String {
+        self.inner.label()
+    }
+
+    fn __add__(&self, other: PyRef<'_, Self>) -> PyResult<Self> {
+        self.inner
+            .try_add(&other.inner)
+            .map(Self::from)
+            .map_err(unit_error)
+    }
+
+    fn __sub__(&self, other: PyRef<'_, Self>) -> PyResult<Self> {
+        self.inner
+            .try_sub(&other.inner)
+            .map(Self::from)
+            .map_err(unit_error)
+    }
+
+    fn __richcmp__(&self, other: PyRef<'_, Self>, op: CompareOp) -> PyResult<bool> {
+        let ordering = self
+            .inner
+            .try_partial_cmp(&other.inner)
+            .map_err(unit_error)?;
+        Ok(match ordering {
+            Some(o) => op.matches(o),
+            // NaN compares unequal to everything
+            None => matches!(op, CompareOp::Ne),
+        })
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Measurement({})", self.inner.label())
+    }
+}
+
+impl From<DynMeasurement> for PyMeasurement {
+    fn from(inner: DynMeasurement) -> Self {
+        Self { inner }
+    }
+}
+
+impl<U: Uom> From<Measurement<U>> for PyMeasurement {
+    fn from(m: Measurement<U>) -> Self {
+        DynMeasurement::from_typed(m).into()
+    }
+}
+
+/// A one-dimensional array with a runtime unit, exposed to Python as `M1d`.
+#[pyclass(name = "M1d", module = "typed_measurements", frozen)]
+#[derive(Clone, Debug)]
+pub struct PyM1d {
+    inner: DynM1d,
+}
+
+impl PyM1d {
+    /// Returns the wrapped array.
+    pub fn as_dyn(&self) -> &DynM1d {
+        &self.inner
+    }
+}
+
+#[pymethods]
+impl PyM1d {
+    /// `M1d(values, unit, prefix="")`, copying `values` once.
+    #[new]
+    #[pyo3(signature = (values, unit, prefix = ""))]
+    fn py_new(values: PyReadonlyArray1<'_, f64>, unit: &str, prefix: &str) -> PyResult<Self> {
+        let values = values.as_array().to_owned();
+        Ok(DynM1d::new(values, parse_prefix(prefix)?, unit).into())
+    }
+
+    /// A read-only NumPy view of the values, sharing memory with this array.
+    #[getter]
+    fn values<'py>(slf: &Bound<'py, Self>) -> PyResult<Bound<'py, PyArray<f64, ndarray::Ix1>>> {
+        let view = slf.get().inner.view();
+        // SAFETY: the class is frozen, so the array lives as long as `slf` and never changes
+        unsafe { borrow_array(&view, slf.clone().into_any()) }
+    }
+
+    #[getter]
+    fn prefix(&self) -> &'static str {
+        self.inner.prefix().get_label()
+    }
+
+    #[getter]
+    fn unit(&self) -> &str {
+        self.inner.unit()
+    }
+
+    fn convert_to(&self, prefix: &str) -> PyResult<Self> {
+        Ok(self.inner.convert_to(parse_prefix(prefix)?).into())
+    }
+
+    fn label(&self) -> String {
+        self.inner.label()
+    }
+
+    /// The mean of all values, or `None` if the array is empty.
+    fn mean(&self) -> Option<PyMeasurement> {
+        let mean = mean_of(self.inner.view().iter())?;
+        Some(DynMeasurement::new(mean, self.inner.prefix(), self.inner.unit()).into())
+    }
+
+    fn __len__(&self) -> usize {
+        self.inner.len()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("M1d<{}> ({})", self.inner.label(), self.inner.len())
+    }
+}
+
+impl From<DynM1d> for PyM1d {
+    fn from(inner: DynM1d) -> Self {
+        Self { inner }
+    }
+}
+
+impl<U: Uom> From<M1d<U>> for PyM1d {
+    fn from(m: M1d<U>) -> Self {
+        DynM1d::from(m).into()
+    }
+}
+
+/// A two-dimensional array with a runtime unit, exposed to Python as `M2d`.
+#[pyclass(name = "M2d", module = "typed_measurements", frozen)]
+#[derive(Clone, Debug)]
+pub struct PyM2d {
+    values: Array2<f64>,
+    prefix: Prefix,
+    unit: String,
+}
+
+#[pymethods]
+impl PyM2d {
+    /// `M2d(values, unit, prefix="")`, copying `values` once.
+    #[new]
+    #[pyo3(signature = (values, unit, prefix = ""))]
+    fn py_new(values: PyReadonlyArray2<'_, f64>, unit: &str, prefix: &str) -> PyResult<Self> {
+        Ok(Self {
+            values: values.as_array().to_owned(),
+            prefix: parse_prefix(prefix)?,
+            unit: unit.to_string(),
+        })
+    }
+
+    /// A read-only NumPy view of the values, sharing memory with this array.
+    #[getter]
+    fn values<'py>(slf: &Bound<'py, Self>) -> PyResult<Bound<'py, PyArray<f64, ndarray::Ix2>>> {
+        // SAFETY: the class is frozen, so the array lives as long as `slf` and never changes
+        unsafe { borrow_array(&slf.get().values, slf.clone().into_any()) }
+    }
+
+    #[getter]
+    fn prefix(&self) -> &'static str {
+        self.prefix.get_label()
+    }
+
+    #[getter]
+    fn unit(&self) -> &str {
+        &self.unit
+    }
+
+    #[getter]
+    fn shape(&self) -> (usize, usize) {
+        self.values.dim()
+    }
+
+    fn convert_to(&self, prefix: &str) -> PyResult<Self> {
+        let prefix = parse_prefix(prefix)?;
+        let factor = self.prefix.get_conversion_factor(prefix);
+        Ok(Self {
+            values: self.values.mapv(|v| v * factor),
+            prefix,
+            unit: self.unit.clone(),
+        })
+    }
+
+    fn label(&self) -> String {
+        self.prefix.get_label().to_string() + &self.unit
+    }
+
+    /// The mean of all values, or `None` if the array is empty.
+    fn mean(&self) -> Option<PyMeasurement> {
+        let mean = mean_of(self.values.iter())?;
+        Some(DynMeasurement::new(mean, self.prefix, self.unit.clone()).into())
+    }
+
+    fn __repr__(&self) -> String {
+        let (rows, cols) = self.values.dim();
+        format!("M2d<{}> ({rows}x{cols})", self.label())
+    }
+}
+
+impl<U: Uom> From<M2d<U>> for PyM2d {
+    fn from(m: M2d<U>) -> Self {
+        Self {
+            values: m.values(),
+            prefix: m.prefix(),
+            unit: U::uom(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod python_tests {
+    use super::*;
+    use crate::uom::Volt;
+    use pyo3::types::PyDict;
+
+    /// Runs `code` with the classes in scope.
+    fn run(code: &std::ffi::CStr) {
+        Python::initialize();
+        Python::attach(|py| {
+            let module = PyModule::new(py, "typed_measurements").unwrap();
+            register(&module).unwrap();
+            let locals = PyDict::new(py);
+            for name in ["Measurement", "M1d", "M2d"] {
+                locals
+                    .set_item(name, module.getattr(name).unwrap())
+                    .unwrap();
+            }
+            if let Err(e) = py.run(code, None, Some(&locals)) {
+                panic!("{e}");
+            }
+        });
+    }
+
+    #[test]
+    fn measurement_round_trip() {
+        run(cr#"
+m = Measurement(1.5, "V", "m")
+assert (m.value, m.prefix, m.unit) == (1.5, "m", "V")
+assert m.label() == "1.5mV"
+assert m.convert_to("u").label() == "1500uV"
+s = m + Measurement(0.5, "V")
+assert s.label() == "0.5015V", s.label()
+assert (m - Measurement(500, "V", "u")).label() == "1000uV"
+assert m == Measurement(1500, "V", "u")
+assert m < Measurement(2, "V", "m") and m >= Measurement(1, "V", "m")
+assert Measurement(float("nan"), "V") != Measurement(float("nan"), "V")
+assert repr(m) == "Measurement(1.5mV)"
+try:
+    m + Measurement(1, "A")
+    raise AssertionError("mixed units must fail")
+except ValueError as e:
+    assert "unit mismatch" in str(e)
+try:
+    Measurement(1, "V", "x")
+    raise AssertionError("unknown prefixes must fail")
+except ValueError:
+    pass
+"#);
+    }
+
+    #[test]
+    fn typed_values_convert() {
+        Python::initialize();
+        Python::attach(|py| {
+            let m = Bound::new(
+                py,
+                PyMeasurement::from(Measurement::<Volt>::new(3.0, Prefix::Kilo)),
+            )
+            .unwrap();
+            let label: String = m.call_method0("label").unwrap().extract().unwrap();
+            assert_eq!(label, "3kV");
+            let a = PyM1d::from(M1d::<Volt>::new(vec![1.0, 2.0], Prefix::Milli));
+            assert_eq!(a.label(), "mV");
+            assert_eq!(a.mean().unwrap().label(), "1.5mV");
+            let b = PyM2d::from(M2d::<Volt>::new(Array2::zeros((0, 3)), Prefix::None));
+            assert_eq!(b.shape(), (0, 3));
+            assert!(b.mean().is_none());
+        });
+    }
+
+    #[test]
+    fn numpy_views() {
+        let has_numpy = {
+            Python::initialize();
+            Python::attach(|py| py.import("numpy").is_ok())
+        };
+        if !has_numpy {
+            // the NumPy views need the numpy package in the embedded interpreter
+            return;
+        }
+        run(cr#"
+import numpy as np
+a = M1d(np.array([1.0, 2.0, 3.0]), "V", "m")
+v = a.values
+assert list(v) == [1.0, 2.0, 3.0] and not v.flags.writeable
+assert v.base is a
+assert a.mean().label() == "2mV" and len(a) == 3
+assert list(a.convert_to("u").values) == [1000.0, 2000.0, 3000.0]
+b = M2d(np.arange(6.0).reshape(2, 3), "A", "n")
+assert b.shape == (2, 3) and b.values[1, 2] == 5.0
+assert b.mean().label() == "2.5nA" and b.label() == "nA"
+"#);
+    }
+}
@@ -0,0 +1,145 @@
+//! Short names for common measurements, e.g. [`Voltage`] for `Measurement<Volt>`, with one
+//! constructor per commonly used prefix.
+//!
+//! ```
+//! use typed_measurements::prelude::*;
+//!
+//! let v = Voltage::millivolts(5.0);
+//! assert_eq!(v, Measurement::<Volt>::new(5.0, Prefix::Milli));
+//! let f = Frequency::kilohertz(20.0);
+//! assert_eq!(f.label(), "20kHz");
+//! ```
+
+use crate::{
+    measurement::Measurement,
+    prefix::Prefix,
+    uom::{Ampere, Coulomb, Farad, Hertz, Ohm, Second, Volt, Watt},
+};
+
+/// A measurement in volts.
+pub type Voltage = Measurement<Volt>;
+/// A measurement in amperes.
+pub type Current = Measurement<Ampere>;
+/// A measurement in watts.
+pub type Power = Measurement<Watt>;
+/// A measurement in seconds.
+pub type Time = Measurement<Second>;
+/// A measurement in hertz.
+pub type Frequency = Measurement<Hertz>;
+/// A measurement in ohms.
+pub type Resistance = Measurement<Ohm>;
+/// A measurement in coulombs.
+pub type Charge = Measurement<Coulomb>;
+/// A measurement in farads.
+pub type Capacitance = Measurement<Farad>;
+
+/// Implements prefix-specific constructors such as `Voltage::millivolts` for a unit.
+macro_rules! constructors {
+    ($unit:ty { $($name:ident => $prefix:ident),* $(,)? }) => {
+        impl Measurement<$unit> {
+            $(
+                #[doc = concat!("Creates a measurement of `value` ", stringify!($name), ".")]
+                pub fn $name<V: Into<f64>>(value: V) -> Self {
+                    Self::new(value, Prefix::$prefix)
+                }
+            )*
+        }
+    };
+}
+
+constructors!(Volt {
+    kilovolts => Kilo,
+    volts => None,
+    millivolts => Milli,
+    microvolts => Micro,
+    nanovolts => Nano,
+});
+constructors!(Ampere {
+    amps => None,
+    milliamps => Milli,
+    microamps => Micro,
+    nanoamps => Nano,
+    picoamps => Pico,
+    femtoamps => Femto,
+});
+constructors!(Watt {
+    megawatts => Mega,
+    kilowatts => Kilo,
+    watts => None,
+    milliwatts => Milli,
+    microwatts => Micro,
+});
+constructors!(Second {
+    seconds => None,
+    milliseconds => Milli,
+    microseconds => Micro,
+    nanoseconds => Nano,
+});
+constructors!(Hertz {
+    gigahertz => Giga,
+    megahertz => Mega,
+    kilohertz => Kilo,
+    hertz => None,
+    millihertz => Milli,
+});
+constructors!(Ohm {
+    gigaohms => Giga,
+    megaohms => Mega,
+    kiloohms => Kilo,
+    ohms => None,
+    milliohms => Milli,
+});
+constructors!(Coulomb {
+    coulombs => None,
+    millicoulombs => Milli,
+    microcoulombs => Micro,
+    nanocoulombs => Nano,
+    picocoulombs => Pico,
+});
+constructors!(Farad {
+    farads => None,
+    millifarads => Milli,
+    microfarads => Micro,
+    nanofarads => Nano,
+    picofarads => Pico,
+});
+
+#[cfg(test)]
+mod quantities_tests {
+    use super::*;
+
+    #[test]
+    fn short_forms_match_new() {
+        assert_eq!(
+            Voltage::millivolts(5.0),
+            Measurement::<Volt>::new(5.0, Prefix::Milli)
+        );
+        assert_eq!(
+            Current::nanoamps(120.0),
+            Measurement::<Ampere>::new(120.0, Prefix::Nano)
+        );
+        assert_eq!(
+            Time::microseconds(50),
+            Measurement::<Second>::new(50, Prefix::Micro)
+        );
+        assert_eq!(
+            Frequency::kilohertz(20.0),
+            Measurement::<Hertz>::new(20.0, Prefix::Kilo)
+        );
+        assert_eq!(
+            Resistance::megaohms(1.5),
+            Measurement::<Ohm>::new(1.5, Prefix::Mega)
+        );
+        assert_eq!(Power::watts(2.0).prefix(), Prefix::None);
+        assert_eq!(Capacitance::picofarads(10.0).label(), "10pF");
+        assert_eq!(Charge::coulombs(1.0).label(), "1C");
+    }
+
+    #[test]
+    fn prefixes_are_kept() {
+        assert_eq!(Current::picoamps(3.0).prefix(), Prefix::Pico);
+        assert_eq!(Current::femtoamps(3.0).prefix(), Prefix::Femto);
+        assert_eq!(Frequency::gigahertz(2.4).label(), "2.4GHz");
+        assert_eq!(Voltage::kilovolts(1.0), Voltage::volts(1000.0));
+    }
+}
@@ -0,0 +1,110 @@
+//! The [`Quantity`] trait, shared by scalar measurements and arrays of measurements.
+
+use crate::{measurement::Measurement, prefix::Prefix, uom::Uom};
+use alloc::string::String;
+
+/// Anything holding values of unit `U` under one SI prefix: [`Measurement`], and with the
+/// `ndarray` feature `M1d` and `M2d`.
+///
+/// Generic code written against this trait works for scalars and arrays alike:
+///
+/// ```
+/// use typed_measurements::prelude::*;
+///
+/// /// Converts any quantity to the base unit, without a prefix.
+/// fn to_base<Q: Quantity<U>, U: Uom>(q: Q) -> Q {
+///     q.convert_to(Prefix::None)
+/// }
+///
+/// let m = to_base(Measurement::<Volt>::new(1500.0, Prefix::Milli));
+/// assert_eq!(m.label(), "1.5V");
+/// # #[cfg(feature = "ndarray")]
+/// # {
+/// let a = to_base(M1d::<Volt>::new(vec![1.0, 2.0], Prefix::Kilo));
+/// assert_eq!(a.values(), ndarray::array![1000.0, 2000.0]);
+/// # }
+/// ```
+///
+/// # Object safety
+/// [`Quantity::convert_to`] takes and returns `Self` by value, so it requires `Self: Sized`
+/// and cannot be called through a `dyn Quantity<U>`. The other methods can, so a
+/// `&mut dyn Quantity<U>` can still be inspected, labelled and scaled.
+pub trait Quantity<U: Uom> {
+    /// Returns the SI prefix of the values.
+    fn prefix(&self) -> Prefix;
+
+    /// Converts to `pfx`, rescaling the values so that the quantity is unchanged.
+    fn convert_to(self, pfx: Prefix) -> Self
+    where
+        Self: Sized;
+
+    /// Returns a label such as "2.5mV"; arrays are labelled by their mean.
+    fn label(&self) -> String;
+
+    /// Multiplies every value by `f`, keeping the prefix.
+    fn scale(&mut self, f: f64);
+}
+
+impl<U: Uom> Quantity<U> for Measurement<U> {
+    fn prefix(&self) -> Prefix {
+        Measurement::prefix(self)
+    }
+
+    fn convert_to(self, pfx: Prefix) -> Self {
+        Measurement::convert_to(&self, pfx)
+    }
+
+    fn label(&self) -> String {
+        Measurement::label(self)
+    }
+
+    fn scale(&mut self, f: f64) {
+        *self = self.scaled(f);
+    }
+}
+
+#[cfg(test)]
+mod quantity_tests {
+    use super::*;
+    use crate::uom::Volt;
+
+    fn to_base<Q: Quantity<U>, U: Uom>(q: Q) -> Q {
+        q.convert_to(Prefix::None)
+    }
+
+    /// Doubles a quantity through a trait object.
+    fn double(q: &mut dyn Quantity<Volt>) -> Prefix {
+        q.scale(2.0);
+        q.prefix()
+    }
+
+    #[test]
+    fn generic_over_measurements() {
+        let mut m = to_base(Measurement::<Volt>::new(250.0, Prefix::Milli));
+        assert_eq!(m.value(), 0.25);
+        assert_eq!(Quantity::prefix(&m), Prefix::None);
+        assert_eq!(double(&mut m), Prefix::None);
+        assert_eq!(Quantity::label(&m), "0.5V");
+    }
+
+    #[cfg(feature = "ndarray")]
+    #[test]
+    fn generic_over_arrays() {
+        use crate::{m1d::M1d, m2d::M2d};
+        use ndarray::array;
+
+        let mut a = to_base(M1d::<Volt>::new(vec![1.0, 3.0], Prefix::Milli));
+        assert_eq!(a.values(), array![0.001, 0.003]);
+        assert_eq!(double(&mut a), Prefix::None);
+        assert_eq!(a.values(), array![0.002, 0.006]);
+        assert_eq!(Quantity::label(&a), "0.004V");
+
+        let mut m = to_base(M2d::<Volt>::new(
+            array![[1.0, 2.0], [3.0, 4.0]],
+            Prefix::Kilo,
+        ));
+        assert_eq!(m.values(), array![[1000.0, 2000.0], [3000.0, 4000.0]]);
+        assert_eq!(double(&mut m), Prefix::None);
+        assert_eq!(Quantity::label(&m), "5000V");
+    }
+}
@@ -1,18 +1,34 @@
 use crate::{
-    measurement::Measurement, percentage, percentage::Percentage, prefix::Prefix, uom::Uom,
+    error::MeasurementError,
+    math::{ceil, floor, floor_log10, powi10, rescale_decimal},
+    measurement::{
+        LabelStyle, Measurement, ParseMeasurementError, ParseOptions, RoundMode, pad, parse_number,
+        parse_unit, same_quantity,
+    },
+    percentage,
+    percentage::Percentage,
+    prefix::{NicePolicy, Prefix, symbol},
+    uom::{Second, Uom},
 };
-use serde::{Deserialize, Serialize};
-use std::marker::PhantomData;
+use alloc::{format, string::String, vec::Vec};
+use core::{fmt, marker::PhantomData};
+use serde::{Deserialize, Deserializer, Serialize, Serializer, de::Error, ser::SerializeStruct};
 
 /// Represents a measurement range with a minimum, maximum, step size, and unit prefix.
 ///
+/// In human-readable formats such as JSON, a stepless range omits `step` and the prefix is
+/// written as its symbol, e.g. `{"min":-1.0,"max":1.0,"prefix":"m"}`. The earlier form,
+/// with `"step":null` and the prefix name (`"Milli"`), is still read.
+///
 /// # Type Parameters
 /// - `U`: The unit of measurement, implementing the [`Uom`] trait.
-#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+#[derive(Clone, Copy, Debug, Deserialize)]
 pub struct RangedMeasurement<U: Uom> {
     min: f64,
     max: f64,
+    #[serde(default)]
     step: Option<f64>,
+    #[serde(deserialize_with = "symbol::deserialize")]
     prefix: Prefix,
     #[serde(skip)]
     uom: PhantomData<U>,
@@ -70,6 +86,52 @@ impl<U: Uom> RangedMeasurement<U> {
         }
     }
 
+    /// Parses a label written by [`RangedMeasurement::label`], such as `"[-10,10,1]mV"` or
+    /// `"[0,5]V"` for a stepless range.
+    ///
+    /// # Returns
+    /// The range, validated as by [`RangedMeasurementBuilder::build`], or
+    /// [`MeasurementError::ParseError`] if the label is malformed.
+    pub fn from_label(s: &str) -> Result<Self, MeasurementError> {
+        Self::from_label_with(s, &ParseOptions::default())
+    }
+
+    /// Parses a range label like [`RangedMeasurement::from_label`], reading the numbers with
+    /// `options`. When a comma may appear in a number, the numbers are separated by
+    /// semicolons instead, e.g. `"[-1,5;1,5;0,1]mV"` with a decimal comma.
+    ///
+    /// # Returns
+    /// The range; [`MeasurementError::ParseError`] if the label is malformed or a number is
+    /// ambiguous, [`MeasurementError::InvalidOptions`] if it does not hold two or three
+    /// numbers, or [`MeasurementError::RangeInvariant`] if the range is invalid.
+    pub fn from_label_with(s: &str, options: &ParseOptions) -> Result<Self, MeasurementError> {
+        let s = s.trim();
+        let (numbers, unit) = s
+            .strip_prefix('[')
+            .and_then(|s| s.split_once(']'))
+            .ok_or_else(|| ParseMeasurementError::Value(String::from(s)))?;
+        let prefix = parse_unit::<U>(unit.trim_start())?;
+        let numbers = numbers
+            .split(options.list_separator())
+            .map(|n| Ok(Measurement::new(parse_number(n, options)?, prefix)))
+            .collect::<Result<Vec<_>, ParseMeasurementError>>()?;
+        let builder = match numbers[..] {
+            [min, max] => Self::builder().min(min).max(max),
+            [min, max, step] => Self::builder().min(min).max(max).step(step),
+            _ => {
+                return Err(MeasurementError::InvalidOptions(
+                    "a range label holds a min, a max and an optional step",
+                ));
+            }
+        };
+        builder.build()
+    }
+
+    /// Returns a [`RangedMeasurementBuilder`] for a validated range.
+    pub fn builder() -> RangedMeasurementBuilder<U> {
+        RangedMeasurementBuilder::new()
+    }
+
     /// Returns the minimum value as a [`Measurement`] with the associated prefix.
     pub fn min(&self) -> Measurement<U> {
         Measurement::new(self.min, self.prefix)
@@ -98,18 +160,116 @@ impl<U: Uom> RangedMeasurement<U> {
         other > self.min() * p && other < self.max() * p
     }
 
+    /// Snaps a measurement onto the range, in the range's prefix.
+    ///
+    /// The value is rounded in the direction given by `mode` to a grid point `min + k·step`,
+    /// then clamped to the grid points inside the range. As for
+    /// [`Measurement::round_to_multiple_of`], [`RoundMode::TowardZero`] and halfway cases of
+    /// [`RoundMode::Nearest`] are judged by the value, not by the grid index. A stepless range
+    /// only clamps.
+    ///
+    /// # Arguments
+    /// * `m` - The measurement to snap.
+    /// * `mode` - The rounding direction.
+    pub fn snap(&self, m: Measurement<U>, mode: RoundMode) -> Measurement<U> {
+        let v = m.convert_to(self.prefix).value();
+        let (lo, hi) = (self.min.min(self.max), self.max.max(self.min));
+        let snapped = match self.step {
+            Some(step) if step > 0.0 && step.is_finite() => {
+                let last = floor((hi - lo) / step + 1e-9);
+                let idx = (v - lo) / step;
+                let k = match mode {
+                    RoundMode::TowardZero if v < 0.0 => RoundMode::Ceil.apply(idx),
+                    RoundMode::TowardZero => RoundMode::Floor.apply(idx),
+                    RoundMode::Nearest if v < 0.0 => RoundMode::Ceil.apply(idx - 0.5),
+                    mode => mode.apply(idx),
+                };
+                lo + k.clamp(0.0, last) * step
+            }
+            _ => v.clamp(lo, hi),
+        };
+        Measurement::new(snapped, self.prefix)
+    }
+
     /// Returns a string label combining min, max, step, prefix, and unit (e.g., "[-10.0,10.0,1.0]mV").
     pub fn label(&self) -> String {
-        "[".to_string()
-            + &self.min.to_string()
-            + ","
-            + &self.max.to_string()
-            + &self
-                .step
-                .map_or("".to_string(), |s| ",".to_string() + &s.to_string())
-            + "]"
-            + self.prefix.get_label()
-            + &U::uom()
+        let (pfx, uom) = (self.prefix.get_label(), U::uom_str());
+        match self.step {
+            Some(step) => format!("[{},{},{step}]{pfx}{uom}", self.min, self.max),
+            None => format!("[{},{}]{pfx}{uom}", self.min, self.max),
+        }
+    }
+
+    /// Returns the range in a readable prefix, chosen from the larger of `|min|` and `|max|`.
+    ///
+    /// Equivalent to [`RangedMeasurement::nice_with`] with the default [`NicePolicy`].
+    pub fn nice(self) -> Self {
+        self.nice_with(&NicePolicy::new())
+    }
+
+    /// Converts the range to the prefix `policy` chooses for the larger of `|min|` and `|max|`.
+    ///
+    /// The bounds and step are rescaled as in [`Measurement::nice_with`].
+    pub fn nice_with(&self, policy: &NicePolicy) -> Self {
+        let largest = self.min.abs().max(self.max.abs());
+        let prefix = policy.choose(largest, self.prefix);
+        let shift = self.prefix.get_exp_value() - prefix.get_exp_value();
+        Self {
+            min: rescale_decimal(self.min, shift),
+            max: rescale_decimal(self.max, shift),
+            step: self.step.map(|s| rescale_decimal(s, shift)),
+            prefix,
+            uom: PhantomData,
+        }
+    }
+
+    /// Returns about `target_count` ticks at round values (1, 2 or 5 × 10^k apart) covering
+    /// the range, in the range's prefix, e.g. 0, 2, 4, 6, 8, 10 for [0, 10] and 5 ticks.
+    ///
+    /// A range whose bounds coincide yields a single tick; a `target_count` of 0 or
+    /// non-finite bounds yield none.
+    pub fn ticks(&self, target_count: usize) -> Vec<Measurement<U>> {
+        if target_count > 0 && self.min == self.max && self.min.is_finite() {
+            return Vec::from([self.min()]);
+        }
+        tick_spacing(self.min, self.max, target_count)
+            .map(|(first, last, m, exp)| {
+                (first..=last)
+                    .map(|i| Measurement::new(tick_value(i, m, exp), self.prefix))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Returns labels for [`RangedMeasurement::ticks`], all in a single prefix chosen so the
+    /// largest tick reads between 1 and 1000 (e.g. "0mV", "100mV", "200mV" rather than
+    /// mixing mV and V).
+    ///
+    /// # Arguments
+    /// * `target_count` - The approximate number of ticks.
+    /// * `style` - How each label is formatted.
+    pub fn tick_labels(&self, target_count: usize, style: &LabelStyle) -> Vec<String> {
+        let Some((first, last, m, exp)) = tick_spacing(self.min, self.max, target_count) else {
+            return self
+                .ticks(target_count)
+                .iter()
+                .map(|t| t.label_with(style))
+                .collect();
+        };
+        let largest = tick_value(first, m, exp)
+            .abs()
+            .max(tick_value(last, m, exp).abs());
+        let prefix = if largest > 0.0 {
+            let exp = floor_log10(largest) + i32::from(self.prefix.get_exp_value());
+            let exp = exp.div_euclid(3) * 3;
+            Prefix::from_exp_value(exp.clamp(i16::MIN.into(), i16::MAX.into()) as i16).0
+        } else {
+            self.prefix
+        };
+        let shift = i32::from(self.prefix.get_exp_value() - prefix.get_exp_value());
+        (first..=last)
+            .map(|i| Measurement::<U>::new(tick_value(i, m, exp + shift), prefix).label_with(style))
+            .collect()
     }
 
     /// Converts the ranged measurement to a different SI prefix, scaling the value accordingly.
@@ -129,17 +289,172 @@ impl<U: Uom> RangedMeasurement<U> {
             uom: PhantomData,
         }
     }
+
+    /// Deserializes a range and rejects it unless it lies within the `sanity` envelope, so
+    /// that a corrupted file cannot load a range such as ±10 TV.
+    ///
+    /// Both bounds must lie within the bounds of `sanity`, and a step must not exceed its
+    /// span. Use it with `#[serde(deserialize_with = "...")]` through a function naming the
+    /// envelope.
+    ///
+    /// # Returns
+    /// The range, or an error naming the first offending field (`min`, `max` or `step`), its
+    /// value and the envelope, e.g. "`max` 10TV is outside the sanity range [-10V, 10V]".
+    ///
+    /// # Example
+    /// ```
+    /// use serde::{Deserialize, Deserializer};
+    /// use typed_measurements::prelude::*;
+    ///
+    /// fn command<'de, D: Deserializer<'de>>(d: D) -> Result<RangedMeasurement<Volt>, D::Error> {
+    ///     let sanity = RangedMeasurement::new_sym_stepless(10.0, Prefix::None);
+    ///     RangedMeasurement::deserialize_validated(d, &sanity)
+    /// }
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Protocol {
+    ///     #[serde(deserialize_with = "command")]
+    ///     command: RangedMeasurement<Volt>,
+    /// }
+    ///
+    /// let ok = r#"{"command":{"min":-500.0,"max":500.0,"prefix":"m"}}"#;
+    /// assert!(serde_json::from_str::<Protocol>(ok).is_ok());
+    /// let corrupted = r#"{"command":{"min":-10.0,"max":10.0,"prefix":"T"}}"#;
+    /// assert!(serde_json::from_str::<Protocol>(corrupted).is_err());
+    /// ```
+    pub fn deserialize_validated<'de, D: Deserializer<'de>>(
+        d: D,
+        sanity: &RangedMeasurement<U>,
+    ) -> Result<Self, D::Error> {
+        let range = Self::deserialize(d)?;
+        let envelope = format!("[{}, {}]", sanity.min().label(), sanity.max().label());
+        for (field, bound) in [("min", range.min()), ("max", range.max())] {
+            if !(bound >= sanity.min() && bound <= sanity.max()) {
+                return Err(D::Error::custom(format_args!(
+                    "`{field}` {} is outside the sanity range {envelope}",
+                    bound.label()
+                )));
+            }
+        }
+        if let Some(step) = range.step()
+            && step
+                .partial_cmp(&(sanity.max() - sanity.min()))
+                .is_none_or(|o| o.is_gt())
+        {
+            return Err(D::Error::custom(format_args!(
+                "`step` {} is larger than the sanity range {envelope}",
+                step.label()
+            )));
+        }
+        Ok(range)
+    }
+}
+
+/// Picks a 1/2/5 × 10^k tick spacing for about `target_count` ticks over [`min`, `max`].
+///
+/// # Returns
+/// The first and last tick indices, the mantissa and the exponent of the spacing, or `None`
+/// for an empty, degenerate or non-finite range.
+fn tick_spacing(min: f64, max: f64, target_count: usize) -> Option<(i64, i64, f64, i32)> {
+    let (lo, hi) = if min <= max { (min, max) } else { (max, min) };
+    let span = hi - lo;
+    if target_count == 0 || !span.is_finite() || span <= 0.0 {
+        return None;
+    }
+    let raw = span / (target_count.max(2) - 1) as f64;
+    let exp = floor_log10(raw);
+    let m = match raw / powi10(exp) {
+        r if r < 1.5 => 1.0,
+        r if r < 3.0 => 2.0,
+        r if r < 7.0 => 5.0,
+        _ => 10.0,
+    };
+    let step = m * powi10(exp);
+    // tolerate rounding so bounds that sit on a tick are included
+    let first = ceil(lo / step - 1e-9) as i64;
+    let last = floor(hi / step + 1e-9) as i64;
+    Some((first, last, m, exp))
+}
+
+/// Returns the `i`th multiple of `m × 10^exp`, dividing for negative exponents so that
+/// e.g. 3 × 0.1 is exactly 0.3.
+fn tick_value(i: i64, m: f64, exp: i32) -> f64 {
+    let v = if exp < 0 {
+        i as f64 * m / powi10(-exp)
+    } else {
+        i as f64 * m * powi10(exp)
+    };
+    // avoid labelling the zero tick as "-0"
+    v + 0.0
+}
+
+impl RangedMeasurement<Second> {
+    /// Creates the time range `[0, total]` with step `dt`, in the finer of their prefixes.
+    ///
+    /// # Arguments
+    /// * `total` - The duration covered by the range.
+    /// * `dt` - The sampling interval.
+    pub fn from_duration(total: Measurement<Second>, dt: Measurement<Second>) -> Self {
+        let prefix = if total.prefix().get_exp_value() < dt.prefix().get_exp_value() {
+            total.prefix()
+        } else {
+            dt.prefix()
+        };
+        Self::new(
+            0.0,
+            total.convert_to(prefix).value(),
+            dt.convert_to(prefix).value(),
+            prefix,
+        )
+    }
+}
+
+impl<U: Uom> fmt::Display for RangedMeasurement<U> {
+    /// Formats the range like [`RangedMeasurement::label`], e.g. `[-10,10,1]uV`.
+    ///
+    /// A precision applies to the bounds and the step (`{:.1}` gives `[-10.0,10.0,1.0]uV`)
+    /// and a width pads the whole label, as for [`Measurement`].
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Some(p) = f.precision() else {
+            return pad(f, &self.label());
+        };
+        let (pfx, uom) = (self.prefix.get_label(), U::uom_str());
+        let label = match self.step {
+            Some(step) => format!("[{:.p$},{:.p$},{step:.p$}]{pfx}{uom}", self.min, self.max),
+            None => format!("[{:.p$},{:.p$}]{pfx}{uom}", self.min, self.max),
+        };
+        pad(f, &label)
+    }
+}
+
+impl<U: Uom> Serialize for RangedMeasurement<U> {
+    /// Writes the fields in order. Human-readable formats skip a missing step; binary
+    /// formats, which cannot tell a skipped field, keep it.
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        let skip_step = self.step.is_none() && s.is_human_readable();
+        let mut state = s.serialize_struct("RangedMeasurement", 4 - usize::from(skip_step))?;
+        state.serialize_field("min", &self.min)?;
+        state.serialize_field("max", &self.max)?;
+        if skip_step {
+            state.skip_field("step")?;
+        } else {
+            state.serialize_field("step", &self.step)?;
+        }
+        state.serialize_field("prefix", &symbol::Symbol(self.prefix))?;
+        state.end()
+    }
 }
 
 impl<U: Uom> PartialEq for RangedMeasurement<U> {
+    /// Compares the bounds and steps as [`Measurement`]s are, by their values in the base
+    /// unit; a stepless range only equals another stepless range.
     fn eq(&self, other: &Self) -> bool {
-        let t = if self.prefix == other.prefix {
-            (self.min, self.max, self.step)
-        } else {
-            let new_rm = self.convert_to(other.prefix);
-            (new_rm.min, new_rm.max, new_rm.step)
+        let same = |a, b| same_quantity(a, self.prefix, b, other.prefix);
+        let step = match (self.step, other.step) {
+            (Some(a), Some(b)) => same(a, b),
+            (a, b) => a.is_none() && b.is_none(),
         };
-        t == (other.min, other.max, other.step)
+        same(self.min, other.min) && same(self.max, other.max) && step
     }
 }
 
@@ -149,12 +464,254 @@ impl<U: Uom> From<Measurement<U>> for RangedMeasurement<U> {
     }
 }
 
+/// Builds a [`RangedMeasurement`], checking its invariants in [`RangedMeasurementBuilder::build`].
+///
+/// Bounds may be given in different prefixes; the range uses the finest of them.
+///
+/// # Example
+/// ```
+/// use typed_measurements::prelude::*;
+///
+/// let r = RangedMeasurement::<Volt>::builder()
+///     .symmetric(Measurement::new(10, Prefix::None))
+///     .step(Measurement::new(100, Prefix::Milli))
+///     .build()
+///     .unwrap();
+/// assert_eq!(r.label(), "[-10000,10000,100]mV");
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct RangedMeasurementBuilder<U: Uom> {
+    min: Option<Measurement<U>>,
+    max: Option<Measurement<U>>,
+    symmetric: Option<Measurement<U>>,
+    step: Option<Measurement<U>>,
+    guard: Option<Percentage>,
+}
+
+impl<U: Uom> RangedMeasurementBuilder<U> {
+    /// Creates a builder with no bounds, step or guard band.
+    pub fn new() -> Self {
+        Self {
+            min: None,
+            max: None,
+            symmetric: None,
+            step: None,
+            guard: None,
+        }
+    }
+
+    /// Sets the lower bound.
+    pub fn min(mut self, min: Measurement<U>) -> Self {
+        self.min = Some(min);
+        self
+    }
+
+    /// Sets the upper bound.
+    pub fn max(mut self, max: Measurement<U>) -> Self {
+        self.max = Some(max);
+        self
+    }
+
+    /// Sets both bounds to `-value` and `value`. Cannot be combined with `min` or `max`.
+    pub fn symmetric(mut self, value: Measurement<U>) -> Self {
+        self.symmetric = Some(value);
+        self
+    }
+
+    /// Sets the step size; without one the range is stepless.
+    pub fn step(mut self, step: Measurement<U>) -> Self {
+        self.step = Some(step);
+        self
+    }
+
+    /// Shrinks the range about its center by `p` of its span, keeping a guard band of
+    /// `p / 2` of the span inside each bound.
+    pub fn guard(mut self, p: Percentage) -> Self {
+        self.guard = Some(p);
+        self
+    }
+
+    /// Builds the range.
+    ///
+    /// # Returns
+    /// The range, or:
+    /// - [`MeasurementError::InvalidOptions`] if the bounds are missing or `symmetric` is
+    ///   combined with `min` or `max`;
+    /// - [`MeasurementError::RangeInvariant`] if a value is not finite, the minimum is not
+    ///   below the maximum (after the guard band), or the step is not positive or exceeds
+    ///   the span.
+    pub fn build(self) -> Result<RangedMeasurement<U>, MeasurementError> {
+        let (min, max) = match (self.symmetric, self.min, self.max) {
+            (Some(_), Some(_), _) | (Some(_), _, Some(_)) => {
+                return Err(MeasurementError::InvalidOptions(
+                    "`symmetric` cannot be combined with `min` or `max`",
+                ));
+            }
+            (Some(v), None, None) => (v * -1.0, v),
+            (None, Some(min), Some(max)) => (min, max),
+            (None, None, _) => return Err(MeasurementError::InvalidOptions("missing `min`")),
+            (None, _, None) => return Err(MeasurementError::InvalidOptions("missing `max`")),
+        };
+        let prefix = [Some(min), Some(max), self.step]
+            .into_iter()
+            .flatten()
+            .map(|m| m.prefix())
+            .min_by_key(|p| p.get_exp_value())
+            .unwrap_or(Prefix::None);
+        let (mut min, mut max) = (
+            min.convert_to(prefix).value(),
+            max.convert_to(prefix).value(),
+        );
+        let step = self.step.map(|s| s.convert_to(prefix).value());
+        let invariant = |min, max, reason| MeasurementError::RangeInvariant {
+            min,
+            max,
+            step,
+            reason,
+        };
+        if !(min.is_finite() && max.is_finite() && step.is_none_or(f64::is_finite)) {
+            return Err(invariant(min, max, "values must be finite"));
+        }
+        if let Some(p) = self.guard {
+            let band = (max - min) * p.get_value() / 2.0;
+            (min, max) = (min + band, max - band);
+        }
+        if min >= max {
+            return Err(invariant(min, max, "min must be less than max"));
+        }
+        match step {
+            Some(s) if s <= 0.0 => Err(invariant(min, max, "step must be positive")),
+            Some(s) if s > max - min => Err(invariant(min, max, "step exceeds the span")),
+            _ => Ok(RangedMeasurement {
+                min,
+                max,
+                step,
+                prefix,
+                uom: PhantomData,
+            }),
+        }
+    }
+}
+
+impl<U: Uom> Default for RangedMeasurementBuilder<U> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 #[cfg(test)]
-mod ranged_measurement {
+mod ranged_measurement_tests {
     use crate::uom::Volt;
 
     use super::*;
 
+    const SANITY: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/sanity");
+
+    /// A stimulus protocol whose command range must lie within ±10 V.
+    #[derive(Debug, Deserialize)]
+    struct Protocol {
+        #[serde(deserialize_with = "command_range")]
+        command: RangedMeasurement<Volt>,
+    }
+
+    fn command_range<'de, D: Deserializer<'de>>(d: D) -> Result<RangedMeasurement<Volt>, D::Error> {
+        RangedMeasurement::deserialize_validated(
+            d,
+            &RangedMeasurement::new_sym_stepless(10.0, Prefix::None),
+        )
+    }
+
+    fn protocol(name: &str) -> Result<Protocol, serde_json::Error> {
+        serde_json::from_str(&std::fs::read_to_string(format!("{SANITY}/{name}")).unwrap())
+    }
+
+    #[test]
+    fn rejects_ranges_outside_the_sanity_envelope() {
+        let valid = protocol("valid_protocol.json").unwrap();
+        assert_eq!(
+            valid.command,
+            RangedMeasurement::new(-500.0, 500.0, 10.0, Prefix::Milli)
+        );
+        let e = protocol("corrupted_protocol.json").unwrap_err().to_string();
+        assert!(
+            e.starts_with("`min` -10TV is outside the sanity range [-10V, 10V]"),
+            "{e}"
+        );
+        let json =
+            |range: &str| serde_json::from_str::<Protocol>(&format!(r#"{{"command":{range}}}"#));
+        // bounds on the envelope are accepted, in any prefix
+        assert!(json(r#"{"min":-10000.0,"max":10.0,"prefix":"m"}"#).is_ok());
+        let e = json(r#"{"min":0.0,"max":10.5,"prefix":""}"#)
+            .unwrap_err()
+            .to_string();
+        assert!(e.starts_with("`max` 10.5V is outside"), "{e}");
+        let e = json(r#"{"min":0.0,"max":1.0,"step":25.0,"prefix":""}"#)
+            .unwrap_err()
+            .to_string();
+        assert!(
+            e.starts_with("`step` 25V is larger than the sanity range"),
+            "{e}"
+        );
+        assert!(json(r#"{"min":null,"max":1.0,"prefix":""}"#).is_err());
+    }
+
+    #[test]
+    fn parses_labels() {
+        let r = RangedMeasurement::<Volt>::new(-10.0, 10.0, 0.5, Prefix::Milli);
+        assert_eq!(RangedMeasurement::from_label(&r.label()), Ok(r));
+        let stepless = RangedMeasurement::<Volt>::from_label(" [0, 5] V").unwrap();
+        assert_eq!(stepless.step(), None);
+        assert_eq!(stepless.max(), Measurement::new(5.0, Prefix::None));
+        assert!(matches!(
+            RangedMeasurement::<Volt>::from_label("[5,0]V"),
+            Err(MeasurementError::RangeInvariant { .. })
+        ));
+        assert!(matches!(
+            RangedMeasurement::<Volt>::from_label("[1]V"),
+            Err(MeasurementError::InvalidOptions(_))
+        ));
+        assert!(RangedMeasurement::<Volt>::from_label("[0,1]A").is_err());
+        assert!(RangedMeasurement::<Volt>::from_label("0,1V").is_err());
+    }
+
+    #[test]
+    fn parses_localized_labels() {
+        let options = ParseOptions {
+            decimal_comma: true,
+            allow_thousands_sep: false,
+        };
+        let r = RangedMeasurement::<Volt>::from_label_with("[-1,5;1,5;0,25]mV", &options);
+        assert_eq!(
+            r,
+            Ok(RangedMeasurement::new(-1.5, 1.5, 0.25, Prefix::Milli))
+        );
+        let options = ParseOptions {
+            decimal_comma: true,
+            allow_thousands_sep: true,
+        };
+        let r = RangedMeasurement::<Volt>::from_label_with("[-1.500;1.500,5]uV", &options);
+        assert!(matches!(
+            r,
+            Err(MeasurementError::ParseError(
+                ParseMeasurementError::Ambiguous(_)
+            ))
+        ));
+        let r = RangedMeasurement::<Volt>::from_label_with("[-1.500,0;1.500,5]uV", &options);
+        assert_eq!(r.unwrap().min(), Measurement::new(-1500.0, Prefix::Micro));
+    }
+
+    #[test]
+    fn from_duration() {
+        let total = Measurement::<Second>::new(2, Prefix::None);
+        let dt = Measurement::<Second>::new(50, Prefix::Micro);
+        let r = RangedMeasurement::from_duration(total, dt);
+        assert_eq!(r.prefix, Prefix::Micro);
+        assert_eq!(r.min(), Measurement::new(0, Prefix::None));
+        assert_eq!(r.max(), total);
+        assert_eq!(r.step(), Some(dt));
+        assert_eq!(r.max().num_samples(r.step().unwrap()), 40_000);
+    }
+
     #[test]
     fn get_min() {
         let r = RangedMeasurement::<Volt>::new(-10, 10, 1, Prefix::Micro);
@@ -202,6 +759,16 @@ mod ranged_measurement {
         assert_eq!(r.label(), "[-10,10,1]uV");
     }
 
+    #[test]
+    fn display() {
+        let r = RangedMeasurement::<Volt>::new(-10, 10, 1, Prefix::Micro);
+        assert_eq!(r.to_string(), "[-10,10,1]uV");
+        assert_eq!(format!("{r:.1}"), "[-10.0,10.0,1.0]uV");
+        assert_eq!(format!("{r:>14}"), "  [-10,10,1]uV");
+        let r = RangedMeasurement::<Volt>::new_sym_stepless(0.5, Prefix::None);
+        assert_eq!(format!("{r:.2}"), "[-0.50,0.50]V");
+    }
+
     #[test]
     fn stepless_label() {
         let r = RangedMeasurement::<Volt>::new_sym_stepless(10, Prefix::Micro);
@@ -220,4 +787,192 @@ mod ranged_measurement {
         let r = RangedMeasurement::<Volt>::new_sym_stepless(100, Prefix::Micro);
         assert_eq!(r, Measurement::new(100, Prefix::Micro).into());
     }
+
+    #[test]
+    fn builder_normalizes_to_finest_prefix() {
+        let r = RangedMeasurement::<Volt>::builder()
+            .min(Measurement::new(-1, Prefix::None))
+            .max(Measurement::new(500, Prefix::Milli))
+            .step(Measurement::new(250, Prefix::Micro))
+            .build()
+            .unwrap();
+        assert_eq!(r.label(), "[-1000000,500000,250]uV");
+        assert_eq!(
+            r,
+            RangedMeasurement::new(-1000.0, 500.0, 0.25, Prefix::Milli)
+        );
+        let stepless = RangedMeasurement::<Volt>::builder()
+            .symmetric(Measurement::new(2, Prefix::Kilo))
+            .build()
+            .unwrap();
+        assert_eq!(
+            stepless,
+            RangedMeasurement::new_sym_stepless(2, Prefix::Kilo)
+        );
+    }
+
+    #[test]
+    fn builder_guard_band() {
+        let r = RangedMeasurement::<Volt>::builder()
+            .min(Measurement::new(0, Prefix::None))
+            .max(Measurement::new(10, Prefix::None))
+            .guard(percentage!(0.1))
+            .build()
+            .unwrap();
+        assert_eq!((r.min().value(), r.max().value()), (0.5, 9.5));
+        let e = RangedMeasurement::<Volt>::builder()
+            .symmetric(Measurement::new(1, Prefix::None))
+            .guard(percentage!(1.0))
+            .build()
+            .unwrap_err();
+        assert_eq!(
+            e.to_string(),
+            "invalid range [0, 0]: min must be less than max"
+        );
+    }
+
+    #[test]
+    fn builder_conflicts_and_invariants() {
+        let v = |x: f64| Measurement::<Volt>::new(x, Prefix::None);
+        let e = RangedMeasurement::builder()
+            .symmetric(v(10.0))
+            .max(v(5.0))
+            .build()
+            .unwrap_err();
+        assert_eq!(
+            e.to_string(),
+            "invalid options: `symmetric` cannot be combined with `min` or `max`"
+        );
+        let e = RangedMeasurement::builder()
+            .min(v(1.0))
+            .build()
+            .unwrap_err();
+        assert_eq!(e, MeasurementError::InvalidOptions("missing `max`"));
+        let e = RangedMeasurement::builder()
+            .min(v(5.0))
+            .max(v(-5.0))
+            .build()
+            .unwrap_err();
+        assert_eq!(
+            e.to_string(),
+            "invalid range [5, -5]: min must be less than max"
+        );
+        let e = RangedMeasurement::builder()
+            .symmetric(v(1.0))
+            .step(v(-0.1))
+            .build()
+            .unwrap_err();
+        assert_eq!(
+            e.to_string(),
+            "invalid range [-1, 1] with step -0.1: step must be positive"
+        );
+        let e = RangedMeasurement::builder()
+            .symmetric(v(1.0))
+            .step(v(3.0))
+            .build()
+            .unwrap_err();
+        assert!(e.to_string().ends_with("step exceeds the span"));
+        let e = RangedMeasurement::builder()
+            .min(v(f64::NAN))
+            .max(v(1.0))
+            .build()
+            .unwrap_err();
+        assert!(e.to_string().ends_with("values must be finite"));
+    }
+
+    fn values(ticks: &[Measurement<Volt>]) -> Vec<f64> {
+        ticks.iter().map(Measurement::value).collect()
+    }
+
+    #[test]
+    fn ticks_pick_round_spacing() {
+        let r = RangedMeasurement::<Volt>::new(0.0, 10.0, 1.0, Prefix::None);
+        assert_eq!(values(&r.ticks(5)), [0.0, 2.0, 4.0, 6.0, 8.0, 10.0]);
+        let r = RangedMeasurement::<Volt>::new(-1.3, 2.7, 0.1, Prefix::Milli);
+        assert_eq!(values(&r.ticks(5)), [-1.0, 0.0, 1.0, 2.0]);
+        assert!(r.ticks(5).iter().all(|t| t.prefix() == Prefix::Milli));
+        let r = RangedMeasurement::<Volt>::new_sym_stepless(10, Prefix::Micro);
+        assert_eq!(values(&r.ticks(3)), [-10.0, 0.0, 10.0]);
+        let r = RangedMeasurement::<Volt>::new(0.0, 0.3, 0.1, Prefix::None);
+        assert_eq!(values(&r.ticks(4)), [0.0, 0.1, 0.2, 0.3]);
+        let r = RangedMeasurement::<Volt>::new(10.0, 0.0, 1.0, Prefix::None);
+        assert_eq!(values(&r.ticks(3)), [0.0, 5.0, 10.0]);
+    }
+
+    #[test]
+    fn ticks_degenerate_ranges() {
+        let r = RangedMeasurement::<Volt>::new(5.0, 5.0, 1.0, Prefix::Milli);
+        assert_eq!(values(&r.ticks(5)), [5.0]);
+        assert_eq!(r.tick_labels(5, &LabelStyle::default()), ["5mV"]);
+        assert!(r.ticks(0).is_empty());
+        let r = RangedMeasurement::<Volt>::new(0.0, f64::NAN, 1.0, Prefix::None);
+        assert!(r.ticks(5).is_empty());
+        let r = RangedMeasurement::<Volt>::new(0.0, f64::INFINITY, 1.0, Prefix::None);
+        assert!(r.ticks(5).is_empty());
+    }
+
+    #[test]
+    fn tick_labels_share_one_prefix() {
+        let r = RangedMeasurement::<Volt>::new(0.0, 0.2, 0.01, Prefix::None);
+        assert_eq!(
+            r.tick_labels(5, &LabelStyle::default()),
+            ["0mV", "50mV", "100mV", "150mV", "200mV"]
+        );
+        let r = RangedMeasurement::<Volt>::new(-1500.0, 1500.0, 1.0, Prefix::Micro);
+        let style = LabelStyle {
+            precision: Some(1),
+            space: true,
+        };
+        assert_eq!(r.tick_labels(4, &style), ["-1.0 mV", "0.0 mV", "1.0 mV"]);
+        let r = RangedMeasurement::<Volt>::new(0.0, 2500.0, 1.0, Prefix::Milli);
+        assert_eq!(
+            r.tick_labels(6, &LabelStyle::default()),
+            ["0V", "0.5V", "1V", "1.5V", "2V", "2.5V"]
+        );
+    }
+
+    #[test]
+    fn snap_to_grid() {
+        use RoundMode::*;
+        let r = RangedMeasurement::<Volt>::new(-10.0, 10.0, 0.5, Prefix::Milli);
+        let snap = |v: f64, mode| r.snap(Measurement::new(v, Prefix::Milli), mode).value();
+        assert_eq!(snap(1.3, Nearest), 1.5);
+        assert_eq!(snap(1.3, Floor), 1.0);
+        assert_eq!(snap(-1.25, Floor), -1.5);
+        assert_eq!(snap(-1.25, Ceil), -1.0);
+        assert_eq!(snap(-1.25, Nearest), -1.5);
+        assert_eq!(snap(-1.25, TowardZero), -1.0);
+        assert_eq!(snap(-1.3, TowardZero), -1.0);
+        assert_eq!(snap(-1.3, Nearest), -1.5);
+        assert_eq!(snap(-1.2, Nearest), -1.0);
+        assert_eq!(snap(1.25, Nearest), 1.5);
+        assert_eq!(snap(12.0, Nearest), 10.0);
+        assert_eq!(snap(-10.2, Floor), -10.0);
+        let v = r.snap(Measurement::new(0.011, Prefix::None), Nearest);
+        assert_eq!((v.value(), v.prefix()), (10.0, Prefix::Milli));
+    }
+
+    #[test]
+    fn snap_uneven_and_stepless() {
+        let r = RangedMeasurement::<Volt>::new(0.0, 1.0, 0.3, Prefix::None);
+        let v = r.snap(Measurement::new(0.95, Prefix::None), RoundMode::Ceil);
+        assert!((v.value() - 0.9).abs() < 1e-12);
+        let r = RangedMeasurement::<Volt>::new_sym_stepless(5, Prefix::None);
+        let v = r.snap(Measurement::new(7, Prefix::None), RoundMode::Nearest);
+        assert_eq!(v.value(), 5.0);
+        let v = r.snap(Measurement::new(1.23, Prefix::None), RoundMode::Floor);
+        assert_eq!(v.value(), 1.23);
+    }
+
+    #[test]
+    fn nice_range() {
+        let r = RangedMeasurement::<Volt>::new(-2500.0, 2500.0, 500.0, Prefix::Milli).nice();
+        assert_eq!(r.label(), "[-2.5,2.5,0.5]V");
+        let policy = NicePolicy::new().with_prefixes(&[Prefix::Milli]);
+        let r = RangedMeasurement::<Volt>::new_sym(2.0, 0.5, Prefix::Kilo).nice_with(&policy);
+        assert_eq!(r.prefix, Prefix::Milli);
+        assert_eq!(r.max().value(), 2e6);
+        let r = RangedMeasurement::<Volt>::new(0.0, 9.99e-16, 3.33e-16, Prefix::Tera).nice();
+        assert_eq!(r.label(), "[0,999,333]uV");
+    }
 }
@@ -0,0 +1,293 @@
+//! Parsing labels whose unit is not known in advance, e.g. `"12.5mV"` or `"3nA"` in a file.
+//!
+//! A [`UnitRegistry`] maps unit labels to parsers and returns a [`DynMeasurement`], which
+//! can then be dispatched to the matching typed measurement.
+
+use crate::{
+    dyn_measurement::DynMeasurement,
+    measurement::{Interpretation, Measurement, ParseMeasurementError, split_value},
+    prefix::Prefix,
+    uom::{
+        Ampere, Candela, Celsius, Coulomb, Dimensionless, Farad, Gram, Henry, Hertz, Joule, Kelvin,
+        Liter, Meter, Mole, Newton, Ohm, Pascal, Second, Siemens, Uom, Volt, Watt,
+    },
+};
+use alloc::{collections::BTreeMap, string::ToString, vec::Vec};
+use core::cmp::Reverse;
+
+/// Builds a measurement of one specific unit from a parsed value and prefix.
+type MakeFn = fn(f64, Prefix) -> DynMeasurement;
+
+/// Builds a [`Measurement<U>`] and erases its unit, which keeps its canonical label.
+fn make<U: Uom>(value: f64, prefix: Prefix) -> DynMeasurement {
+    DynMeasurement::from_typed(Measurement::<U>::new(value, prefix))
+}
+
+/// A set of known units used to parse labels into [`DynMeasurement`]s.
+///
+/// [`UnitRegistry::new`] knows every unit defined in this crate, with their
+/// [`Uom::aliases`]; call [`UnitRegistry::register`] to add your own and
+/// [`UnitRegistry::alias`] to accept other labels for a unit. A measurement parsed from an
+/// alias carries the canonical label of its unit (`"50 ksps"` is 50 kHz).
+///
+/// # Ambiguous labels
+/// Labels are split in two stages: the registered unit labels that end the string are
+/// matched first, longest first, and the remainder of each must then be a valid prefix. So
+/// `"5mm"` is 5 millimeters, `"5mS"` 5 millisiemens and `"5ms"` 5 milliseconds, whatever
+/// the registration order. A label that still splits in more than one way, such as `"5m"`
+/// (5 meters, or 5 milli with the dimensionless unit), is rejected with
+/// [`ParseMeasurementError::AmbiguousUnit`] listing every reading instead of picking one.
+///
+/// # Example
+/// ```
+/// use typed_measurements::prelude::*;
+///
+/// let registry = UnitRegistry::new();
+/// let m = registry.parse("3nA").unwrap();
+/// assert_eq!(m.unit(), "A");
+/// assert_eq!(m.prefix(), Prefix::Nano);
+/// ```
+#[derive(Clone, Debug)]
+pub struct UnitRegistry {
+    parsers: BTreeMap<&'static str, MakeFn>,
+}
+
+impl UnitRegistry {
+    /// Creates a registry with every unit defined in this crate.
+    pub fn new() -> Self {
+        let mut registry = Self::empty();
+        registry
+            .register::<Dimensionless>()
+            .register::<Volt>()
+            .register::<Ampere>()
+            .register::<Watt>()
+            .register::<Second>()
+            .register::<Hertz>()
+            .register::<Ohm>()
+            .register::<Siemens>()
+            .register::<Coulomb>()
+            .register::<Farad>()
+            .register::<Henry>()
+            .register::<Meter>()
+            .register::<Gram>()
+            .register::<Kelvin>()
+            .register::<Mole>()
+            .register::<Candela>()
+            .register::<Joule>()
+            .register::<Newton>()
+            .register::<Pascal>()
+            .register::<Liter>()
+            .register::<Celsius>();
+        registry
+    }
+
+    /// Creates a registry with no units.
+    pub fn empty() -> Self {
+        Self {
+            parsers: BTreeMap::new(),
+        }
+    }
+
+    /// Adds the unit `U` under its label and its [`Uom::aliases`], replacing any unit already
+    /// registered with the same labels.
+    ///
+    /// # Returns
+    /// The registry, so that calls can be chained.
+    pub fn register<U: Uom>(&mut self) -> &mut Self {
+        self.parsers.insert(U::uom_str(), make::<U>);
+        for &alias in U::aliases() {
+            self.alias::<U>(alias);
+        }
+        self
+    }
+
+    /// Accepts `alias` as another label of the unit `U` (e.g. "cps" for a count rate),
+    /// replacing any unit already registered with that label. `U` itself need not be
+    /// registered.
+    ///
+    /// # Returns
+    /// The registry, so that calls can be chained.
+    pub fn alias<U: Uom>(&mut self, alias: &'static str) -> &mut Self {
+        self.parsers.insert(alias, make::<U>);
+        self
+    }
+
+    /// Returns `true` if a unit with the given label or alias is registered.
+    pub fn contains(&self, label: &str) -> bool {
+        self.parsers.contains_key(label)
+    }
+
+    /// Returns the registered unit labels and aliases, in lexicographic order.
+    pub fn labels(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.parsers.keys().copied()
+    }
+
+    /// Parses a label such as `"12.5mV"` or `"3 nA"` into a measurement of a registered unit.
+    ///
+    /// # Arguments
+    /// * `s` - The label: a number, an optional SI prefix, and a unit label.
+    ///
+    /// # Returns
+    /// The measurement of the only registered unit that splits the label (see the type-level
+    /// docs); [`ParseMeasurementError::AmbiguousUnit`] if several do, or another
+    /// [`ParseMeasurementError`] if the number is missing or no registered unit matches.
+    pub fn parse(&self, s: &str) -> Result<DynMeasurement, ParseMeasurementError> {
+        let (value, label) = split_value(s)?;
+        let readings = self.readings(label);
+        match readings.len() {
+            0 => Err(ParseMeasurementError::UnknownUnit(label.to_string())),
+            1 => Ok((readings[0].1)(value, readings[0].0.prefix)),
+            _ => Err(ParseMeasurementError::AmbiguousUnit(
+                readings.into_iter().map(|(r, _)| r).collect(),
+            )),
+        }
+    }
+
+    /// Returns every split of `label` into a prefix and a registered unit, longest unit
+    /// first, with the parser of that unit.
+    fn readings(&self, label: &str) -> Vec<(Interpretation, MakeFn)> {
+        let mut readings = self
+            .parsers
+            .iter()
+            .filter_map(|(&unit, &parse)| {
+                let prefix = Prefix::from_label(label.strip_suffix(unit)?)?;
+                Some((Interpretation { prefix, unit }, parse))
+            })
+            .collect::<Vec<_>>();
+        readings.sort_by_key(|(r, _)| Reverse(r.unit.len()));
+        readings
+    }
+}
+
+impl Default for UnitRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod registry_tests {
+    use super::*;
+    use crate::{define_unit, prefix::Prefix};
+
+    #[test]
+    fn mixed_labels() {
+        let registry = UnitRegistry::new();
+        let cases = [
+            ("12.5mV", "V", 12.5e-3),
+            ("3nA", "A", 3e-9),
+            ("-2 kΩ", "Ω", -2e3),
+            ("20kHz", "Hz", 20e3),
+            ("4.7uF", "F", 4.7e-6),
+            ("1.5k", "", 1.5e3),
+            ("25°C", "°C", 25.0),
+            ("101.3kPa", "Pa", 101.3e3),
+            ("50 ksps", "Hz", 50e3),
+        ];
+        for (s, unit, base) in cases {
+            let m = registry.parse(s).unwrap();
+            assert_eq!(m.unit(), unit, "{s}");
+            let value = m.convert_to(Prefix::None).value();
+            assert!((value - base).abs() <= 1e-12 * base.abs(), "{s}: {value}");
+        }
+    }
+
+    #[test]
+    fn splits_unit_before_prefix() {
+        let registry = UnitRegistry::new();
+        let cases = [
+            ("2ms", "s", Prefix::Milli),
+            ("2mS", "S", Prefix::Milli),
+            ("5mm", "m", Prefix::Milli),
+            ("101Pa", "Pa", Prefix::None),
+            ("5mmol", "mol", Prefix::Milli),
+            ("5mol", "mol", Prefix::None),
+        ];
+        for (s, unit, prefix) in cases {
+            let m = registry.parse(s).unwrap();
+            assert_eq!((m.unit(), m.prefix()), (unit, prefix), "{s}");
+            // parsing the label back gives the same measurement
+            let again = registry.parse(&m.label()).unwrap();
+            assert_eq!(
+                (again.unit(), again.prefix(), again.value()),
+                (m.unit(), m.prefix(), m.value()),
+                "{s}"
+            );
+        }
+    }
+
+    #[test]
+    fn lists_ambiguous_readings() {
+        let registry = UnitRegistry::new();
+        let readings = vec![
+            Interpretation {
+                prefix: Prefix::None,
+                unit: "m",
+            },
+            Interpretation {
+                prefix: Prefix::Milli,
+                unit: "",
+            },
+        ];
+        let e = registry.parse("5m").unwrap_err();
+        assert_eq!(e, ParseMeasurementError::AmbiguousUnit(readings));
+        assert_eq!(
+            e.to_string(),
+            "`m` is ambiguous: it may be read as `m` without a prefix, or milli with no unit"
+        );
+        // without the dimensionless unit, only meters remain
+        let mut registry = UnitRegistry::empty();
+        registry.register::<Meter>();
+        let m = registry.parse("5m").unwrap();
+        assert_eq!((m.unit(), m.prefix()), ("m", Prefix::None));
+    }
+
+    #[test]
+    fn dispatch_to_typed() {
+        let registry = UnitRegistry::new();
+        let m = registry.parse("3nA").unwrap();
+        assert!(m.try_into_typed::<Volt>().is_err());
+        assert_eq!(
+            m.try_into_typed::<Ampere>().unwrap(),
+            Measurement::<Ampere>::new(3, Prefix::Nano)
+        );
+    }
+
+    #[test]
+    fn user_units() {
+        define_unit!(PhotonCount, "ph");
+        let mut registry = UnitRegistry::new();
+        assert_eq!(
+            registry.parse("10kph"),
+            Err(ParseMeasurementError::UnknownUnit("kph".to_string()))
+        );
+        registry.register::<PhotonCount>();
+        assert!(registry.contains("ph"));
+        let m = registry.parse("10kph").unwrap();
+        assert_eq!((m.unit(), m.prefix()), ("ph", Prefix::Kilo));
+        // an alias parses to the canonical label of its unit
+        define_unit!(CountRate, "cps");
+        registry.alias::<CountRate>("counts/s");
+        assert!(registry.contains("counts/s") && !registry.contains("cps"));
+        let m = registry.parse("3 kcounts/s").unwrap();
+        assert_eq!(
+            (m.unit(), m.prefix(), m.value()),
+            ("cps", Prefix::Kilo, 3.0)
+        );
+        assert_eq!(m.label(), "3kcps");
+    }
+
+    #[test]
+    fn errors() {
+        let registry = UnitRegistry::empty();
+        assert_eq!(registry.labels().count(), 0);
+        assert_eq!(
+            registry.parse("1V"),
+            Err(ParseMeasurementError::UnknownUnit("V".to_string()))
+        );
+        assert_eq!(
+            UnitRegistry::new().parse("mV"),
+            Err(ParseMeasurementError::Value("mV".to_string()))
+        );
+    }
+}
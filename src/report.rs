@@ -0,0 +1,526 @@
+//! Per-channel summary statistics of a run, with a stable JSON schema for user interfaces.
+//!
+//! A [`RunReport`] collects a [`DynChannelReport`] per channel, whatever its unit, plus custom
+//! run-level [`Metric`]s. Every value is written with explicit `"unit"` and `"prefix"` fields,
+//! the prefix as its symbol:
+//!
+//! ```json
+//! {
+//!   "version": 1,
+//!   "channels": [
+//!     {
+//!       "name": "Vm",
+//!       "unit": "V",
+//!       "samples": 5,
+//!       "nan_samples": 1,
+//!       "mean": { "value": -65.0, "prefix": "m", "unit": "V" },
+//!       "std": { "value": 1.0, "prefix": "m", "unit": "V" },
+//!       "min": { "value": -66.0, "prefix": "m", "unit": "V" },
+//!       "max": { "value": -64.0, "prefix": "m", "unit": "V" },
+//!       "metrics": []
+//!     }
+//!   ],
+//!   "metrics": [{ "name": "duration", "value": 2.0, "prefix": "", "unit": "s" }]
+//! }
+//! ```
+
+use crate::{
+    dyn_measurement::{DynMeasurement, UnitMismatch},
+    m1d::M1d,
+    measurement::Measurement,
+    prefix::{Prefix, symbol},
+    stats::RunningStats,
+    uom::Uom,
+};
+use serde::{Deserialize, Deserializer, Serialize, Serializer, de::Error};
+
+/// The schema version written in the `"version"` field of a [`RunReport`].
+pub const REPORT_VERSION: u32 = 1;
+
+/// A named custom value attached to a channel or to a run, e.g. a rise time.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Metric {
+    name: String,
+    #[serde(deserialize_with = "nan_if_null")]
+    value: f64,
+    #[serde(with = "symbol")]
+    prefix: Prefix,
+    unit: String,
+}
+
+impl Metric {
+    /// Creates a metric named `name` holding `m`, of any unit.
+    pub fn new(name: impl Into<String>, m: impl Into<DynMeasurement>) -> Self {
+        let m = m.into();
+        Self {
+            name: name.into(),
+            value: m.value(),
+            prefix: m.prefix(),
+            unit: m.unit().to_string(),
+        }
+    }
+
+    /// Returns the name of the metric.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the value of the metric with its unit.
+    pub fn measurement(&self) -> DynMeasurement {
+        DynMeasurement::new(self.value, self.prefix, self.unit.clone())
+    }
+}
+
+/// Reads a value written as `null`, which is how JSON holds a non-finite value, as NaN.
+fn nan_if_null<'de, D: Deserializer<'de>>(d: D) -> Result<f64, D::Error> {
+    Ok(Option::<f64>::deserialize(d)?.unwrap_or(f64::NAN))
+}
+
+/// Serde helpers writing an optional [`DynMeasurement`] as a `value`, `prefix` and `unit`
+/// object, the prefix as its symbol, or `null`.
+mod reading {
+    use super::*;
+
+    #[derive(Serialize, Deserialize)]
+    struct Reading {
+        #[serde(deserialize_with = "nan_if_null")]
+        value: f64,
+        #[serde(with = "symbol")]
+        prefix: Prefix,
+        unit: String,
+    }
+
+    pub(super) fn serialize<S: Serializer>(
+        m: &Option<DynMeasurement>,
+        s: S,
+    ) -> Result<S::Ok, S::Error> {
+        let reading = m.as_ref().map(|m| Reading {
+            value: m.value(),
+            prefix: m.prefix(),
+            unit: m.unit().to_string(),
+        });
+        reading.serialize(s)
+    }
+
+    pub(super) fn deserialize<'de, D: Deserializer<'de>>(
+        d: D,
+    ) -> Result<Option<DynMeasurement>, D::Error> {
+        let reading = Option::<Reading>::deserialize(d)?;
+        Ok(reading.map(|r| DynMeasurement::new(r.value, r.prefix, r.unit)))
+    }
+}
+
+/// Summary statistics of one channel of unit `U`, each in the prefix [`Measurement::nice`]
+/// chooses for it.
+///
+/// NaN samples are left out of the statistics and counted separately. The standard deviation
+/// is the population one. Serializes as its [`DynChannelReport`].
+///
+/// ```
+/// use typed_measurements::{prelude::*, report::ChannelReport};
+///
+/// let vm = M1d::<Volt>::new(vec![-0.066, -0.065, -0.065, -0.064], Prefix::None);
+/// let report = ChannelReport::from_m1d("Vm", &vm)
+///     .with_metric("rest", Measurement::<Volt>::new(-65.0, Prefix::Milli));
+/// assert_eq!(report.mean().unwrap().label(), "-65mV");
+/// assert_eq!(report.metrics()[0].name(), "rest");
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct ChannelReport<U: Uom> {
+    name: String,
+    samples: usize,
+    nan_samples: usize,
+    mean: Option<Measurement<U>>,
+    std: Option<Measurement<U>>,
+    min: Option<Measurement<U>>,
+    max: Option<Measurement<U>>,
+    metrics: Vec<Metric>,
+}
+
+impl<U: Uom> ChannelReport<U> {
+    /// Summarizes the channel `name` recorded in `trace`.
+    ///
+    /// # Returns
+    /// The report; its statistics are `None` if `trace` holds no sample other than NaN.
+    pub fn from_m1d(name: impl Into<String>, trace: &M1d<U>) -> Self {
+        let prefix = trace.prefix();
+        let mut stats = RunningStats::new(prefix);
+        stats.extend(
+            trace
+                .view()
+                .iter()
+                .filter(|v| !v.is_nan())
+                .map(|&v| Measurement::new(v, prefix)),
+        );
+        let nice = |m: Option<Measurement<U>>| m.map(Measurement::nice);
+        Self {
+            name: name.into(),
+            samples: trace.len(),
+            nan_samples: trace.len() - stats.count() as usize,
+            mean: nice(stats.mean()),
+            std: nice(stats.std(0.0)),
+            min: nice(stats.min()),
+            max: nice(stats.max()),
+            metrics: Vec::new(),
+        }
+    }
+
+    /// Attaches the custom metric `name` holding `m`, of any unit.
+    pub fn with_metric(mut self, name: impl Into<String>, m: impl Into<DynMeasurement>) -> Self {
+        self.metrics.push(Metric::new(name, m));
+        self
+    }
+
+    /// Returns the name of the channel.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the number of samples, NaN included.
+    pub fn samples(&self) -> usize {
+        self.samples
+    }
+
+    /// Returns the number of NaN samples, which the statistics leave out.
+    pub fn nan_samples(&self) -> usize {
+        self.nan_samples
+    }
+
+    /// Returns the mean, or `None` if there are no samples besides NaN.
+    pub fn mean(&self) -> Option<Measurement<U>> {
+        self.mean
+    }
+
+    /// Returns the population standard deviation, or `None` if there are no samples besides
+    /// NaN.
+    pub fn std(&self) -> Option<Measurement<U>> {
+        self.std
+    }
+
+    /// Returns the smallest sample, or `None` if there are no samples besides NaN.
+    pub fn min(&self) -> Option<Measurement<U>> {
+        self.min
+    }
+
+    /// Returns the largest sample, or `None` if there are no samples besides NaN.
+    pub fn max(&self) -> Option<Measurement<U>> {
+        self.max
+    }
+
+    /// Returns the custom metrics, in the order they were attached.
+    pub fn metrics(&self) -> &[Metric] {
+        &self.metrics
+    }
+}
+
+impl<U: Uom> Serialize for ChannelReport<U> {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        DynChannelReport::from_typed(self.clone()).serialize(s)
+    }
+}
+
+impl<'de, U: Uom> Deserialize<'de> for ChannelReport<U> {
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        DynChannelReport::deserialize(d)?
+            .try_into_typed()
+            .map_err(D::Error::custom)
+    }
+}
+
+/// A [`ChannelReport`] whose unit is only known at runtime, so that channels of different
+/// units can be collected in one [`RunReport`].
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct DynChannelReport {
+    name: String,
+    unit: String,
+    samples: usize,
+    nan_samples: usize,
+    #[serde(with = "reading")]
+    mean: Option<DynMeasurement>,
+    #[serde(with = "reading")]
+    std: Option<DynMeasurement>,
+    #[serde(with = "reading")]
+    min: Option<DynMeasurement>,
+    #[serde(with = "reading")]
+    max: Option<DynMeasurement>,
+    #[serde(default)]
+    metrics: Vec<Metric>,
+}
+
+impl DynChannelReport {
+    /// Creates a [`DynChannelReport`] from a typed [`ChannelReport`], recording its unit label.
+    pub fn from_typed<U: Uom>(report: ChannelReport<U>) -> Self {
+        let dynamic = |m: Option<Measurement<U>>| m.map(DynMeasurement::from_typed);
+        Self {
+            name: report.name,
+            unit: U::uom_str().to_string(),
+            samples: report.samples,
+            nan_samples: report.nan_samples,
+            mean: dynamic(report.mean),
+            std: dynamic(report.std),
+            min: dynamic(report.min),
+            max: dynamic(report.max),
+            metrics: report.metrics,
+        }
+    }
+
+    /// Converts into a typed [`ChannelReport<U>`].
+    ///
+    /// # Returns
+    /// The typed report, or a [`UnitMismatch`] if the unit label of the channel or of one of
+    /// its statistics is not `U`'s. Custom metrics keep their own units.
+    pub fn try_into_typed<U: Uom>(self) -> Result<ChannelReport<U>, UnitMismatch> {
+        DynMeasurement::new(0.0, Prefix::None, self.unit.clone()).try_into_typed::<U>()?;
+        let typed = |m: Option<DynMeasurement>| m.map(|m| m.try_into_typed()).transpose();
+        Ok(ChannelReport {
+            name: self.name,
+            samples: self.samples,
+            nan_samples: self.nan_samples,
+            mean: typed(self.mean)?,
+            std: typed(self.std)?,
+            min: typed(self.min)?,
+            max: typed(self.max)?,
+            metrics: self.metrics,
+        })
+    }
+
+    /// Returns the name of the channel.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the unit label of the channel (e.g. "V").
+    pub fn unit(&self) -> &str {
+        &self.unit
+    }
+
+    /// Returns the number of samples, NaN included.
+    pub fn samples(&self) -> usize {
+        self.samples
+    }
+
+    /// Returns the number of NaN samples, which the statistics leave out.
+    pub fn nan_samples(&self) -> usize {
+        self.nan_samples
+    }
+
+    /// Returns the mean, or `None` if there are no samples besides NaN.
+    pub fn mean(&self) -> Option<&DynMeasurement> {
+        self.mean.as_ref()
+    }
+
+    /// Returns the population standard deviation, or `None` if there are no samples besides
+    /// NaN.
+    pub fn std(&self) -> Option<&DynMeasurement> {
+        self.std.as_ref()
+    }
+
+    /// Returns the smallest sample, or `None` if there are no samples besides NaN.
+    pub fn min(&self) -> Option<&DynMeasurement> {
+        self.min.as_ref()
+    }
+
+    /// Returns the largest sample, or `None` if there are no samples besides NaN.
+    pub fn max(&self) -> Option<&DynMeasurement> {
+        self.max.as_ref()
+    }
+
+    /// Returns the custom metrics, in the order they were attached.
+    pub fn metrics(&self) -> &[Metric] {
+        &self.metrics
+    }
+}
+
+impl<U: Uom> From<ChannelReport<U>> for DynChannelReport {
+    fn from(report: ChannelReport<U>) -> Self {
+        Self::from_typed(report)
+    }
+}
+
+/// The summary of a run: one report per channel, of any units, and custom run-level metrics.
+///
+/// Built by chaining [`RunReport::with_channel`] and [`RunReport::with_metric`]; serializes
+/// to the schema shown in the [module documentation](self), versioned by
+/// [`REPORT_VERSION`].
+///
+/// ```
+/// use typed_measurements::{prelude::*, report::{ChannelReport, RunReport}};
+///
+/// let vm = M1d::<Volt>::new(vec![-66.0, -65.0, -64.0], Prefix::Milli);
+/// let im = M1d::<Ampere>::new(vec![10.0, 20.0], Prefix::Pico);
+/// let report = RunReport::new()
+///     .with_channel(ChannelReport::from_m1d("Vm", &vm))
+///     .with_channel(ChannelReport::from_m1d("Im", &im))
+///     .with_metric("duration", Measurement::<Second>::new(2.0, Prefix::None));
+/// assert_eq!(report.channels()[1].unit(), "A");
+/// assert_eq!(report.metrics()[0].name(), "duration");
+/// ```
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RunReport {
+    version: u32,
+    channels: Vec<DynChannelReport>,
+    #[serde(default)]
+    metrics: Vec<Metric>,
+}
+
+impl RunReport {
+    /// Creates an empty report.
+    pub fn new() -> Self {
+        Self {
+            version: REPORT_VERSION,
+            channels: Vec::new(),
+            metrics: Vec::new(),
+        }
+    }
+
+    /// Adds the report of one channel, typed or not.
+    pub fn with_channel(mut self, channel: impl Into<DynChannelReport>) -> Self {
+        self.channels.push(channel.into());
+        self
+    }
+
+    /// Attaches the custom run-level metric `name` holding `m`, of any unit.
+    pub fn with_metric(mut self, name: impl Into<String>, m: impl Into<DynMeasurement>) -> Self {
+        self.metrics.push(Metric::new(name, m));
+        self
+    }
+
+    /// Returns the schema version the report was written with.
+    pub fn version(&self) -> u32 {
+        self.version
+    }
+
+    /// Returns the channel reports, in the order they were added.
+    pub fn channels(&self) -> &[DynChannelReport] {
+        &self.channels
+    }
+
+    /// Returns the custom run-level metrics, in the order they were attached.
+    pub fn metrics(&self) -> &[Metric] {
+        &self.metrics
+    }
+
+    /// Returns the report as a JSON value, following the schema in the
+    /// [module documentation](self).
+    ///
+    /// Non-finite values, which JSON cannot hold, are written as `null` and read back as NaN.
+    #[cfg(feature = "json")]
+    pub fn to_json_value(&self) -> serde_json::Value {
+        serde_json::to_value(self).expect("a report serializes to JSON")
+    }
+}
+
+impl Default for RunReport {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod report_tests {
+    use super::*;
+    use crate::uom::{Ampere, Second, Volt};
+
+    const GOLDEN: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/report");
+
+    fn golden(name: &str) -> String {
+        std::fs::read_to_string(format!("{GOLDEN}/{name}")).unwrap()
+    }
+
+    fn run() -> RunReport {
+        let vm = M1d::<Volt>::new(vec![-66.0, -64.0, f64::NAN, -66.0, -64.0], Prefix::Milli);
+        let im = M1d::<Ampere>::new(vec![1500.0, 2500.0], Prefix::Pico);
+        let vm = ChannelReport::from_m1d("Vm", &vm).with_metric(
+            "rise time",
+            Measurement::<Second>::new(0.0012, Prefix::None),
+        );
+        RunReport::new()
+            .with_channel(vm)
+            .with_channel(ChannelReport::from_m1d("Im", &im))
+            .with_channel(ChannelReport::from_m1d(
+                "empty",
+                &M1d::<Volt>::new(vec![], Prefix::Milli),
+            ))
+            .with_metric("duration", Measurement::<Second>::new(2.0, Prefix::None))
+    }
+
+    #[test]
+    fn summarizes_channels_in_nice_prefixes() {
+        let vm = M1d::<Volt>::new(vec![-0.066, -0.065, f64::NAN, -0.065, -0.064], Prefix::None);
+        let report = ChannelReport::from_m1d("Vm", &vm);
+        assert_eq!((report.samples(), report.nan_samples()), (5, 1));
+        let mv = |v| Some(Measurement::<Volt>::new(v, Prefix::Milli));
+        assert_eq!(report.mean().map(|m| m.prefix()), Some(Prefix::Milli));
+        assert_eq!(report.mean(), mv(-65.0));
+        assert_eq!(report.min(), mv(-66.0));
+        assert_eq!(report.max(), mv(-64.0));
+        let std = report.std().unwrap();
+        assert_eq!(std.prefix(), Prefix::Micro);
+        assert!(
+            (std.value() - 0.5f64.sqrt() * 1000.0).abs() < 1e-6,
+            "{}",
+            std.label()
+        );
+        let empty = ChannelReport::<Volt>::from_m1d("empty", &M1d::new(vec![], Prefix::Milli));
+        assert_eq!(
+            (empty.mean(), empty.std(), empty.samples()),
+            (None, None, 0)
+        );
+    }
+
+    #[test]
+    fn schema_matches_golden_file() {
+        let json = serde_json::to_string_pretty(&run()).unwrap();
+        assert_eq!(json, golden("run_report.json").trim_end());
+        let parsed: RunReport = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, run());
+        assert_eq!(parsed.version(), REPORT_VERSION);
+    }
+
+    #[test]
+    fn non_finite_values_read_back_as_nan() {
+        let trace = M1d::<Volt>::new(vec![1.0, f64::INFINITY], Prefix::Milli);
+        let report = RunReport::new()
+            .with_channel(ChannelReport::from_m1d("Vm", &trace))
+            .with_metric(
+                "peak",
+                Measurement::<Volt>::new(f64::INFINITY, Prefix::None),
+            );
+        let json = serde_json::to_string(&report).unwrap();
+        assert!(json.contains(r#""max":{"value":null,"prefix":"m","unit":"V"}"#));
+        let parsed: RunReport = serde_json::from_str(&json).unwrap();
+        let vm = parsed.channels()[0]
+            .clone()
+            .try_into_typed::<Volt>()
+            .unwrap();
+        assert!(vm.max().unwrap().value().is_nan());
+        assert_eq!(vm.min(), Some(Measurement::new(1.0, Prefix::Milli)));
+        assert!(parsed.metrics()[0].measurement().value().is_nan());
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn json_value_matches_golden_file() {
+        let golden: serde_json::Value = serde_json::from_str(&golden("run_report.json")).unwrap();
+        assert_eq!(run().to_json_value(), golden);
+        let vm = &golden["channels"][0];
+        assert_eq!(
+            (&vm["mean"]["prefix"], &vm["mean"]["unit"]),
+            (&"m".into(), &"V".into())
+        );
+    }
+
+    #[test]
+    fn typed_channels_check_the_unit() {
+        let report = run();
+        let vm = report.channels()[0].clone();
+        let typed = vm.clone().try_into_typed::<Volt>().unwrap();
+        assert_eq!(typed.metrics()[0].measurement().unit(), "s");
+        assert_eq!(DynChannelReport::from(typed), vm);
+        let e = vm.try_into_typed::<Ampere>().unwrap_err();
+        assert_eq!((e.expected.as_str(), e.found.as_str()), ("A", "V"));
+        let json = serde_json::to_string(&report.channels()[1]).unwrap();
+        assert!(serde_json::from_str::<ChannelReport<Ampere>>(&json).is_ok());
+        assert!(serde_json::from_str::<ChannelReport<Volt>>(&json).is_err());
+    }
+}
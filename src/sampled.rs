@@ -0,0 +1,278 @@
+//! Arrays carrying their sampling rate, so the time base travels with the data.
+//!
+//! [`SampledM1d`] and [`SampledM2d`] dereference to the wrapped [`M1d`] and [`M2d`], so the
+//! whole numeric API stays available. Operations that change the sample spacing, such as
+//! decimation, are defined on the wrappers and update the rate.
+
+use crate::{
+    m1d::M1d,
+    m2d::M2d,
+    measurement::Measurement,
+    prefix::Prefix,
+    uom::{Hertz, Second, Uom},
+};
+use ndarray::{Array1, Axis, s};
+use serde::{Deserialize, Serialize};
+use std::ops::Deref;
+
+/// Returns the sampling rate in Hz.
+fn hertz(rate: Measurement<Hertz>) -> f64 {
+    rate.convert_to(Prefix::None).value()
+}
+
+/// Returns the times `i / rate` of `len` samples, in seconds.
+fn time_axis(len: usize, rate: Measurement<Hertz>) -> M1d<Second> {
+    let fs = hertz(rate);
+    M1d::new(Array1::from_shape_fn(len, |i| i as f64 / fs), Prefix::None)
+}
+
+/// Returns the duration `len / rate` in seconds.
+fn duration(len: usize, rate: Measurement<Hertz>) -> Measurement<Second> {
+    Measurement::new(len as f64 / hertz(rate), Prefix::None)
+}
+
+/// Returns `rate / factor`, the rate after keeping one sample in `factor`.
+///
+/// # Panics
+/// Panics if `factor` is zero.
+fn reduced_rate(rate: Measurement<Hertz>, factor: usize) -> Measurement<Hertz> {
+    assert!(factor > 0, "decimation factor must be positive");
+    rate / factor as f64
+}
+
+/// An [`M1d`] trace together with its sampling rate.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SampledM1d<U: Uom> {
+    data: M1d<U>,
+    rate: Measurement<Hertz>,
+}
+
+impl<U: Uom> M1d<U> {
+    /// Attaches the sampling rate of the trace.
+    pub fn with_sampling_rate(self, rate: Measurement<Hertz>) -> SampledM1d<U> {
+        SampledM1d { data: self, rate }
+    }
+}
+
+impl<U: Uom> SampledM1d<U> {
+    /// Returns the sampling rate.
+    pub fn rate(&self) -> Measurement<Hertz> {
+        self.rate
+    }
+
+    /// Returns the time of every sample, `i / rate`, in seconds.
+    pub fn time_axis(&self) -> M1d<Second> {
+        time_axis(self.data.len(), self.rate)
+    }
+
+    /// Returns the duration covered by the trace, `len / rate`, in seconds.
+    pub fn duration(&self) -> Measurement<Second> {
+        duration(self.data.len(), self.rate)
+    }
+
+    /// Returns the trace without its sampling rate.
+    pub fn into_inner(self) -> M1d<U> {
+        self.data
+    }
+
+    /// Decimates by averaging consecutive blocks of `factor` samples, as
+    /// [`M2d::decimate_axis`], and divides the rate by `factor`.
+    ///
+    /// # Panics
+    /// Panics if `factor` is zero.
+    pub fn decimate(&self, factor: usize) -> SampledM1d<U> {
+        let rate = reduced_rate(self.rate, factor);
+        let row = self.data.view().insert_axis(Axis(0)).to_owned();
+        let decimated = M2d::<U>::new(row, self.data.prefix()).decimate_axis(Axis(1), factor);
+        let data = decimated.row(0).expect("a single row was decimated");
+        data.with_sampling_rate(rate)
+    }
+
+    /// Keeps every `factor`-th sample, starting with the first, and divides the rate by
+    /// `factor`. Unlike [`SampledM1d::decimate`] this does not average, so it aliases.
+    ///
+    /// # Panics
+    /// Panics if `factor` is zero.
+    pub fn downsample(&self, factor: usize) -> SampledM1d<U> {
+        let rate = reduced_rate(self.rate, factor);
+        let values = self.data.view().slice(s![..;factor]).to_owned();
+        M1d::new(values, self.data.prefix()).with_sampling_rate(rate)
+    }
+}
+
+impl<U: Uom> Deref for SampledM1d<U> {
+    type Target = M1d<U>;
+
+    fn deref(&self) -> &M1d<U> {
+        &self.data
+    }
+}
+
+/// An [`M2d`] with one channel per row, sampled along `Axis(1)` at a known rate.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SampledM2d<U: Uom> {
+    data: M2d<U>,
+    rate: Measurement<Hertz>,
+}
+
+impl<U: Uom> M2d<U> {
+    /// Attaches the sampling rate of the rows.
+    pub fn with_sampling_rate(self, rate: Measurement<Hertz>) -> SampledM2d<U> {
+        SampledM2d { data: self, rate }
+    }
+}
+
+impl<U: Uom> SampledM2d<U> {
+    /// Returns the sampling rate.
+    pub fn rate(&self) -> Measurement<Hertz> {
+        self.rate
+    }
+
+    /// Returns the time of every column, `j / rate`, in seconds.
+    pub fn time_axis(&self) -> M1d<Second> {
+        time_axis(self.data.ncols(), self.rate)
+    }
+
+    /// Returns the duration covered by each row, `ncols / rate`, in seconds.
+    pub fn duration(&self) -> Measurement<Second> {
+        duration(self.data.ncols(), self.rate)
+    }
+
+    /// Returns the array without its sampling rate.
+    pub fn into_inner(self) -> M2d<U> {
+        self.data
+    }
+
+    /// Returns channel `i` with the same sampling rate, or `None` if out of bounds.
+    pub fn channel(&self, i: usize) -> Option<SampledM1d<U>> {
+        Some(self.data.row(i)?.with_sampling_rate(self.rate))
+    }
+
+    /// Decimates every channel by averaging blocks of `factor` samples, as
+    /// [`M2d::decimate_axis`], and divides the rate by `factor`.
+    ///
+    /// # Panics
+    /// Panics if `factor` is zero.
+    pub fn decimate(&self, factor: usize) -> SampledM2d<U> {
+        let rate = reduced_rate(self.rate, factor);
+        self.data
+            .decimate_axis(Axis(1), factor)
+            .with_sampling_rate(rate)
+    }
+
+    /// Keeps the minimum and maximum of every block of `factor` samples, as
+    /// [`M2d::min_max_decimate_axis`], dividing the rate by `factor`.
+    ///
+    /// # Panics
+    /// Panics if `factor` is zero.
+    pub fn min_max_decimate(&self, factor: usize) -> (SampledM2d<U>, SampledM2d<U>) {
+        let rate = reduced_rate(self.rate, factor);
+        let (min, max) = self.data.min_max_decimate_axis(Axis(1), factor);
+        (min.with_sampling_rate(rate), max.with_sampling_rate(rate))
+    }
+
+    /// Keeps every `factor`-th column, starting with the first, and divides the rate by
+    /// `factor`.
+    ///
+    /// # Panics
+    /// Panics if `factor` is zero.
+    pub fn downsample(&self, factor: usize) -> SampledM2d<U> {
+        let rate = reduced_rate(self.rate, factor);
+        let columns = (0..self.data.ncols()).step_by(factor).collect::<Vec<_>>();
+        self.data
+            .select_columns(&columns)
+            .expect("column indices are in bounds")
+            .with_sampling_rate(rate)
+    }
+}
+
+impl<U: Uom> Deref for SampledM2d<U> {
+    type Target = M2d<U>;
+
+    fn deref(&self) -> &M2d<U> {
+        &self.data
+    }
+}
+
+#[cfg(test)]
+mod sampled_tests {
+    use super::*;
+    use crate::uom::Volt;
+    use ndarray::array;
+
+    fn rate(khz: f64) -> Measurement<Hertz> {
+        Measurement::new(khz, Prefix::Kilo)
+    }
+
+    #[test]
+    fn duration_of_one_second() {
+        let trace =
+            M1d::<Volt>::new(Array1::zeros(20_000), Prefix::Milli).with_sampling_rate(rate(20.0));
+        assert_eq!(trace.duration(), Measurement::new(1.0, Prefix::None));
+        assert_eq!(trace.duration().value(), 1.0);
+        let times = trace.time_axis();
+        assert_eq!(times.len(), 20_000);
+        assert_eq!(times.view()[1], 5e-5);
+        assert_eq!(times.view()[10_000], 0.5);
+
+        let m = M2d::<Volt>::zeros((3, 20_000), Prefix::None).with_sampling_rate(rate(20.0));
+        assert_eq!(m.duration().value(), 1.0);
+        assert_eq!(m.time_axis().len(), 20_000);
+    }
+
+    #[test]
+    fn delegates_to_the_array() {
+        let trace =
+            M1d::<Volt>::new(vec![1.0, 2.0, 3.0], Prefix::Milli).with_sampling_rate(rate(1.0));
+        assert_eq!(trace.len(), 3);
+        assert_eq!(trace.mean(), Some(Measurement::new(2.0, Prefix::Milli)));
+        assert_eq!(trace.rate(), rate(1.0));
+        assert_eq!(
+            trace.into_inner(),
+            M1d::new(vec![1.0, 2.0, 3.0], Prefix::Milli)
+        );
+    }
+
+    #[test]
+    fn decimation_updates_the_rate() {
+        let trace = M1d::<Volt>::new(vec![1.0, 3.0, 5.0, 7.0, 9.0], Prefix::Milli)
+            .with_sampling_rate(rate(10.0));
+        let decimated = trace.decimate(2);
+        assert_eq!(decimated.rate(), rate(5.0));
+        assert_eq!(decimated.values(), array![2.0, 6.0, 9.0]);
+        let downsampled = trace.downsample(2);
+        assert_eq!(downsampled.rate(), rate(5.0));
+        assert_eq!(downsampled.values(), array![1.0, 5.0, 9.0]);
+
+        let m = M2d::<Volt>::new(
+            array![[1.0, 3.0, 5.0, 7.0], [0.0, 0.0, 4.0, 4.0]],
+            Prefix::None,
+        )
+        .with_sampling_rate(rate(4.0));
+        let decimated = m.decimate(2);
+        assert_eq!(decimated.rate(), rate(2.0));
+        assert_eq!(decimated.values(), array![[2.0, 6.0], [0.0, 4.0]]);
+        assert_eq!(decimated.duration(), m.duration());
+        let (min, max) = m.min_max_decimate(4);
+        assert_eq!(min.rate(), rate(1.0));
+        assert_eq!(
+            (min.values(), max.values()),
+            (array![[1.0], [0.0]], array![[7.0], [4.0]])
+        );
+        assert_eq!(m.downsample(3).values(), array![[1.0, 7.0], [0.0, 4.0]]);
+        let channel = m.channel(1).unwrap();
+        assert_eq!(channel.rate(), rate(4.0));
+        assert!(m.channel(2).is_none());
+    }
+
+    #[test]
+    fn serde_round_trip() {
+        let trace = M1d::<Volt>::new(vec![1.0, 2.0], Prefix::Micro).with_sampling_rate(rate(20.0));
+        let json = serde_json::to_string(&trace).unwrap();
+        let back: SampledM1d<Volt> = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, trace);
+        let m = M2d::<Volt>::ones((2, 2), Prefix::None).with_sampling_rate(rate(1.0));
+        let json = serde_json::to_string(&m).unwrap();
+        assert_eq!(serde_json::from_str::<SampledM2d<Volt>>(&json).unwrap(), m);
+    }
+}
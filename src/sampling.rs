@@ -0,0 +1,204 @@
+//! Conversions between sample indices and times at a given sampling rate.
+//!
+//! Rates and times are combined in their own prefixes before scaling, so e.g. 500 µs at
+//! 20 kHz is computed as `500 * 20 / 1000` and lands exactly on sample 10. The conversions
+//! are exact whenever the rate is a whole number of hertz and the index or time is exactly
+//! representable.
+
+#[cfg(feature = "ndarray")]
+use crate::m1d::M1d;
+use crate::{
+    error::MeasurementError,
+    math::{canonical_base, round_ties_even},
+    measurement::Measurement,
+    prefix::Prefix,
+    uom::{Hertz, Second},
+};
+
+/// Returns the value and prefix exponent of `rate`.
+///
+/// # Panics
+/// Panics if `rate` is not positive and finite.
+fn checked_rate(rate: Measurement<Hertz>) -> (f64, i32) {
+    assert!(
+        rate.value() > 0.0 && rate.value().is_finite(),
+        "sampling rate must be positive and finite"
+    );
+    (rate.value(), rate.prefix().get_exp_value() as i32)
+}
+
+/// Returns the time of sample `idx`, `idx / rate`, in seconds.
+///
+/// # Panics
+/// Panics if `rate` is not positive and finite.
+pub fn sample_to_time(idx: usize, rate: Measurement<Hertz>) -> Measurement<Second> {
+    let (value, exp) = checked_rate(rate);
+    Measurement::new(idx as f64 / canonical_base(value, exp), Prefix::None)
+}
+
+/// Returns the index of the sample taken at time `t`, `t * rate` rounded to the nearest
+/// integer.
+///
+/// A time exactly halfway between two samples rounds to the even index, so that halfway
+/// times do not all drift the same way: at 1 kHz, 0.5 ms is sample 0 and 1.5 ms is sample 2.
+///
+/// # Returns
+/// The index; [`MeasurementError::OutOfRange`], with the time in seconds, if `t` is
+/// negative, not finite, or past the last index `usize` can hold.
+///
+/// # Panics
+/// Panics if `rate` is not positive and finite.
+pub fn time_to_sample(
+    t: Measurement<Second>,
+    rate: Measurement<Hertz>,
+) -> Result<usize, MeasurementError> {
+    let (value, exp) = checked_rate(rate);
+    let position = canonical_base(t.value() * value, t.prefix().get_exp_value() as i32 + exp);
+    let index = round_ties_even(position);
+    // checked before rounding, which would take times just before zero to sample 0;
+    // `usize::MAX as f64` rounds up past `usize::MAX`, so it is excluded
+    if t.value() >= 0.0 && index < usize::MAX as f64 {
+        Ok(index as usize)
+    } else {
+        Err(MeasurementError::OutOfRange {
+            value: t.convert_to(Prefix::None).value(),
+            min: 0.0,
+            max: usize::MAX as f64 / canonical_base(value, exp),
+        })
+    }
+}
+
+#[cfg(feature = "ndarray")]
+impl<U: crate::uom::Uom> M1d<U> {
+    /// Returns the index of the sample of this trace taken at time `t`, rounded as
+    /// [`time_to_sample`], when the trace is sampled at `rate` starting at time zero.
+    ///
+    /// # Returns
+    /// The index; [`MeasurementError::EmptyInput`] if the trace is empty, or
+    /// [`MeasurementError::OutOfRange`], with the time in seconds, if `t` does not round to a
+    /// sample of the trace.
+    ///
+    /// # Panics
+    /// Panics if `rate` is not positive and finite.
+    pub fn index_of_time(
+        &self,
+        t: Measurement<Second>,
+        rate: Measurement<Hertz>,
+    ) -> Result<usize, MeasurementError> {
+        if self.is_empty() {
+            return Err(MeasurementError::EmptyInput("no sample to look up"));
+        }
+        let last = self.len() - 1;
+        match time_to_sample(t, rate) {
+            Ok(i) if i <= last => Ok(i),
+            _ => Err(MeasurementError::OutOfRange {
+                value: t.convert_to(Prefix::None).value(),
+                min: 0.0,
+                max: sample_to_time(last, rate).value(),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod sampling_tests {
+    use super::*;
+
+    fn khz(v: f64) -> Measurement<Hertz> {
+        Measurement::new(v, Prefix::Kilo)
+    }
+
+    fn at(value: f64, prefix: Prefix) -> Measurement<Second> {
+        Measurement::new(value, prefix)
+    }
+
+    #[test]
+    fn round_trips_integer_rates() {
+        for rate in [khz(20.0), khz(44.1), Measurement::new(3.0, Prefix::None)] {
+            for idx in [0, 1, 7, 999, 20_000, 123_456_789] {
+                let t = sample_to_time(idx, rate);
+                assert_eq!(time_to_sample(t, rate), Ok(idx), "{idx} at {rate}");
+            }
+        }
+        assert_eq!(sample_to_time(10_000, khz(20.0)), at(0.5, Prefix::None));
+        assert_eq!(time_to_sample(at(500.0, Prefix::Micro), khz(20.0)), Ok(10));
+        assert_eq!(time_to_sample(at(0.3, Prefix::None), khz(0.01)), Ok(3));
+    }
+
+    #[test]
+    fn halfway_times_round_to_even() {
+        let rate = khz(1.0);
+        assert_eq!(time_to_sample(at(0.5, Prefix::Milli), rate), Ok(0));
+        assert_eq!(time_to_sample(at(1.5, Prefix::Milli), rate), Ok(2));
+        assert_eq!(time_to_sample(at(2.5, Prefix::Milli), rate), Ok(2));
+        assert_eq!(time_to_sample(at(2500.0, Prefix::Micro), rate), Ok(2));
+        assert_eq!(time_to_sample(at(2.5000001, Prefix::Milli), rate), Ok(3));
+        assert_eq!(time_to_sample(at(2.4999999, Prefix::Milli), rate), Ok(2));
+        // 25 µs is halfway between the first two samples at 20 kHz
+        assert_eq!(time_to_sample(at(25.0, Prefix::Micro), khz(20.0)), Ok(0));
+        assert_eq!(time_to_sample(at(75.0, Prefix::Micro), khz(20.0)), Ok(2));
+        assert!(matches!(
+            time_to_sample(at(-0.4, Prefix::Milli), rate),
+            Err(MeasurementError::OutOfRange { value, .. }) if value == -0.4e-3
+        ));
+        assert_eq!(time_to_sample(at(-0.0, Prefix::Milli), rate), Ok(0));
+    }
+
+    #[test]
+    fn very_high_rates() {
+        let rate = Measurement::<Hertz>::new(1.0, Prefix::Giga);
+        assert_eq!(sample_to_time(3, rate), at(3e-9, Prefix::None));
+        assert_eq!(time_to_sample(at(3.0, Prefix::Nano), rate), Ok(3));
+        assert_eq!(time_to_sample(at(3.5, Prefix::Nano), rate), Ok(4));
+        // an hour at 1 GHz
+        assert_eq!(
+            time_to_sample(at(3600.0, Prefix::None), rate),
+            Ok(3_600_000_000_000)
+        );
+        let t = sample_to_time(3_600_000_000_001, rate);
+        assert_eq!(time_to_sample(t, rate), Ok(3_600_000_000_001));
+        let rate = Measurement::<Hertz>::new(2.5, Prefix::Tera);
+        assert_eq!(time_to_sample(at(1.0, Prefix::Micro), rate), Ok(2_500_000));
+    }
+
+    #[test]
+    fn rejects_times_without_a_sample() {
+        assert!(matches!(
+            time_to_sample(at(-1.0, Prefix::Milli), khz(1.0)),
+            Err(MeasurementError::OutOfRange { value: -0.001, .. })
+        ));
+        assert!(time_to_sample(at(f64::NAN, Prefix::None), khz(1.0)).is_err());
+        assert!(time_to_sample(at(f64::INFINITY, Prefix::None), khz(1.0)).is_err());
+        assert!(time_to_sample(at(1e30, Prefix::None), khz(1.0)).is_err());
+    }
+
+    #[test]
+    #[should_panic(expected = "sampling rate must be positive and finite")]
+    fn rejects_a_zero_rate() {
+        sample_to_time(1, khz(0.0));
+    }
+
+    #[cfg(feature = "ndarray")]
+    #[test]
+    fn looks_up_trace_samples() {
+        use crate::uom::Volt;
+
+        let trace = M1d::<Volt>::new(vec![0.0, 1.0, 2.0, 3.0], Prefix::Milli);
+        let rate = khz(1.0);
+        assert_eq!(trace.index_of_time(at(2.0, Prefix::Milli), rate), Ok(2));
+        assert_eq!(trace.index_of_time(at(3.4, Prefix::Milli), rate), Ok(3));
+        assert_eq!(
+            trace.index_of_time(at(3.5, Prefix::Milli), rate),
+            Err(MeasurementError::OutOfRange {
+                value: 0.0035,
+                min: 0.0,
+                max: 0.003
+            })
+        );
+        assert!(trace.index_of_time(at(-1.0, Prefix::Milli), rate).is_err());
+        assert!(matches!(
+            M1d::<Volt>::new(vec![], Prefix::None).index_of_time(at(0.0, Prefix::None), rate),
+            Err(MeasurementError::EmptyInput(_))
+        ));
+    }
+}
@@ -0,0 +1,215 @@
+//! Constant-memory statistics over streams of measurements.
+
+#[cfg(feature = "ndarray")]
+use crate::m1d::M1d;
+use crate::{math::CompensatedSum, measurement::Measurement, prefix::Prefix, uom::Uom};
+use core::marker::PhantomData;
+
+/// Running count, sum, mean, variance, minimum and maximum of a stream of measurements.
+///
+/// Values are accumulated in a fixed prefix. The sum and mean use compensated summation and
+/// the variance uses Welford's update, so accuracy does not degrade with the number of
+/// samples. NaN values propagate into every statistic.
+///
+/// # Type Parameters
+/// - `U`: The unit of measurement, implementing the [`Uom`] trait.
+#[derive(Clone, Copy, Debug)]
+pub struct RunningStats<U: Uom> {
+    count: u64,
+    sum: CompensatedSum,
+    mean: f64,
+    m2: CompensatedSum,
+    min: f64,
+    max: f64,
+    prefix: Prefix,
+    uom: PhantomData<U>,
+}
+
+impl<U: Uom> RunningStats<U> {
+    /// Creates empty statistics accumulated in `prefix`.
+    pub fn new(prefix: Prefix) -> Self {
+        Self {
+            count: 0,
+            sum: CompensatedSum::default(),
+            mean: 0.0,
+            m2: CompensatedSum::default(),
+            min: f64::INFINITY,
+            max: f64::NEG_INFINITY,
+            prefix,
+            uom: PhantomData,
+        }
+    }
+
+    /// Adds one measurement, converting it to the accumulation prefix.
+    pub fn push(&mut self, m: Measurement<U>) {
+        let v = m.convert_to(self.prefix).value();
+        self.count += 1;
+        self.sum.add(v);
+        let delta = v - self.mean;
+        self.mean += delta / self.count as f64;
+        self.m2.add(delta * (v - self.mean));
+        if v.is_nan() || self.min.is_nan() {
+            self.min = f64::NAN;
+            self.max = f64::NAN;
+        } else {
+            self.min = self.min.min(v);
+            self.max = self.max.max(v);
+        }
+    }
+
+    /// Adds every sample of `a`.
+    #[cfg(feature = "ndarray")]
+    pub fn push_array(&mut self, a: &M1d<U>) {
+        let cf = a.prefix().get_conversion_factor(self.prefix);
+        a.view()
+            .iter()
+            .for_each(|v| self.push(Measurement::new(v * cf, self.prefix)));
+    }
+
+    /// Returns the number of measurements added so far.
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// Returns the prefix the statistics are accumulated in.
+    pub fn prefix(&self) -> Prefix {
+        self.prefix
+    }
+
+    /// Returns the compensated sum, or `None` if nothing has been added.
+    pub fn sum(&self) -> Option<Measurement<U>> {
+        (self.count > 0).then(|| Measurement::new(self.sum.total(), self.prefix))
+    }
+
+    /// Returns the mean, or `None` if nothing has been added.
+    pub fn mean(&self) -> Option<Measurement<U>> {
+        (self.count > 0)
+            .then(|| Measurement::new(self.sum.total() / self.count as f64, self.prefix))
+    }
+
+    /// Returns the variance in the accumulation prefix (squared), dividing by `count - ddof`.
+    ///
+    /// # Returns
+    /// The variance, or `None` if no more than `ddof` measurements have been added.
+    pub fn variance(&self, ddof: f64) -> Option<f64> {
+        let n = self.count as f64;
+        (n > ddof).then(|| self.m2.total() / (n - ddof))
+    }
+
+    /// Returns the standard deviation, dividing by `count - ddof`.
+    ///
+    /// # Returns
+    /// The standard deviation, or `None` if no more than `ddof` measurements have been added.
+    #[cfg(feature = "std")]
+    pub fn std(&self, ddof: f64) -> Option<Measurement<U>> {
+        Some(Measurement::new(self.variance(ddof)?.sqrt(), self.prefix))
+    }
+
+    /// Returns the smallest measurement, or `None` if nothing has been added.
+    pub fn min(&self) -> Option<Measurement<U>> {
+        (self.count > 0).then(|| Measurement::new(self.min, self.prefix))
+    }
+
+    /// Returns the largest measurement, or `None` if nothing has been added.
+    pub fn max(&self) -> Option<Measurement<U>> {
+        (self.count > 0).then(|| Measurement::new(self.max, self.prefix))
+    }
+}
+
+impl<U: Uom> Default for RunningStats<U> {
+    fn default() -> Self {
+        Self::new(Prefix::None)
+    }
+}
+
+impl<U: Uom> Extend<Measurement<U>> for RunningStats<U> {
+    fn extend<I: IntoIterator<Item = Measurement<U>>>(&mut self, iter: I) {
+        iter.into_iter().for_each(|m| self.push(m));
+    }
+}
+
+impl<U: Uom> FromIterator<Measurement<U>> for RunningStats<U> {
+    /// Collects measurements into statistics accumulated in the prefix of the first one.
+    fn from_iter<I: IntoIterator<Item = Measurement<U>>>(iter: I) -> Self {
+        let mut iter = iter.into_iter().peekable();
+        let prefix = iter.peek().map_or(Prefix::None, Measurement::prefix);
+        let mut stats = Self::new(prefix);
+        stats.extend(iter);
+        stats
+    }
+}
+
+#[cfg(test)]
+mod stats_tests {
+    use super::*;
+    use crate::uom::Volt;
+
+    #[test]
+    fn matches_two_pass_statistics() {
+        let values = [2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0];
+        let stats = values
+            .iter()
+            .map(|v| Measurement::<Volt>::new(*v, Prefix::Milli))
+            .collect::<RunningStats<Volt>>();
+        assert_eq!(stats.count(), 8);
+        assert_eq!(stats.prefix(), Prefix::Milli);
+        assert_eq!(stats.sum(), Some(Measurement::new(40.0, Prefix::Milli)));
+        assert_eq!(stats.mean(), Some(Measurement::new(5.0, Prefix::Milli)));
+        assert_eq!(stats.variance(0.0), Some(4.0));
+        #[cfg(feature = "std")]
+        assert_eq!(stats.std(0.0), Some(Measurement::new(2.0, Prefix::Milli)));
+        assert_eq!(stats.min(), Some(Measurement::new(2.0, Prefix::Milli)));
+        assert_eq!(stats.max(), Some(Measurement::new(9.0, Prefix::Milli)));
+    }
+
+    #[test]
+    fn converts_and_handles_empty() {
+        let mut stats = RunningStats::<Volt>::new(Prefix::Milli);
+        assert_eq!(stats.mean(), None);
+        assert_eq!(stats.variance(1.0), None);
+        stats.push(Measurement::new(1.0, Prefix::None));
+        stats.push(Measurement::new(500.0, Prefix::Micro));
+        assert_eq!(stats.sum(), Some(Measurement::new(1000.5, Prefix::Milli)));
+        assert_eq!(stats.variance(1.0), Some(499_500.125));
+        stats.push(Measurement::new(f64::NAN, Prefix::None));
+        assert!(stats.mean().unwrap().value().is_nan());
+        assert!(stats.max().unwrap().value().is_nan());
+    }
+
+    #[test]
+    fn pathological_sum_keeps_small_terms() {
+        // 1 followed by a million terms below half an ulp of 1: a naive running sum never moves
+        let n = 1_000_000;
+        let tiny = 1e-16;
+        let values = || std::iter::once(1.0).chain(std::iter::repeat_n(tiny, n));
+        assert_eq!(values().fold(0.0, |acc, v| acc + v), 1.0);
+        let reference = 1.0 + n as f64 * tiny;
+        let assert_close = |actual: f64, expected: f64| {
+            assert!(
+                (actual - expected).abs() <= 4.0 * f64::EPSILON * expected,
+                "{actual}"
+            );
+        };
+
+        let measurements = || values().map(|v| Measurement::<Volt>::new(v, Prefix::None));
+        assert_close(measurements().sum::<Measurement<Volt>>().value(), reference);
+        let stats = measurements().collect::<RunningStats<Volt>>();
+        assert_close(stats.sum().unwrap().value(), reference);
+        assert_close(stats.mean().unwrap().value(), reference / (n + 1) as f64);
+
+        #[cfg(feature = "ndarray")]
+        {
+            use crate::m2d::M2d;
+            let a = M1d::<Volt>::new(values().collect::<Vec<_>>(), Prefix::None);
+            assert_close(a.sum().unwrap().value(), reference);
+            assert_close(a.mean().unwrap().value(), reference / (n + 1) as f64);
+            let m =
+                M2d::<Volt>::from_shape_vec((1, n + 1), values().collect(), Prefix::None).unwrap();
+            assert_close(m.sum().unwrap().value(), reference);
+            assert_close(m.mean().unwrap().value(), reference / (n + 1) as f64);
+            let mut stats = RunningStats::<Volt>::default();
+            stats.push_array(&a);
+            assert_close(stats.sum().unwrap().value(), reference);
+        }
+    }
+}
@@ -0,0 +1,232 @@
+//! Assertions for tests comparing measurements, enabled by the `testing` feature.
+//!
+//! [`assert_measurement_eq!`](crate::assert_measurement_eq) and
+//! [`assert_m1d_eq!`](crate::assert_m1d_eq) compare values within an absolute tolerance and,
+//! on failure, print both sides in one readable prefix with their difference, so a
+//! discrepancy between `1.5mV` and `1502µV` does not have to be converted by hand.
+
+#[cfg(feature = "ndarray")]
+use crate::m1d::M1d;
+use crate::{
+    measurement::Measurement,
+    prefix::{NicePolicy, Prefix},
+    uom::Uom,
+};
+use std::fmt::Write;
+
+/// Returns the prefix that suits the largest finite magnitude of `values`, given in base
+/// units, or no prefix if none is finite and non-zero.
+fn common_prefix(values: impl IntoIterator<Item = f64>) -> Prefix {
+    let largest = values
+        .into_iter()
+        .map(f64::abs)
+        .filter(|v| v.is_finite())
+        .fold(0.0, f64::max);
+    NicePolicy::new().choose(largest, Prefix::None)
+}
+
+/// Returns `true` if `a` and `b`, in base units, differ by more than `tol`; NaN only
+/// matches NaN.
+fn differs(a: f64, b: f64, tol: f64) -> bool {
+    match (a.is_nan(), b.is_nan()) {
+        (true, true) => false,
+        // equal infinities differ by NaN, which is not above `tol`
+        (false, false) => (a - b).abs() > tol,
+        _ => true,
+    }
+}
+
+/// Writes the two values, their absolute and relative difference and the tolerance, all
+/// converted to `prefix`.
+///
+/// Each value is converted directly from its own prefix, so `1502µV` reads `1.502mV`, and
+/// the difference is rounded to 12 significant digits to hide the rounding of the
+/// subtraction.
+fn write_values<U: Uom>(
+    out: &mut String,
+    a: Measurement<U>,
+    b: Measurement<U>,
+    tol: Measurement<U>,
+    prefix: Prefix,
+) {
+    let (a, b) = (a.convert_to(prefix), b.convert_to(prefix));
+    let diff = (a.value() - b.value()).abs();
+    let relative = diff / a.value().abs().max(b.value().abs());
+    let diff = format!("{diff:.11e}").parse().unwrap_or(diff);
+    let _ = writeln!(out, "  left: {}", a.label());
+    let _ = writeln!(out, " right: {}", b.label());
+    let diff = Measurement::<U>::new(diff, prefix).label();
+    let _ = write!(out, "  diff: {diff} (relative {relative:.3e}, ");
+    let tol = Measurement::<U>::new(tol.convert_to(prefix).value().abs(), prefix);
+    let _ = write!(out, "tolerance {})", tol.label());
+}
+
+/// Compares two measurements within the absolute tolerance `tol`.
+///
+/// # Returns
+/// `None` if they match, otherwise a report of both values in a common readable prefix with
+/// their absolute and relative difference. NaN only matches NaN.
+pub fn measurement_diff<U: Uom>(
+    a: &Measurement<U>,
+    b: &Measurement<U>,
+    tol: Measurement<U>,
+) -> Option<String> {
+    let base = |m: &Measurement<U>| m.convert_to(Prefix::None).value();
+    let (x, y) = (base(a), base(b));
+    if !differs(x, y, base(&tol).abs()) {
+        return None;
+    }
+    let mut out = String::from("measurements differ\n");
+    write_values(&mut out, *a, *b, tol, common_prefix([x, y]));
+    Some(out)
+}
+
+/// Compares two arrays element-wise within the absolute tolerance `tol`.
+///
+/// # Returns
+/// `None` if they have the same length and every pair matches, otherwise a report of the
+/// number of mismatches and of the worst one: its index, both values in a common readable
+/// prefix and their difference. A NaN facing a number is the worst mismatch.
+#[cfg(feature = "ndarray")]
+pub fn m1d_diff<U: Uom>(a: &M1d<U>, b: &M1d<U>, tol: Measurement<U>) -> Option<String> {
+    if a.len() != b.len() {
+        return Some(format!(
+            "arrays differ in length\n  left: {}\n right: {}",
+            a.len(),
+            b.len()
+        ));
+    }
+    let base = |m: &M1d<U>| m.clone().convert_to(Prefix::None).values();
+    let (x, y) = (base(a), base(b));
+    let tol_base = tol.convert_to(Prefix::None).value().abs();
+    let mut mismatches = 0;
+    let mut worst: Option<(usize, f64)> = None;
+    for (i, (&x, &y)) in x.iter().zip(&y).enumerate() {
+        if differs(x, y, tol_base) {
+            mismatches += 1;
+            let gap = if x.is_nan() || y.is_nan() {
+                f64::INFINITY
+            } else {
+                (x - y).abs()
+            };
+            if worst.is_none_or(|(_, g)| gap > g) {
+                worst = Some((i, gap));
+            }
+        }
+    }
+    let (i, _) = worst?;
+    let prefix = common_prefix(x.iter().chain(&y).copied());
+    let mut out = format!(
+        "arrays differ at {mismatches} of {} indices, worst at index {i}\n",
+        a.len()
+    );
+    let at = |m: &M1d<U>| Measurement::new(m.view()[i], m.prefix());
+    write_values(&mut out, at(a), at(b), tol, prefix);
+    Some(out)
+}
+
+/// Asserts that two measurements are equal within an absolute tolerance.
+///
+/// On failure, panics with both values converted to a common readable prefix and their
+/// absolute and relative difference.
+///
+/// ```
+/// use typed_measurements::{assert_measurement_eq, prelude::*};
+///
+/// let expected = Measurement::<Volt>::new(1.5, Prefix::Milli);
+/// let actual = Measurement::<Volt>::new(1500.4, Prefix::Micro);
+/// assert_measurement_eq!(actual, expected, Measurement::new(1.0, Prefix::Micro));
+/// ```
+#[macro_export]
+macro_rules! assert_measurement_eq {
+    ($left:expr, $right:expr, $tol:expr $(,)?) => {
+        if let Some(report) = $crate::testing::measurement_diff(&$left, &$right, $tol) {
+            panic!("assertion `left == right` failed: {report}");
+        }
+    };
+}
+
+/// Asserts that two [`M1d`](crate::m1d::M1d) arrays are equal element-wise within an
+/// absolute tolerance.
+///
+/// On failure, panics with the number of mismatches and the worst one: its index, both
+/// values in a common readable prefix and their difference.
+#[cfg(feature = "ndarray")]
+#[macro_export]
+macro_rules! assert_m1d_eq {
+    ($left:expr, $right:expr, $tol:expr $(,)?) => {
+        if let Some(report) = $crate::testing::m1d_diff(&$left, &$right, $tol) {
+            panic!("assertion `left == right` failed: {report}");
+        }
+    };
+}
+
+#[cfg(test)]
+mod testing_tests {
+    use super::*;
+    use crate::uom::Volt;
+    use std::panic::catch_unwind;
+
+    fn mv(v: f64) -> Measurement<Volt> {
+        Measurement::new(v, Prefix::Milli)
+    }
+
+    fn uv(v: f64) -> Measurement<Volt> {
+        Measurement::new(v, Prefix::Micro)
+    }
+
+    /// Returns the panic message of `f`, or `None` if it does not panic.
+    fn panic_message(f: impl FnOnce() + std::panic::UnwindSafe) -> Option<String> {
+        let payload = catch_unwind(f).err()?;
+        payload.downcast_ref::<String>().cloned()
+    }
+
+    #[test]
+    fn measurements_within_tolerance_pass() {
+        assert_measurement_eq!(mv(1.5), uv(1500.4), uv(1.0));
+        assert_measurement_eq!(mv(f64::NAN), uv(f64::NAN), uv(0.0));
+        assert_measurement_eq!(mv(f64::INFINITY), uv(f64::INFINITY), uv(0.0));
+        assert!(measurement_diff(&mv(1.5), &uv(1500.0), uv(0.0)).is_none());
+    }
+
+    #[test]
+    fn measurement_failures_show_a_common_prefix() {
+        let message = panic_message(|| assert_measurement_eq!(mv(1.5), uv(1502.0), uv(1.0)));
+        assert_eq!(
+            message.unwrap(),
+            "assertion `left == right` failed: measurements differ\n  \
+             left: 1.5mV\n right: 1.502mV\n  \
+             diff: 0.002mV (relative 1.332e-3, tolerance 0.001mV)"
+        );
+        let nan = panic_message(|| assert_measurement_eq!(mv(f64::NAN), mv(1.0), mv(1.0)));
+        assert!(nan.unwrap().contains("left: NaNmV"));
+    }
+
+    #[cfg(feature = "ndarray")]
+    #[test]
+    fn arrays_report_the_worst_mismatch() {
+        let a = M1d::<Volt>::new(vec![1.0, 2.0, 3.0, 4.0], Prefix::Milli);
+        let b = M1d::<Volt>::new(vec![1000.0, 2100.0, 3000.0, 3500.0], Prefix::Micro);
+        assert_m1d_eq!(a, b.clone().convert_to(Prefix::Milli), uv(600.0));
+        let message = panic_message(|| assert_m1d_eq!(a, b, uv(50.0))).unwrap();
+        assert!(
+            message.contains("differ at 2 of 4 indices, worst at index 3"),
+            "{message}"
+        );
+        assert!(
+            message.contains(
+                "  left: 4mV\n right: 3.5mV\n  diff: 0.5mV (relative 1.250e-1, tolerance 0.05mV)"
+            ),
+            "{message}"
+        );
+        let short = M1d::<Volt>::new(vec![1.0], Prefix::Milli);
+        let message = panic_message(|| assert_m1d_eq!(a, short, uv(50.0))).unwrap();
+        assert!(message.contains("differ in length"), "{message}");
+        let with_nan = M1d::<Volt>::new(vec![1.0, f64::NAN, 3.0, 4.0], Prefix::Milli);
+        let message = m1d_diff(&a, &with_nan, uv(1000.0)).unwrap();
+        assert!(
+            message.contains("1 of 4 indices, worst at index 1"),
+            "{message}"
+        );
+    }
+}
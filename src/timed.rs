@@ -0,0 +1,220 @@
+//! Event-based series, whose samples carry their own timestamps.
+
+use crate::{
+    error::MeasurementError,
+    m1d::{M1d, check_increasing_keys},
+    math::{CompensatedSum, canonical_base},
+    measurement::Measurement,
+    sampling::sample_to_time,
+    uom::{Hertz, Product, Second, Uom},
+};
+use ndarray::Array1;
+use serde::{Deserialize, Serialize};
+
+/// Values paired with strictly increasing timestamps, for logs that record a value on every
+/// event rather than at a fixed rate.
+///
+/// Statistics treat the signal as piecewise linear between samples, so a value held for a
+/// long time weighs more than a burst of short-lived ones. Deserialization checks the same
+/// invariants as [`TimedSeries::new`].
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "UncheckedTimedSeries<U>")]
+pub struct TimedSeries<U: Uom> {
+    values: M1d<U>,
+    times: M1d<Second>,
+}
+
+/// The serialized form of a [`TimedSeries`], before validation.
+#[derive(Deserialize)]
+struct UncheckedTimedSeries<U: Uom> {
+    values: M1d<U>,
+    times: M1d<Second>,
+}
+
+impl<U: Uom> TryFrom<UncheckedTimedSeries<U>> for TimedSeries<U> {
+    type Error = MeasurementError;
+
+    fn try_from(raw: UncheckedTimedSeries<U>) -> Result<Self, Self::Error> {
+        Self::new(raw.values, raw.times)
+    }
+}
+
+impl<U: Uom> TimedSeries<U> {
+    /// Pairs `values` with the `times` they were recorded at.
+    ///
+    /// # Returns
+    /// The series; [`MeasurementError::ShapeMismatch`] if the lengths differ,
+    /// [`MeasurementError::EmptyInput`] if there are fewer than two samples, or
+    /// [`MeasurementError::NotIncreasing`] at the first timestamp that is not after the
+    /// previous one (including NaN).
+    pub fn new(values: M1d<U>, times: M1d<Second>) -> Result<Self, MeasurementError> {
+        check_increasing_keys(
+            times.view(),
+            values.len(),
+            "a timed series needs two samples to span time",
+        )?;
+        Ok(Self { values, times })
+    }
+
+    /// Returns the values.
+    pub fn values(&self) -> &M1d<U> {
+        &self.values
+    }
+
+    /// Returns the timestamps.
+    pub fn times(&self) -> &M1d<Second> {
+        &self.times
+    }
+
+    /// Returns the time from the first to the last sample, in the prefix of the timestamps.
+    pub fn duration(&self) -> Measurement<Second> {
+        let t = self.times.view();
+        Measurement::new(t[t.len() - 1] - t[0], self.times.prefix())
+    }
+
+    /// Returns the timestamps in seconds, dividing by exact powers of ten.
+    fn seconds(&self) -> Array1<f64> {
+        let exp = self.times.prefix().get_exp_value() as i32;
+        self.times.view().mapv(|t| canonical_base(t, exp))
+    }
+
+    /// Returns the trapezoidal integral of the values over the timestamps, in the prefix of
+    /// the values times seconds, e.g. "mV·s".
+    ///
+    /// The sum is compensated, so long series do not drift.
+    pub fn integral(&self) -> Measurement<Product<U, Second>> {
+        let (v, t) = (self.values.view(), self.seconds());
+        let mut sum = CompensatedSum::default();
+        for i in 1..v.len() {
+            sum.add((v[i - 1] + v[i]) / 2.0 * (t[i] - t[i - 1]));
+        }
+        Measurement::new(sum.total(), self.values.prefix())
+    }
+
+    /// Returns the mean of the values weighted by the time they span, the
+    /// [`TimedSeries::integral`] divided by the [`TimedSeries::duration`].
+    pub fn time_weighted_mean(&self) -> Measurement<U> {
+        let t = self.seconds();
+        let duration = t[t.len() - 1] - t[0];
+        Measurement::new(self.integral().value() / duration, self.values.prefix())
+    }
+
+    /// Resamples the series onto a regular grid at `rate`, starting at the first timestamp
+    /// and ending at or before the last one, interpolating linearly between samples.
+    ///
+    /// Grid points that coincide with a timestamp take its value exactly.
+    ///
+    /// # Panics
+    /// Panics if `rate` is not positive and finite.
+    pub fn resample_uniform(&self, rate: Measurement<Hertz>) -> M1d<U> {
+        let (v, t) = (self.values.view(), self.seconds());
+        let (start, end) = (t[0], t[t.len() - 1]);
+        let mut resampled = Vec::new();
+        let mut j = 0;
+        for k in 0.. {
+            let offset = sample_to_time(k, rate).value();
+            if offset > end - start {
+                break;
+            }
+            let tk = start + offset;
+            while j + 2 < t.len() && t[j + 1] <= tk {
+                j += 1;
+            }
+            let f = (tk - t[j]) / (t[j + 1] - t[j]);
+            resampled.push(match f {
+                0.0 => v[j],
+                1.0 => v[j + 1],
+                f => v[j] + (v[j + 1] - v[j]) * f,
+            });
+        }
+        M1d::new(resampled, self.values.prefix())
+    }
+}
+
+#[cfg(test)]
+mod timed_tests {
+    use super::*;
+    use crate::{prefix::Prefix, uom::Volt};
+    use ndarray::array;
+
+    /// Five samples at irregular times: 2, 4, 0, 6 and 2 mV at 0, 1, 3, 4 and 8 s.
+    fn series() -> TimedSeries<Volt> {
+        TimedSeries::new(
+            M1d::new(vec![2.0, 4.0, 0.0, 6.0, 2.0], Prefix::Milli),
+            M1d::new(vec![0.0, 1000.0, 3000.0, 4000.0, 8000.0], Prefix::Milli),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn weights_samples_by_time() {
+        let s = series();
+        assert_eq!(s.duration(), Measurement::new(8.0, Prefix::None));
+        assert_eq!(s.duration().prefix(), Prefix::Milli);
+        // 3 + 4 + 3 + 16 mV·s
+        let integral = s.integral();
+        assert_eq!(integral.value(), 26.0);
+        assert_eq!(integral.label(), "26mV·s");
+        assert_eq!(
+            s.time_weighted_mean(),
+            Measurement::new(3.25, Prefix::Milli)
+        );
+        assert_eq!(
+            s.values().mean(),
+            Some(Measurement::new(2.8, Prefix::Milli))
+        );
+    }
+
+    #[test]
+    fn resamples_onto_a_regular_grid() {
+        let s = series();
+        let every_2s = s.resample_uniform(Measurement::new(500.0, Prefix::Milli));
+        assert_eq!(every_2s.values(), array![2.0, 2.0, 6.0, 4.0, 2.0]);
+        assert_eq!(every_2s.prefix(), Prefix::Milli);
+        let every_1s = s.resample_uniform(Measurement::new(1.0, Prefix::None));
+        assert_eq!(
+            every_1s.values(),
+            array![2.0, 4.0, 2.0, 0.0, 6.0, 5.0, 4.0, 3.0, 2.0]
+        );
+        // the grid stops before the last sample when it does not land on it
+        let every_2_5s = s.resample_uniform(Measurement::new(400.0, Prefix::Milli));
+        assert_eq!(every_2_5s.values(), array![2.0, 1.0, 5.0, 2.5]);
+    }
+
+    #[test]
+    fn validates_timestamps() {
+        let values = || M1d::<Volt>::new(vec![1.0, 2.0, 3.0], Prefix::None);
+        let times = |t: Vec<f64>| M1d::<Second>::new(t, Prefix::None);
+        assert_eq!(
+            TimedSeries::new(values(), times(vec![0.0, 1.0, 1.0])),
+            Err(MeasurementError::NotIncreasing { index: 2 })
+        );
+        assert_eq!(
+            TimedSeries::new(values(), times(vec![f64::NAN, 1.0, 2.0])),
+            Err(MeasurementError::NotIncreasing { index: 1 })
+        );
+        assert!(matches!(
+            TimedSeries::new(values(), times(vec![0.0, 1.0])),
+            Err(MeasurementError::ShapeMismatch(_))
+        ));
+        let one = M1d::<Volt>::new(vec![1.0], Prefix::None);
+        assert!(matches!(
+            TimedSeries::new(one, times(vec![0.0])),
+            Err(MeasurementError::EmptyInput(_))
+        ));
+    }
+
+    #[test]
+    fn serde_round_trip_validates() {
+        let s = series();
+        let json = serde_json::to_string(&s).unwrap();
+        assert_eq!(serde_json::from_str::<TimedSeries<Volt>>(&json).unwrap(), s);
+        let reversed = json.replace("0.0,1000.0,3000.0", "0.0,3000.0,1000.0");
+        assert_ne!(reversed, json);
+        let e = serde_json::from_str::<TimedSeries<Volt>>(&reversed).unwrap_err();
+        assert!(
+            e.to_string().contains("not strictly increasing at index 2"),
+            "{e}"
+        );
+    }
+}
@@ -0,0 +1,266 @@
+//! Measurements with a symmetric uncertainty, written `value ± uncertainty`, and the
+//! propagation of that uncertainty through arithmetic.
+
+use crate::{
+    error::MeasurementError,
+    math::floor_log10,
+    measurement::{Measurement, format_value},
+    percentage::Percentage,
+    prefix::Prefix,
+    ranged_measurement::RangedMeasurement,
+    uom::Uom,
+};
+use core::{
+    fmt,
+    ops::{Add, Div, Mul, Sub},
+};
+use serde::{Deserialize, Serialize};
+
+/// A measured value with a symmetric absolute uncertainty σ, e.g. 10 mV ± 12 µV.
+///
+/// Sums and differences treat the uncertainties as independent and add them in quadrature;
+/// scaling by a constant scales the uncertainty by its magnitude.
+///
+/// ```
+/// use typed_measurements::prelude::*;
+///
+/// let a = UncertainMeasurement::new(
+///     Measurement::<Volt>::new(10.0, Prefix::Milli),
+///     Measurement::new(30.0, Prefix::Micro),
+/// );
+/// let b = UncertainMeasurement::new(
+///     Measurement::<Volt>::new(5.0, Prefix::Milli),
+///     Measurement::new(40.0, Prefix::Micro),
+/// );
+/// assert_eq!((a + b).label(), "15.000 ± 0.050 mV");
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(from = "UncheckedUncertainMeasurement<U>")]
+pub struct UncertainMeasurement<U: Uom> {
+    value: Measurement<U>,
+    uncertainty: Measurement<U>,
+}
+
+/// The serialized form of an [`UncertainMeasurement`], before the uncertainty is made
+/// non-negative.
+#[derive(Deserialize)]
+struct UncheckedUncertainMeasurement<U: Uom> {
+    value: Measurement<U>,
+    uncertainty: Measurement<U>,
+}
+
+impl<U: Uom> From<UncheckedUncertainMeasurement<U>> for UncertainMeasurement<U> {
+    fn from(raw: UncheckedUncertainMeasurement<U>) -> Self {
+        Self::new(raw.value, raw.uncertainty)
+    }
+}
+
+impl<U: Uom> UncertainMeasurement<U> {
+    /// Creates a measurement of `value` with the absolute uncertainty `uncertainty`.
+    ///
+    /// # Arguments
+    /// * `value` - The central value.
+    /// * `uncertainty` - The uncertainty σ, in any prefix; its sign is ignored.
+    pub fn new(value: Measurement<U>, uncertainty: Measurement<U>) -> Self {
+        let uncertainty = Measurement::new(uncertainty.value().abs(), uncertainty.prefix());
+        Self { value, uncertainty }
+    }
+
+    /// Creates a measurement of `value` with an uncertainty relative to its magnitude, e.g.
+    /// 0.1% of reading.
+    ///
+    /// # Arguments
+    /// * `value` - The central value.
+    /// * `relative` - The uncertainty as a fraction of `|value|`.
+    pub fn from_relative(value: Measurement<U>, relative: Percentage) -> Self {
+        Self::new(value, value.scaled(relative.get_value()))
+    }
+
+    /// Returns the central value.
+    pub fn value(&self) -> Measurement<U> {
+        self.value
+    }
+
+    /// Returns the absolute uncertainty σ, never negative.
+    pub fn uncertainty(&self) -> Measurement<U> {
+        self.uncertainty
+    }
+
+    /// Returns a label with the uncertainty in the prefix of the value (e.g.,
+    /// "10.000 ± 0.012 mV").
+    ///
+    /// The uncertainty is written with two significant digits and the value with as many
+    /// decimals. A zero or non-finite uncertainty, or a non-finite value, is written as in
+    /// [`Measurement::label`].
+    pub fn label(&self) -> String {
+        let prefix = self.value.prefix();
+        let (value, sigma) = (self.value.value(), self.uncertainty_in(prefix));
+        let (pfx, uom) = (prefix.get_label(), U::uom_str());
+        if !(value.is_finite() && sigma.is_finite()) || sigma == 0.0 {
+            let (value, sigma) = (format_value(value, None), format_value(sigma, None));
+            return format!("{value} ± {sigma} {pfx}{uom}");
+        }
+        let decimals = (1 - floor_log10(sigma)).clamp(0, 17) as usize;
+        // -0.0 would print with its sign
+        let value = if value == 0.0 { 0.0 } else { value };
+        format!("{value:.decimals$} ± {sigma:.decimals$} {pfx}{uom}")
+    }
+
+    /// Returns the range `value ± k·σ`, e.g. a 95% interval with `k` = 2 for a normal
+    /// distribution.
+    ///
+    /// # Returns
+    /// The stepless range, in the finer of the two prefixes, or
+    /// [`MeasurementError::RangeInvariant`] if it is empty: a zero uncertainty, a `k` that
+    /// is not positive, or a value that is not finite.
+    pub fn to_range(&self, k: f64) -> Result<RangedMeasurement<U>, MeasurementError> {
+        let half_width = self.uncertainty.scaled(k);
+        RangedMeasurement::builder()
+            .min(self.value - half_width)
+            .max(self.value + half_width)
+            .build()
+    }
+
+    /// Returns the uncertainty converted to `prefix`.
+    fn uncertainty_in(&self, prefix: Prefix) -> f64 {
+        self.uncertainty.convert_to(prefix).value()
+    }
+}
+
+impl<U: Uom> From<Measurement<U>> for UncertainMeasurement<U> {
+    /// Creates an exact measurement, with a zero uncertainty in its prefix.
+    fn from(value: Measurement<U>) -> Self {
+        Self::new(value, Measurement::new(0.0, value.prefix()))
+    }
+}
+
+impl<U: Uom> Add for UncertainMeasurement<U> {
+    /// Adds the values like [`Measurement`]s and the uncertainties in quadrature.
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self::Output {
+        let value = self.value + rhs.value;
+        let prefix = value.prefix();
+        let sigma = self
+            .uncertainty_in(prefix)
+            .hypot(rhs.uncertainty_in(prefix));
+        Self::new(value, Measurement::new(sigma, prefix))
+    }
+}
+
+impl<U: Uom> Sub for UncertainMeasurement<U> {
+    /// Subtracts the values like [`Measurement`]s and adds the uncertainties in quadrature.
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self::Output {
+        let value = self.value - rhs.value;
+        let prefix = value.prefix();
+        let sigma = self
+            .uncertainty_in(prefix)
+            .hypot(rhs.uncertainty_in(prefix));
+        Self::new(value, Measurement::new(sigma, prefix))
+    }
+}
+
+impl<U: Uom> Mul<f64> for UncertainMeasurement<U> {
+    /// Scales the value by `rhs` and the uncertainty by `|rhs|`.
+    type Output = Self;
+    fn mul(self, rhs: f64) -> Self::Output {
+        Self::new(self.value.scaled(rhs), self.uncertainty.scaled(rhs))
+    }
+}
+
+impl<U: Uom> Div<f64> for UncertainMeasurement<U> {
+    /// Divides the value by `rhs` and the uncertainty by `|rhs|`.
+    type Output = Self;
+    fn div(self, rhs: f64) -> Self::Output {
+        Self::new(self.value / rhs, self.uncertainty / rhs)
+    }
+}
+
+impl<U: Uom> fmt::Display for UncertainMeasurement<U> {
+    /// Formats the measurement like [`UncertainMeasurement::label`].
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.label())
+    }
+}
+
+#[cfg(test)]
+mod uncertain_tests {
+    use super::*;
+    use crate::{percentage, uom::Volt};
+
+    fn mv(v: f64) -> Measurement<Volt> {
+        Measurement::new(v, Prefix::Milli)
+    }
+
+    fn uv(v: f64) -> Measurement<Volt> {
+        Measurement::new(v, Prefix::Micro)
+    }
+
+    fn close(a: Measurement<Volt>, b: Measurement<Volt>) -> bool {
+        let base = |m: Measurement<Volt>| m.convert_to(Prefix::None).value();
+        (base(a) - base(b)).abs() < 1e-15
+    }
+
+    #[test]
+    fn uncertainties_add_in_quadrature() {
+        // σ = 0.3 mV and 0.4 mV combine to 0.5 mV, whether added or subtracted
+        let a = UncertainMeasurement::new(mv(10.0), uv(300.0));
+        let b = UncertainMeasurement::new(mv(5.0), mv(-0.4));
+        assert_eq!(b.uncertainty(), mv(0.4));
+        let sum = a + b;
+        assert_eq!(sum.value(), mv(15.0));
+        assert!(close(sum.uncertainty(), mv(0.5)), "{sum}");
+        let difference = a - b;
+        assert_eq!(difference.value(), mv(5.0));
+        assert!(close(difference.uncertainty(), mv(0.5)), "{difference}");
+        // an exact term leaves the uncertainty unchanged
+        assert!(close((a + mv(2.0).into()).uncertainty(), uv(300.0)));
+        let scaled = a * -2.0;
+        assert_eq!(
+            (scaled.value(), scaled.uncertainty()),
+            (mv(-20.0), uv(600.0))
+        );
+        let halved = a / -2.0;
+        assert_eq!(
+            (halved.value(), halved.uncertainty()),
+            (mv(-5.0), uv(150.0))
+        );
+    }
+
+    #[test]
+    fn labels_share_the_prefix_of_the_value() {
+        let m = UncertainMeasurement::new(mv(10.0), uv(12.0));
+        assert_eq!(m.label(), "10.000 ± 0.012 mV");
+        let relative = UncertainMeasurement::from_relative(mv(10.0), percentage!(0.0012));
+        assert_eq!(relative.label(), "10.000 ± 0.012 mV");
+        let coarse = UncertainMeasurement::new(mv(-1234.4), mv(25.0));
+        assert_eq!(coarse.to_string(), "-1234 ± 25 mV");
+        let exact = UncertainMeasurement::from(mv(1.5));
+        assert_eq!(exact.label(), "1.5 ± 0 mV");
+    }
+
+    #[test]
+    fn converts_to_a_coverage_range() {
+        let m = UncertainMeasurement::new(mv(10.0), uv(12.0));
+        let range = m.to_range(2.0).unwrap();
+        assert_eq!(range.min(), uv(9976.0));
+        assert_eq!(range.max(), uv(10024.0));
+        assert_eq!(range.step(), None);
+        assert!(UncertainMeasurement::from(mv(1.0)).to_range(2.0).is_err());
+        assert!(m.to_range(-1.0).is_err());
+    }
+
+    #[test]
+    fn round_trips_through_serde() {
+        let m = UncertainMeasurement::new(mv(10.0), uv(12.0));
+        let json = serde_json::to_string(&m).unwrap();
+        assert_eq!(
+            serde_json::from_str::<UncertainMeasurement<Volt>>(&json).unwrap(),
+            m
+        );
+        let negative = json.replace("12.0", "-12.0");
+        assert_ne!(negative, json);
+        let parsed = serde_json::from_str::<UncertainMeasurement<Volt>>(&negative).unwrap();
+        assert_eq!(parsed.uncertainty(), uv(12.0));
+    }
+}
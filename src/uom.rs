@@ -1,86 +1,622 @@
+use alloc::string::{String, ToString};
+use core::{fmt::Debug, marker::PhantomData};
 use serde::{Deserialize, Serialize};
-use std::fmt::Debug;
+#[cfg(feature = "std")]
+use std::{
+    any::TypeId,
+    collections::HashMap,
+    format,
+    sync::{Mutex, OnceLock, PoisonError},
+};
 use uom_derive::Uom;
 
 /// Trait for units of measurement (UOM).
 ///
 /// Implement this trait for each unit type to provide a string label for the unit.
-pub trait Uom: Clone + Copy + Debug + Serialize +  PartialEq + Send + Sync {
-    /// Returns the string label for the unit (e.g., "V" for Volt).
-    fn uom() -> String;
+/// Only [`Uom::uom_str`] is required; `#[derive(Uom)]` emits it from the `label` attribute.
+pub trait Uom: Clone + Copy + Debug + Serialize + PartialEq + Send + Sync + 'static {
+    /// Returns the static string label for the unit (e.g., "V" for Volt), without allocating.
+    fn uom_str() -> &'static str;
+
+    /// Returns the string label for the unit as an owned `String`.
+    fn uom() -> String {
+        Self::uom_str().to_string()
+    }
+
+    /// Returns other labels accepted for the unit when parsing (e.g., "sps" for Hertz),
+    /// defaulting to none.
+    ///
+    /// `#[derive(Uom)]` emits them from the `aliases` attribute. Labels and other
+    /// formatting always use [`Uom::uom_str`].
+    fn aliases() -> &'static [&'static str] {
+        &[]
+    }
+
+    /// Returns the static full name of the unit (e.g., "volt"), defaulting to the label.
+    ///
+    /// `#[derive(Uom)]` emits it from the `name` attribute. Units whose name is built from
+    /// other units, such as [`Per`], override [`Uom::name`] instead.
+    fn name_str() -> &'static str {
+        Self::uom_str()
+    }
+
+    /// Returns the full name of the unit as an owned `String`.
+    fn name() -> String {
+        Self::name_str().to_string()
+    }
+
+    /// Returns the plural of [`Uom::name`] (e.g., "volts").
+    ///
+    /// Appends an "s" unless the name already ends in "s" or "z" (e.g., "siemens", "hertz").
+    /// A unit without a name keeps its label unchanged (e.g., "Wb", not "Wbs").
+    fn plural() -> String {
+        let name = Self::name();
+        if name.is_empty() || name == Self::uom_str() || name.ends_with(['s', 'z']) {
+            name
+        } else {
+            name + "s"
+        }
+    }
+}
+
+/// Defines a unit type and its [`Uom`] impl in one line, without the derive macro.
+///
+/// Takes the type name, the label and optionally the long name used by [`Uom::name`].
+/// Outer attributes such as doc comments are forwarded to the struct.
+///
+/// # Example
+/// ```
+/// use typed_measurements::{define_unit, prelude::*};
+///
+/// define_unit!(PhotonCount, "ph");
+/// define_unit!(
+///     /// Counts per second from a photon counter.
+///     CountRate, "cps", "count per second"
+/// );
+///
+/// let m = Measurement::<PhotonCount>::new(1.5, Prefix::Kilo);
+/// assert_eq!(m.label(), "1.5kph");
+/// assert_eq!(CountRate::name(), "count per second");
+/// ```
+#[macro_export]
+macro_rules! define_unit {
+    ($(#[$attr:meta])* $name:ident, $label:literal $(, $long:literal)? $(,)?) => {
+        $(#[$attr])*
+        #[derive(
+            ::core::clone::Clone,
+            ::core::marker::Copy,
+            ::core::fmt::Debug,
+            ::core::cmp::PartialEq,
+        )]
+        pub struct $name;
+
+        impl $crate::uom::Uom for $name {
+            fn uom_str() -> &'static str {
+                $label
+            }
+            $(
+                fn name_str() -> &'static str {
+                    $long
+                }
+            )?
+        }
+
+        impl $crate::__private::serde::Serialize for $name {
+            fn serialize<S: $crate::__private::serde::Serializer>(
+                &self,
+                serializer: S,
+            ) -> ::core::result::Result<S::Ok, S::Error> {
+                serializer.serialize_unit_struct(::core::stringify!($name))
+            }
+        }
+    };
+}
+
+/// Unit algebra: the unit obtained by multiplying `Self` by `Rhs` (e.g. V·A = W).
+///
+/// Only named results are mapped (plus the cancellations of [`Per`] and [`Product`]).
+/// There is deliberately no blanket fallback to [`Product`]: without specialization it would
+/// overlap with every specific mapping. Use [`Measurement::times`] for an arbitrary product.
+///
+/// [`Measurement::times`]: crate::measurement::Measurement::times
+pub trait UomMul<Rhs: Uom>: Uom {
+    /// The resulting unit.
+    type Output: Uom;
+}
+
+/// Unit algebra: the unit obtained by dividing `Self` by `Rhs` (e.g. V/A = Ω).
+///
+/// As with [`UomMul`], there is no blanket fallback to [`Per`]; use [`Measurement::per`]
+/// for an arbitrary quotient.
+///
+/// # Example
+/// ```
+/// use typed_measurements::prelude::*;
+///
+/// let v = Measurement::<Volt>::new(2.0, Prefix::None);
+/// let i = Measurement::<Ampere>::new(4.0, Prefix::Milli);
+/// let r: Measurement<Ohm> = &v / &i;
+/// assert_eq!(r.label(), "0.5kΩ");
+///
+/// // V/s has no named unit, so `&v / &t` does not compile.
+/// let t = Measurement::<Second>::new(4.0, Prefix::None);
+/// assert_eq!(v.per(&t).label(), "0.5V/s");
+/// ```
+///
+/// [`Measurement::per`]: crate::measurement::Measurement::per
+pub trait UomDiv<Rhs: Uom>: Uom {
+    /// The resulting unit.
+    type Output: Uom;
+}
+
+/// Dividing a unit by itself yields a [`Dimensionless`] ratio.
+impl<U: Uom> UomDiv<U> for U {
+    type Output = Dimensionless;
+}
+
+// A compound unit times its denominator, or divided by its last factor, cancels out.
+#[cfg(feature = "std")]
+impl<A: Uom, B: Uom> UomMul<B> for Per<A, B> {
+    type Output = A;
+}
+
+#[cfg(feature = "std")]
+impl<A: Uom, B: Uom> UomDiv<B> for Product<A, B> {
+    type Output = A;
+}
+
+/// Implements [`UomMul`] for a unit product in both operand orders.
+macro_rules! uom_mul {
+    ($a:ty, $b:ty => $out:ty) => {
+        impl UomMul<$b> for $a {
+            type Output = $out;
+        }
+        impl UomMul<$a> for $b {
+            type Output = $out;
+        }
+    };
 }
 
-/// Represents the unit Adimensional (F).
+/// Implements [`UomDiv`] for a unit quotient.
+macro_rules! uom_div {
+    ($a:ty, $b:ty => $out:ty) => {
+        impl UomDiv<$b> for $a {
+            type Output = $out;
+        }
+    };
+}
+
+uom_mul!(Volt, Ampere => Watt);
+uom_mul!(Ampere, Ohm => Volt);
+uom_mul!(Ampere, Second => Coulomb);
+uom_mul!(Volt, Siemens => Ampere);
+uom_mul!(Farad, Volt => Coulomb);
+uom_div!(Watt, Volt => Ampere);
+uom_div!(Watt, Ampere => Volt);
+uom_div!(Volt, Ampere => Ohm);
+uom_div!(Volt, Ohm => Ampere);
+uom_div!(Ampere, Volt => Siemens);
+uom_div!(Ampere, Siemens => Volt);
+uom_div!(Coulomb, Second => Ampere);
+uom_div!(Coulomb, Ampere => Second);
+uom_div!(Coulomb, Volt => Farad);
+uom_div!(Coulomb, Farad => Volt);
+uom_mul!(Ohm, Second => Henry);
+uom_mul!(Watt, Second => Joule);
+uom_mul!(Volt, Coulomb => Joule);
+uom_div!(Joule, Second => Watt);
+uom_div!(Joule, Watt => Second);
+uom_div!(Joule, Volt => Coulomb);
+uom_div!(Joule, Coulomb => Volt);
+uom_div!(Henry, Second => Ohm);
+uom_div!(Henry, Ohm => Second);
+
+/// Represents a pure ratio or count with no unit, e.g. V/V or a gain setting.
+///
+/// Its label is empty, so a dimensionless measurement prints as just the value and
+/// prefix (e.g. "1.5k" for 1500).
 #[derive(PartialEq, Debug, Clone, Copy, Serialize, Deserialize)]
-pub struct Adimensional;
-impl Uom for Adimensional {
-    fn uom() -> String {
-        "".to_string()
+pub struct Dimensionless;
+impl Uom for Dimensionless {
+    fn uom_str() -> &'static str {
+        ""
     }
 }
 
+/// Former name of [`Dimensionless`].
+#[deprecated(note = "use `Dimensionless`")]
+pub type Adimensional = Dimensionless;
+
 /// Represents the unit Volt (V).
 #[derive(Uom, PartialEq, Debug, Clone, Copy, Serialize, Deserialize)]
-#[uom(label = V)]
+#[uom(label = V, name = "volt")]
 pub struct Volt;
 
 /// Represents the unit Ampere (A).
 #[derive(Uom, PartialEq, Debug, Clone, Copy, Serialize, Deserialize)]
-#[uom(label = A)]
+#[uom(label = A, name = "ampere")]
 pub struct Ampere;
 
 /// Represents the unit Watt (W).
 #[derive(Uom, PartialEq, Debug, Clone, Copy, Serialize, Deserialize)]
-#[uom(label = W)]
+#[uom(label = W, name = "watt")]
 pub struct Watt;
 
 /// Represents the unit Second (s).
 #[derive(Uom, PartialEq, Debug, Clone, Copy, Serialize, Deserialize)]
-#[uom(label = s)]
+#[uom(label = s, name = "second")]
 pub struct Second;
 
 /// Represents the unit Hertz (Hz).
+///
+/// Also parsed from "sps", samples per second, so sampling rates such as "50 ksps" read as
+/// 50 kHz.
 #[derive(Uom, PartialEq, Debug, Clone, Copy, Serialize, Deserialize)]
-#[uom(label = Hz)]
+#[uom(label = Hz, name = "hertz", aliases("sps"))]
 pub struct Hertz;
 
 /// Represents the unit Ohm (Ω).
 #[derive(Uom, PartialEq, Debug, Clone, Copy, Serialize, Deserialize)]
-#[uom(label = Ω)]
+#[uom(label = "Ω", name = "ohm")]
 pub struct Ohm;
 
 /// Represents the unit Siemens (S).
 #[derive(Uom, PartialEq, Debug, Clone, Copy, Serialize, Deserialize)]
-#[uom(label = S)]
+#[uom(label = S, name = "siemens")]
 pub struct Siemens;
 
 /// Represents the unit Coulomb (C).
 #[derive(Uom, PartialEq, Debug, Clone, Copy, Serialize, Deserialize)]
-#[uom(label = C)]
+#[uom(label = C, name = "coulomb")]
 pub struct Coulomb;
 
 /// Represents the unit Farad (F).
 #[derive(Uom, PartialEq, Debug, Clone, Copy, Serialize, Deserialize)]
-#[uom(label = F)]
+#[uom(label = F, name = "farad")]
 pub struct Farad;
 
+/// Represents the unit Henry (H).
+#[derive(Uom, PartialEq, Debug, Clone, Copy, Serialize, Deserialize)]
+#[uom(label = H, name = "henry")]
+pub struct Henry;
+
+/// Represents the unit Meter (m).
+#[derive(Uom, PartialEq, Debug, Clone, Copy, Serialize, Deserialize)]
+#[uom(label = m, name = "meter")]
+pub struct Meter;
+
+/// Represents the unit Gram (g).
+///
+/// The SI base unit of mass is the kilogram, but its label already carries a prefix, so
+/// mass is modelled as grams: a kilogram is a `Measurement<Gram>` with [`Prefix::Kilo`].
+/// This keeps prefix conversions uniform (`mg`, `g`, `kg`) and avoids labels like "kkg".
+///
+/// [`Prefix::Kilo`]: crate::prefix::Prefix::Kilo
+#[derive(Uom, PartialEq, Debug, Clone, Copy, Serialize, Deserialize)]
+#[uom(label = g, name = "gram")]
+pub struct Gram;
+
+/// Represents the unit Kelvin (K).
+#[derive(Uom, PartialEq, Debug, Clone, Copy, Serialize, Deserialize)]
+#[uom(label = K, name = "kelvin")]
+pub struct Kelvin;
+
+/// Represents the unit Mole (mol).
+#[derive(Uom, PartialEq, Debug, Clone, Copy, Serialize, Deserialize)]
+#[uom(label = mol, name = "mole")]
+pub struct Mole;
+
+/// Represents the unit Candela (cd).
+#[derive(Uom, PartialEq, Debug, Clone, Copy, Serialize, Deserialize)]
+#[uom(label = cd, name = "candela")]
+pub struct Candela;
+
+/// Represents the unit Joule (J).
+#[derive(Uom, PartialEq, Debug, Clone, Copy, Serialize, Deserialize)]
+#[uom(label = J, name = "joule")]
+pub struct Joule;
+
+/// Represents the unit Newton (N).
+#[derive(Uom, PartialEq, Debug, Clone, Copy, Serialize, Deserialize)]
+#[uom(label = N, name = "newton")]
+pub struct Newton;
+
+/// Represents the unit Pascal (Pa).
+#[derive(Uom, PartialEq, Debug, Clone, Copy, Serialize, Deserialize)]
+#[uom(label = Pa, name = "pascal")]
+pub struct Pascal;
+
+/// Represents the unit Liter (L).
+///
+/// The liter is not a coherent SI unit (1 L = 1 dm³), but volumes are almost always
+/// reported in liters, so it is provided as a unit of its own with no algebra attached.
+#[derive(Uom, PartialEq, Debug, Clone, Copy, Serialize, Deserialize)]
+#[uom(label = L, name = "liter")]
+pub struct Liter;
+
+/// A unit related to a base unit by an offset as well as a scale, such as Celsius to Kelvin.
+///
+/// A value `x` in `Self` (without prefix) is `x * SCALE + OFFSET` in [`AffineUom::Base`].
+/// Because of the offset, only differences of affine values are meaningful on their own:
+/// adding two temperatures in °C does not give a temperature. The type system does not enforce
+/// this: `+` and `-` work on affine measurements like on any other and keep the affine unit,
+/// so a difference of two Celsius values is still typed as Celsius. Use
+/// [`Measurement::delta`](crate::measurement::Measurement::delta) for a difference in Kelvin.
+pub trait AffineUom: Uom {
+    /// The unit the offset and scale are relative to.
+    type Base: Uom;
+    /// The size of one `Self` step in `Base` units.
+    const SCALE: f64;
+    /// The `Base` value of zero in `Self`.
+    const OFFSET: f64;
+}
+
+/// Represents the unit degree Celsius (°C), an affine unit over [`Kelvin`].
+#[derive(PartialEq, Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct Celsius;
+impl Uom for Celsius {
+    fn uom_str() -> &'static str {
+        "°C"
+    }
+
+    fn name_str() -> &'static str {
+        "degree Celsius"
+    }
+
+    fn plural() -> String {
+        "degrees Celsius".to_string()
+    }
+}
+
+impl AffineUom for Celsius {
+    type Base = Kelvin;
+    const SCALE: f64 = 1.0;
+    const OFFSET: f64 = 273.15;
+}
+
+/// A non-SI unit that is a fixed multiple of `U`, such as minutes of [`Second`].
+///
+/// Scaled units are only used for explicit conversion and display; a measurement is always
+/// stored in `U`, and [`Measurement::nice`] never picks a scaled unit.
+///
+/// [`Measurement::nice`]: crate::measurement::Measurement::nice
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub struct ScaledUnit<U: Uom> {
+    factor: f64,
+    label: &'static str,
+    uom: PhantomData<U>,
+}
+
+impl<U: Uom> ScaledUnit<U> {
+    /// Creates a scaled unit worth `factor` times `U` (without prefix), displayed as `label`.
+    pub const fn new(factor: f64, label: &'static str) -> Self {
+        Self {
+            factor,
+            label,
+            uom: PhantomData,
+        }
+    }
+
+    /// Returns how many `U` one scaled unit is worth.
+    pub fn factor(&self) -> f64 {
+        self.factor
+    }
+
+    /// Returns the display label (e.g. "min").
+    pub fn label(&self) -> &str {
+        self.label
+    }
+}
+
+impl ScaledUnit<Second> {
+    /// One minute, 60 s.
+    pub const MINUTE: Self = Self::new(60.0, "min");
+    /// One hour, 3600 s.
+    pub const HOUR: Self = Self::new(3600.0, "h");
+}
+
+impl ScaledUnit<Pascal> {
+    /// One millimeter of mercury, 133.322387415 Pa.
+    pub const MMHG: Self = Self::new(133.322387415, "mmHg");
+}
+
+impl ScaledUnit<Meter> {
+    /// One inch, 0.0254 m.
+    pub const INCH: Self = Self::new(0.0254, "in");
+}
+
+/// The quotient of two units, e.g. `Per<Volt, Second>` for a slew rate in "V/s".
+#[cfg(feature = "std")]
+#[derive(PartialEq, Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct Per<A: Uom, B: Uom>(PhantomData<(A, B)>);
 
+#[cfg(feature = "std")]
+impl<A: Uom, B: Uom> Uom for Per<A, B> {
+    /// Returns "A/B", parenthesizing a compound denominator (e.g. "C/(A·s)").
+    fn uom_str() -> &'static str {
+        intern_label::<Self>(|| format!("{}/{}", A::uom_str(), group(B::uom_str())))
+    }
+
+    fn name() -> String {
+        format!("{} per {}", A::name(), B::name())
+    }
+
+    fn plural() -> String {
+        format!("{} per {}", A::plural(), B::name())
+    }
+}
+
+/// The product of two units, e.g. `Product<Ampere, Second>` for a charge in "A·s".
+#[cfg(feature = "std")]
+#[derive(PartialEq, Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct Product<A: Uom, B: Uom>(PhantomData<(A, B)>);
+
+#[cfg(feature = "std")]
+impl<A: Uom, B: Uom> Uom for Product<A, B> {
+    /// Returns "A·B", parenthesizing a quotient factor (e.g. "(V/s)·s").
+    fn uom_str() -> &'static str {
+        intern_label::<Self>(|| {
+            let factor = |l: &str| {
+                if l.contains('/') {
+                    format!("({l})")
+                } else {
+                    l.to_string()
+                }
+            };
+            format!("{}·{}", factor(A::uom_str()), factor(B::uom_str()))
+        })
+    }
+
+    fn name() -> String {
+        format!("{} {}", A::name(), B::name())
+    }
+
+    fn plural() -> String {
+        format!("{} {}", A::name(), B::plural())
+    }
+}
+
+/// Wraps a compound label in parentheses so it can be used as a denominator.
+#[cfg(feature = "std")]
+fn group(label: &str) -> String {
+    if label.contains(['/', '·']) {
+        format!("({label})")
+    } else {
+        label.to_string()
+    }
+}
+
+/// Builds the label of a compound unit once per type and keeps it for the life of the program.
+///
+/// Generic units such as [`Per`] cannot hold their label in a `static`, so it is leaked on
+/// first use; there is one allocation per distinct compound type, not per call.
+#[cfg(feature = "std")]
+fn intern_label<U: 'static>(make: impl FnOnce() -> String) -> &'static str {
+    static LABELS: OnceLock<Mutex<HashMap<TypeId, &'static str>>> = OnceLock::new();
+    let labels = || {
+        LABELS
+            .get_or_init(Default::default)
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    };
+    if let Some(label) = labels().get(&TypeId::of::<U>()) {
+        return label;
+    }
+    // Built without holding the lock: nested compounds intern their own factors first.
+    let label = make();
+    labels()
+        .entry(TypeId::of::<U>())
+        .or_insert_with(|| Box::leak(label.into_boxed_str()))
+}
 
 #[cfg(test)]
-mod uom {
+mod uom_tests {
     use super::*;
+    use crate::prelude::{Measurement, Prefix};
     fn assert_send<T: Send>() {}
     fn assert_sync<T: Sync>() {}
 
+    define_unit!(PhotonCount, "ph", "photon");
+
+    // The expansion must not depend on the names in scope at the call site.
+    #[allow(dead_code)]
+    mod shadowed {
+        type Result = ();
+        struct String;
+        crate::define_unit!(Shadowed, "sh", "shadowed unit");
+    }
+
+    #[test]
+    fn define_unit_macro() {
+        assert_eq!(PhotonCount::uom_str(), "ph");
+        assert_eq!(PhotonCount::plural(), "photons");
+        assert_eq!(shadowed::Shadowed::name(), "shadowed unit");
+        let m = Measurement::<PhotonCount>::new(250, Prefix::Kilo);
+        assert_eq!(m.label(), "250kph");
+        assert_eq!(m.convert_to(Prefix::Mega).value(), 0.25);
+        #[cfg(feature = "ndarray")]
+        {
+            let a =
+                crate::m1d::M1d::<PhotonCount>::new(ndarray::array![1.0, 2.0, 3.0], Prefix::None);
+            assert_eq!(a.sum().unwrap().label(), "6ph");
+            assert_eq!(a.to_string(), "M1d<ph> (3) [1, 2, 3]");
+        }
+    }
+
     #[test]
     fn equality_check() {
         assert_eq!(Volt, Volt);
     }
 
+    #[test]
+    fn electrical_labels() {
+        assert_eq!(Ohm::uom(), "Ω");
+        assert_eq!(Siemens::uom(), "S");
+        assert_eq!(Farad::uom(), "F");
+        assert_eq!(Coulomb::uom(), "C");
+        assert_eq!(Henry::uom(), "H");
+    }
+
+    #[test]
+    fn base_labels() {
+        assert_eq!(Meter::uom(), "m");
+        assert_eq!(Gram::uom(), "g");
+        assert_eq!(Kelvin::uom(), "K");
+        assert_eq!(Mole::uom(), "mol");
+        assert_eq!(Candela::uom(), "cd");
+    }
+
+    #[test]
+    fn mechanical_labels() {
+        assert_eq!(Joule::uom(), "J");
+        assert_eq!(Newton::uom(), "N");
+        assert_eq!(Pascal::uom(), "Pa");
+        assert_eq!(Liter::uom(), "L");
+    }
+
+    #[test]
+    fn names() {
+        assert_eq!(Volt::name(), "volt");
+        assert_eq!(Volt::plural(), "volts");
+        assert_eq!(Siemens::plural(), "siemens");
+        assert_eq!(Hertz::plural(), "hertz");
+        assert_eq!(Dimensionless::name(), "");
+        assert_eq!(Dimensionless::plural(), "");
+    }
+
+    #[derive(Uom, PartialEq, Debug, Clone, Copy, Serialize, Deserialize)]
+    #[uom(label = Wb)]
+    struct Weber;
+
+    #[test]
+    fn name_defaults_to_label() {
+        assert_eq!(Weber::name(), "Wb");
+        assert_eq!(Weber::plural(), "Wb");
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn compound_labels() {
+        assert_eq!(Per::<Volt, Second>::uom(), "V/s");
+        assert_eq!(Product::<Ampere, Second>::uom(), "A·s");
+        assert_eq!(Per::<Product<Ampere, Second>, Volt>::uom(), "A·s/V");
+        assert_eq!(Per::<Coulomb, Product<Ampere, Second>>::uom(), "C/(A·s)");
+        assert_eq!(Per::<Volt, Per<Meter, Second>>::uom(), "V/(m/s)");
+        assert_eq!(Product::<Per<Volt, Second>, Second>::uom(), "(V/s)·s");
+        assert_eq!(Per::<Volt, Second>::plural(), "volts per second");
+        assert_eq!(Product::<Ampere, Second>::plural(), "ampere seconds");
+    }
+
+    #[test]
+    fn celsius_label() {
+        assert_eq!(Celsius::uom(), "°C");
+        assert_eq!(Celsius::plural(), "degrees Celsius");
+    }
+
     // checks that Uoms are send and sync
     #[test]
     fn send_sync() {
         assert_send::<Volt>();
         assert_sync::<Volt>();
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,165 @@
+//! Stimulus waveforms built from steps, ramps and sine segments.
+//!
+//! Segments may be given in different prefixes; combining them converts every segment to
+//! the finest prefix involved, so a step in mV followed by a ramp in µV is a trace in µV.
+
+use crate::{
+    m1d::M1d,
+    math::canonical_base,
+    measurement::Measurement,
+    prefix::{Prefix, finest},
+    uom::{Hertz, Uom},
+};
+use ndarray::Array1;
+use std::f64::consts::TAU;
+
+/// Returns `len` samples held at `amplitude`.
+pub fn step<U: Uom>(amplitude: Measurement<U>, len: usize) -> M1d<U> {
+    M1d::new(
+        Array1::from_elem(len, amplitude.value()),
+        amplitude.prefix(),
+    )
+}
+
+/// Returns `len` samples going linearly from `from` to `to`, both included, in the finer of
+/// their prefixes.
+///
+/// A single sample is `from`; the last of several samples is exactly `to`.
+pub fn ramp<U: Uom>(from: Measurement<U>, to: Measurement<U>, len: usize) -> M1d<U> {
+    let prefix = finest([from.prefix(), to.prefix()]).unwrap_or(Prefix::None);
+    let (a, b) = (
+        from.convert_to(prefix).value(),
+        to.convert_to(prefix).value(),
+    );
+    let last = len.saturating_sub(1);
+    let values = Array1::from_shape_fn(len, |i| match i {
+        0 => a,
+        i if i == last => b,
+        i => a + (b - a) * (i as f64 / last as f64),
+    });
+    M1d::new(values, prefix)
+}
+
+/// Returns `len` samples of `amplitude * sin(2π * freq * t + phase)`, with `t = i / rate`
+/// and `phase` in radians.
+///
+/// # Panics
+/// Panics if `rate` is not positive and finite.
+pub fn sine<U: Uom>(
+    amplitude: Measurement<U>,
+    freq: Measurement<Hertz>,
+    rate: Measurement<Hertz>,
+    len: usize,
+    phase: f64,
+) -> M1d<U> {
+    assert!(
+        rate.value() > 0.0 && rate.value().is_finite(),
+        "sampling rate must be positive and finite"
+    );
+    // cycles per sample, combining the prefixes before scaling
+    let exp = freq.prefix().get_exp_value() as i32 - rate.prefix().get_exp_value() as i32;
+    let cycles = canonical_base(freq.value() / rate.value(), exp);
+    let values = Array1::from_shape_fn(len, |i| {
+        amplitude.value() * (TAU * cycles * i as f64 + phase).sin()
+    });
+    M1d::new(values, amplitude.prefix())
+}
+
+/// Joins `segments` end to end, in the finest of their prefixes.
+///
+/// An empty slice gives an empty trace without a prefix.
+pub fn concat<U: Uom>(segments: &[M1d<U>]) -> M1d<U> {
+    let prefix = finest(segments.iter().map(M1d::prefix)).unwrap_or(Prefix::None);
+    let values = segments
+        .iter()
+        .flat_map(|s| s.clone().convert_to(prefix).to_vec())
+        .collect::<Vec<_>>();
+    M1d::new(values, prefix)
+}
+
+/// Returns `trace` played `n` times in a row.
+pub fn repeat<U: Uom>(trace: &M1d<U>, n: usize) -> M1d<U> {
+    let values = trace.view();
+    let repeated = (0..n * values.len())
+        .map(|i| values[i % values.len()])
+        .collect::<Vec<_>>();
+    M1d::new(repeated, trace.prefix())
+}
+
+#[cfg(test)]
+mod waveform_tests {
+    use super::*;
+    use crate::uom::Volt;
+    use ndarray::array;
+
+    fn mv(v: f64) -> Measurement<Volt> {
+        Measurement::new(v, Prefix::Milli)
+    }
+
+    #[test]
+    fn builds_steps_and_ramps() {
+        let s = step(mv(-70.0), 3);
+        assert_eq!(s.values(), array![-70.0, -70.0, -70.0]);
+        assert_eq!(s.prefix(), Prefix::Milli);
+        let r = ramp(mv(-80.0), Measurement::new(40_000.0, Prefix::Micro), 7);
+        assert_eq!(r.len(), 7);
+        assert_eq!(r.prefix(), Prefix::Micro);
+        assert_eq!(r.view()[0], -80_000.0);
+        assert_eq!(r.view()[6], 40_000.0);
+        assert_eq!(r.view()[3], -20_000.0);
+        assert_eq!(ramp(mv(1.0), mv(2.0), 1).values(), array![1.0]);
+        assert!(ramp(mv(1.0), mv(2.0), 0).is_empty());
+    }
+
+    #[test]
+    fn sine_has_the_expected_rms() {
+        // 50 Hz at 10 kHz: 200 samples per period, 5 whole periods
+        let rate = Measurement::new(10.0, Prefix::Kilo);
+        let wave = sine(
+            mv(10.0),
+            Measurement::new(50.0, Prefix::None),
+            rate,
+            1000,
+            0.0,
+        );
+        assert_eq!(wave.len(), 1000);
+        assert_eq!(wave.view()[0], 0.0);
+        assert!((wave.view()[50] - 10.0).abs() < 1e-12);
+        let rms = (wave.view().mapv(|v| v * v).sum() / wave.len() as f64).sqrt();
+        assert!((rms - 10.0 / 2f64.sqrt()).abs() < 1e-12, "{rms}");
+        let cosine = sine(
+            mv(10.0),
+            Measurement::new(50.0, Prefix::None),
+            rate,
+            1,
+            TAU / 4.0,
+        );
+        assert_eq!(cosine.view()[0], 10.0);
+    }
+
+    #[test]
+    fn concatenates_segments_in_the_finest_prefix() {
+        let protocol = concat(&[
+            step(mv(-70.0), 2),
+            step(Measurement::new(-0.01, Prefix::None), 1),
+            ramp(
+                Measurement::new(-10_000.0, Prefix::Micro),
+                Measurement::new(0.0, Prefix::Micro),
+                3,
+            ),
+        ]);
+        assert_eq!(protocol.prefix(), Prefix::Micro);
+        assert_eq!(
+            protocol.values(),
+            array![-70_000.0, -70_000.0, -10_000.0, -10_000.0, -5_000.0, 0.0]
+        );
+        assert!(concat::<Volt>(&[]).is_empty());
+        let pulses = repeat(&concat(&[step(mv(0.0), 2), step(mv(5.0), 1)]), 3);
+        assert_eq!(pulses.len(), 9);
+        assert_eq!(
+            pulses.values(),
+            array![0.0, 0.0, 5.0, 0.0, 0.0, 5.0, 0.0, 0.0, 5.0]
+        );
+        assert!(repeat(&step(mv(1.0), 0), 4).is_empty());
+    }
+}
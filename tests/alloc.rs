@@ -0,0 +1,56 @@
+// Allocation counts for unit labels, measured with a counting global allocator.
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+};
+use typed_measurements::prelude::*;
+
+struct Counting;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.with(|n| n.set(n.get() + 1));
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+}
+
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+fn allocations<T>(f: impl FnOnce() -> T) -> usize {
+    let before = ALLOCATIONS.with(Cell::get);
+    let out = f();
+    let after = ALLOCATIONS.with(Cell::get);
+    drop(out);
+    after - before
+}
+
+#[test]
+fn uom_str_does_not_allocate() {
+    assert_eq!(allocations(Volt::uom_str), 0);
+    assert_eq!(allocations(Celsius::uom_str), 0);
+    // Compound labels are built on first use only.
+    #[cfg(feature = "std")]
+    {
+        Per::<Volt, Second>::uom_str();
+        assert_eq!(allocations(Per::<Volt, Second>::uom_str), 0);
+    }
+}
+
+#[test]
+fn label_beats_concatenation() {
+    let m = Measurement::<Volt>::new(12.5, Prefix::Milli);
+    let concatenated =
+        allocations(|| m.value().to_string() + m.prefix().get_label() + &Volt::uom());
+    let label = allocations(|| m.label());
+    assert_eq!(m.label(), "12.5mV");
+    assert!(label < concatenated, "{label} >= {concatenated}");
+}
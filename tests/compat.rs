@@ -0,0 +1,116 @@
+#![cfg(feature = "json")]
+
+//! Recordings written by earlier releases. Every fixture under `tests/fixtures/compat` must keep
+//! parsing: add new fixtures when the layout changes, never edit or remove existing ones.
+
+use ndarray::Array2;
+use typed_measurements::{
+    compat::{Recording, from_json_any_version},
+    prelude::*,
+};
+
+fn fixture(name: &str) -> String {
+    let path = format!(
+        "{}/tests/fixtures/compat/{name}",
+        env!("CARGO_MANIFEST_DIR")
+    );
+    std::fs::read_to_string(path).unwrap()
+}
+
+fn v1_m2d() -> M2d<Volt> {
+    M2d::new(
+        Array2::from_shape_vec((2, 3), vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]).unwrap(),
+        Prefix::Milli,
+    )
+}
+
+#[test]
+fn v1_fixtures_parse() {
+    let cases = [
+        (
+            "v1_measurement.json",
+            Recording::Measurement(Measurement::<Volt>::new(1.5, Prefix::Milli)),
+        ),
+        (
+            "v1_measurement_femto.json",
+            Recording::Measurement(Measurement::new(-250.0, Prefix::Femto)),
+        ),
+        (
+            "v1_m1d.json",
+            Recording::M1d(M1d::new(vec![1.0, -2.5, 3e-7], Prefix::Micro)),
+        ),
+        ("v1_m2d.json", Recording::M2d(v1_m2d())),
+        (
+            "v1_m2d_empty.json",
+            Recording::M2d(M2d::new(Array2::zeros((0, 4)), Prefix::None)),
+        ),
+    ];
+    for (name, expected) in cases {
+        let parsed = from_json_any_version::<Volt>(&fixture(name)).unwrap();
+        assert_eq!(prefix(&parsed), prefix(&expected), "{name}");
+        assert_eq!(parsed, expected, "{name}");
+    }
+}
+
+fn prefix(r: &Recording<Volt>) -> Prefix {
+    match r {
+        Recording::Measurement(m) => m.prefix(),
+        Recording::M1d(m) => m.prefix(),
+        Recording::M2d(m) => m.prefix(),
+    }
+}
+
+#[test]
+fn v1_fixtures_parse_with_versioned_deserializers() {
+    let json = fixture("v1_measurement.json");
+    let mut de = serde_json::Deserializer::from_str(&json);
+    let m = Measurement::<Volt>::deserialize_v1(&mut de).unwrap();
+    assert_eq!((m.value(), m.prefix()), (1.5, Prefix::Milli));
+    let json = fixture("v1_m2d.json");
+    let mut de = serde_json::Deserializer::from_str(&json);
+    let m = M2d::<Volt>::deserialize_v1(&mut de).unwrap();
+    assert_eq!(m.prefix(), Prefix::Milli);
+    assert_eq!(m.values(), v1_m2d().values());
+}
+
+#[test]
+fn current_layout_is_still_v1() {
+    let m = v1_m2d();
+    let json = serde_json::to_string(&m).unwrap();
+    assert_eq!(json, fixture("v1_m2d.json").trim_end());
+    let m = Measurement::<Volt>::new(1.5, Prefix::Milli);
+    let json = serde_json::to_string(&m).unwrap();
+    assert_eq!(json, fixture("v1_measurement.json").trim_end());
+}
+
+fn ranged(label: &str) -> RangedMeasurement<Volt> {
+    RangedMeasurement::from_label(label).unwrap()
+}
+
+#[test]
+fn ranged_fixtures_parse() {
+    let cases = [
+        ("v1_ranged.json", "[-1.5,1.5,0.1]mV"),
+        ("v1_ranged_stepless.json", "[0,250]uV"),
+        ("v2_ranged.json", "[-1.5,1.5,0.1]mV"),
+        ("v2_ranged_stepless.json", "[0,250]uV"),
+    ];
+    for (name, label) in cases {
+        let parsed: RangedMeasurement<Volt> = serde_json::from_str(&fixture(name)).unwrap();
+        let expected = ranged(label);
+        assert_eq!(parsed.label(), expected.label(), "{name}");
+        assert_eq!(parsed.step(), expected.step(), "{name}");
+        assert_eq!(parsed, expected, "{name}");
+    }
+}
+
+#[test]
+fn ranged_layout_is_v2() {
+    let json = serde_json::to_string(&ranged("[-1.5,1.5,0.1]mV")).unwrap();
+    assert_eq!(json, fixture("v2_ranged.json").trim_end());
+    let json = serde_json::to_string(&ranged("[0,250]uV")).unwrap();
+    assert_eq!(json, fixture("v2_ranged_stepless.json").trim_end());
+    let unknown = r#"{"min":0.0,"max":1.0,"prefix":"x"}"#;
+    let e = serde_json::from_str::<RangedMeasurement<Volt>>(unknown).unwrap_err();
+    assert!(e.to_string().contains("unknown prefix `x`"), "{e}");
+}
@@ -0,0 +1,7 @@
+// Compile-time checks for `#[derive(Uom)]`: accepted attribute forms and diagnostics.
+#[test]
+fn derive_ui() {
+    let t = trybuild::TestCases::new();
+    t.pass("tests/ui/pass_*.rs");
+    t.compile_fail("tests/ui/fail_*.rs");
+}
@@ -0,0 +1,20 @@
+"""Prints the scipy reference values for the Savitzky-Golay test in src/filter.rs.
+
+Run with `python3 tests/fixtures/savgol/generate.py` and paste the arrays into
+`savitzky_golay_matches_exact_least_squares`, updating the versions noted there.
+"""
+import numpy as np
+import scipy
+from scipy.signal import savgol_filter
+
+# `noisy_step()` in the filter tests
+x = np.array([
+    0.03, -0.02, 0.05, -0.04, 0.01, 0.0, 0.9, 0.02, 0.04, -0.05, 1.02, 0.99, 1.03,
+    0.98, 1.0, 1.05, 0.96, 1.01, 0.97, 1.02, 1.01,
+])
+
+print(f"// numpy {np.__version__}, scipy {scipy.__version__}")
+for window, order in [(7, 2), (5, 3)]:
+    print(f"// savgol_filter(x, {window}, {order})")
+    for v in savgol_filter(x, window, order, mode="interp"):
+        print(f"{float(v)!r},")
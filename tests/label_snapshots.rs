@@ -0,0 +1,102 @@
+// Snapshot of `label()` and `nice().label()` across every prefix, so changes to the label
+// strings are deliberate. Regenerate the fixture with `UPDATE_FIXTURES=1 cargo test --test
+// label_snapshots` and review the diff.
+use typed_measurements::prelude::*;
+
+const FIXTURE: &str = concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/tests/fixtures/labels/labels.tsv"
+);
+
+/// The values snapshotted in every prefix: signs, zero, non-finite values and both sides of
+/// every power-of-1000 boundary from femto to tera.
+fn values() -> Vec<f64> {
+    let mut values = vec![
+        0.0,
+        -0.0,
+        f64::NAN,
+        f64::INFINITY,
+        f64::NEG_INFINITY,
+        1.5,
+        -2.5,
+        123.456,
+        1e-7,
+        1e21,
+        5e-320,
+    ];
+    for exp in (-15..=12).step_by(3) {
+        // parsed rather than multiplied, so the table holds the shortest decimals
+        let near = |mantissa: &str| format!("{mantissa}e{exp}").parse::<f64>().unwrap();
+        values.extend([near("0.999"), near("1"), near("-1"), near("1.001")]);
+    }
+    values
+}
+
+/// Forces a new [`Prefix`] variant to be considered here, and added to [`Prefix::ALL`].
+fn known(p: Prefix) -> bool {
+    match p {
+        Prefix::Tera
+        | Prefix::Giga
+        | Prefix::Mega
+        | Prefix::Kilo
+        | Prefix::None
+        | Prefix::Milli
+        | Prefix::Micro
+        | Prefix::Nano
+        | Prefix::Pico
+        | Prefix::Femto => true,
+    }
+}
+
+fn row(value: f64, prefix: Prefix) -> String {
+    let m = Measurement::<Volt>::new(value, prefix);
+    format!("{value:?}\t{prefix:?}\t{}\t{}", m.label(), m.nice().label())
+}
+
+fn parse_prefix(s: &str) -> Prefix {
+    *Prefix::ALL
+        .iter()
+        .find(|p| format!("{p:?}") == s)
+        .unwrap_or_else(|| panic!("unknown prefix `{s}` in the fixture"))
+}
+
+#[test]
+fn labels_match_the_snapshot() {
+    let expected = Prefix::ALL
+        .iter()
+        .flat_map(|&p| values().into_iter().map(move |v| row(v, p)))
+        .collect::<Vec<_>>();
+    if std::env::var_os("UPDATE_FIXTURES").is_some() {
+        let table = format!("value\tprefix\tlabel\tnice\n{}\n", expected.join("\n"));
+        std::fs::write(FIXTURE, table).unwrap();
+    }
+    assert!(Prefix::ALL.into_iter().all(known));
+    let fixture = std::fs::read_to_string(FIXTURE).unwrap();
+    let mut mismatches = Vec::new();
+    let mut rows = Vec::new();
+    for line in fixture.lines().skip(1) {
+        let [value, prefix, label, nice] = line.split('\t').collect::<Vec<_>>()[..] else {
+            panic!("malformed fixture row `{line}`");
+        };
+        let value = value.parse::<f64>().unwrap();
+        let prefix = parse_prefix(prefix);
+        let m = Measurement::<Volt>::new(value, prefix);
+        let actual = (m.label(), m.nice().label());
+        if (actual.0.as_str(), actual.1.as_str()) != (label, nice) {
+            mismatches.push(format!(
+                "{value:?} {prefix:?}: expected {label} / {nice}, got {} / {}",
+                actual.0, actual.1
+            ));
+        }
+        rows.push(row(value, prefix));
+    }
+    assert!(mismatches.is_empty(), "{}", mismatches.join("\n"));
+    let missing = expected
+        .iter()
+        .filter(|r| !rows.contains(r))
+        .collect::<Vec<_>>();
+    assert!(
+        missing.is_empty(),
+        "the fixture lacks rows, regenerate it with UPDATE_FIXTURES=1:\n{missing:#?}"
+    );
+}
@@ -0,0 +1,42 @@
+#![cfg(feature = "ndarray")]
+
+use ndarray::Array2;
+use typed_measurements::prelude::*;
+
+fn sample() -> M2d<Volt> {
+    M2d::new(
+        Array2::from_shape_vec((2, 3), vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]).unwrap(),
+        Prefix::Milli,
+    )
+}
+
+#[test]
+fn serde_round_trip() {
+    let m = sample();
+    let json = serde_json::to_string(&m).unwrap();
+    let back: M2d<Volt> = serde_json::from_str(&json).unwrap();
+    assert_eq!(m, back);
+    assert_eq!(back.prefix(), Prefix::Milli);
+}
+
+#[test]
+fn concatenate_axis_converts_prefix() {
+    let a = sample();
+    let b = M2d::<Volt>::new(
+        Array2::from_shape_vec((1, 3), vec![7000.0, 8000.0, 9000.0]).unwrap(),
+        Prefix::Micro,
+    );
+    let c = a.concatenate_axis(&b, Axis(0));
+    assert_eq!(c.prefix(), Prefix::Milli);
+    assert_eq!(
+        c.values(),
+        Array2::from_shape_vec((3, 3), (1..=9).map(f64::from).collect()).unwrap()
+    );
+}
+
+#[test]
+fn mean_axis_from_prelude() {
+    let m = sample();
+    let mean = m.mean_axis(Axis(1)).unwrap();
+    assert_eq!(mean, M1d::new(vec![2.0, 5.0], Prefix::Milli));
+}
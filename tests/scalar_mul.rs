@@ -0,0 +1,41 @@
+// Compile-time checks that scalars multiply measurements from either side, with integer and
+// float literals inferred as before.
+use typed_measurements::prelude::*;
+
+fn mv(v: f64) -> Measurement<Volt> {
+    Measurement::new(v, Prefix::Milli)
+}
+
+#[test]
+fn scalars_multiply_measurements_from_either_side() {
+    let m = mv(1.5);
+    assert_eq!(2 * m, mv(3.0));
+    assert_eq!(2.0 * m, mv(3.0));
+    assert_eq!(m * 2, mv(3.0));
+    assert_eq!(m * 2.0, mv(3.0));
+    assert_eq!(2u8 * m, mv(3.0));
+    assert_eq!(0.5f32 * m, mv(0.75));
+    let n: i32 = -4;
+    assert_eq!(n * m, mv(-6.0));
+    // formula-shaped expressions read as written
+    let energy = 0.5 * (&mv(2.0) * &Measurement::<Ampere>::new(3.0, Prefix::None));
+    assert_eq!(energy, Measurement::<Watt>::new(3.0, Prefix::Milli));
+}
+
+#[cfg(feature = "ndarray")]
+#[test]
+fn scalars_multiply_arrays_from_either_side() {
+    let a = M1d::<Volt>::new(vec![1.0, 2.0], Prefix::Milli);
+    let doubled = M1d::<Volt>::new(vec![2.0, 4.0], Prefix::Milli);
+    assert_eq!(2.0 * &a, doubled);
+    assert_eq!(&a * 2.0, doubled);
+    assert_eq!(2 * &a, doubled);
+    assert_eq!(2u8 * a.clone(), doubled);
+    assert_eq!(2.0 * a, doubled);
+    let b = M2d::<Volt>::new(ndarray::array![[1.0, 2.0]], Prefix::Milli);
+    let doubled = M2d::<Volt>::new(ndarray::array![[2.0, 4.0]], Prefix::Milli);
+    assert_eq!(2.0 * &b, doubled);
+    assert_eq!(2 * &b, doubled);
+    assert_eq!(2.0f32 * b.clone(), doubled);
+    assert_eq!(2.0 * b.clone(), b * 2.0);
+}
@@ -0,0 +1,8 @@
+use serde::{Deserialize, Serialize};
+use uom_derive::Uom;
+
+#[derive(Uom, PartialEq, Debug, Clone, Copy, Serialize, Deserialize)]
+#[uom(label = Hz, aliases(sps))]
+struct Rate;
+
+fn main() {}
@@ -0,0 +1,9 @@
+use serde::{Deserialize, Serialize};
+use uom_derive::Uom;
+
+#[derive(Uom, PartialEq, Debug, Clone, Copy, Serialize, Deserialize)]
+#[uom(label = V)]
+#[uom(label = "V")]
+struct Twice;
+
+fn main() {}
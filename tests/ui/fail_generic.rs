@@ -0,0 +1,9 @@
+use serde::{Deserialize, Serialize};
+use std::marker::PhantomData;
+use uom_derive::Uom;
+
+#[derive(Uom, PartialEq, Debug, Clone, Copy, Serialize, Deserialize)]
+#[uom(label = V)]
+struct Generic<T>(PhantomData<T>);
+
+fn main() {}
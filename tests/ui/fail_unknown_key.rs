@@ -0,0 +1,8 @@
+use serde::{Deserialize, Serialize};
+use uom_derive::Uom;
+
+#[derive(Uom, PartialEq, Debug, Clone, Copy, Serialize, Deserialize)]
+#[uom(lable = V)]
+struct Typo;
+
+fn main() {}
@@ -0,0 +1,33 @@
+use serde::{Deserialize, Serialize};
+use typed_measurements::prelude::*;
+use uom_derive::Uom;
+
+#[derive(Uom, PartialEq, Debug, Clone, Copy, Serialize, Deserialize)]
+struct Bare;
+
+#[derive(Uom, PartialEq, Debug, Clone, Copy, Serialize, Deserialize)]
+#[uom(label = Wb)]
+struct PathLabel;
+
+#[derive(Uom, PartialEq, Debug, Clone, Copy, Serialize, Deserialize)]
+#[uom(label = "Wb")]
+struct LiteralLabel;
+
+#[derive(Uom, PartialEq, Debug, Clone, Copy, Serialize, Deserialize)]
+#[uom(label = "Wb", name = "weber")]
+struct Named;
+
+#[derive(Uom, PartialEq, Debug, Clone, Copy, Serialize, Deserialize)]
+#[uom(label = Hz, aliases("sps", "S/s"))]
+struct Aliased;
+
+fn main() {
+    assert_eq!(Bare::uom(), "Bare");
+    assert_eq!(PathLabel::uom(), "Wb");
+    assert_eq!(LiteralLabel::uom(), "Wb");
+    assert_eq!(LiteralLabel::name(), "Wb");
+    assert_eq!(Named::plural(), "webers");
+    assert_eq!(Aliased::aliases(), ["sps", "S/s"]);
+    assert_eq!(Bare::aliases(), [] as [&str; 0]);
+    assert_eq!("2ksps".parse::<Measurement<Aliased>>().unwrap().label(), "2kHz");
+}
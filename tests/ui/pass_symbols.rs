@@ -0,0 +1,20 @@
+use serde::{Deserialize, Serialize};
+use typed_measurements::prelude::*;
+use uom_derive::Uom;
+
+#[derive(Uom, PartialEq, Debug, Clone, Copy, Serialize, Deserialize)]
+#[uom(label = "°C", name = "degree Celsius")]
+struct DegreeCelsius;
+
+#[derive(Uom, PartialEq, Debug, Clone, Copy, Serialize, Deserialize)]
+#[uom(label = "m/s")]
+struct MeterPerSecond;
+
+fn main() {
+    assert_eq!(DegreeCelsius::uom(), "°C");
+    assert_eq!(MeterPerSecond::uom(), "m/s");
+    let speed: Measurement<MeterPerSecond> = "3km/s".parse().unwrap();
+    assert_eq!(speed.prefix(), Prefix::Kilo);
+    let t: Measurement<DegreeCelsius> = "21.5°C".parse().unwrap();
+    assert_eq!(t.label(), "21.5°C");
+}
@@ -1,35 +1,267 @@
 use proc_macro::{self, TokenStream};
 use quote::quote;
-use syn::{parse_macro_input, DeriveInput};
-use darling::FromDeriveInput;
+use syn::{
+    DeriveInput, Expr, ExprLit, Lit, LitStr, Token, meta::ParseNestedMeta, parse_macro_input,
+};
 
-#[derive(FromDeriveInput, Default)]
-#[darling(default, attributes(uom), forward_attrs(allow, doc, cfg))]
+/// The keys accepted in `#[uom(...)]`.
+const KEYS: [&str; 3] = ["label", "name", "aliases"];
+
+#[derive(Default)]
 struct Opts {
-    label: Option<syn::Path>,
+    label: Option<Label>,
+    name: Option<LitStr>,
+    aliases: Option<Vec<LitStr>>,
+}
+
+/// A unit label, given either as a bare path (`label = V`) or a string literal (`label = "V"`).
+enum Label {
+    Path(syn::Path),
+    Literal(LitStr),
+}
+
+impl Opts {
+    /// Reads every `#[uom(...)]` attribute of the input; other attributes are ignored.
+    fn from_attrs(attrs: &[syn::Attribute]) -> syn::Result<Self> {
+        let mut opts = Self::default();
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident("uom")) {
+            attr.parse_nested_meta(|meta| opts.parse_key(meta))?;
+        }
+        Ok(opts)
+    }
+
+    /// Parses one `key = value` pair.
+    fn parse_key(&mut self, meta: ParseNestedMeta) -> syn::Result<()> {
+        let key = meta
+            .path
+            .get_ident()
+            .map(ToString::to_string)
+            .unwrap_or_default();
+        match key.as_str() {
+            "label" if self.label.is_some() => Err(meta.error("Duplicate field `label`")),
+            "label" => {
+                self.label = Some(match meta.value()?.parse::<Expr>()? {
+                    Expr::Path(p) => Label::Path(p.path),
+                    Expr::Lit(ExprLit {
+                        lit: Lit::Str(s), ..
+                    }) => Label::Literal(s),
+                    expr => {
+                        return Err(syn::Error::new_spanned(
+                            expr,
+                            "expected a unit label such as `V` or `\"V\"`",
+                        ));
+                    }
+                });
+                Ok(())
+            }
+            "name" if self.name.is_some() => Err(meta.error("Duplicate field `name`")),
+            "name" => {
+                self.name = Some(match meta.value()?.parse::<Expr>()? {
+                    Expr::Lit(ExprLit {
+                        lit: Lit::Str(s), ..
+                    }) => s,
+                    expr => {
+                        return Err(syn::Error::new_spanned(
+                            expr,
+                            "expected a unit name such as `\"volt\"`",
+                        ));
+                    }
+                });
+                Ok(())
+            }
+            "aliases" if self.aliases.is_some() => Err(meta.error("Duplicate field `aliases`")),
+            "aliases" => {
+                let content;
+                syn::parenthesized!(content in meta.input);
+                let aliases = content
+                    .parse_terminated(|input| input.parse::<LitStr>(), Token![,])
+                    .map_err(|e| {
+                        syn::Error::new(e.span(), "expected unit aliases such as `(\"sps\")`")
+                    })?;
+                self.aliases = Some(aliases.into_iter().collect());
+                Ok(())
+            }
+            _ => {
+                let path = &meta.path;
+                let key = quote!(#path).to_string().replace(' ', "");
+                let message = match KEYS.iter().find(|k| similar(k, &key)) {
+                    Some(k) => format!("Unknown field: `{key}`. Did you mean `{k}`?"),
+                    None => format!("Unknown field: `{key}`"),
+                };
+                Err(meta.error(message))
+            }
+        }
+    }
+}
+
+/// Returns `true` if `key` is likely a typo of `known`: at most two single-character edits
+/// apart, swapping two neighbours counting as one.
+fn similar(known: &str, key: &str) -> bool {
+    let (a, b) = (known.as_bytes(), key.as_bytes());
+    // optimal string alignment distance, rows for `a`, columns for `b`
+    let mut d = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+    for (i, row) in d.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    d[0] = (0..=b.len()).collect();
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            d[i][j] = (d[i - 1][j] + 1)
+                .min(d[i][j - 1] + 1)
+                .min(d[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                d[i][j] = d[i][j].min(d[i - 2][j - 2] + 1);
+            }
+        }
+    }
+    d[a.len()][b.len()] <= 2
 }
 
 #[proc_macro_derive(Uom, attributes(uom))]
 pub fn derive(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input);
-    let opts = Opts::from_derive_input(&input).expect("Wrong options");
-    let DeriveInput { ident, .. } = input;
+    expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Generates the `Uom` impl for `input`.
+fn expand(input: DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let opts = Opts::from_attrs(&input.attrs)?;
+    let DeriveInput {
+        ident, generics, ..
+    } = input;
+    if !generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(
+            generics,
+            "Uom cannot be derived for generic types",
+        ));
+    }
     let uom = match opts.label {
-        Some(path) => quote! {
-            fn uom() -> String {
-                format!(stringify!(#path))
+        Some(Label::Path(path)) => quote! {
+            fn uom_str() -> &'static str {
+                stringify!(#path)
+            }
+        },
+        Some(Label::Literal(label)) => quote! {
+            fn uom_str() -> &'static str {
+                #label
             }
         },
         None => quote! {
-            fn uom() -> String {
-                format!(stringify!(#ident))
+            fn uom_str() -> &'static str {
+                stringify!(#ident)
             }
         },
     };
-    let output = quote! {
+    let name = opts.name.map(|name| {
+        quote! {
+            fn name_str() -> &'static str {
+                #name
+            }
+        }
+    });
+    let aliases = opts.aliases.map(|aliases| {
+        quote! {
+            fn aliases() -> &'static [&'static str] {
+                &[#(#aliases),*]
+            }
+        }
+    });
+    Ok(quote! {
         impl Uom for #ident {
             #uom
+            #name
+            #aliases
         }
-    };
-    output.into()
-}
\ No newline at end of file
+    })
+}
+
+#[cfg(test)]
+mod expand_tests {
+    use super::*;
+    use syn::parse_quote;
+
+    /// Expands the derive for `input` and renders the tokens.
+    fn expanded(input: DeriveInput) -> String {
+        expand(input).unwrap().to_string()
+    }
+
+    #[test]
+    fn snapshot_of_every_form() {
+        assert_eq!(
+            expanded(parse_quote!(
+                struct Bare;
+            )),
+            quote! {
+                impl Uom for Bare {
+                    fn uom_str() -> &'static str {
+                        stringify!(Bare)
+                    }
+                }
+            }
+            .to_string()
+        );
+        assert_eq!(
+            expanded(parse_quote!(
+                #[uom(label = Wb)]
+                struct Weber;
+            )),
+            quote! {
+                impl Uom for Weber {
+                    fn uom_str() -> &'static str {
+                        stringify!(Wb)
+                    }
+                }
+            }
+            .to_string()
+        );
+        assert_eq!(
+            expanded(parse_quote! {
+                /// Speed.
+                #[uom(label = "m/s")]
+                #[uom(name = "meter per second")]
+                struct MeterPerSecond;
+            }),
+            quote! {
+                impl Uom for MeterPerSecond {
+                    fn uom_str() -> &'static str {
+                        "m/s"
+                    }
+                    fn name_str() -> &'static str {
+                        "meter per second"
+                    }
+                }
+            }
+            .to_string()
+        );
+        assert_eq!(
+            expanded(parse_quote!(
+                #[uom(label = Hz, aliases("sps", "S/s"))]
+                struct Rate;
+            )),
+            quote! {
+                impl Uom for Rate {
+                    fn uom_str() -> &'static str {
+                        stringify!(Hz)
+                    }
+                    fn aliases() -> &'static [&'static str] {
+                        &["sps", "S/s"]
+                    }
+                }
+            }
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn suggests_close_keys() {
+        assert!(similar("label", "lable"));
+        assert!(similar("label", "labels"));
+        assert!(similar("name", "nme"));
+        assert!(similar("aliases", "alias"));
+        assert!(!similar("name", "unit"));
+        assert!(!similar("label", "symbol"));
+    }
+}